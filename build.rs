@@ -0,0 +1,51 @@
+use std::{env, fs, path::Path};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto/elite.proto");
+    println!("cargo:rerun-if-changed=src/journal/events.rs");
+
+    event_names()?;
+
+    // protox compiles the schema without needing protoc installed
+    #[cfg(feature = "grpc")]
+    tonic_build::configure()
+        .build_client(true)
+        .compile_fds(protox::compile(["elite.proto"], ["proto"])?)?;
+
+    Ok(())
+}
+
+/// Writes the sorted names of the variants of Event, the kinds of events
+/// it knows, for telling unknown ones apart before deserializing
+fn event_names() -> Result<(), Box<dyn std::error::Error>> {
+    let source = fs::read_to_string("src/journal/events.rs")?;
+    let body = source
+        .split_once("pub enum Event {\n")
+        .and_then(|(_, rest)| rest.split_once("\n}\n"))
+        .ok_or("No Event enum in src/journal/events.rs")?
+        .0;
+
+    let mut names = Vec::new();
+    let mut skip = false;
+    for line in body.lines() {
+        // Variants and their attributes are the only lines indented once
+        let Some(line) = line.strip_prefix("    ").filter(|l| !l.starts_with(' ')) else {
+            continue;
+        };
+        if line.starts_with("#[serde(skip)]") {
+            skip = true;
+        } else if let Some(name) = line.strip_suffix(" {").or(line.strip_suffix(',')) {
+            if !std::mem::take(&mut skip) {
+                names.push(name.to_owned());
+            }
+        }
+    }
+    names.sort();
+
+    let out = Path::new(&env::var("OUT_DIR")?).join("event_names.rs");
+    fs::write(
+        out,
+        format!("pub(crate) const EVENT_NAMES: &[&str] = &{names:?};\n"),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+/* C API of elite-rs, build the shared library with
+ * `cargo rustc --release --lib --features ffi --crate-type cdylib` */
+#ifndef ELITE_H
+#define ELITE_H
+
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct GameState GameState;
+
+/* Normalized event as JSON or NULL if the line couldn't be parsed */
+char *elite_parse_line(const char *line);
+
+/* Free a string returned by this library */
+void elite_string_free(char *s);
+
+GameState *elite_state_new(void);
+void elite_state_free(GameState *state);
+
+/* Returns false if the line couldn't be parsed */
+bool elite_state_update(GameState *state, const char *line);
+
+/* Current state as JSON */
+char *elite_state_json(const GameState *state);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
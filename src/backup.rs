@@ -0,0 +1,267 @@
+//! Compressed copies of the journals and the other files the game writes
+//! next to them, with a manifest of checksums to tell if a copy got
+//! damaged since.
+//!
+//! Every file is gzipped on its own as `<name>.gz`, so a backup can be
+//! updated by only writing the files that changed and read without this
+//! crate.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Component, Path},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use time::{serde::rfc3339 as Rfc3339, OffsetDateTime};
+
+pub const MANIFEST: &str = "manifest.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ManifestEntry {
+    /// Size before compression
+    pub size: u64,
+    pub crc32: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Manifest {
+    #[serde(with = "Rfc3339")]
+    pub updated: OffsetDateTime,
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Fails with [io::ErrorKind::InvalidData] if a file in it isn't a
+    /// plain file name, which would restore it outside the target directory
+    pub fn read(backup: &Path) -> Result<Self, io::Error> {
+        let manifest: Self = serde_json::from_str(&fs::read_to_string(backup.join(MANIFEST))?)?;
+        match manifest.files.keys().find(|name| !is_file_name(name)) {
+            Some(name) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Not a file name in the manifest: {name:?}"),
+            )),
+            None => Ok(manifest),
+        }
+    }
+
+    fn write(&self, backup: &Path) -> Result<(), io::Error> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_replacing(&backup.join(MANIFEST), |mut file| {
+            file.write_all(json.as_bytes())
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BackupSummary {
+    /// Files new or changed since the last backup
+    pub copied: Vec<String>,
+    pub unchanged: usize,
+}
+
+/// Something wrong with a file in a backup
+#[derive(Debug, PartialEq, Eq)]
+pub enum Damage {
+    Missing(String),
+    /// Contents don't match the checksum in the manifest
+    Corrupt(String),
+}
+
+fn entry(contents: &[u8]) -> ManifestEntry {
+    ManifestEntry {
+        size: contents.len() as u64,
+        crc32: crc32fast::hash(contents),
+    }
+}
+
+fn is_file_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(first)), None) if first == name
+    )
+}
+
+/// Write a file next to `path` and move it into place, so an interrupted
+/// write doesn't leave a truncated file behind
+fn write_replacing(
+    path: &Path,
+    write: impl FnOnce(File) -> Result<(), io::Error>,
+) -> Result<(), io::Error> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    write(File::create(&temp)?)?;
+    fs::rename(&temp, path)
+}
+
+/// Whether the game wrote this file, journals and the JSON files like
+/// Status.json
+fn is_game_file(name: &str) -> bool {
+    (name.starts_with("Journal") && name.ends_with(".log")) || name.ends_with(".json")
+}
+
+/// Back up the game's files in `source` to `backup`, only writing files
+/// that aren't in the backup yet or changed since
+pub fn backup(source: &Path, backup: &Path) -> Result<BackupSummary, io::Error> {
+    fs::create_dir_all(backup)?;
+    let mut manifest = match Manifest::read(backup) {
+        Ok(manifest) => manifest,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Manifest {
+            updated: OffsetDateTime::now_utc(),
+            files: BTreeMap::new(),
+        },
+        Err(e) => return Err(e),
+    };
+
+    let mut names: Vec<String> = fs::read_dir(source)?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| is_game_file(name))
+        .collect();
+    names.sort();
+
+    let mut summary = BackupSummary::default();
+    for name in names {
+        let contents = fs::read(source.join(&name))?;
+        let entry = entry(&contents);
+        if manifest.files.get(&name) == Some(&entry) {
+            summary.unchanged += 1;
+            continue;
+        }
+
+        write_replacing(&backup.join(format!("{name}.gz")), |file| {
+            let mut encoder = GzEncoder::new(file, Compression::best());
+            encoder.write_all(&contents)?;
+            encoder.finish()?.sync_all()
+        })?;
+
+        manifest.files.insert(name.clone(), entry);
+        summary.copied.push(name);
+    }
+
+    manifest.updated = OffsetDateTime::now_utc();
+    manifest.write(backup)?;
+
+    Ok(summary)
+}
+
+/// Contents of one file in a backup, checked against the manifest
+fn read_file(backup: &Path, name: &str, expected: &ManifestEntry) -> Result<Vec<u8>, Damage> {
+    let file = File::open(backup.join(format!("{name}.gz")))
+        .map_err(|_| Damage::Missing(name.to_owned()))?;
+    let mut contents = Vec::new();
+    GzDecoder::new(file)
+        .read_to_end(&mut contents)
+        .map_err(|_| Damage::Corrupt(name.to_owned()))?;
+
+    match entry(&contents) == *expected {
+        true => Ok(contents),
+        false => Err(Damage::Corrupt(name.to_owned())),
+    }
+}
+
+/// Files in the backup that are missing or don't match their checksum
+pub fn verify(backup: &Path) -> Result<Vec<Damage>, io::Error> {
+    let manifest = Manifest::read(backup)?;
+
+    Ok(manifest
+        .files
+        .iter()
+        .filter_map(|(name, expected)| read_file(backup, name, expected).err())
+        .collect())
+}
+
+/// Restore the files of a backup to `target`, keeping files already there
+/// unless `overwrite` is set. Damaged files are left out and returned.
+pub fn restore(
+    backup: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<(Vec<String>, Vec<Damage>), io::Error> {
+    let manifest = Manifest::read(backup)?;
+    fs::create_dir_all(target)?;
+
+    let mut restored = Vec::new();
+    let mut damaged = Vec::new();
+    for (name, expected) in &manifest.files {
+        let path = target.join(name);
+        if path.exists() && !overwrite {
+            continue;
+        }
+        match read_file(backup, name, expected) {
+            Ok(contents) => {
+                fs::write(path, contents)?;
+                restored.push(name.clone());
+            }
+            Err(damage) => damaged.push(damage),
+        }
+    }
+
+    Ok((restored, damaged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup() {
+        let dir = std::env::temp_dir().join(format!("elite-backup-{}", std::process::id()));
+        let (source, target, restored) =
+            (dir.join("game"), dir.join("backup"), dir.join("restored"));
+        fs::create_dir_all(&source).unwrap();
+        let journal = "Journal.2024-09-15T120000.01.log";
+        fs::write(
+            source.join(journal),
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Shutdown" }"#,
+        )
+        .unwrap();
+        fs::write(source.join("Status.json"), "{}").unwrap();
+        fs::write(source.join("desktop.ini"), "").unwrap();
+
+        let first = backup(&source, &target).unwrap();
+        fs::write(source.join("Status.json"), r#"{ "Flags":16 }"#).unwrap();
+        let second = backup(&source, &target).unwrap();
+        let intact = verify(&target).unwrap();
+        fs::write(target.join(format!("{journal}.gz")), "not gzip").unwrap();
+        let damaged = verify(&target).unwrap();
+        let (files, _) = restore(&target, &restored, false).unwrap();
+        let status = fs::read_to_string(restored.join("Status.json")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.copied.len(), 2);
+        assert_eq!((second.copied.len(), second.unchanged), (1, 1));
+        assert!(intact.is_empty());
+        assert_eq!(damaged, [Damage::Corrupt(journal.to_owned())]);
+        assert_eq!(files, ["Status.json"]);
+        assert_eq!(status, r#"{ "Flags":16 }"#);
+    }
+
+    #[test]
+    fn test_restore_outside() {
+        let dir = std::env::temp_dir().join(format!("elite-backup-escape-{}", std::process::id()));
+        let (target, restored) = (dir.join("backup"), dir.join("restored"));
+        fs::create_dir_all(&target).unwrap();
+        let entry = entry(b"{}");
+        for name in ["../Status.json", "/tmp/Status.json", "game/Status.json"] {
+            Manifest {
+                updated: OffsetDateTime::now_utc(),
+                files: BTreeMap::from([(name.to_owned(), entry)]),
+            }
+            .write(&target)
+            .unwrap();
+            let error = restore(&target, &restored, true).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+        let leftovers = fs::read_dir(&target).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!dir.join("Status.json").exists());
+        // Only the manifest, no temporary file
+        assert_eq!(leftovers, 1);
+        assert!(is_file_name("Journal.2024-09-15T120000.01.log"));
+        assert!(!is_file_name("."));
+    }
+}
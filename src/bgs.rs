@@ -0,0 +1,140 @@
+//! Influence and reputation gained for minor factions through missions,
+//! for reporting contributions to the background simulation.
+
+use std::collections::{BTreeMap, HashMap};
+
+use time::{Date, OffsetDateTime};
+
+use crate::journal::Event;
+
+/// Influence one completed mission had on a faction in a system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfluenceEntry {
+    pub timestamp: OffsetDateTime,
+    pub faction: String,
+    pub system_address: u64,
+    /// Pluses, negative when the influence went down
+    pub influence: i64,
+    pub reputation: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Contribution {
+    pub missions: u32,
+    pub influence: i64,
+    pub reputation: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct BgsContributions {
+    pub entries: Vec<InfluenceEntry>,
+    /// Names of the systems jumped to by address
+    pub systems: HashMap<u64, String>,
+}
+
+impl BgsContributions {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump {
+                star_system,
+                system_address,
+                ..
+            }
+            | Event::Location {
+                star_system,
+                system_address,
+                ..
+            } => {
+                self.systems.insert(*system_address, star_system.clone());
+            }
+            Event::MissionCompleted {
+                timestamp,
+                faction_effects,
+                ..
+            } => {
+                for effect in faction_effects.iter().filter(|e| !e.faction.is_empty()) {
+                    let reputation = effect.reputation.matches('+').count() as u32;
+                    for influence in &effect.influence {
+                        let pluses = influence.influence.matches('+').count() as i64;
+                        self.entries.push(InfluenceEntry {
+                            timestamp: *timestamp,
+                            faction: effect.faction.clone(),
+                            system_address: influence.system_address,
+                            influence: match influence.trend.starts_with("Down") {
+                                true => -pluses,
+                                false => pluses,
+                            },
+                            reputation,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// System name if it was visited, the address otherwise
+    pub fn system_name(&self, address: u64) -> String {
+        self.systems
+            .get(&address)
+            .cloned()
+            .unwrap_or_else(|| address.to_string())
+    }
+
+    /// Contributions by day, faction and system, with `date` giving the day
+    /// of a timestamp in whatever timezone the report is for
+    pub fn daily(
+        &self,
+        date: impl Fn(OffsetDateTime) -> Date,
+    ) -> BTreeMap<(Date, String, String), Contribution> {
+        let mut daily: BTreeMap<_, Contribution> = BTreeMap::new();
+        for entry in &self.entries {
+            let key = (
+                date(entry.timestamp),
+                entry.faction.clone(),
+                self.system_name(entry.system_address),
+            );
+            let contribution = daily.entry(key).or_default();
+            contribution.missions += 1;
+            contribution.influence += entry.influence;
+            contribution.reputation += entry.reputation;
+        }
+        daily
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+    use time::macros::date;
+
+    #[test]
+    fn test_daily_contributions() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"FSDJump", "StarSystem":"Ross 128", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":10.0, "FuelUsed":1.0, "FuelLevel":10.0 }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"MissionCompleted", "Faction":"Ross 128 Blue Vision", "Name":"Mission_Delivery_name", "MissionID":1, "FactionEffects":[ { "Faction":"Ross 128 Blue Vision", "Effects":[], "Influence":[ { "SystemAddress":1, "Trend":"UpGood", "Influence":"++" }, { "SystemAddress":2, "Trend":"UpGood", "Influence":"+" } ], "ReputationTrend":"UpGood", "Reputation":"++" } ] }
+{ "timestamp":"2024-09-15T13:30:00Z", "event":"MissionCompleted", "Faction":"Ross 128 Crimson Travel", "Name":"Mission_Assassinate_name", "MissionID":2, "FactionEffects":[ { "Faction":"Ross 128 Blue Vision", "Effects":[], "Influence":[ { "SystemAddress":1, "Trend":"DownBad", "Influence":"+" } ], "ReputationTrend":"DownBad", "Reputation":"" } ] }
+"#,
+        )
+        .unwrap();
+        let mut bgs = BgsContributions::default();
+        for event in &events {
+            bgs.update(event);
+        }
+
+        let daily = bgs.daily(OffsetDateTime::date);
+        let day = date!(2024 - 09 - 15);
+        let faction = "Ross 128 Blue Vision".to_owned();
+        assert_eq!(
+            daily[&(day, faction.clone(), "Ross 128".to_owned())],
+            Contribution {
+                missions: 2,
+                influence: 1,
+                reputation: 2
+            }
+        );
+        assert_eq!(daily[&(day, faction, "2".to_owned())].influence, 1);
+    }
+}
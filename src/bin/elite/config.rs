@@ -0,0 +1,98 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use directories::ProjectDirs;
+use elite::{rules::Rule, twitch::TwitchConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::output::OutputFormat;
+
+/// Persistent settings, overridden by the matching command line flags
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Directory containing the journals, instead of the game's default
+    pub journal_dir: Option<PathBuf>,
+    /// Journal directories copied from other machines, merged with the
+    /// journals in `journal_dir`
+    pub merge_dirs: Vec<PathBuf>,
+    /// Read journals from another machine instead, a WebDAV URL,
+    /// "serve://host:port" for one running `elite serve --journals` or
+    /// "grpc://host:port" for one running `elite grpc`
+    pub source: Option<String>,
+    /// FID of the commander to report on when several play on this machine
+    pub commander: Option<String>,
+    /// Skip session start snapshots that didn't change since the last one
+    pub dedup: bool,
+    /// Timezone for showing times, "local" or a name like "Europe/Berlin"
+    pub timezone: Option<String>,
+    /// Output format used when --output isn't given
+    pub output: Option<OutputFormat>,
+    /// API keys for integrations keyed by service, e.g. "edsm"
+    pub api_keys: BTreeMap<String, String>,
+    /// Database of market prices, instead of the platform's data directory
+    pub market_db: Option<PathBuf>,
+    /// `RegionMapData.json` for putting systems in galactic regions, see
+    /// [elite::regions::RegionMap]. Without it only systems with codex
+    /// entries get a region
+    pub region_map: Option<PathBuf>,
+    /// Rhai scripts run by `elite watch`, instead of `scripts` next to
+    /// this file
+    pub scripts_dir: Option<PathBuf>,
+    /// Built-in alert rules to turn on by name, see [elite::rules::BUILTIN_RULES]
+    pub builtin_rules: Vec<String>,
+    /// Alert rules of `elite watch`, see [elite::rules]
+    pub rules: Vec<Rule>,
+    /// Events `elite watch` announces in a Twitch chat, see [elite::twitch].
+    /// The OAuth token goes in `api_keys` as "twitch"
+    pub twitch: Option<TwitchConfig>,
+}
+
+impl Config {
+    /// `config.toml` in the platform's config directory,
+    /// e.g. `~/.config/elite/config.toml` on Linux
+    pub fn default_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "elite").map(|dirs| dirs.config_dir().join("config.toml"))
+    }
+
+    /// `markets.db` in the platform's data directory unless configured,
+    /// e.g. `~/.local/share/elite/markets.db` on Linux
+    #[cfg(feature = "market")]
+    pub fn market_db_path(&self) -> Option<PathBuf> {
+        self.market_db.clone().or_else(|| {
+            ProjectDirs::from("", "", "elite").map(|dirs| dirs.data_dir().join("markets.db"))
+        })
+    }
+
+    /// `scripts` in the platform's config directory unless configured,
+    /// e.g. `~/.config/elite/scripts` on Linux
+    #[cfg(feature = "scripting")]
+    pub fn scripts_dir_path(&self) -> Option<PathBuf> {
+        self.scripts_dir.clone().or_else(|| {
+            ProjectDirs::from("", "", "elite").map(|dirs| dirs.config_dir().join("scripts"))
+        })
+    }
+
+    /// Load from `path` or the default location.
+    /// A missing file at the default location is the same as an empty one.
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn Error>> {
+        let (path, required) = match path {
+            Some(path) => (path.to_owned(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default()),
+            },
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|e| format!("Invalid config {}: {e}", path.display()).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => Ok(Config::default()),
+            Err(e) => Err(format!("Can't read config {}: {e}", path.display()).into()),
+        }
+    }
+}
@@ -0,0 +1,505 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use elite::{
+    journal::{Event, FriendStatus},
+    route::is_scoopable,
+    ships,
+    timezone::Timezone,
+};
+use serde_json::Value;
+use time::macros::format_description;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Category {
+    Session,
+    Travel,
+    Combat,
+    Exploration,
+    Trade,
+    Missions,
+    Social,
+    Other,
+}
+
+impl Category {
+    fn of(name: &str) -> Self {
+        match name {
+            "Fileheader" | "Commander" | "LoadGame" | "NewCommander" | "ClearSavedGame"
+            | "Continued" | "Shutdown" | "Music" => Category::Session,
+            "FSDJump" | "FSDTarget" | "StartJump" | "Location" | "ApproachBody" | "LeaveBody"
+            | "Liftoff" | "Touchdown" | "Undocked" | "USSDrop" | "Embark" | "Disembark"
+            | "FuelScoop" | "JetConeBoost" | "ApproachSettlement" => Category::Travel,
+            "Bounty" | "CapShipBond" | "Died" | "EscapeInterdiction" | "FactionKillBond"
+            | "FighterDestroyed" | "HeatDamage" | "HeatWarning" | "HullDamage" | "Interdicted"
+            | "Interdiction" | "PVPKill" | "ShieldState" | "ShipTargeted" | "SRVDestroyed"
+            | "UnderAttack" | "CockpitBreached" | "CommitCrime" | "CrimeVictim"
+            | "SelfDestruct" | "Resurrect" => Category::Combat,
+            "CodexEntry"
+            | "DiscoveryScan"
+            | "Scan"
+            | "ScanBaryCentre"
+            | "NavBeaconScan"
+            | "MultiSellExplorationData"
+            | "SellExplorationData"
+            | "BuyExplorationData"
+            | "ScanOrganic"
+            | "SellOrganicData"
+            | "Screenshot" => Category::Exploration,
+            "MarketBuy" | "MarketSell" | "Market" | "BuyTradeData" | "CollectCargo"
+            | "EjectCargo" | "MiningRefined" | "AsteroidCracked" | "ProspectedAsteroid"
+            | "Cargo" | "CargoTransfer" => Category::Trade,
+            "ReceiveText" | "SendText" | "Friends" | "JoinACrew" | "QuitACrew"
+            | "KickCrewMember" | "EndCrewSession" | "ChangeCrewRole" => Category::Social,
+            _ if name.starts_with("Docking")
+                || name.starts_with("Supercruise")
+                || name.starts_with("NavRoute")
+                || name.starts_with("CarrierJump") =>
+            {
+                Category::Travel
+            }
+            _ if name.starts_with("FSS")
+                || name.starts_with("SAA")
+                || name.starts_with("Material") =>
+            {
+                Category::Exploration
+            }
+            _ if name.starts_with("Mission")
+                || name.starts_with("CommunityGoal")
+                || name.starts_with("Passengers") =>
+            {
+                Category::Missions
+            }
+            _ if name.starts_with("Wing") || name.starts_with("CrewMember") => Category::Social,
+            _ => Category::Other,
+        }
+    }
+
+    /// ANSI color code
+    fn color(self) -> &'static str {
+        match self {
+            Category::Session => "2",
+            Category::Travel => "36",
+            Category::Combat => "31",
+            Category::Exploration => "32",
+            Category::Trade => "33",
+            Category::Missions => "35",
+            Category::Social => "34",
+            Category::Other => "0",
+        }
+    }
+}
+
+/// Credits with thousands separators, e.g. "1,234,567 CR"
+fn credits(amount: u64) -> String {
+    let digits = amount.to_string();
+    let mut grouped = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    format!("{grouped} CR")
+}
+
+fn localised<'a>(name: &'a str, localised: &'a Option<String>) -> &'a str {
+    localised.as_deref().unwrap_or(name)
+}
+
+/// Short description of what happened, empty for events without one
+fn summary(event: &Event) -> String {
+    match event {
+        Event::Commander { name, .. } => format!("CMDR {name}"),
+        Event::LoadGame {
+            commander,
+            ship,
+            credits: balance,
+            ..
+        } => format!(
+            "CMDR {} in {}, {}",
+            commander,
+            ship.as_deref().map_or("no ship", ships::ship_name),
+            credits(*balance)
+        ),
+        Event::FSDJump {
+            star_system,
+            jump_dist,
+            ..
+        } => format!("Jumped {jump_dist:.2} ly to {star_system}"),
+        Event::Location {
+            star_system,
+            station_name,
+            ..
+        } => match station_name {
+            Some(station) => format!("At {station} in {star_system}"),
+            None => format!("In {star_system}"),
+        },
+        Event::Docked {
+            station_name,
+            star_system,
+            ..
+        } => format!("Docked at {station_name} in {star_system}"),
+        Event::Undocked { station_name, .. } => format!("Undocked from {station_name}"),
+        Event::StartJump {
+            star_system: Some(system),
+            star_class,
+            ..
+        } => match star_class {
+            Some(class) if !is_scoopable(class) => {
+                format!("Jumping to {system}, {class} star not scoopable")
+            }
+            Some(class) => format!("Jumping to {system}, {class} star"),
+            None => format!("Jumping to {system}"),
+        },
+        Event::SupercruiseExit {
+            body: Some(body), ..
+        } => format!("Dropped at {body}"),
+        Event::SupercruiseDestinationDrop {
+            r#type,
+            type_localised,
+            threat,
+            ..
+        } => match threat {
+            Some(threat) if *threat > 0 => {
+                format!(
+                    "Dropped at {}, threat {threat}",
+                    localised(r#type, type_localised)
+                )
+            }
+            _ => format!("Dropped at {}", localised(r#type, type_localised)),
+        },
+        Event::ApproachBody { body, .. } => format!("Approaching {body}"),
+        Event::ProspectedAsteroid {
+            materials,
+            motherlode_material,
+            motherlode_material_localised,
+            content_localised,
+            content,
+            ..
+        } => {
+            let mut line = localised(content, content_localised).to_owned();
+            for material in materials {
+                line += &format!(
+                    ", {} {:.1}%",
+                    localised(&material.name, &material.name_localised),
+                    material.proportion
+                );
+            }
+            if let Some(core) = motherlode_material {
+                line += &format!(
+                    ", {} motherlode",
+                    localised(core, motherlode_material_localised)
+                );
+            }
+            line
+        }
+        Event::MiningRefined {
+            r#type,
+            type_localised,
+            ..
+        } => format!("Refined {}", localised(r#type, type_localised)),
+        Event::AsteroidCracked { body, .. } => format!("Cracked an asteroid in {body}"),
+        Event::Repair {
+            item, items, cost, ..
+        } => {
+            let repaired = match item {
+                Some(item) => item.clone(),
+                None => items.join(", "),
+            };
+            format!("Repaired {repaired} for {}", credits(*cost))
+        }
+        Event::RepairAll { cost, .. } => format!("Repaired everything for {}", credits(*cost)),
+        Event::AfmuRepairs {
+            module,
+            module_localised,
+            health,
+            ..
+        } => format!(
+            "{} repaired to {:.0}%",
+            localised(module, module_localised),
+            health * 100.0
+        ),
+        Event::RebootRepair { modules, .. } => match modules.is_empty() {
+            true => "Rebooted".to_owned(),
+            false => format!("Rebooted, repairing {}", modules.join(", ")),
+        },
+        Event::USSDrop {
+            uss_type,
+            uss_type_localised,
+            uss_threat,
+            ..
+        } => match uss_threat {
+            0 => format!("Dropped at {}", localised(uss_type, uss_type_localised)),
+            threat => format!(
+                "Dropped at {}, threat {threat}",
+                localised(uss_type, uss_type_localised)
+            ),
+        },
+        Event::DataScanned {
+            r#type,
+            type_localised,
+            ..
+        } => format!("Scanned {}", localised(r#type, type_localised)),
+        Event::DatalinkVoucher {
+            reward,
+            payee_faction,
+            ..
+        } => format!(
+            "Data link voucher of {} from {payee_faction}",
+            credits(*reward)
+        ),
+        Event::NavBeaconScan { num_bodies, .. } => {
+            format!("Nav beacon revealed {num_bodies} bodies")
+        }
+        Event::Friends { status, name, .. } => match status {
+            FriendStatus::Requested => format!("Friend request from {name}"),
+            FriendStatus::Declined => format!("{name} declined the friend request"),
+            FriendStatus::Added => format!("{name} added as a friend"),
+            FriendStatus::Lost => format!("{name} is no longer a friend"),
+            FriendStatus::Offline => format!("{name} went offline"),
+            FriendStatus::Online => format!("{name} came online"),
+            FriendStatus::Other => format!("Friend status of {name} changed"),
+        },
+        Event::WingJoin { others, .. } => match others.is_empty() {
+            true => "Joined a wing".to_owned(),
+            false => format!("Joined a wing with {}", others.join(", ")),
+        },
+        Event::WingAdd { name, .. } => format!("{name} joined the wing"),
+        Event::WingInvite { name, .. } => format!("Invited {name} to the wing"),
+        Event::WingLeave { .. } => "Left the wing".to_owned(),
+        Event::Touchdown { landing, .. } => format!(
+            "{} on {}",
+            if landing.player_controlled {
+                "Touched down"
+            } else {
+                "Ship landed"
+            },
+            landing.body.as_deref().unwrap_or("the surface")
+        ),
+        Event::Liftoff { landing, .. } => format!(
+            "{} from {}",
+            if landing.player_controlled {
+                "Lifted off"
+            } else {
+                "Ship dismissed"
+            },
+            landing.body.as_deref().unwrap_or("the surface")
+        ),
+        Event::LeaveBody { body, .. } => format!("Left {body}"),
+        Event::ApproachSettlement {
+            name,
+            name_localised,
+            body_name,
+            ..
+        } => format!(
+            "Approaching {} on {body_name}",
+            localised(name, name_localised)
+        ),
+        Event::CarrierJumpRequest { system_name, .. } => {
+            format!("Carrier jump to {system_name} requested")
+        }
+        Event::Died {
+            killer_name,
+            killer_name_localised,
+            killers,
+            ..
+        } => match (killer_name, killers.len()) {
+            (Some(name), _) => format!("Killed by {}", localised(name, killer_name_localised)),
+            (None, 0) => "Died".to_owned(),
+            (None, n) => format!("Killed by a wing of {n}"),
+        },
+        Event::Interdicted {
+            interdictor,
+            interdictor_localised,
+            submitted,
+            ..
+        } => format!(
+            "{} by {}",
+            if *submitted {
+                "Submitted to interdiction"
+            } else {
+                "Interdicted"
+            },
+            interdictor
+                .as_ref()
+                .map_or("unknown", |i| localised(i, interdictor_localised))
+        ),
+        Event::EscapeInterdiction {
+            interdictor,
+            interdictor_localised,
+            ..
+        } => format!(
+            "Escaped interdiction by {}",
+            interdictor
+                .as_ref()
+                .map_or("unknown", |i| localised(i, interdictor_localised))
+        ),
+        Event::Interdiction {
+            success,
+            interdicted,
+            interdicted_localised,
+            ..
+        } => format!(
+            "{} {}",
+            if *success {
+                "Interdicted"
+            } else {
+                "Failed to interdict"
+            },
+            interdicted
+                .as_ref()
+                .map_or("unknown", |i| localised(i, interdicted_localised))
+        ),
+        Event::ShipTargeted {
+            target_locked: true,
+            ship: Some(ship),
+            ship_localised,
+            pilot_name,
+            pilot_name_localised,
+            pilot_rank,
+            legal_status,
+            bounty,
+            ..
+        } => {
+            let mut line = format!("Targeted {}", localised(ship, ship_localised));
+            if let Some(pilot) = pilot_name {
+                line += &format!(", {}", localised(pilot, pilot_name_localised));
+            }
+            if let Some(rank) = pilot_rank {
+                line += &format!(" ({rank})");
+            }
+            if let Some(status) = legal_status {
+                line += &format!(", {status}");
+            }
+            if let Some(bounty) = bounty.filter(|b| *b > 0) {
+                line += &format!(" for {}", credits(bounty));
+            }
+            line
+        }
+        Event::HullDamage { health, .. } => format!("Hull at {:.0}%", health * 100.0),
+        Event::ShieldState { shields_up, .. } => match shields_up {
+            true => "Shields up".to_owned(),
+            false => "Shields down".to_owned(),
+        },
+        Event::UnderAttack { target, .. } => match target.as_deref() {
+            Some("You") | None => "Under attack".to_owned(),
+            Some(target) => format!("{target} under attack"),
+        },
+        Event::CockpitBreached { .. } => "Cockpit breached".to_owned(),
+        Event::FactionKillBond {
+            reward,
+            awarding_faction,
+            awarding_faction_localised,
+            ..
+        } => format!(
+            "{} from {}",
+            credits(*reward),
+            localised(awarding_faction, awarding_faction_localised)
+        ),
+        Event::PayFines { amount, .. }
+        | Event::PayBounties { amount, .. }
+        | Event::PayLegacyFines { amount, .. } => format!("Paid {}", credits(*amount)),
+        Event::SellOrganicData { bio_data, .. } => format!(
+            "Sold {} samples for {}",
+            bio_data.len(),
+            credits(bio_data.iter().map(|s| s.value + s.bonus).sum())
+        ),
+        Event::MissionAccepted {
+            name,
+            localised_name,
+            ..
+        } => format!("Accepted {}", localised(name, localised_name)),
+        Event::MissionCompleted { name, reward, .. } => match reward {
+            Some(reward) => format!("Completed {name} for {}", credits(*reward)),
+            None => format!("Completed {name}"),
+        },
+        Event::MissionFailed { name, .. } => format!("Failed {name}"),
+        Event::MissionAbandoned { name, .. } => format!("Abandoned {name}"),
+        Event::MissionRedirected {
+            name,
+            localised_name,
+            new_destination_station,
+            new_destination_system,
+            ..
+        } => format!(
+            "Hand in {} at {new_destination_station} in {new_destination_system}",
+            localised(name, localised_name)
+        ),
+        Event::CommunityGoalReward { name, reward, .. } => {
+            format!("{} from {}", credits(*reward), name)
+        }
+        Event::ReceiveText { from, message, .. } => format!("{from}: {message}"),
+        Event::SendText { to, message, .. } => format!("to {to}: {message}"),
+        _ => String::new(),
+    }
+}
+
+/// Formats events as one line each for reading in a terminal
+#[derive(Debug)]
+pub struct LogFormatter {
+    /// Print the full payload below each line
+    pub verbose: bool,
+    pub timezone: Timezone,
+    color: bool,
+}
+
+impl LogFormatter {
+    /// Colors are only used on terminals and when `NO_COLOR` isn't set
+    pub fn new(verbose: bool, timezone: Timezone) -> Self {
+        LogFormatter {
+            verbose,
+            timezone,
+            color: io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+        }
+    }
+
+    pub fn format(&self, event: &Event) -> String {
+        let json = serde_json::to_value(event).unwrap_or_default();
+        let name = json["event"].as_str().unwrap_or("Unknown");
+        let category = Category::of(name);
+        let time = self
+            .timezone
+            .convert(event.timestamp())
+            .format(format_description!(
+                "[year]-[month]-[day] [hour]:[minute]:[second]"
+            ))
+            .unwrap_or_default();
+
+        let label = format!("{:<11}", format!("{category:?}"));
+        let label = match self.color {
+            true => format!("\x1b[{}m{label}\x1b[0m", category.color()),
+            false => label,
+        };
+        let mut line = format!("{time}  {label}  {name:<24}  {}", summary(event));
+        line.truncate(line.trim_end().len());
+
+        if self.verbose {
+            if let Value::Object(mut fields) = json {
+                fields.remove("event");
+                fields.remove("timestamp");
+                for (key, value) in fields {
+                    line.push_str(&format!("\n    {key}: {value}"));
+                }
+            }
+        }
+
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credits() {
+        assert_eq!(credits(0), "0 CR");
+        assert_eq!(credits(999), "999 CR");
+        assert_eq!(credits(1_234_567), "1,234,567 CR");
+    }
+}
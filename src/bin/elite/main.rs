@@ -0,0 +1,3284 @@
+#[cfg(feature = "spansh")]
+use std::collections::HashSet;
+use std::{
+    cmp::Reverse,
+    error::Error,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use config::Config;
+#[cfg(feature = "notifications")]
+use elite::notifications::{Alert, NotificationConfig, Notifier};
+#[cfg(feature = "scripting")]
+use elite::scripting::Script;
+use elite::{
+    backup,
+    bgs::BgsContributions,
+    codex::Codex,
+    colonisation::Colonisation,
+    community_goals::CommunityGoals,
+    conflict_zones::ConflictZones,
+    cqc::{without_cqc, Cqc, CQC_RANKS},
+    credits::CreditLedger,
+    crew::{NpcCrew, COMBAT_RANKS},
+    crime::CrimeLedger,
+    engineers::{Check, EngineerAdvisor, Requirement, ENGINEERS},
+    exobiology::Exobiology,
+    exploration::Exploration,
+    firsts::Firsts,
+    fleet::Fleet,
+    friends::FriendsLog,
+    ground::GroundCombat,
+    http::HttpOptions,
+    journal,
+    macro_files::{MacroFiles, MacroFilesConfig},
+    map::TravelMap,
+    materials::{material_type, material_types, MaterialInventory},
+    mining::Mining,
+    networth::NetWorth,
+    odyssey::{next_upgrade, Armoury, MicroResourceHistory},
+    passengers::{MissionOutcome, PassengerMissions},
+    playtime::{Activity, Playtime},
+    powerplay::MeritLedger,
+    processor::{self, Processors, RunOptions},
+    ranks::{RankHistory, RankTrack},
+    rares::RareCargo,
+    rebuy::RebuyMonitor,
+    regions::{region_name, RegionMap, Regions},
+    reputation::{FactionReputation, ReputationChange},
+    route::{is_scoopable, RouteChange, RouteProgress},
+    rules::{self, RuleEngine, RuleProcessor},
+    screenshots::Screenshot,
+    ships::ship_name,
+    social::SocialHistory,
+    stats::EventCounts,
+    survey::Surveys,
+    synthesis::RECIPES,
+    system_states::SystemStates,
+    tech_broker::{TechBroker, BROKER_ITEMS},
+    thargoid::ThargoidCombat,
+    timezone::Timezone,
+    twitch::{Announcer, TwitchChat},
+};
+use human_panic::setup_panic;
+use log::LogFormatter;
+use output::{print_delimited, print_event, Delimited, OutputFormat, Report, Table};
+#[cfg(feature = "notifications")]
+use processors::DesktopNotifications;
+use processors::{show_notification, Printer};
+use serde_json::Value;
+use time::{macros::format_description, Date, OffsetDateTime};
+
+mod config;
+mod log;
+mod output;
+mod processors;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    setup_panic!();
+    let cli = Cli::parse();
+
+    let mut config = Config::load(cli.config.as_deref())?;
+    if cli.journal_dir.is_some() {
+        config.journal_dir = cli.journal_dir;
+    }
+    config.merge_dirs.extend(cli.merge);
+    if cli.source.is_some() {
+        config.source = cli.source;
+    }
+    if cli.commander.is_some() {
+        config.commander = cli.commander;
+    }
+    if cli.dedup {
+        config.dedup = true;
+    }
+    if cli.local {
+        config.timezone = Some("local".to_owned());
+    }
+    if cli.tz.is_some() {
+        config.timezone = cli.tz;
+    }
+    if cli.output.is_some() {
+        config.output = cli.output;
+    }
+    if let Some(dir) = &config.journal_dir {
+        let _ = journal::set_journals_path(dir.to_owned());
+    }
+    let output = config.output.unwrap_or_default();
+    let tz: Timezone = match &config.timezone {
+        Some(name) => name.parse()?,
+        None => Timezone::Utc,
+    };
+
+    match cli.command {
+        Commands::Config => {
+            if let Some(path) = cli.config.or_else(Config::default_path) {
+                println!("# {}", path.display());
+            }
+            print!("{}", toml::to_string_pretty(&config)?);
+        }
+        Commands::Path { command } => match command {
+            PathCommands::Backpack => println!("{}", journal::backpack_path().display()),
+            PathCommands::Cargo => println!("{}", journal::cargo_path().display()),
+            PathCommands::Market => println!("{}", journal::market_path().display()),
+            PathCommands::ModulesInfo => println!("{}", journal::modules_info_path().display()),
+            PathCommands::NavRoute => println!("{}", journal::nav_route_path().display()),
+            PathCommands::Outfitting => println!("{}", journal::outfitting_path().display()),
+            PathCommands::ShipLocker => println!("{}", journal::ship_locker_path().display()),
+            PathCommands::Shipyard => println!("{}", journal::shipyard_path().display()),
+            PathCommands::Status => println!("{}", journal::status_path().display()),
+            PathCommands::LatestJournal => {
+                println!("{}", journal::latest_journal_path()?.display())
+            }
+        },
+        Commands::Log { range, verbose } => {
+            let formatter = LogFormatter::new(verbose, tz);
+            for event in range.events(&config)? {
+                print_event(&event, output, &formatter)?;
+            }
+        }
+        Commands::ChatHistory { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut table = Table::new("Chat", &["Time", "Channel", "From", "To", "Message"]);
+
+            for event in range.events(&config)? {
+                match event {
+                    journal::Event::SendText {
+                        timestamp,
+                        to,
+                        message,
+                    } => table.row([
+                        tz.convert(timestamp).format(format)?.into(),
+                        Value::Null,
+                        "me".into(),
+                        to.into(),
+                        message.into(),
+                    ]),
+                    journal::Event::ReceiveText {
+                        timestamp,
+                        from,
+                        message,
+                        channel,
+                    } => table.row([
+                        tz.convert(timestamp).format(format)?.into(),
+                        format!("{channel:?}").into(),
+                        from.into(),
+                        Value::Null,
+                        message.into(),
+                    ]),
+                    _ => {}
+                }
+            }
+
+            print_table(table, output)?;
+        }
+        Commands::Exobio { range, min_signals } => {
+            let mut exobiology = Exobiology::default();
+            for event in range.events(&config)? {
+                exobiology.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut species_table = Table::new(
+                "Species",
+                &["Species", "Genus", "Analysed", "Sold", "Credits"],
+            );
+            for (name, species) in &exobiology.species {
+                species_table.row([
+                    name.as_str().into(),
+                    species.genus.as_str().into(),
+                    species.analysed.into(),
+                    species.sold.into(),
+                    (species.value + species.bonus).into(),
+                ]);
+            }
+            report.add(species_table);
+
+            let mut totals = Table::new(
+                "Totals",
+                &[
+                    "Credits earned",
+                    "First logged bonuses",
+                    "Bodies with biological signals",
+                    "In progress",
+                ],
+            );
+            totals.row([
+                exobiology.credits().into(),
+                exobiology
+                    .species
+                    .values()
+                    .map(|s| s.bonus)
+                    .sum::<u64>()
+                    .into(),
+                exobiology.bio_signals.len().into(),
+                exobiology
+                    .in_progress
+                    .as_ref()
+                    .map(|(sample, progress)| format!("{} ({:?})", sample.name, progress))
+                    .into(),
+            ]);
+            report.add(totals);
+
+            // Analysed but unsold samples are lost on death
+            let mut at_risk = Table::new("At risk", &["Species", "System address", "Body"]);
+            for sample in &exobiology.unsold {
+                at_risk.row([
+                    sample.name.as_str().into(),
+                    sample.system_address.into(),
+                    sample.body.into(),
+                ]);
+            }
+            report.add(at_risk);
+
+            if let Some(min) = min_signals {
+                let mut bodies = Table::new("Bio signals", &["Body", "Signals", "Genera"]);
+                for (body, count) in exobiology.bodies_with_bio_signals(min) {
+                    bodies.row([
+                        body.into(),
+                        count.into(),
+                        exobiology.genuses.get(body).map(|g| g.join(", ")).into(),
+                    ]);
+                }
+                report.add(bodies);
+            }
+
+            report.print(output)?;
+        }
+        Commands::Exploration(args) => {
+            let mut exploration = Exploration::default();
+            for event in args.range.events(&config)? {
+                exploration.update(&event);
+            }
+
+            #[cfg(feature = "edsm")]
+            let edsm = elite::edsm::Client::default();
+            let mut report = Report::default();
+            let mut unsold = Table::new(
+                "Unsold",
+                &[
+                    "System",
+                    "Bodies scanned",
+                    "Scan value",
+                    "EDSM value",
+                    "EDSM mapped value",
+                ],
+            );
+            for (system, scans) in &exploration.unsold {
+                #[cfg(feature = "edsm")]
+                let estimate = match args.edsm {
+                    true => edsm
+                        .estimated_value(system)?
+                        .map(|e| (e.estimated_value, e.estimated_value_mapped)),
+                    false => None,
+                };
+                #[cfg(not(feature = "edsm"))]
+                let estimate: Option<(u64, u64)> = None;
+
+                unsold.row([
+                    system.as_str().into(),
+                    (*scans).into(),
+                    exploration.unsold_value(system).into(),
+                    estimate.map(|e| e.0).into(),
+                    estimate.map(|e| e.1).into(),
+                ]);
+            }
+            report.add(unsold);
+
+            let mut totals = Table::new(
+                "Totals",
+                &["Credits earned", "Unsold scan value", "Systems lost"],
+            );
+            let unsold_value: u64 = exploration
+                .unsold
+                .keys()
+                .map(|system| exploration.unsold_value(system))
+                .sum();
+            totals.row([
+                exploration.earned.into(),
+                unsold_value.into(),
+                exploration.lost.len().into(),
+            ]);
+            report.add(totals);
+
+            report.print(output)?;
+        }
+        Commands::Diff { a, b } => {
+            let diffs = journal::diff_events(&a.raw_events()?, &b.raw_events()?);
+            let mut table = Table::new("Diff", &["Time", "Event", "Change", "Fields"]);
+            for diff in &diffs {
+                let (change, fields) = match diff {
+                    journal::EventDiff::Added(_) => ("added", Value::Null),
+                    journal::EventDiff::Removed(_) => ("removed", Value::Null),
+                    journal::EventDiff::Changed { fields, .. } => {
+                        ("changed", fields.join(", ").into())
+                    }
+                };
+                let event = diff.event();
+                table.row([
+                    event["timestamp"].clone(),
+                    event["event"].clone(),
+                    change.into(),
+                    fields,
+                ]);
+            }
+
+            print_table(table, output)?;
+        }
+        Commands::Extract {
+            event,
+            fields,
+            format,
+            range,
+        } => {
+            let headers: Vec<String> = fields.iter().map(ToString::to_string).collect();
+            let rows: Vec<Vec<Value>> = range
+                .raw_events(&config)?
+                .iter()
+                .filter(|e| event.as_ref().is_none_or(|kind| e["event"] == **kind))
+                .map(|e| {
+                    fields
+                        .iter()
+                        .map(|field| field.get(e).cloned().unwrap_or_default())
+                        .collect()
+                })
+                .collect();
+
+            print_delimited(&headers, &rows, format)?;
+        }
+        Commands::Export { command } => match command {
+            ExportCommands::Map { range, format } => {
+                let mut map = TravelMap::default();
+                for event in range.events(&config)? {
+                    map.update(&event);
+                }
+
+                match format {
+                    MapFormat::Geojson => {
+                        println!("{}", serde_json::to_string_pretty(&map.to_geojson())?)
+                    }
+                    MapFormat::Svg => println!("{}", map.to_svg(1000)),
+                    MapFormat::Csv => {
+                        let mut table = Table::new("Visits", &["Time", "System", "X", "Y", "Z"]);
+                        for visit in &map.visits {
+                            let [x, y, z] = visit.star_pos;
+                            table.row([
+                                tz.convert(visit.timestamp)
+                                    .format(format_description!(
+                                        "[year]-[month]-[day] [hour]:[minute]:[second]"
+                                    ))?
+                                    .into(),
+                                visit.system.as_str().into(),
+                                x.into(),
+                                y.into(),
+                                z.into(),
+                            ]);
+                        }
+                        print_table(table, OutputFormat::Csv)?;
+                    }
+                }
+            }
+        },
+        Commands::Firsts { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut firsts = Firsts::default();
+            for event in range.events(&config)? {
+                firsts.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut bodies = Table::new(
+                "Bodies",
+                &[
+                    "Body",
+                    "System",
+                    "Scanned",
+                    "First discovered",
+                    "First mapped",
+                    "Probes",
+                    "Sold",
+                ],
+            );
+            for first in firsts.bodies.values() {
+                bodies.row([
+                    first.body.as_str().into(),
+                    first.system.as_deref().into(),
+                    tz.convert(first.scanned).format(format)?.into(),
+                    first.discovered.into(),
+                    first.mapped.is_some().into(),
+                    first
+                        .probes
+                        .map(|(used, target)| format!("{used}/{target}"))
+                        .into(),
+                    first
+                        .sold
+                        .map(|t| tz.convert(t).format(format))
+                        .transpose()?
+                        .into(),
+                ]);
+            }
+            report.add(bodies);
+
+            let mut sales = Table::new("Sales", &["Time", "Firsts", "Bonus"]);
+            for sale in &firsts.sales {
+                sales.row([
+                    tz.convert(sale.timestamp).format(format)?.into(),
+                    sale.bodies.into(),
+                    sale.bonus.into(),
+                ]);
+            }
+            report.add(sales);
+
+            report.print(output)?;
+        }
+        Commands::Friends { range, history } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut friends = FriendsLog::default();
+            for event in range.events(&config)? {
+                friends.update(&event);
+            }
+
+            let table = if history {
+                let mut table = Table::new("Sightings", &["Time", "Name", "Status"]);
+                for sighting in &friends.sightings {
+                    table.row([
+                        tz.convert(sighting.timestamp).format(format)?.into(),
+                        sighting.name.as_str().into(),
+                        format!("{:?}", sighting.status).into(),
+                    ]);
+                }
+                table
+            } else {
+                let mut table = Table::new(
+                    "Friends",
+                    &["Name", "Status", "Times online", "Last online"],
+                );
+                for (name, summary) in &friends.friends {
+                    table.row([
+                        name.as_str().into(),
+                        format!("{:?}", summary.last_status).into(),
+                        summary.times_online.into(),
+                        summary
+                            .last_online
+                            .map(|t| tz.convert(t).format(format))
+                            .transpose()?
+                            .into(),
+                    ]);
+                }
+                table
+            };
+
+            print_table(table, output)?;
+        }
+        Commands::Materials { command } => {
+            let mut inventory = MaterialInventory::default();
+            for event in DateRange::default().events(&config)? {
+                inventory.update(&event);
+            }
+
+            match command {
+                MaterialsCommands::Inventory => {
+                    let mut table = Table::new(
+                        "Materials",
+                        &["Kind", "Category", "Grade", "Material", "Count"],
+                    );
+                    for material in material_types() {
+                        let count = inventory.count(material.symbol);
+                        if count > 0 {
+                            table.row([
+                                material.kind.into(),
+                                material.category.into(),
+                                material.grade.into(),
+                                material.name.into(),
+                                count.into(),
+                            ]);
+                        }
+                    }
+                    print_table(table, output)?;
+                }
+                MaterialsCommands::Plan { material, count } => {
+                    let target = material_type(&material)
+                        .ok_or_else(|| format!("Unknown material {material:?}"))?;
+                    let plan = inventory.plan(target, count).ok_or_else(|| {
+                        format!("Not enough materials to trade for {count} {}", target.name)
+                    })?;
+
+                    let mut table = Table::new("Trades", &["Give", "Count", "Receive", "Count"]);
+                    for trade in plan {
+                        table.row([
+                            trade.give.name.into(),
+                            trade.give_count.into(),
+                            trade.receive.name.into(),
+                            trade.receive_count.into(),
+                        ]);
+                    }
+                    print_table(table, output)?;
+                }
+            }
+        }
+        Commands::Odyssey { command } => match command {
+            OdysseyCommands::Upgrades { range } => {
+                let mut armoury = Armoury::default();
+                for event in range.events(&config)? {
+                    armoury.update(&event);
+                }
+
+                let mut gear: Vec<_> = armoury.gear.values().collect();
+                gear.sort_by_key(|gear| (gear.kind as u8, gear.name.as_str(), gear.grade));
+                let mut table =
+                    Table::new("Upgrades", &["Kind", "Name", "Grade", "Cost", "Missing"]);
+                for gear in gear {
+                    let Some(upgrade) = next_upgrade(gear.kind, gear.grade) else {
+                        continue;
+                    };
+                    let missing: Vec<String> = armoury
+                        .missing(upgrade)
+                        .iter()
+                        .map(|(name, count)| format!("{count} {name}"))
+                        .collect();
+                    table.row([
+                        format!("{:?}", gear.kind).into(),
+                        gear.name.as_str().into(),
+                        format!("{} -> {}", gear.grade, upgrade.grade).into(),
+                        upgrade.credits.into(),
+                        missing.join(", ").into(),
+                    ]);
+                }
+                print_table(table, output)?;
+            }
+            OdysseyCommands::Resources { range, item } => {
+                let mut history = MicroResourceHistory::default();
+                for event in range.events(&config)? {
+                    history.update(&event);
+                }
+
+                let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+                let mut report = Report::default();
+                let mut changes = Table::new("Changes", &["Time", "Item", "Change", "Cause"]);
+                for change in &history.changes {
+                    if item
+                        .as_ref()
+                        .is_some_and(|item| !change.name.eq_ignore_ascii_case(item))
+                    {
+                        continue;
+                    }
+                    changes.row([
+                        tz.convert(change.timestamp).format(format)?.into(),
+                        change.name.as_str().into(),
+                        change.change.into(),
+                        change.cause.into(),
+                    ]);
+                }
+                report.add(changes);
+
+                if let Some(item) = &item {
+                    let mut causes = Table::new("Causes", &["Cause", "Gained", "Lost"]);
+                    for (cause, gained, lost) in history.summary(item) {
+                        causes.row([cause.into(), gained.into(), lost.into()]);
+                    }
+                    report.add(causes);
+                }
+                report.print(output)?;
+            }
+        },
+        Commands::States { range, min_visits } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut states = SystemStates::default();
+            for event in range.events(&config)? {
+                states.update(&event);
+            }
+
+            let mut table = Table::new(
+                "State changes",
+                &["Time", "System", "Faction", "From", "To"],
+            );
+            for change in &states.changes {
+                if states.visits.get(&change.system).copied().unwrap_or(0) < min_visits {
+                    continue;
+                }
+                table.row([
+                    tz.convert(change.timestamp).format(format)?.into(),
+                    change.system.as_str().into(),
+                    change.faction.as_str().into(),
+                    change.from.as_deref().into(),
+                    change.to.as_deref().into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Synth => {
+            let mut inventory = MaterialInventory::default();
+            for event in DateRange::default().events(&config)? {
+                inventory.update(&event);
+            }
+
+            let mut table = Table::new(
+                "Synthesis",
+                &["Recipe", "Grade", "Effect", "Craftable", "Missing"],
+            );
+            for recipe in RECIPES {
+                let missing: Vec<String> = recipe
+                    .missing(&inventory)
+                    .iter()
+                    .map(|(material, count)| format!("{count} {}", material.name))
+                    .collect();
+                table.row([
+                    recipe.name.into(),
+                    recipe.grade.into(),
+                    recipe.effect.into(),
+                    recipe.craftable(&inventory).into(),
+                    missing.join(", ").into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        #[cfg(feature = "market")]
+        Commands::Market { command } => {
+            let path = config
+                .market_db_path()
+                .ok_or("No directory for the market database")?;
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let mut db = elite::market::MarketDb::open(&path)?;
+
+            match command {
+                MarketCommands::Collect { follow } => {
+                    let mut record = |event: &journal::Event| -> Result<(), Box<dyn Error>> {
+                        if db.record(event)? {
+                            if let journal::Event::Market {
+                                station_name,
+                                star_system,
+                                ..
+                            } = event
+                            {
+                                println!("Saved prices of {station_name} in {star_system}");
+                            }
+                        }
+                        Ok(())
+                    };
+
+                    record(&journal::read_market()?)?;
+                    if follow {
+                        let mut tail = journal::JournalTail::new()?;
+                        let mut pending = false;
+                        loop {
+                            // Market.json is written before the event
+                            pending |= tail
+                                .poll()?
+                                .iter()
+                                .any(|event| matches!(event, journal::Event::Market { .. }));
+                            if pending {
+                                match journal::read_market() {
+                                    Ok(market) => {
+                                        record(&market)?;
+                                        pending = false;
+                                    }
+                                    // Caught while the game rewrites it, read again next time
+                                    Err(e)
+                                        if matches!(
+                                            e.kind(),
+                                            std::io::ErrorKind::InvalidData
+                                                | std::io::ErrorKind::UnexpectedEof
+                                        ) => {}
+                                    Err(e) => return Err(e.into()),
+                                }
+                            }
+                            thread::sleep(std::time::Duration::from_secs(1));
+                        }
+                    }
+                }
+                MarketCommands::History { commodity, station } => {
+                    let market_id = match station {
+                        Some(station) => Some(
+                            db.find_market(&station)?
+                                .ok_or_else(|| format!("No prices saved for {station}"))?,
+                        ),
+                        None => None,
+                    };
+
+                    let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+                    let mut table = Table::new(
+                        "Prices",
+                        &[
+                            "Time", "Station", "System", "Buy", "Sell", "Stock", "Demand",
+                        ],
+                    );
+                    for point in db.price_history(&commodity, market_id)? {
+                        table.row([
+                            tz.convert(point.timestamp).format(format)?.into(),
+                            point.station_name.into(),
+                            point.star_system.into(),
+                            point.buy_price.into(),
+                            point.sell_price.into(),
+                            point.stock.into(),
+                            point.demand.into(),
+                        ]);
+                    }
+                    print_table(table, output)?;
+                }
+            }
+        }
+        #[cfg(feature = "market")]
+        Commands::Trade { command } => match command {
+            TradeCommands::Suggest {
+                max_age,
+                max_jumps,
+                limit,
+            } => {
+                let mut positions = std::collections::HashMap::new();
+                let mut ship = None;
+                for event in DateRange::default().events(&config)? {
+                    match event {
+                        journal::Event::FSDJump {
+                            star_system,
+                            star_pos,
+                            ..
+                        }
+                        | journal::Event::Location {
+                            star_system,
+                            star_pos,
+                            ..
+                        } => {
+                            positions.insert(star_system, star_pos);
+                        }
+                        journal::Event::Loadout {
+                            cargo_capacity,
+                            max_jump_range,
+                            ..
+                        } => ship = Some((cargo_capacity, max_jump_range)),
+                        _ => {}
+                    }
+                }
+                let (cargo_capacity, jump_range) = ship.ok_or("No ship loadout in the journals")?;
+
+                let path = config
+                    .market_db_path()
+                    .ok_or("No directory for the market database")?;
+                let db = elite::market::MarketDb::open(&path)?;
+                let since = OffsetDateTime::now_utc() - time::Duration::hours(max_age);
+                let snapshots = db.latest_snapshots(since)?;
+
+                let mut table = Table::new(
+                    "Trades",
+                    &[
+                        "From",
+                        "System",
+                        "To",
+                        "System",
+                        "Commodity",
+                        "Buy",
+                        "Sell",
+                        "Units",
+                        "Profit",
+                        "Distance",
+                        "Jumps",
+                    ],
+                );
+                for route in elite::market::suggest_trades(
+                    &snapshots,
+                    &positions,
+                    cargo_capacity.into(),
+                    jump_range.into(),
+                )
+                .into_iter()
+                .filter(|route| max_jumps.is_none_or(|max| route.jumps <= max))
+                .take(limit)
+                {
+                    table.row([
+                        route.from.into(),
+                        route.from_system.into(),
+                        route.to.into(),
+                        route.to_system.into(),
+                        route.commodity.into(),
+                        route.buy_price.into(),
+                        route.sell_price.into(),
+                        route.units.into(),
+                        route.profit.into(),
+                        ((route.distance * 100.0).round() / 100.0).into(),
+                        route.jumps.into(),
+                    ]);
+                }
+                print_table(table, output)?;
+            }
+        },
+        Commands::Regions { range } => {
+            let mut regions = match &config.region_map {
+                Some(path) => Regions::new(RegionMap::load(path)?),
+                None => Regions::default(),
+            };
+            for event in range.events(&config)? {
+                regions.update(&event);
+            }
+
+            let mut table = Table::new("Regions", &["Region", "Systems", "Jumps", "Hours"]);
+            for (id, summary) in regions.summary() {
+                table.row([
+                    id.and_then(region_name).unwrap_or("Unknown").into(),
+                    summary.systems.into(),
+                    summary.jumps.into(),
+                    ((summary.time.as_seconds_f64() / 360.0).round() / 10.0).into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Rebuy { count } => {
+            let mut monitor = RebuyMonitor::default();
+            for event in DateRange::default().events(&config)? {
+                monitor.update(&event);
+            }
+
+            let mut table = Table::new(
+                "Rebuy",
+                &["Rebuy", "Credits", "Affordable rebuys", "Can afford"],
+            );
+            table.row([
+                monitor.rebuy.into(),
+                monitor.balance.credits.into(),
+                monitor.affordable_rebuys().into(),
+                monitor.can_afford(count).into(),
+            ]);
+            print_table(table, output)?;
+        }
+        Commands::Ranks { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut history = RankHistory::default();
+            for event in range.events(&config)? {
+                history.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut ranks = Table::new("Ranks", &["Track", "Rank", "Progress", "Promoted"]);
+            for track in RankTrack::ALL {
+                let Some(rank) = history.ranks.get(&track) else {
+                    continue;
+                };
+                let promoted = match history.last_promotion(track) {
+                    Some(promotion) => Some(tz.convert(promotion.timestamp).format(format)?),
+                    None => None,
+                };
+                ranks.row([
+                    track.name().into(),
+                    track
+                        .rank_name(*rank)
+                        .map_or(rank.to_string(), str::to_owned)
+                        .into(),
+                    history.progress.get(&track).copied().into(),
+                    promoted.into(),
+                ]);
+            }
+            report.add(ranks);
+
+            let mut promotions = Table::new("Promotions", &["Time", "Track", "Rank"]);
+            for promotion in &history.promotions {
+                promotions.row([
+                    tz.convert(promotion.timestamp).format(format)?.into(),
+                    promotion.track.name().into(),
+                    promotion
+                        .track
+                        .rank_name(promotion.rank)
+                        .map_or(promotion.rank.to_string(), str::to_owned)
+                        .into(),
+                ]);
+            }
+            report.add(promotions);
+
+            report.print(output)?;
+        }
+        Commands::Rares { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut rares = RareCargo::default();
+            for event in range.events(&config)? {
+                rares.update(&event);
+            }
+
+            let mut table = Table::new(
+                "Rare goods",
+                &[
+                    "Commodity",
+                    "Count",
+                    "Bought",
+                    "Station",
+                    "System",
+                    "Paid",
+                    "Distance",
+                    "Estimated price",
+                    "Estimated value",
+                ],
+            );
+            for lot in &rares.lots {
+                let distance = rares.star_pos.and_then(|pos| lot.distance(pos));
+                let price = distance.map(|d| lot.estimated_price(d));
+                table.row([
+                    lot.name.as_str().into(),
+                    lot.count.into(),
+                    tz.convert(lot.bought).format(format)?.into(),
+                    lot.station.as_deref().into(),
+                    lot.system.as_deref().into(),
+                    lot.buy_price.into(),
+                    distance.map(|d| (d * 100.0).round() / 100.0).into(),
+                    price.into(),
+                    price.map(|p| p * u64::from(lot.count)).into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Passengers { range } => {
+            let format = format_description!("[year]-[month]-[day]");
+            let mut passengers = PassengerMissions::default();
+            for event in range.events(&config)? {
+                passengers.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut table = Table::new(
+                "Missions",
+                &[
+                    "Accepted",
+                    "Type",
+                    "Count",
+                    "VIP",
+                    "Wanted",
+                    "Origin",
+                    "Destination",
+                    "Distance",
+                    "Reward",
+                    "Outcome",
+                ],
+            );
+            for mission in passengers.missions.values() {
+                table.row([
+                    tz.convert(mission.accepted).format(format)?.into(),
+                    mission.passenger_type.as_deref().into(),
+                    mission.count.into(),
+                    mission.vip.into(),
+                    mission.wanted.into(),
+                    mission.origin.as_deref().into(),
+                    mission.destination.as_deref().into(),
+                    passengers
+                        .distance(mission)
+                        .map(|d| (d * 10.0).round() / 10.0)
+                        .into(),
+                    mission.reward.into(),
+                    format!("{:?}", mission.outcome).into(),
+                ]);
+            }
+            report.add(table);
+
+            let missions: Vec<_> = passengers.missions.values().collect();
+            let count = |outcome| missions.iter().filter(|m| m.outcome == outcome).count();
+            let mut totals = Table::new(
+                "Totals",
+                &[
+                    "VIP missions",
+                    "Bulk missions",
+                    "Wanted passengers",
+                    "Payouts",
+                    "Completed",
+                    "Failed",
+                    "Expired",
+                    "Abandoned",
+                    "Active",
+                ],
+            );
+            totals.row([
+                missions.iter().filter(|m| m.vip).count().into(),
+                missions.iter().filter(|m| !m.vip).count().into(),
+                missions
+                    .iter()
+                    .filter(|m| m.wanted)
+                    .map(|m| m.count)
+                    .sum::<u32>()
+                    .into(),
+                missions
+                    .iter()
+                    .filter(|m| m.outcome == MissionOutcome::Completed)
+                    .map(|m| m.reward)
+                    .sum::<u64>()
+                    .into(),
+                count(MissionOutcome::Completed).into(),
+                count(MissionOutcome::Failed).into(),
+                count(MissionOutcome::Expired).into(),
+                count(MissionOutcome::Abandoned).into(),
+                count(MissionOutcome::Active).into(),
+            ]);
+            report.add(totals);
+
+            report.print(output)?;
+        }
+        Commands::Time { range } => {
+            let mut playtime = Playtime::default();
+            for session in journal::journal_sessions(&range.files()?)? {
+                for event in session.events()? {
+                    if range.contains(event.timestamp()) {
+                        playtime.update(&event);
+                    }
+                }
+            }
+
+            const ACTIVITIES: [Activity; 7] = [
+                Activity::Docked,
+                Activity::Supercruise,
+                Activity::Flying,
+                Activity::OnFoot,
+                Activity::Combat,
+                Activity::Mining,
+                Activity::Exploring,
+            ];
+            let hours = |d: Option<&time::Duration>| {
+                (d.map_or(0.0, |d| d.as_seconds_f64()) / 360.0).round() / 10.0
+            };
+            let mut table = Table::new(
+                "Hours",
+                &[
+                    "Week",
+                    "Docked",
+                    "Supercruise",
+                    "Flying",
+                    "On foot",
+                    "Combat",
+                    "Mining",
+                    "Exploring",
+                    "Total",
+                ],
+            );
+            let format = format_description!("[year]-[month]-[day]");
+            for (week, activities) in &playtime.weeks(|t| tz.convert(t)) {
+                let hours = ACTIVITIES.map(|a| hours(activities.get(&a)));
+                table.row([
+                    week.format(format)?.into(),
+                    hours[0].into(),
+                    hours[1].into(),
+                    hours[2].into(),
+                    hours[3].into(),
+                    hours[4].into(),
+                    hours[5].into(),
+                    hours[6].into(),
+                    ((hours.iter().sum::<f64>() * 10.0).round() / 10.0).into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Reputation { range, faction } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut reputation = FactionReputation::default();
+            for event in range.events(&config)? {
+                reputation.update(&event);
+            }
+
+            let mut table = Table::new(
+                "Reputation",
+                &["Time", "Faction", "Source", "Reputation", "Change"],
+            );
+            for entry in &reputation.history {
+                if faction
+                    .as_ref()
+                    .is_some_and(|faction| !entry.faction.eq_ignore_ascii_case(faction))
+                {
+                    continue;
+                }
+                let (source, reputation, change) = match &entry.change {
+                    ReputationChange::Seen {
+                        system,
+                        reputation,
+                        change,
+                    } => (
+                        system.as_str(),
+                        Value::from(*reputation),
+                        change.map(|c| format!("{c:+.1}")).into(),
+                    ),
+                    ReputationChange::Mission { name, pluses } => (
+                        name.as_str(),
+                        Value::Null,
+                        "+".repeat(*pluses as usize).into(),
+                    ),
+                };
+                table.row([
+                    tz.convert(entry.timestamp).format(format)?.into(),
+                    entry.faction.as_str().into(),
+                    source.into(),
+                    reputation,
+                    change,
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Powerplay { range } => {
+            let format = format_description!("[year]-[month]-[day]");
+            let mut ledger = MeritLedger::default();
+            for event in range.events(&config)? {
+                ledger.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut weeks = Table::new(
+                "Weeks",
+                &["Week of", "Merits", "Rank", "Promotions", "Activities"],
+            );
+            for (start, week) in &ledger.weeks {
+                let activities = week
+                    .merits
+                    .iter()
+                    .map(|(activity, merits)| format!("{activity} {merits}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                weeks.row([
+                    start.format(format)?.into(),
+                    week.total_merits().into(),
+                    week.rank.into(),
+                    week.promotions.into(),
+                    activities.into(),
+                ]);
+            }
+            report.add(weeks);
+
+            let mut totals = Table::new("Pledge", &["Power", "Rank", "Merits"]);
+            totals.row([
+                ledger.power.as_deref().into(),
+                ledger.rank.into(),
+                ledger.total_merits.into(),
+            ]);
+            report.add(totals);
+
+            report.print(output)?;
+        }
+        Commands::Thargoids { range } => {
+            let format = format_description!("[year]-[month]-[day]");
+            let mut combat = ThargoidCombat::default();
+            for event in range.events(&config)? {
+                combat.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut weeks = Table::new(
+                "Weeks",
+                &["Week of", "Interceptors", "Bonds", "Deaths", "Kills"],
+            );
+            for (start, week) in &combat.weeks {
+                let interceptors: u32 = week
+                    .kills
+                    .iter()
+                    .filter(|(v, _)| v.is_interceptor())
+                    .map(|(_, n)| n)
+                    .sum();
+                let kills = week
+                    .kills
+                    .iter()
+                    .map(|(v, n)| format!("{v} x{n}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                weeks.row([
+                    start.format(format)?.into(),
+                    interceptors.into(),
+                    week.bonds.into(),
+                    week.deaths.into(),
+                    kills.into(),
+                ]);
+            }
+            report.add(weeks);
+
+            let mut totals = Table::new("Totals", &["Kills", "Bonds"]);
+            totals.row([combat.total_kills().into(), combat.total_bonds().into()]);
+            report.add(totals);
+
+            report.print(output)?;
+        }
+        Commands::Anonymize { out_dir, range } => {
+            std::fs::create_dir_all(&out_dir)?;
+            let mut anonymizer = journal::Anonymizer::default();
+            for path in range.files()? {
+                let mut contents = String::new();
+                for line in std::fs::read_to_string(&path)?.lines() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    contents.push_str(&anonymizer.line(line)?);
+                    contents.push_str("\r\n");
+                }
+                let out = out_dir.join(path.file_name().unwrap());
+                std::fs::write(&out, contents)?;
+                println!("{}", out.display());
+            }
+        }
+        Commands::Backup(args) => {
+            let damaged = match (&args.restore, args.verify) {
+                (Some(target), _) => {
+                    let (restored, damaged) = backup::restore(&args.dest, target, args.overwrite)?;
+                    println!("Restored {} files to {}", restored.len(), target.display());
+                    damaged
+                }
+                (None, true) => {
+                    let manifest = backup::Manifest::read(&args.dest)?;
+                    let damaged = backup::verify(&args.dest)?;
+                    println!(
+                        "Checked {} files backed up until {}",
+                        manifest.files.len(),
+                        tz.convert(manifest.updated)
+                            .format(format_description!("[year]-[month]-[day] [hour]:[minute]"))?
+                    );
+                    damaged
+                }
+                (None, false) => {
+                    let summary = backup::backup(&journal::journals_path(), &args.dest)?;
+                    for name in &summary.copied {
+                        println!("{name}");
+                    }
+                    println!(
+                        "Backed up {} files, {} unchanged",
+                        summary.copied.len(),
+                        summary.unchanged
+                    );
+                    Vec::new()
+                }
+            };
+
+            if !damaged.is_empty() {
+                for damage in &damaged {
+                    match damage {
+                        backup::Damage::Missing(name) => eprintln!("Missing: {name}"),
+                        backup::Damage::Corrupt(name) => eprintln!("Corrupt: {name}"),
+                    }
+                }
+                return Err(format!("{} damaged files in the backup", damaged.len()).into());
+            }
+        }
+        Commands::Aggregate {
+            event,
+            group_by,
+            sum,
+            avg,
+            min,
+            max,
+            range,
+        } => {
+            type Column = fn(&journal::FieldStats) -> Value;
+            let columns: Vec<(&str, Column, journal::FieldPath)> = [
+                ("sum", (|s| whole_number(s.sum)) as Column, sum),
+                ("avg", |s| s.avg().into(), avg),
+                ("min", |s| s.min.map(whole_number).into(), min),
+                ("max", |s| s.max.map(whole_number).into(), max),
+            ]
+            .into_iter()
+            .flat_map(|(name, stat, fields)| fields.into_iter().map(move |f| (name, stat, f)))
+            .collect();
+
+            let mut headers: Vec<String> = group_by.iter().map(ToString::to_string).collect();
+            headers.push("Count".to_owned());
+            headers.extend(
+                columns
+                    .iter()
+                    .map(|(name, _, field)| format!("{name}({field})")),
+            );
+
+            let fields = columns.iter().map(|(_, _, field)| field.clone()).collect();
+            let mut aggregation = journal::Aggregation::new(group_by, fields);
+            for e in range.raw_events(&config)? {
+                if event.as_ref().is_none_or(|kind| e["event"] == **kind) {
+                    aggregation.add(&e);
+                }
+            }
+
+            let mut table = Table::with_headers("Groups", headers);
+            for (key, group) in &aggregation.groups {
+                let mut cells: Vec<Value> = key.iter().map(|k| k.as_str().into()).collect();
+                cells.push(group.events.into());
+                cells.extend(
+                    columns
+                        .iter()
+                        .zip(&group.fields)
+                        .map(|((_, stat, _), stats)| stat(stats)),
+                );
+                table.push(cells);
+            }
+
+            print_table(table, output)?;
+        }
+        Commands::Bgs { range } => {
+            let mut bgs = BgsContributions::default();
+            for event in range.events(&config)? {
+                bgs.update(&event);
+            }
+
+            let mut table = Table::new(
+                "Contributions",
+                &[
+                    "Day",
+                    "Faction",
+                    "System",
+                    "Missions",
+                    "Influence",
+                    "Reputation",
+                ],
+            );
+            let format = format_description!("[year]-[month]-[day]");
+            for ((day, faction, system), contribution) in bgs.daily(|t| tz.convert(t).date()) {
+                table.row([
+                    day.format(format)?.into(),
+                    faction.into(),
+                    system.into(),
+                    contribution.missions.into(),
+                    contribution.influence.into(),
+                    contribution.reputation.into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Bodies { range, incomplete } => {
+            let mut surveys = Surveys::default();
+            for event in range.events(&config)? {
+                surveys.update(&event);
+            }
+
+            let mut table = Table::new(
+                "Systems",
+                &["System", "Bodies", "Scanned", "Remaining", "All found"],
+            );
+            for survey in surveys.systems.values() {
+                if incomplete && survey.complete() {
+                    continue;
+                }
+                table.row([
+                    survey.system.as_str().into(),
+                    survey.body_count.into(),
+                    survey.scanned.len().into(),
+                    survey.remaining().into(),
+                    survey.all_found.into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Cg { range } => {
+            let format = format_description!("[year]-[month]-[day]");
+            let mut community_goals = CommunityGoals::default();
+            for event in range.events(&config)? {
+                community_goals.update(&event);
+            }
+
+            let now = OffsetDateTime::now_utc();
+            let mut table = Table::new(
+                "Community goals",
+                &[
+                    "Title",
+                    "System",
+                    "Market",
+                    "Current",
+                    "Tier",
+                    "Contribution",
+                    "Top percent",
+                    "Expires",
+                    "Reward",
+                ],
+            );
+            for goal in community_goals.goals.values() {
+                table.row([
+                    goal.title.as_str().into(),
+                    goal.system.as_str().into(),
+                    goal.market.as_deref().into(),
+                    goal.is_current(now).into(),
+                    goal.tier.as_deref().into(),
+                    goal.contribution.into(),
+                    goal.percentile_band.into(),
+                    goal.expiry
+                        .map(|e| tz.convert(e).format(format))
+                        .transpose()?
+                        .into(),
+                    goal.reward.into(),
+                ]);
+            }
+
+            print_table(table, output)?;
+        }
+        Commands::Codex { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut codex = Codex::default();
+            for event in range.events(&config)? {
+                codex.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut summary = Table::new(
+                "Regions",
+                &["Region", "Category", "Logged", "New entries", "Vouchers"],
+            );
+            for ((region, category), counts) in &codex.summary {
+                summary.row([
+                    region.as_str().into(),
+                    category.as_str().into(),
+                    counts.logged.into(),
+                    counts.new_entries.into(),
+                    counts.vouchers.into(),
+                ]);
+            }
+            report.add(summary);
+
+            let mut new_entries = Table::new(
+                "New entries",
+                &[
+                    "Time",
+                    "Name",
+                    "Subcategory",
+                    "Region",
+                    "System",
+                    "Nearest",
+                    "New traits",
+                    "Voucher",
+                ],
+            );
+            for find in codex.new_entries() {
+                new_entries.row([
+                    tz.convert(find.timestamp).format(format)?.into(),
+                    find.name.as_str().into(),
+                    find.sub_category.as_str().into(),
+                    find.region.as_str().into(),
+                    find.system.as_str().into(),
+                    find.nearest_destination.clone().into(),
+                    find.new_traits.into(),
+                    find.voucher.into(),
+                ]);
+            }
+            report.add(new_entries);
+
+            report.print(output)?;
+        }
+        Commands::Colonisation { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut colonisation = Colonisation::default();
+            for event in range.events(&config)? {
+                colonisation.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut claims = Table::new("Claims", &["System", "Claimed", "Beacon", "Released"]);
+            for claim in &colonisation.claims {
+                claims.row([
+                    claim.system.as_str().into(),
+                    tz.convert(claim.claimed).format(format)?.into(),
+                    claim
+                        .beacon_deployed
+                        .map(|t| tz.convert(t).format(format))
+                        .transpose()?
+                        .into(),
+                    claim.released.into(),
+                ]);
+            }
+            report.add(claims);
+
+            let mut sites = Table::new(
+                "Construction sites",
+                &[
+                    "Site",
+                    "System",
+                    "Progress",
+                    "Status",
+                    "Remaining",
+                    "Delivered",
+                    "Last update",
+                ],
+            );
+            let mut resources = Table::new(
+                "Resources",
+                &[
+                    "Site",
+                    "Commodity",
+                    "Required",
+                    "Provided",
+                    "Remaining",
+                    "Delivered",
+                ],
+            );
+            for site in colonisation.sites.values() {
+                let name = site
+                    .station
+                    .clone()
+                    .unwrap_or_else(|| site.market_id.to_string());
+                let status = match (site.complete, site.failed) {
+                    (true, _) => "Complete",
+                    (_, true) => "Failed",
+                    _ => "In progress",
+                };
+                sites.row([
+                    name.as_str().into(),
+                    site.system.as_deref().into(),
+                    format!("{:.1}%", site.progress * 100.0).into(),
+                    status.into(),
+                    site.remaining().into(),
+                    site.contributed.values().sum::<u32>().into(),
+                    tz.convert(site.last_update).format(format)?.into(),
+                ]);
+
+                if site.complete || site.failed {
+                    continue;
+                }
+                for resource in &site.resources {
+                    let commodity = resource.name_localised.as_ref().unwrap_or(&resource.name);
+                    resources.row([
+                        name.as_str().into(),
+                        commodity.as_str().into(),
+                        resource.required_amount.into(),
+                        resource.provided_amount.into(),
+                        resource
+                            .required_amount
+                            .saturating_sub(resource.provided_amount)
+                            .into(),
+                        site.contributed.get(commodity).copied().unwrap_or(0).into(),
+                    ]);
+                }
+            }
+            report.add(sites);
+            report.add(resources);
+
+            report.print(output)?;
+        }
+        Commands::Cz { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut zones = ConflictZones::default();
+            for event in range.events(&config)? {
+                zones.update(&event);
+            }
+
+            let mut table = Table::new(
+                "Conflict zones",
+                &[
+                    "Start",
+                    "System",
+                    "Intensity",
+                    "Settlement",
+                    "Faction",
+                    "Kills",
+                    "Bonds",
+                    "Duration",
+                ],
+            );
+            for zone in &zones.zones {
+                table.row([
+                    tz.convert(zone.start).format(format)?.into(),
+                    zone.system.as_deref().into(),
+                    zone.intensity.into(),
+                    zone.settlement.as_deref().into(),
+                    zone.faction().into(),
+                    zone.kills.into(),
+                    zone.bonds.into(),
+                    format_duration(zone.end - zone.start).into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Mining { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut mining = Mining::default();
+            for event in range.events(&config)? {
+                mining.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut sessions = Table::new(
+                "Sessions",
+                &[
+                    "Start",
+                    "System",
+                    "Ring",
+                    "Prospected",
+                    "High",
+                    "Motherlodes",
+                    "Cracked",
+                    "Tons",
+                    "Tons per hour",
+                    "Duration",
+                ],
+            );
+            let mut refined = Table::new("Refined", &["Start", "Commodity", "Tons"]);
+            for session in &mining.sessions {
+                let start = tz.convert(session.start).format(format)?;
+                sessions.row([
+                    start.as_str().into(),
+                    session.system.as_deref().into(),
+                    session.ring.as_deref().into(),
+                    session.prospected.into(),
+                    session.content[0].into(),
+                    session.motherlodes.values().sum::<u32>().into(),
+                    session.cracked.into(),
+                    session.tons().into(),
+                    session
+                        .tons_per_hour()
+                        .map(|rate| (rate * 10.0).round() / 10.0)
+                        .into(),
+                    format_duration(session.duration()).into(),
+                ]);
+                for (commodity, tons) in &session.refined {
+                    refined.row([
+                        start.as_str().into(),
+                        commodity.as_str().into(),
+                        (*tons).into(),
+                    ]);
+                }
+            }
+            report.add(sessions);
+            report.add(refined);
+
+            report.print(output)?;
+        }
+        Commands::Credits { range, graph } => {
+            let mut ledger = CreditLedger::default();
+            for event in range.events(&config)? {
+                ledger.update(&event);
+            }
+
+            // Closing balance of every day
+            let format = format_description!("[year]-[month]-[day]");
+            let mut days: Vec<(String, i64)> = Vec::new();
+            for entry in &ledger.entries {
+                let Some(balance) = entry.balance else {
+                    continue;
+                };
+                let day = tz.convert(entry.timestamp).format(format)?;
+                match days.last_mut() {
+                    Some((last, closing)) if *last == day => *closing = balance,
+                    _ => days.push((day, balance)),
+                }
+            }
+
+            if graph {
+                let balances: Vec<i64> = days.iter().map(|(_, b)| *b).collect();
+                if let (Some((first, _)), Some((last, _))) = (days.first(), days.last()) {
+                    println!("{first} {} {last}", sparkline(&balances));
+                    println!(
+                        "Low {}, high {}",
+                        balances.iter().min().unwrap_or(&0),
+                        balances.iter().max().unwrap_or(&0)
+                    );
+                }
+                return Ok(());
+            }
+
+            let mut report = Report::default();
+            let mut categories =
+                Table::new("Categories", &["Category", "Income", "Expenses", "Net"]);
+            for (category, income, expenses) in ledger.totals() {
+                categories.row([
+                    format!("{category:?}").into(),
+                    income.into(),
+                    expenses.into(),
+                    (income - expenses).into(),
+                ]);
+            }
+            report.add(categories);
+
+            let mut balance = Table::new("Balance", &["Date", "Credits"]);
+            for (day, credits) in days {
+                balance.row([day.into(), credits.into()]);
+            }
+            report.add(balance);
+
+            report.print(output)?;
+        }
+        Commands::Networth { range } => {
+            let mut net_worth = NetWorth::default();
+            for event in range.events(&config)? {
+                net_worth.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut assets = Table::new("Assets", &["Asset", "Value"]);
+            if let Some(current) = net_worth.assets() {
+                assets.row(["Credits".into(), current.credits.into()]);
+                assets.row(["Ships".into(), current.ships.into()]);
+                assets.row(["Stored modules".into(), current.stored_modules.into()]);
+                assets.row(["Carrier".into(), current.carrier.into()]);
+                assets.row(["Total".into(), current.total().into()]);
+            }
+            report.add(assets);
+
+            // Closing net worth of every day
+            let format = format_description!("[year]-[month]-[day]");
+            let mut days: Vec<(String, i64)> = Vec::new();
+            for (timestamp, total) in &net_worth.history {
+                let day = tz.convert(*timestamp).format(format)?;
+                match days.last_mut() {
+                    Some((last, closing)) if *last == day => *closing = *total,
+                    _ => days.push((day, *total)),
+                }
+            }
+            let mut history = Table::new("History", &["Date", "Net worth"]);
+            for (day, total) in days {
+                history.row([day.into(), total.into()]);
+            }
+            report.add(history);
+
+            report.print(output)?;
+        }
+        Commands::Fleet { range } => {
+            let mut fleet = Fleet::default();
+            for event in range.events(&config)? {
+                fleet.update(&event);
+            }
+            let now = OffsetDateTime::now_utc();
+            let location =
+                |system: Option<&String>, station: Option<&String>| match (system, station) {
+                    (Some(system), Some(station)) => format!("{system} / {station}"),
+                    (Some(system), None) => system.clone(),
+                    _ => "Unknown".to_owned(),
+                };
+
+            let mut report = Report::default();
+            let mut ships = Table::new("Ships", &["Ship", "Name", "Location", "Value", "Transfer"]);
+            for ship in fleet.ships() {
+                let location = if Some(ship.ship_id) == fleet.current_ship {
+                    "In use".to_owned()
+                } else if ship.in_transit && ship.arrival.is_none_or(|arrival| arrival > now) {
+                    format!(
+                        "In transit to {}",
+                        location(ship.system.as_ref(), ship.station.as_ref())
+                    )
+                } else {
+                    location(ship.system.as_ref(), ship.station.as_ref())
+                };
+                ships.row([
+                    ship_name(&ship.ship_type).into(),
+                    ship.name.clone().into(),
+                    location.into(),
+                    ship.value.into(),
+                    ship.transfer_price.into(),
+                ]);
+            }
+            report.add(ships);
+
+            let mut modules = Table::new(
+                "Modules",
+                &["Module", "Engineering", "Location", "Transfer"],
+            );
+            for module in &fleet.modules {
+                let engineering =
+                    module
+                        .engineer_modifications
+                        .as_ref()
+                        .map(|blueprint| match module.level {
+                            Some(level) => format!("{blueprint} G{level}"),
+                            None => blueprint.clone(),
+                        });
+                let location = match module.in_transit {
+                    true => "In transit".to_owned(),
+                    false => location(module.star_system.as_ref(), None),
+                };
+                modules.row([
+                    module
+                        .name_localised
+                        .clone()
+                        .unwrap_or_else(|| module.name.clone())
+                        .into(),
+                    engineering.into(),
+                    location.into(),
+                    module.transfer_cost.into(),
+                ]);
+            }
+            report.add(modules);
+
+            report.print(output)?;
+        }
+        Commands::Cqc { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut cqc = Cqc::default();
+            for event in range.events_with_cqc(&config)? {
+                cqc.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut summary = Table::new("Summary", &["Statistic", "Value"]);
+            summary.row(["Matches".into(), cqc.matches.len().into()]);
+            summary.row([
+                "Time in matches".into(),
+                format_seconds(cqc.match_time()).into(),
+            ]);
+            summary.row(["Kills".into(), cqc.kills().into()]);
+            summary.row(["Deaths".into(), cqc.deaths.into()]);
+            if let Some(career) = &cqc.last {
+                summary.row(["Career kills".into(), career.kills.into()]);
+                summary.row(["Career K/D".into(), career.kd.into()]);
+                summary.row(["Career W/L".into(), career.wl.into()]);
+                summary.row(["Career credits".into(), career.credits_earned.into()]);
+            }
+            report.add(summary);
+
+            let mut ranks = Table::new("Ranks", &["Time", "Rank", "Progress"]);
+            for rank in &cqc.ranks {
+                ranks.row([
+                    tz.convert(rank.timestamp).format(format)?.into(),
+                    CQC_RANKS.get(rank.rank as usize).copied().into(),
+                    format!("{}%", rank.progress).into(),
+                ]);
+            }
+            report.add(ranks);
+
+            report.print(output)?;
+        }
+        Commands::Ground { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut combat = GroundCombat::default();
+            for event in range.events(&config)? {
+                combat.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut settlements = Table::new(
+                "Settlements",
+                &[
+                    "Settlement",
+                    "Visits",
+                    "Time",
+                    "Kills",
+                    "Bonds",
+                    "Loot",
+                    "Missions",
+                ],
+            );
+            for (name, sessions) in combat.by_settlement() {
+                settlements.row([
+                    name.into(),
+                    sessions.len().into(),
+                    format_duration(sessions.iter().filter_map(|s| s.duration()).sum()).into(),
+                    sessions.iter().map(|s| s.kills).sum::<u32>().into(),
+                    sessions.iter().map(|s| s.bonds).sum::<u64>().into(),
+                    sessions.iter().map(|s| s.loot_count()).sum::<u32>().into(),
+                    sessions
+                        .iter()
+                        .map(|s| s.missions_completed)
+                        .sum::<u32>()
+                        .into(),
+                ]);
+            }
+            report.add(settlements);
+
+            let mut sessions = Table::new(
+                "Sessions",
+                &[
+                    "Start",
+                    "System",
+                    "Settlement",
+                    "Duration",
+                    "Kills",
+                    "Bonds",
+                    "Loot",
+                ],
+            );
+            for session in &combat.sessions {
+                sessions.row([
+                    tz.convert(session.start).format(format)?.into(),
+                    session.system.as_str().into(),
+                    session.settlement.as_deref().into(),
+                    session.duration().map(format_duration).into(),
+                    session.kills.into(),
+                    session.bonds.into(),
+                    session.loot_count().into(),
+                ]);
+            }
+            report.add(sessions);
+
+            report.print(output)?;
+        }
+        Commands::Crew { range } => {
+            let format = format_description!("[year]-[month]-[day]");
+            let mut crew = NpcCrew::default();
+            for event in range.events(&config)? {
+                crew.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut members = Table::new(
+                "Crew",
+                &[
+                    "Name",
+                    "Faction",
+                    "Hired",
+                    "Fired",
+                    "Hire cost",
+                    "Wages",
+                    "Rank",
+                ],
+            );
+            for (name, member) in &crew.members {
+                members.row([
+                    name.as_str().into(),
+                    member.faction.as_str().into(),
+                    member
+                        .hired
+                        .map(|t| tz.convert(t).format(format))
+                        .transpose()?
+                        .into(),
+                    member
+                        .fired
+                        .map(|t| tz.convert(t).format(format))
+                        .transpose()?
+                        .into(),
+                    member.hire_cost.into(),
+                    member.wages.into(),
+                    member
+                        .rank()
+                        .and_then(|r| COMBAT_RANKS.get(r as usize).copied())
+                        .into(),
+                ]);
+            }
+            report.add(members);
+
+            let mut summary = Table::new("Summary", &["Earnings", "Wages", "Share"]);
+            summary.row([
+                crew.earnings.into(),
+                crew.wages().into(),
+                format!("{:.1}%", crew.share() * 100.0).into(),
+            ]);
+            report.add(summary);
+
+            report.print(output)?;
+        }
+        Commands::Engineers { range } => {
+            let mut advisor = EngineerAdvisor::default();
+            for event in range.events(&config)? {
+                advisor.update(&event);
+            }
+
+            let mut table = Table::new("Engineers", &["Engineer", "System", "Progress", "Missing"]);
+            for engineer in ENGINEERS {
+                let missing = advisor.missing(engineer);
+                if missing.is_empty() {
+                    continue;
+                }
+                let missing: Vec<String> = missing
+                    .iter()
+                    .map(|(requirement, check)| match (requirement, check) {
+                        (
+                            Requirement::Commodity(..)
+                            | Requirement::Material(..)
+                            | Requirement::Credits(_),
+                            Check::Missing(n),
+                        ) => format!("{requirement} ({n} missing)"),
+                        (_, Check::Unknown) => format!("{requirement} (unknown)"),
+                        _ => requirement.to_string(),
+                    })
+                    .collect();
+                table.row([
+                    engineer.name.into(),
+                    engineer.system.into(),
+                    advisor.progress(engineer.name).unwrap_or("Unknown").into(),
+                    missing.join(", ").into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Brokers { range } => {
+            let mut broker = TechBroker::default();
+            for event in range.events(&config)? {
+                broker.update(&event);
+            }
+
+            let mut table = Table::new("Tech brokers", &["Broker", "Item", "Unlocked", "Missing"]);
+            for item in BROKER_ITEMS {
+                let missing: Vec<String> = broker
+                    .missing(item)
+                    .iter()
+                    .map(|(requirement, check)| match check {
+                        Check::Missing(n) => format!("{requirement} ({n} missing)"),
+                        _ => requirement.to_string(),
+                    })
+                    .collect();
+                table.row([
+                    item.broker.into(),
+                    item.name.into(),
+                    broker.is_unlocked(item).into(),
+                    missing.join(", ").into(),
+                ]);
+            }
+            print_table(table, output)?;
+        }
+        Commands::Crime { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut ledger = CrimeLedger::default();
+            for event in range.events(&config)? {
+                ledger.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut offenses = Table::new(
+                "Offenses",
+                &["Time", "Faction", "Crime", "Fine", "Bounty", "Victim"],
+            );
+            for offense in &ledger.offenses {
+                offenses.row([
+                    tz.convert(offense.timestamp).format(format)?.into(),
+                    offense.faction.as_str().into(),
+                    offense.crime_type.as_str().into(),
+                    offense.fine.into(),
+                    offense.bounty.into(),
+                    offense.victim.as_deref().into(),
+                ]);
+            }
+            report.add(offenses);
+
+            let mut outstanding = Table::new("Outstanding", &["Faction", "Fines", "Bounties"]);
+            for (faction, jurisdiction) in &ledger.outstanding {
+                outstanding.row([
+                    faction.as_str().into(),
+                    jurisdiction.fines.into(),
+                    jurisdiction.bounties.into(),
+                ]);
+            }
+            report.add(outstanding);
+
+            let mut totals = Table::new(
+                "Totals",
+                &[
+                    "Fines paid",
+                    "Bounties paid",
+                    "Legacy fines paid",
+                    "Through brokers",
+                    "Impounds cleared",
+                ],
+            );
+            totals.row([
+                ledger.fines_paid.into(),
+                ledger.bounties_paid.into(),
+                ledger.legacy_fines_paid.into(),
+                ledger.paid_through_brokers.into(),
+                ledger.impounds_cleared.into(),
+            ]);
+            report.add(totals);
+
+            report.print(output)?;
+        }
+        Commands::Screenshots {
+            range,
+            dir,
+            keep_originals,
+            dry_run,
+        } => {
+            let dir = dir.unwrap_or_else(journal::screenshots_path);
+            let mut table = Table::new("Screenshots", &["Original", "Converted"]);
+
+            for event in range.events(&config)? {
+                let Some(screenshot) = Screenshot::from_event(&event, &dir) else {
+                    continue;
+                };
+                // Already converted, deleted or replaced by a later screenshot
+                if !screenshot.source_matches() {
+                    continue;
+                }
+
+                let target = match dry_run {
+                    true => screenshot.target(),
+                    false => screenshot.convert(keep_originals)?,
+                };
+                table.row([
+                    screenshot.source.display().to_string().into(),
+                    target.display().to_string().into(),
+                ]);
+            }
+
+            print_table(table, output)?;
+        }
+        Commands::Route { command } => match command {
+            RouteCommands::Status { follow } => {
+                let mut tail = journal::JournalTail::new()?;
+                let mut progress = RouteProgress::default();
+                // The journal only announces routes, they are read from NavRoute.json
+                let update = |progress: &mut RouteProgress, event: &journal::Event| match event {
+                    journal::Event::NavRoute { .. } => {
+                        Ok(progress.set_route(journal::read_nav_route()?))
+                    }
+                    event => Ok::<_, std::io::Error>(progress.update(event)),
+                };
+
+                progress.set_route(journal::read_nav_route()?);
+                for event in journal::read_events(&[tail.path().to_owned()])? {
+                    update(&mut progress, &event)?;
+                }
+                print_table(route_progress(&progress), output)?;
+
+                if follow {
+                    loop {
+                        for event in tail.poll()? {
+                            let change = update(&mut progress, &event)?;
+                            match change {
+                                Some(RouteChange::Cleared) => println!("Route cleared"),
+                                Some(RouteChange::Replotted) => println!("Route replotted"),
+                                Some(RouteChange::Plotted) => println!("Route plotted"),
+                                None => {}
+                            }
+                            let jumped = matches!(event, journal::Event::FSDJump { .. });
+                            if !progress.route.is_empty() && (jumped || change.is_some()) {
+                                println!("{}", route_progress_line(&progress));
+                            }
+                        }
+                        thread::sleep(std::time::Duration::from_secs(1));
+                    }
+                }
+            }
+            #[cfg(feature = "spansh")]
+            RouteCommands::Plot {
+                from,
+                to,
+                range: jump_range,
+                efficiency,
+                dates,
+            } => {
+                let route = elite::spansh::Client::default()
+                    .neutron_route(&from, &to, jump_range, efficiency)?;
+                let events = dates.events(&config)?;
+                // The route is flown from the last time the commander was at its start
+                let start = events.iter().rposition(|event| match event {
+                    journal::Event::FSDJump { star_system, .. }
+                    | journal::Event::Location { star_system, .. } => {
+                        star_system.eq_ignore_ascii_case(&from)
+                    }
+                    _ => false,
+                });
+                let visited: HashSet<String> = events
+                    .into_iter()
+                    .skip(start.map_or(usize::MAX, |i| i + 1))
+                    .filter_map(|event| match event {
+                        journal::Event::FSDJump { star_system, .. } => Some(star_system),
+                        _ => None,
+                    })
+                    .collect();
+
+                let mut report = Report::default();
+                let mut waypoints = Table::new(
+                    "Waypoints",
+                    &["System", "Jumps", "Distance left", "Neutron", "Visited"],
+                );
+                for waypoint in &route {
+                    waypoints.row([
+                        waypoint.system.as_str().into(),
+                        waypoint.jumps.into(),
+                        ((waypoint.distance_left * 100.0).round() / 100.0).into(),
+                        waypoint.neutron_star.into(),
+                        visited.contains(&waypoint.system).into(),
+                    ]);
+                }
+                report.add(waypoints);
+
+                // Progress counts from the last waypoint reached
+                let reached = route
+                    .iter()
+                    .rposition(|w| visited.contains(&w.system))
+                    .map_or(0, |i| i + 1);
+                let mut progress = Table::new(
+                    "Progress",
+                    &[
+                        "Waypoints reached",
+                        "Waypoints left",
+                        "Jumps left",
+                        "Distance left",
+                    ],
+                );
+                progress.row([
+                    reached.into(),
+                    (route.len() - reached).into(),
+                    route[reached..].iter().map(|w| w.jumps).sum::<u32>().into(),
+                    reached
+                        .checked_sub(1)
+                        .and_then(|i| route.get(i))
+                        .or(route.first())
+                        .map(|w| (w.distance_left * 100.0).round() / 100.0)
+                        .into(),
+                ]);
+                report.add(progress);
+
+                report.print(output)?;
+            }
+        },
+        Commands::Social { range } => {
+            let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+            let mut social = SocialHistory::default();
+            for event in range.events(&config)? {
+                social.update(&event);
+            }
+
+            let mut report = Report::default();
+            let mut stints = Table::new("Stints", &["Start", "Kind", "Name", "Duration"]);
+            for stint in &social.stints {
+                stints.row([
+                    tz.convert(stint.start).format(format)?.into(),
+                    format!("{:?}", stint.kind).into(),
+                    stint.name.as_str().into(),
+                    stint.duration().map(format_duration).into(),
+                ]);
+            }
+            report.add(stints);
+
+            let mut people = Table::new("People", &["Name", "Sessions", "Time"]);
+            for (name, (sessions, duration)) in social.by_person() {
+                people.row([
+                    name.into(),
+                    sessions.into(),
+                    format_duration(duration).into(),
+                ]);
+            }
+            report.add(people);
+
+            report.print(output)?;
+        }
+        Commands::Watch(args) => {
+            let formatter = LogFormatter::new(false, tz);
+            let saved = match &args.state {
+                Some(path) => journal::TailPosition::load(path)?,
+                None => None,
+            };
+            let mut tail = match &saved {
+                Some(position) => journal::JournalTail::resume(position)?,
+                None => journal::JournalTail::new()?,
+            };
+
+            let mut processors = Processors::default();
+            processors.register(Printer {
+                format: output,
+                formatter,
+            });
+            #[cfg(feature = "notifications")]
+            if args.notify {
+                let mut notifier = Notifier::new(NotificationConfig {
+                    alerts: args.notify_on,
+                    hull_threshold: args.hull_threshold,
+                    mission_warning: time::Duration::minutes(args.mission_warning),
+                    fuel_threshold: args.fuel_threshold,
+                    cargo_limit: args.cargo_limit,
+                    min_rebuys: args.min_rebuys,
+                    rearm_after: time::Duration::seconds(args.rearm_after),
+                });
+                // Catch up on missions and carrier jumps from earlier in the session
+                for event in journal::read_events(&[tail.path().to_owned()])? {
+                    notifier.check_event(&event);
+                }
+                processors.register(DesktopNotifications(notifier));
+            }
+            if args.files.is_some() || args.pipe.is_some() {
+                let mut files = MacroFiles::new(MacroFilesConfig {
+                    dir: args.files,
+                    pipe: args.pipe,
+                    events: args.file_events,
+                })?;
+                // Catch up on the state from earlier in the session
+                for event in journal::read_events(&[tail.path().to_owned()])? {
+                    files.catch_up(&event);
+                }
+                processors.register(files);
+            }
+
+            let mut rules = Vec::new();
+            for name in &config.builtin_rules {
+                rules.push(rules::builtin_rule(name).ok_or_else(|| {
+                    format!(
+                        "Unknown built-in rule {name}, expected one of {}",
+                        rules::BUILTIN_RULES.join(", ")
+                    )
+                })?);
+            }
+            rules.extend(config.rules.iter().cloned());
+            let mut engine = RuleEngine::new(rules)?;
+            if !engine.is_empty() {
+                // Catch up on missions from earlier in the session
+                for event in journal::read_raw_events(&[tail.path().to_owned()])? {
+                    engine.track(&event);
+                }
+                processors.register(RuleProcessor::new(engine, Arc::new(show_notification)));
+            }
+
+            if let Some(twitch) = &config.twitch {
+                let token = config
+                    .api_keys
+                    .get("twitch")
+                    .ok_or("Twitch needs an OAuth token, set api_keys.twitch in the config")?;
+                let mut announcer = Announcer::new(twitch.announce.clone())?;
+                // Catch up on totals from earlier in the session
+                for event in journal::read_raw_events(&[tail.path().to_owned()])? {
+                    announcer.catch_up(&event);
+                }
+                processors.register(TwitchChat::start(twitch, token, announcer));
+            }
+
+            #[cfg(feature = "scripting")]
+            if let (false, Some(dir)) = (args.no_scripts, config.scripts_dir_path()) {
+                for script in Script::load_dir(&dir, Arc::new(show_notification))? {
+                    processors.register(script);
+                }
+            }
+
+            // A failed notification or post is reported and watching goes
+            // on, only not being able to print stops it, like a closed pipe
+            let running = AtomicBool::new(true);
+            let mut failure = None;
+            let options = RunOptions {
+                state: args.state,
+                ..RunOptions::default()
+            };
+            processor::run(&mut tail, &mut processors, &options, &running, |e| {
+                if e.processor == Printer::NAME {
+                    running.store(false, Ordering::Relaxed);
+                    failure.get_or_insert(e);
+                } else {
+                    eprintln!("{e}");
+                }
+            })?;
+            if let Some(e) = failure {
+                return Err(e.into());
+            }
+        }
+        #[cfg(feature = "schema")]
+        Commands::Schema => {
+            let schema = schemars::schema_for!(journal::Event);
+            println!("{}", serde_json::to_string_pretty(&schema)?);
+        }
+        Commands::Serve {
+            listen,
+            overlay,
+            journals,
+            allow_origin,
+        } => {
+            println!("Serving on http://{listen}");
+            if overlay {
+                println!("Overlay for OBS at http://{listen}/overlay");
+            }
+            let options = HttpOptions {
+                overlay,
+                journals,
+                allow_origin,
+            };
+            elite::http::serve(listen, &options)?;
+        }
+        #[cfg(feature = "grpc")]
+        Commands::Grpc { listen } => {
+            tokio::runtime::Runtime::new()?.block_on(elite::grpc::serve(listen))?;
+        }
+        Commands::Stats { command } => match command {
+            StatsCommands::Events { range, by } => {
+                let mut counts = EventCounts::default();
+
+                for session in journal::journal_sessions(&range.files()?)? {
+                    let mut headers = session.headers()?;
+                    headers.retain(|h| range.contains(h.timestamp));
+                    for header in &mut headers {
+                        header.timestamp = tz.convert(header.timestamp);
+                    }
+                    counts.add_session(&headers);
+                }
+
+                let day_format = format_description!("[year]-[month]-[day]");
+                let session_format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+                let rows: Vec<(String, u32)> = match by {
+                    Grouping::Kind => {
+                        let mut rows: Vec<_> = counts.by_kind.into_iter().collect();
+                        rows.sort_by_key(|(_, n)| Reverse(*n));
+                        rows
+                    }
+                    Grouping::Day => counts
+                        .by_day
+                        .into_iter()
+                        .map(|(day, n)| Ok((day.format(day_format)?, n)))
+                        .collect::<Result<_, time::error::Format>>()?,
+                    Grouping::Session => counts
+                        .by_session
+                        .into_iter()
+                        .map(|(start, n)| Ok((start.format(session_format)?, n)))
+                        .collect::<Result<_, time::error::Format>>()?,
+                };
+
+                if output == OutputFormat::Table {
+                    print_histogram(&rows);
+                } else {
+                    let mut table = Table::new("Events", &["Group", "Count"]);
+                    for (group, n) in rows {
+                        table.row([group.into(), n.into()]);
+                    }
+                    print_table(table, output)?;
+                }
+            }
+        },
+    }
+
+    Ok(())
+}
+
+fn format_duration(duration: time::Duration) -> String {
+    format!(
+        "{}h {:02}m",
+        duration.whole_hours(),
+        duration.whole_minutes() % 60
+    )
+}
+
+fn route_progress(progress: &RouteProgress) -> Table {
+    let mut table = Table::new(
+        "Progress",
+        &[
+            "Destination",
+            "Next system",
+            "Jumps left",
+            "Distance left",
+            "Jump interval",
+            "ETA",
+        ],
+    );
+    if let Some(destination) = progress.route.last() {
+        table.row([
+            destination.star_system.as_str().into(),
+            progress.next().map(|s| s.star_system.as_str()).into(),
+            progress.remaining_jumps().into(),
+            ((progress.remaining_distance() * 100.0).round() / 100.0).into(),
+            progress.jump_interval().map(format_seconds).into(),
+            progress.eta().map(format_duration).into(),
+        ]);
+    }
+    table
+}
+
+fn route_progress_line(progress: &RouteProgress) -> String {
+    let mut line = format!(
+        "{} jumps, {:.2} ly left",
+        progress.remaining_jumps(),
+        progress.remaining_distance()
+    );
+    if let Some(next) = progress.next() {
+        line += &format!(", next {}", next.star_system);
+        if !is_scoopable(&next.star_class) {
+            line += &format!(" ({} star, not scoopable)", next.star_class);
+        }
+    }
+    if let Some(eta) = progress.eta() {
+        line += &format!(", ETA {}", format_duration(eta));
+    }
+    line
+}
+
+fn format_seconds(duration: time::Duration) -> String {
+    format!("{}s", duration.whole_seconds())
+}
+
+fn print_table(table: Table, format: OutputFormat) -> std::io::Result<()> {
+    let mut report = Report::default();
+    report.add(table);
+    report.print(format)
+}
+
+/// Values scaled to block characters of increasing height
+fn sparkline(values: &[i64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let min = values.iter().min().copied().unwrap_or(0);
+    let range = (values.iter().max().copied().unwrap_or(0) - min).max(1) as f64;
+    values
+        .iter()
+        .map(|v| BARS[((v - min) as f64 / range * 7.0).round() as usize])
+        .collect()
+}
+
+fn print_histogram(rows: &[(String, u32)]) {
+    const WIDTH: u32 = 50;
+
+    let label_width = rows.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
+    let max = rows.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+
+    for (label, n) in rows {
+        let bar = "#".repeat((n * WIDTH).div_ceil(max) as usize);
+        println!("{label:<label_width$} {n:>8} {bar}");
+    }
+}
+
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+    /// Config file to use instead of the default one
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Directory containing the journals
+    #[arg(long, global = true)]
+    journal_dir: Option<PathBuf>,
+    /// Also read journals from this directory, e.g. copied from another
+    /// machine, leaving out entries that are in both
+    #[arg(long, global = true)]
+    merge: Vec<PathBuf>,
+    /// Read journals from another machine, a WebDAV URL,
+    /// "serve://host:port" for one running `elite serve --journals` or
+    /// "grpc://host:port" for one running `elite grpc`
+    #[arg(long, global = true)]
+    source: Option<String>,
+    /// Only report on the commander with this FID
+    #[arg(long, global = true)]
+    commander: Option<String>,
+    /// Don't count session start snapshots like Materials or Rank again
+    /// when nothing changed since the last session
+    #[arg(long, global = true)]
+    dedup: bool,
+    /// How to print reports
+    #[arg(long, global = true, value_enum)]
+    output: Option<OutputFormat>,
+    /// Show times in the system's timezone instead of UTC
+    #[arg(long, global = true)]
+    local: bool,
+    /// Show times in this timezone, e.g. "Europe/Berlin"
+    #[arg(long, global = true, conflicts_with = "local")]
+    tz: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Print the configuration after applying command line flags
+    Config,
+    /// Print path to...
+    Path {
+        #[command(subcommand)]
+        command: PathCommands,
+    },
+    /// Print events one per line
+    #[command(alias = "test")]
+    Log {
+        #[command(flatten)]
+        range: DateRange,
+        /// Show the full payload of every event
+        #[arg(long, short)]
+        verbose: bool,
+    },
+    ChatHistory {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Species found, credits earned and unsold samples
+    Exobio {
+        #[command(flatten)]
+        range: DateRange,
+        /// Also list bodies with at least this many biological signals
+        #[arg(long)]
+        min_signals: Option<u32>,
+    },
+    /// Scan data not sold yet and cartographics earnings
+    Exploration(ExplorationArgs),
+    /// Copy journals with the names of the commander, friends and
+    /// squadrons replaced and chat removed, for sharing in bug reports
+    Anonymize {
+        /// Directory to write the anonymized journals to
+        out_dir: PathBuf,
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Back up the journals and the game's other files with checksums
+    Backup(BackupArgs),
+    /// Count events and sum up fields per group, like the credits from
+    /// MarketSell per commodity
+    Aggregate {
+        /// Only events of this kind, e.g. "MarketSell"
+        #[arg(long)]
+        event: Option<String>,
+        /// Fields whose values make up the groups
+        #[arg(long, value_delimiter = ',')]
+        group_by: Vec<journal::FieldPath>,
+        /// Fields to sum up per group
+        #[arg(long, value_delimiter = ',')]
+        sum: Vec<journal::FieldPath>,
+        /// Fields to average per group
+        #[arg(long, value_delimiter = ',')]
+        avg: Vec<journal::FieldPath>,
+        /// Fields to find the smallest value of per group
+        #[arg(long, value_delimiter = ',')]
+        min: Vec<journal::FieldPath>,
+        /// Fields to find the largest value of per group
+        #[arg(long, value_delimiter = ',')]
+        max: Vec<journal::FieldPath>,
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Influence and reputation from missions per day, faction and system
+    Bgs {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Bodies scanned and left to scan in every visited system
+    Bodies {
+        #[command(flatten)]
+        range: DateRange,
+        /// Only list systems with bodies left to scan or an unknown body count
+        #[arg(long)]
+        incomplete: bool,
+    },
+    /// Technology broker items unlocked and what is missing for the rest
+    Brokers {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Current and past community goal participation
+    Cg {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Codex discoveries per region and category
+    Codex {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Colonisation claims and progress of construction sites
+    Colonisation {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Conflict zones fought in, for which faction and the bonds earned
+    Cz {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Credits earned and spent per category and the balance over time
+    Credits {
+        #[command(flatten)]
+        range: DateRange,
+        /// Draw the daily balance as a sparkline
+        #[arg(long)]
+        graph: bool,
+    },
+    /// Close Quarters Combat matches, kills and rank, apart from the galaxy
+    Cqc {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Wages and ranks of hired NPC crew
+    Crew {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// History of offenses and outstanding fines and bounties
+    Crime {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// What is missing to unlock each remaining engineer
+    Engineers {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Events added, removed or changed between two journals
+    Diff {
+        /// Journal file, or dates like "2024-09-15..2024-09-16" with either
+        /// end left open
+        #[arg(value_parser = parse_source)]
+        a: JournalSource,
+        #[arg(value_parser = parse_source)]
+        b: JournalSource,
+    },
+    /// Fields of events as tab or comma separated values
+    Extract {
+        /// Only events of this kind, e.g. "FSDJump"
+        #[arg(long)]
+        event: Option<String>,
+        /// Fields to extract, nested ones like "Rewards[0].Faction"
+        #[arg(long, value_delimiter = ',', required = true)]
+        fields: Vec<journal::FieldPath>,
+        #[arg(long, value_enum, default_value_t = Delimited::Tsv)]
+        format: Delimited,
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Export data for use in other tools
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+    /// Bodies discovered or mapped first and bonuses for selling them
+    Firsts {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// When friends were seen online
+    Friends {
+        #[command(flatten)]
+        range: DateRange,
+        /// List every status change instead of a summary per friend
+        #[arg(long)]
+        history: bool,
+    },
+    /// Loot, bonds and missions of on-foot sessions per settlement
+    Ground {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Commodity prices of markets visited
+    #[cfg(feature = "market")]
+    Market {
+        #[command(subcommand)]
+        command: MarketCommands,
+    },
+    /// Engineering materials held and trades to get more
+    Materials {
+        #[command(subcommand)]
+        command: MaterialsCommands,
+    },
+    /// Mining sessions with the asteroids prospected and the tons refined
+    Mining {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Every ship and stored module and where it's parked
+    Fleet {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Credits plus the value of ships, stored modules and carrier
+    Networth {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Suits, weapons and micro-resources on foot
+    Odyssey {
+        #[command(subcommand)]
+        command: OdysseyCommands,
+    },
+    /// Passenger missions flown, payouts and failures
+    Passengers {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Pilots' Federation and navy ranks and when they were reached
+    Ranks {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Rare goods in the hold and what they would sell for here
+    Rares {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Powerplay merits per weekly cycle and activity
+    Powerplay {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Whether the credits cover the rebuy of the current ship
+    Rebuy {
+        /// Number of rebuys to check for
+        #[arg(long, default_value_t = 1)]
+        count: u64,
+    },
+    /// Systems, jumps and time spent per galactic region
+    Regions {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Changes in reputation with minor factions
+    Reputation {
+        #[command(flatten)]
+        range: DateRange,
+        /// Only this faction
+        #[arg(long)]
+        faction: Option<String>,
+    },
+    /// Thargoid kills, bonds and deaths per war week
+    Thargoids {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Hours per activity and week
+    Time {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Trading between markets with saved prices
+    #[cfg(feature = "market")]
+    Trade {
+        #[command(subcommand)]
+        command: TradeCommands,
+    },
+    /// Convert screenshots to PNG and name them after where they were taken
+    Screenshots {
+        #[command(flatten)]
+        range: DateRange,
+        /// Directory containing the screenshots
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// Don't delete the bitmaps after converting them
+        #[arg(long)]
+        keep_originals: bool,
+        /// Only show what would be converted
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Plot routes and follow progress along them
+    Route {
+        #[command(subcommand)]
+        command: RouteCommands,
+    },
+    /// Players flown with in wings and multicrew
+    Social {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Statistics about the journal itself
+    Stats {
+        #[command(subcommand)]
+        command: StatsCommands,
+    },
+    /// Changes in faction states between visits to a system
+    States {
+        #[command(flatten)]
+        range: DateRange,
+        /// Only systems visited at least this often
+        #[arg(long, default_value_t = 2)]
+        min_visits: u32,
+    },
+    /// Synthesis recipes craftable with the materials held
+    Synth,
+    /// Print events as they are written to the journal
+    Watch(WatchArgs),
+    /// Print the JSON Schema of journal events
+    #[cfg(feature = "schema")]
+    Schema,
+    /// Serve the current system, fuel and more over HTTP, for Stream Deck
+    /// plugins and AutoHotkey scripts to poll
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        listen: std::net::SocketAddr,
+        /// Also serve a page with the system, ship and recent events to add
+        /// as a browser source in OBS
+        #[arg(long)]
+        overlay: bool,
+        /// Also serve the journal files, for reading them with
+        /// `--source serve://host:port` on another machine
+        #[arg(long)]
+        journals: bool,
+        /// Also let pages from this origin, like `https://example.com`,
+        /// follow the events
+        #[arg(long)]
+        allow_origin: Option<String>,
+    },
+    /// Serve live events, game state and the journals over gRPC
+    #[cfg(feature = "grpc")]
+    Grpc {
+        /// Address to listen on. There is no authentication, only listen
+        /// on other interfaces in a network you trust.
+        #[arg(long, default_value = "127.0.0.1:50051")]
+        listen: std::net::SocketAddr,
+    },
+}
+
+#[derive(Args)]
+struct ExplorationArgs {
+    #[command(flatten)]
+    range: DateRange,
+    /// Look up the estimated value of unsold systems on EDSM
+    #[cfg(feature = "edsm")]
+    #[arg(long)]
+    edsm: bool,
+}
+
+#[derive(Args)]
+struct BackupArgs {
+    /// Directory of the backup, only files changed since the last backup
+    /// to it are written
+    dest: PathBuf,
+    /// Check the backup against its checksums instead
+    #[arg(long)]
+    verify: bool,
+    /// Restore the backup to this directory instead
+    #[arg(long, conflicts_with = "verify")]
+    restore: Option<PathBuf>,
+    /// Replace files that already exist when restoring
+    #[arg(long, requires = "restore")]
+    overwrite: bool,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    /// File to save how far the journal was read to, so a restart picks
+    /// up from there instead of skipping or repeating events
+    #[arg(long)]
+    state: Option<PathBuf>,
+    /// Directory to keep state.txt and the latest of --file-events in as
+    /// key=value lines, for macro tools like VoiceAttack
+    #[arg(long)]
+    files: Option<PathBuf>,
+    /// Named pipe or file to write --file-events to, one line each
+    #[arg(long)]
+    pipe: Option<PathBuf>,
+    /// Events to write to --files and --pipe
+    #[arg(long, value_delimiter = ',')]
+    file_events: Vec<String>,
+    /// Don't run the Rhai scripts in the scripts directory
+    #[cfg(feature = "scripting")]
+    #[arg(long)]
+    no_scripts: bool,
+    /// Raise desktop notifications for selected events
+    #[cfg(feature = "notifications")]
+    #[arg(long)]
+    notify: bool,
+    /// Events to raise notifications for, system-state is also available
+    #[cfg(feature = "notifications")]
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "interdicted,hull-damage,carrier-jump,mission-expiry,low-fuel,overheating,cargo-full,rebuy"
+    )]
+    notify_on: Vec<Alert>,
+    /// Hull health from 0 to 1 below which damage is notified
+    #[cfg(feature = "notifications")]
+    #[arg(long, default_value_t = 0.5)]
+    hull_threshold: f32,
+    /// Minutes before expiry to warn about missions
+    #[cfg(feature = "notifications")]
+    #[arg(long, default_value_t = 60)]
+    mission_warning: i64,
+    /// Tons of fuel in the main tank below which fuel is low,
+    /// defaults to the game's warning at 25%
+    #[cfg(feature = "notifications")]
+    #[arg(long)]
+    fuel_threshold: Option<f64>,
+    /// Tons of cargo at which the hold is full
+    #[cfg(feature = "notifications")]
+    #[arg(long)]
+    cargo_limit: Option<f64>,
+    /// Number of rebuys the credits should cover
+    #[cfg(feature = "notifications")]
+    #[arg(long, default_value_t = 1)]
+    min_rebuys: u64,
+    /// Seconds a condition has to be over before it is notified again
+    #[cfg(feature = "notifications")]
+    #[arg(long, default_value_t = 60)]
+    rearm_after: i64,
+}
+
+#[derive(Subcommand)]
+enum RouteCommands {
+    /// Progress along the route plotted in the galaxy map
+    Status {
+        /// Keep following the journal and print progress after every jump
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Plot a neutron highway route on Spansh and check it against jumps
+    /// already made
+    #[cfg(feature = "spansh")]
+    Plot {
+        from: String,
+        to: String,
+        /// Laden jump range in light years
+        #[arg(long)]
+        range: f64,
+        /// Higher values take fewer detours for more jumps, from 1 to 100
+        #[arg(long, default_value_t = 60)]
+        efficiency: u32,
+        /// When to look for jumps along the route, only the ones after the
+        /// last visit to its first system count
+        #[command(flatten)]
+        dates: DateRange,
+    },
+}
+
+#[cfg(feature = "market")]
+#[derive(Subcommand)]
+enum MarketCommands {
+    /// Save the prices of the last market opened
+    Collect {
+        /// Keep saving prices whenever a market is opened
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Prices of a commodity over time
+    History {
+        /// Name like "Tritium" or "Low Temperature Diamonds"
+        commodity: String,
+        /// Only prices at this station
+        #[arg(long)]
+        station: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MaterialsCommands {
+    /// Materials held by kind, category and grade
+    Inventory,
+    /// Cheapest material trader trades to get more of a material
+    Plan {
+        /// Name like "Shield Emitters" or journal symbol like "shieldemitters"
+        material: String,
+        /// How many more are needed
+        count: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum OdysseyCommands {
+    /// Next upgrade of every suit and weapon and what is missing for it
+    Upgrades {
+        #[command(flatten)]
+        range: DateRange,
+    },
+    /// Gains and losses of micro-resources and what caused them
+    Resources {
+        #[command(flatten)]
+        range: DateRange,
+        /// Only this item, by symbol like "surveillanceequipment"
+        #[arg(long)]
+        item: Option<String>,
+    },
+}
+
+#[cfg(feature = "market")]
+#[derive(Subcommand)]
+enum TradeCommands {
+    /// Most profitable trades between two markets for the current ship
+    Suggest {
+        /// Only use prices saved in the last N hours
+        #[arg(long, default_value_t = 48)]
+        max_age: i64,
+        /// Leave out trades needing more jumps than this
+        #[arg(long)]
+        max_jumps: Option<u32>,
+        /// Number of trades to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExportCommands {
+    /// Coordinates of every system jumped to
+    Map {
+        #[command(flatten)]
+        range: DateRange,
+        #[arg(long, value_enum, default_value_t = MapFormat::Geojson)]
+        format: MapFormat,
+    },
+}
+
+#[derive(Clone, ValueEnum)]
+enum MapFormat {
+    /// GeoJSON with galactic coordinates in place of longitude and latitude
+    Geojson,
+    Csv,
+    /// Top down view of the galactic plane
+    Svg,
+}
+
+#[derive(Subcommand)]
+enum StatsCommands {
+    /// Histogram of journal entries
+    Events {
+        #[command(flatten)]
+        range: DateRange,
+        /// How to group the entries
+        #[arg(long, value_enum, default_value_t = Grouping::Kind)]
+        by: Grouping,
+    },
+}
+
+#[derive(Clone, ValueEnum)]
+enum Grouping {
+    Kind,
+    Day,
+    Session,
+}
+
+#[derive(Args, Default)]
+struct DateRange {
+    /// Only include events on or after this date (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_date)]
+    since: Option<Date>,
+    /// Only include events before this date (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_date)]
+    until: Option<Date>,
+    /// Only include events from the last N days
+    #[arg(long, conflicts_with = "since")]
+    days: Option<i64>,
+    /// Only include events matching an expression on their journal JSON,
+    /// e.g. 'event == "Bounty" && TotalReward > 100000'
+    #[arg(long)]
+    filter: Option<journal::Filter>,
+}
+
+impl DateRange {
+    fn bounds(&self) -> Option<(OffsetDateTime, OffsetDateTime)> {
+        let start = match (self.since, self.days) {
+            (Some(date), _) => Some(date.midnight().assume_utc()),
+            (None, Some(days)) => Some(OffsetDateTime::now_utc() - time::Duration::days(days)),
+            (None, None) => None,
+        };
+        let end = self.until.map(|date| date.midnight().assume_utc());
+
+        match (start, end) {
+            (None, None) => None,
+            (start, end) => Some((
+                start.unwrap_or(OffsetDateTime::UNIX_EPOCH),
+                end.unwrap_or_else(OffsetDateTime::now_utc),
+            )),
+        }
+    }
+
+    fn contains(&self, timestamp: OffsetDateTime) -> bool {
+        self.bounds()
+            .is_none_or(|(start, end)| start <= timestamp && timestamp < end)
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>, std::io::Error> {
+        match self.bounds() {
+            Some((start, end)) => journal::journal_files_between(start, end),
+            None => journal::journal_files(),
+        }
+    }
+
+    /// Journal lines in the range matching the filter, from wherever the
+    /// config says to read journals
+    fn lines(&self, config: &Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let keep = |line: &str| self.filter.as_ref().is_none_or(|f| f.matches_line(line));
+        let (start, end) = self.bounds().unwrap_or((
+            OffsetDateTime::UNIX_EPOCH,
+            time::PrimitiveDateTime::MAX.assume_utc(),
+        ));
+
+        Ok(if let Some(url) = &config.source {
+            journal_source(url)?.lines_between_where(start, end, &keep)?
+        } else if !config.merge_dirs.is_empty() {
+            let mut dirs = vec![journal::journals_path()];
+            dirs.extend(config.merge_dirs.iter().cloned());
+            journal::merged_lines_between(&dirs, start, end, keep)?
+        } else {
+            journal::lines_between(start, end, keep)?
+        })
+    }
+
+    /// Like [DateRange::lines] but the raw JSON of every entry
+    fn raw_events(&self, config: &Config) -> Result<Vec<Value>, Box<dyn Error>> {
+        Ok(self
+            .lines(config)?
+            .iter()
+            .map(|line| serde_json::from_str(line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Events in the range, leaving out CQC sessions, which only the
+    /// `cqc` command reports on
+    fn events(&self, config: &Config) -> Result<Vec<journal::Event>, Box<dyn Error>> {
+        Ok(without_cqc(self.events_with_cqc(config)?))
+    }
+
+    fn events_with_cqc(&self, config: &Config) -> Result<Vec<journal::Event>, Box<dyn Error>> {
+        let mut parser = journal::JournalParser::default();
+        let events = self
+            .lines(config)?
+            .iter()
+            .map(|line| parser.parse_line(line))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let events = match &config.commander {
+            Some(fid) => journal::commander_events(events, fid),
+            None => events,
+        };
+
+        Ok(match config.dedup {
+            true => journal::dedup_snapshots(events),
+            false => events,
+        })
+    }
+}
+
+/// Integers summed up as floats shown without a fraction
+fn whole_number(n: f64) -> Value {
+    match n.fract() == 0.0 && n.abs() < 2f64.powi(53) {
+        true => (n as i64).into(),
+        false => n.into(),
+    }
+}
+
+/// Source for reading journals from another machine
+fn journal_source(url: &str) -> Result<Box<dyn journal::JournalSource>, Box<dyn Error>> {
+    match url.split_once("://") {
+        #[cfg(feature = "grpc")]
+        Some(("grpc", address)) => Ok(Box::new(elite::grpc::GrpcSource::connect(&format!(
+            "http://{address}"
+        ))?)),
+        #[cfg(feature = "remote")]
+        Some(("serve", address)) => Ok(Box::new(elite::remote::ServeSource::new(&format!(
+            "http://{address}"
+        )))),
+        #[cfg(feature = "remote")]
+        Some(("http" | "https", _)) => Ok(Box::new(elite::remote::WebDavSource::new(url))),
+        _ => Err(format!("Unsupported journal source {url}").into()),
+    }
+}
+
+fn parse_date(s: &str) -> Result<Date, time::error::Parse> {
+    Date::parse(s, format_description!("[year]-[month]-[day]"))
+}
+
+#[derive(Clone)]
+enum JournalSource {
+    File(PathBuf),
+    Dates(Option<Date>, Option<Date>),
+}
+
+impl JournalSource {
+    /// Entries as raw JSON, so fields the parser doesn't know are kept
+    fn raw_events(&self) -> Result<Vec<Value>, Box<dyn Error>> {
+        let (paths, range) = match self {
+            JournalSource::File(path) => (vec![path.to_owned()], DateRange::default()),
+            JournalSource::Dates(since, until) => {
+                let range = DateRange {
+                    since: *since,
+                    until: *until,
+                    ..DateRange::default()
+                };
+                (range.files()?, range)
+            }
+        };
+
+        let mut events = Vec::new();
+        for path in paths {
+            for line in std::fs::read_to_string(path)?.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let event: Value = serde_json::from_str(line)?;
+                let timestamp = event["timestamp"].as_str().and_then(|t| {
+                    OffsetDateTime::parse(t, &time::format_description::well_known::Rfc3339).ok()
+                });
+                if timestamp.is_none_or(|t| range.contains(t)) {
+                    events.push(event);
+                }
+            }
+        }
+        Ok(events)
+    }
+}
+
+fn parse_source(s: &str) -> Result<JournalSource, time::error::Parse> {
+    let path = PathBuf::from(s);
+    match s.split_once("..") {
+        Some((since, until)) if !path.exists() => {
+            let date = |s: &str| (!s.is_empty()).then(|| parse_date(s)).transpose();
+            Ok(JournalSource::Dates(date(since)?, date(until)?))
+        }
+        _ => Ok(JournalSource::File(path)),
+    }
+}
+
+#[derive(Subcommand)]
+enum PathCommands {
+    Backpack,
+    Cargo,
+    Market,
+    ModulesInfo,
+    NavRoute,
+    Outfitting,
+    ShipLocker,
+    Shipyard,
+    Status,
+    LatestJournal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+}
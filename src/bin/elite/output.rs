@@ -0,0 +1,239 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use elite::journal::Event;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::log::LogFormatter;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Aligned columns for reading in a terminal
+    #[default]
+    Table,
+    /// JSON for piping into jq and the like
+    Json,
+    /// Comma separated values for spreadsheets
+    Csv,
+}
+
+/// Plain text formats for columns only known at runtime
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Delimited {
+    /// Tab separated values
+    #[default]
+    Tsv,
+    /// Comma separated values
+    Csv,
+}
+
+/// Rows of a report with named columns
+#[derive(Debug)]
+pub struct Table {
+    name: &'static str,
+    headers: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    pub fn new(name: &'static str, headers: &[&'static str]) -> Self {
+        Self::with_headers(name, headers.iter().map(|h| h.to_string()).collect())
+    }
+
+    /// Table whose columns are only known at runtime
+    pub fn with_headers(name: &'static str, headers: Vec<String>) -> Self {
+        Table {
+            name,
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row<const N: usize>(&mut self, cells: [Value; N]) {
+        self.push(cells.to_vec());
+    }
+
+    pub fn push(&mut self, cells: Vec<Value>) {
+        debug_assert_eq!(cells.len(), self.headers.len());
+        self.rows.push(cells);
+    }
+}
+
+/// Plain text for a cell, missing values are shown as a dash
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_owned(),
+        Value::String(s) => s.to_owned(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+/// Print rows under a header line, missing values are left empty
+pub fn print_delimited(
+    headers: &[String],
+    rows: &[Vec<Value>],
+    format: Delimited,
+) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let field = |text: &str| match format {
+        Delimited::Tsv => text.replace(['\t', '\n', '\r'], " "),
+        Delimited::Csv => csv_field(text),
+    };
+    let separator = match format {
+        Delimited::Tsv => "\t",
+        Delimited::Csv => ",",
+    };
+
+    let headers: Vec<String> = headers.iter().map(|h| field(h)).collect();
+    writeln!(out, "{}", headers.join(separator))?;
+    for row in rows {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|value| match value {
+                Value::Null => String::new(),
+                value => field(&cell_text(value)),
+            })
+            .collect();
+        writeln!(out, "{}", fields.join(separator))?;
+    }
+
+    Ok(())
+}
+
+/// Print a single event as a line of its own, so streams of events can be
+/// processed as they come in
+pub fn print_event(event: &Event, format: OutputFormat, log: &LogFormatter) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+
+    match format {
+        OutputFormat::Table => writeln!(out, "{}", log.format(event)),
+        OutputFormat::Json => writeln!(out, "{}", serde_json::to_string(event)?),
+        OutputFormat::Csv => {
+            let json = serde_json::to_value(event)?;
+            writeln!(
+                out,
+                "{},{},{}",
+                json["timestamp"].as_str().unwrap_or_default(),
+                csv_field(json["event"].as_str().unwrap_or_default()),
+                csv_field(&json.to_string())
+            )
+        }
+    }
+}
+
+/// One or more tables printed together, e.g. a list and its totals
+#[derive(Debug, Default)]
+pub struct Report {
+    tables: Vec<Table>,
+}
+
+impl Report {
+    pub fn add(&mut self, table: Table) {
+        self.tables.push(table);
+    }
+
+    pub fn print(&self, format: OutputFormat) -> io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        match format {
+            OutputFormat::Table => {
+                for (i, table) in self.tables.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(out)?;
+                    }
+                    if self.tables.len() > 1 {
+                        writeln!(out, "{}:", table.name)?;
+                    }
+
+                    let rows: Vec<Vec<String>> = table
+                        .rows
+                        .iter()
+                        .map(|row| row.iter().map(cell_text).collect())
+                        .collect();
+                    let widths: Vec<usize> = (0..table.headers.len())
+                        .map(|i| {
+                            rows.iter()
+                                .map(|row| row[i].chars().count())
+                                .chain([table.headers[i].len()])
+                                .max()
+                                .unwrap_or(0)
+                        })
+                        .collect();
+
+                    for row in std::iter::once(table.headers.clone()).chain(rows) {
+                        let line = row
+                            .iter()
+                            .zip(&widths)
+                            .map(|(text, width)| format!("{text:<width$}"))
+                            .collect::<Vec<_>>()
+                            .join("  ");
+                        writeln!(out, "{}", line.trim_end())?;
+                    }
+                }
+            }
+            OutputFormat::Csv => {
+                for (i, table) in self.tables.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(out)?;
+                    }
+                    let headers: Vec<_> = table.headers.iter().map(|h| csv_field(h)).collect();
+                    writeln!(out, "{}", headers.join(","))?;
+                    for row in &table.rows {
+                        let fields: Vec<_> = row.iter().map(|v| csv_field(&cell_text(v))).collect();
+                        writeln!(out, "{}", fields.join(","))?;
+                    }
+                }
+            }
+            OutputFormat::Json => {
+                let objects = |table: &Table| -> Value {
+                    table
+                        .rows
+                        .iter()
+                        .map(|row| {
+                            table
+                                .headers
+                                .iter()
+                                .map(|h| h.to_lowercase().replace(' ', "_"))
+                                .zip(row.iter().cloned())
+                                .collect::<Map<_, _>>()
+                        })
+                        .map(Value::Object)
+                        .collect()
+                };
+                // A single table is just its rows, several are keyed by name
+                let json = match self.tables.as_slice() {
+                    [table] => objects(table),
+                    tables => tables
+                        .iter()
+                        .map(|t| (t.name.to_lowercase().replace(' ', "_"), objects(t)))
+                        .collect::<Map<_, _>>()
+                        .into(),
+                };
+                writeln!(out, "{}", serde_json::to_string_pretty(&json)?)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("Sol"), "Sol");
+        assert_eq!(csv_field("Hello, \"world\""), "\"Hello, \"\"world\"\"\"");
+    }
+}
@@ -0,0 +1,88 @@
+//! What `elite watch` does with live events, run like the processors of
+//! other crates
+
+use elite::{
+    journal::Event,
+    processor::{EventProcessor, ProcessorResult},
+};
+#[cfg(feature = "notifications")]
+use elite::{
+    journal::Status,
+    notifications::{Notification, Notifier},
+};
+#[cfg(feature = "notifications")]
+use time::OffsetDateTime;
+
+use crate::{
+    log::LogFormatter,
+    output::{print_event, OutputFormat},
+};
+
+/// Prints every event to stdout
+#[derive(Debug)]
+pub struct Printer {
+    pub format: OutputFormat,
+    pub formatter: LogFormatter,
+}
+
+impl Printer {
+    pub const NAME: &str = "output";
+}
+
+impl EventProcessor for Printer {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_event(&mut self, event: &Event) -> ProcessorResult {
+        Ok(print_event(event, self.format, &self.formatter)?)
+    }
+}
+
+/// Raises desktop notifications
+#[cfg(feature = "notifications")]
+#[derive(Debug)]
+pub struct DesktopNotifications(pub Notifier);
+
+#[cfg(feature = "notifications")]
+fn show(notifications: impl IntoIterator<Item = Notification>) -> ProcessorResult {
+    for notification in notifications {
+        notification.show()?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "notifications")]
+impl EventProcessor for DesktopNotifications {
+    fn name(&self) -> &str {
+        "notifications"
+    }
+
+    fn on_event(&mut self, event: &Event) -> ProcessorResult {
+        show(self.0.check_event(event))
+    }
+
+    fn on_status(&mut self, status: &Status) -> ProcessorResult {
+        let now = OffsetDateTime::now_utc();
+        let mut notifications = self.0.check_time(now);
+        notifications.extend(self.0.check_status(status, now));
+        show(notifications)
+    }
+}
+
+/// Shows what rules and scripts notify about, on stderr without desktop
+/// notifications
+pub fn show_notification(summary: &str, body: &str) {
+    #[cfg(feature = "notifications")]
+    {
+        let notification = Notification {
+            summary: summary.to_owned(),
+            body: body.to_owned(),
+        };
+        if let Err(e) = notification.show() {
+            eprintln!("Can't show notification: {e}");
+        }
+    }
+    #[cfg(not(feature = "notifications"))]
+    eprintln!("{summary}: {body}");
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+/// Entry logged in the codex
+#[derive(Debug, Clone)]
+pub struct CodexFind {
+    pub timestamp: OffsetDateTime,
+    pub entry_id: u64,
+    pub name: String,
+    pub category: String,
+    pub sub_category: String,
+    pub region: String,
+    pub system: String,
+    /// Closest body or station
+    pub nearest_destination: Option<String>,
+    /// New to the commander's codex in this region.
+    /// The journal doesn't say whether anyone else logged it before.
+    pub new_entry: bool,
+    pub new_traits: bool,
+    pub voucher: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct CategorySummary {
+    /// Entries logged, including repeats
+    pub logged: u32,
+    pub new_entries: u32,
+    pub vouchers: u64,
+}
+
+/// Codex discoveries and their vouchers
+#[derive(Debug, Default)]
+pub struct Codex {
+    pub finds: Vec<CodexFind>,
+    /// Keyed by region and category
+    pub summary: BTreeMap<(String, String), CategorySummary>,
+}
+
+impl Codex {
+    pub fn update(&mut self, event: &Event) {
+        let Event::CodexEntry {
+            timestamp,
+            entry_id,
+            name,
+            name_localised,
+            sub_category,
+            sub_category_localised,
+            category,
+            category_localised,
+            region,
+            region_localised,
+            system,
+            nearest_destination,
+            nearest_destination_localised,
+            is_new_entry,
+            new_traits_discovered,
+            voucher_amount,
+            ..
+        } = event
+        else {
+            return;
+        };
+
+        let find = CodexFind {
+            timestamp: *timestamp,
+            entry_id: *entry_id,
+            name: name_localised.as_ref().unwrap_or(name).to_owned(),
+            category: category_localised.as_ref().unwrap_or(category).to_owned(),
+            sub_category: sub_category_localised
+                .as_ref()
+                .unwrap_or(sub_category)
+                .to_owned(),
+            region: region_localised.as_ref().unwrap_or(region).to_owned(),
+            system: system.to_owned(),
+            nearest_destination: nearest_destination_localised
+                .as_ref()
+                .or(nearest_destination.as_ref())
+                .cloned(),
+            new_entry: *is_new_entry,
+            new_traits: *new_traits_discovered,
+            voucher: voucher_amount.unwrap_or(0),
+        };
+
+        let summary = self
+            .summary
+            .entry((find.region.clone(), find.category.clone()))
+            .or_default();
+        summary.logged += 1;
+        summary.new_entries += u32::from(find.new_entry);
+        summary.vouchers += find.voucher;
+
+        self.finds.push(find);
+    }
+
+    /// Entries that were new to the commander's codex
+    pub fn new_entries(&self) -> impl Iterator<Item = &CodexFind> {
+        self.finds.iter().filter(|find| find.new_entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_codex() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"CodexEntry", "EntryID":2420407, "Name":"$Codex_Ent_Stratum_07_Name;", "Name_Localised":"Stratum Tectonicas - Green", "SubCategory":"$Codex_SubCategory_Organic_Structures;", "SubCategory_Localised":"Organic structures", "Category":"$Codex_Category_Biology;", "Category_Localised":"Biological and Geological", "Region":"$Codex_RegionName_18;", "Region_Localised":"Inner Orion Spur", "System":"Synuefe AA-A c1", "SystemAddress":1, "BodyID":5, "NearestDestination":"$SAA_Unknown_Signal:#type=$SAA_SignalType_Geological;:#index=3;", "NearestDestination_Localised":"Surface signal: Geological (3)", "Latitude":-12.5, "Longitude":101.25, "IsNewEntry":true, "VoucherAmount":50000 }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"CodexEntry", "EntryID":2420407, "Name":"$Codex_Ent_Stratum_07_Name;", "Name_Localised":"Stratum Tectonicas - Green", "SubCategory":"$Codex_SubCategory_Organic_Structures;", "SubCategory_Localised":"Organic structures", "Category":"$Codex_Category_Biology;", "Category_Localised":"Biological and Geological", "Region":"$Codex_RegionName_18;", "Region_Localised":"Inner Orion Spur", "System":"Synuefe AB-A c1", "SystemAddress":2 }
+"#,
+        )
+        .unwrap();
+        let mut codex = Codex::default();
+        for event in &events {
+            codex.update(event);
+        }
+
+        let summary = &codex.summary[&(
+            "Inner Orion Spur".to_owned(),
+            "Biological and Geological".to_owned(),
+        )];
+        assert_eq!(summary.logged, 2);
+        assert_eq!(summary.new_entries, 1);
+        assert_eq!(summary.vouchers, 50000);
+        let find = codex.new_entries().next().unwrap();
+        assert_eq!(codex.new_entries().count(), 1);
+        assert_eq!(
+            find.nearest_destination.as_deref(),
+            Some("Surface signal: Geological (3)")
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::{BTreeMap, HashMap};
+
+use time::OffsetDateTime;
+
+use crate::journal::{ConstructionResource, Event};
+
+/// System the commander claimed for colonisation
+#[derive(Debug)]
+pub struct Claim {
+    pub system: String,
+    pub system_address: u64,
+    pub claimed: OffsetDateTime,
+    pub beacon_deployed: Option<OffsetDateTime>,
+    pub released: bool,
+}
+
+/// Construction site the commander docked at or delivered to
+#[derive(Debug)]
+pub struct ConstructionSite {
+    pub market_id: u64,
+    pub station: Option<String>,
+    pub system: Option<String>,
+    /// From 0 to 1, as of the last visit
+    pub progress: f64,
+    pub complete: bool,
+    pub failed: bool,
+    /// Commodities needed as of the last visit
+    pub resources: Vec<ConstructionResource>,
+    /// Tons delivered by the commander keyed by commodity name
+    pub contributed: BTreeMap<String, u32>,
+    pub last_update: OffsetDateTime,
+}
+
+impl ConstructionSite {
+    /// Tons still to be delivered by anyone
+    pub fn remaining(&self) -> u32 {
+        self.resources
+            .iter()
+            .map(|r| r.required_amount.saturating_sub(r.provided_amount))
+            .sum()
+    }
+}
+
+/// Colonisation claims and construction progress
+#[derive(Debug, Default)]
+pub struct Colonisation {
+    pub claims: Vec<Claim>,
+    /// Keyed by market ID of the construction depot
+    pub sites: BTreeMap<u64, ConstructionSite>,
+    /// Station and system names by market ID, from docking
+    stations: HashMap<u64, (String, String)>,
+}
+
+impl Colonisation {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::Docked {
+                station_name,
+                star_system,
+                market_id,
+                ..
+            } => {
+                self.stations.insert(
+                    *market_id,
+                    (station_name.to_owned(), star_system.to_owned()),
+                );
+            }
+            Event::ColonisationSystemClaim {
+                timestamp,
+                star_system,
+                system_address,
+            } => self.claims.push(Claim {
+                system: star_system.to_owned(),
+                system_address: *system_address,
+                claimed: *timestamp,
+                beacon_deployed: None,
+                released: false,
+            }),
+            Event::ColonisationSystemClaimRelease { system_address, .. } => {
+                for claim in &mut self.claims {
+                    if claim.system_address == *system_address {
+                        claim.released = true;
+                    }
+                }
+            }
+            Event::ColonisationBeaconDeployed { timestamp } => {
+                if let Some(claim) = self.claims.iter_mut().rev().find(|c| !c.released) {
+                    claim.beacon_deployed = Some(*timestamp);
+                }
+            }
+            Event::ColonisationConstructionDepot {
+                timestamp,
+                market_id,
+                construction_progress,
+                construction_complete,
+                construction_failed,
+                resources_required,
+            } => {
+                let site = self.site(*market_id, *timestamp);
+                site.progress = *construction_progress;
+                site.complete = *construction_complete;
+                site.failed = *construction_failed;
+                site.resources = resources_required.clone();
+            }
+            Event::ColonisationContribution {
+                timestamp,
+                market_id,
+                contributions,
+            } => {
+                let site = self.site(*market_id, *timestamp);
+                for contribution in contributions {
+                    let name = contribution
+                        .name_localised
+                        .as_ref()
+                        .unwrap_or(&contribution.name);
+                    *site.contributed.entry(name.to_owned()).or_default() += contribution.amount;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn site(&mut self, market_id: u64, timestamp: OffsetDateTime) -> &mut ConstructionSite {
+        let (station, system) = self.stations.get(&market_id).cloned().unzip();
+        let site = self
+            .sites
+            .entry(market_id)
+            .or_insert_with(|| ConstructionSite {
+                market_id,
+                station,
+                system,
+                progress: 0.0,
+                complete: false,
+                failed: false,
+                resources: Vec::new(),
+                contributed: BTreeMap::new(),
+                last_update: timestamp,
+            });
+        site.last_update = timestamp;
+        site
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_colonisation() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2025-03-01T12:00:00Z", "event":"ColonisationSystemClaim", "StarSystem":"HIP 1", "SystemAddress":42 }
+{ "timestamp":"2025-03-01T12:10:00Z", "event":"ColonisationBeaconDeployed" }
+{ "timestamp":"2025-03-02T12:00:00Z", "event":"Docked", "StationName":"$EXT_PANEL_ColonisationShip; Alpha", "StarSystem":"HIP 1", "MarketID":7 }
+{ "timestamp":"2025-03-02T12:00:05Z", "event":"ColonisationConstructionDepot", "MarketID":7, "ConstructionProgress":0.25, "ConstructionComplete":false, "ConstructionFailed":false, "ResourcesRequired":[ { "Name":"$steel_name;", "Name_Localised":"Steel", "RequiredAmount":1000, "ProvidedAmount":250, "Payment":1000 } ] }
+{ "timestamp":"2025-03-02T12:01:00Z", "event":"ColonisationContribution", "MarketID":7, "Contributions":[ { "Name":"$steel_name;", "Name_Localised":"Steel", "Amount":200 } ] }
+"#,
+        )
+        .unwrap();
+        let mut colonisation = Colonisation::default();
+        for event in &events {
+            colonisation.update(event);
+        }
+
+        assert!(colonisation.claims[0].beacon_deployed.is_some());
+        let site = &colonisation.sites[&7];
+        assert_eq!(site.system.as_deref(), Some("HIP 1"));
+        assert_eq!(site.remaining(), 750);
+        assert_eq!(site.contributed["Steel"], 200);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+#[derive(Debug)]
+pub struct CommunityGoalRecord {
+    pub cgid: u32,
+    pub title: String,
+    pub system: String,
+    pub market: Option<String>,
+    pub joined: Option<OffsetDateTime>,
+    pub discarded: bool,
+    pub expiry: Option<OffsetDateTime>,
+    pub complete: bool,
+    /// Tier in the form of "Tier 3"
+    pub tier: Option<String>,
+    pub contribution: u64,
+    /// Percentage band the commander is in, e.g. 10 for the top 10%
+    pub percentile_band: Option<u32>,
+    pub reward: Option<u64>,
+}
+
+impl CommunityGoalRecord {
+    fn new(cgid: u32, title: &str, system: &str) -> Self {
+        CommunityGoalRecord {
+            cgid,
+            title: title.to_owned(),
+            system: system.to_owned(),
+            market: None,
+            joined: None,
+            discarded: false,
+            expiry: None,
+            complete: false,
+            tier: None,
+            contribution: 0,
+            percentile_band: None,
+            reward: None,
+        }
+    }
+
+    /// Whether the goal is still running at `now` and hasn't been left
+    pub fn is_current(&self, now: OffsetDateTime) -> bool {
+        !self.complete && !self.discarded && self.expiry.is_none_or(|e| e > now)
+    }
+}
+
+/// Community goal participation keyed by CGID
+#[derive(Debug, Default)]
+pub struct CommunityGoals {
+    pub goals: BTreeMap<u32, CommunityGoalRecord>,
+}
+
+impl CommunityGoals {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::CommunityGoalJoin {
+                timestamp,
+                cgid,
+                name,
+                system,
+            } => {
+                let goal = self
+                    .goals
+                    .entry(*cgid)
+                    .or_insert_with(|| CommunityGoalRecord::new(*cgid, name, system));
+                goal.joined = Some(*timestamp);
+                goal.discarded = false;
+            }
+            Event::CommunityGoalDiscard {
+                cgid, name, system, ..
+            } => {
+                self.goals
+                    .entry(*cgid)
+                    .or_insert_with(|| CommunityGoalRecord::new(*cgid, name, system))
+                    .discarded = true;
+            }
+            Event::CommunityGoal { current_goals, .. } => {
+                for status in current_goals {
+                    let goal = self.goals.entry(status.cgid).or_insert_with(|| {
+                        CommunityGoalRecord::new(status.cgid, &status.title, &status.system_name)
+                    });
+                    goal.market = Some(status.market_name.to_owned());
+                    goal.expiry = Some(status.expiry);
+                    goal.complete = status.is_complete;
+                    goal.tier = status.tier_reached.clone();
+                    goal.contribution = status.player_contribution;
+                    goal.percentile_band = Some(status.player_percentile_band);
+                }
+            }
+            Event::CommunityGoalReward {
+                cgid,
+                name,
+                system,
+                reward,
+                ..
+            } => {
+                let goal = self
+                    .goals
+                    .entry(*cgid)
+                    .or_insert_with(|| CommunityGoalRecord::new(*cgid, name, system));
+                goal.complete = true;
+                goal.reward = Some(*reward);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_community_goals() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"CommunityGoalJoin", "CGID":726, "Name":"Alliance Research Initiative - Trade", "System":"Kaushpoos" }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"CommunityGoal", "CurrentGoals":[ { "CGID":726, "Title":"Alliance Research Initiative - Trade", "SystemName":"Kaushpoos", "MarketName":"Neville Horizons", "Expiry":"2024-09-19T06:00:00Z", "IsComplete":false, "CurrentTotal":10062, "PlayerContribution":562, "NumContributors":101, "TopTier":{ "Name":"Tier 5", "Bonus":"" }, "TopRankSize":10, "PlayerInTopRank":false, "TierReached":"Tier 1", "PlayerPercentileBand":50, "Bonus":200000 } ] }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"CommunityGoalJoin", "CGID":727, "Name":"Defence of Kaushpoos", "System":"Kaushpoos" }
+{ "timestamp":"2024-09-15T13:10:00Z", "event":"CommunityGoalDiscard", "CGID":727, "Name":"Defence of Kaushpoos", "System":"Kaushpoos" }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"CommunityGoalReward", "CGID":700, "Name":"Earlier goal", "System":"Sol", "Reward":1000000 }
+"#,
+        )
+        .unwrap();
+        let mut goals = CommunityGoals::default();
+        for event in &events {
+            goals.update(event);
+        }
+
+        let now = datetime!(2024-09-16 12:00 UTC);
+        let trade = &goals.goals[&726];
+        assert_eq!(trade.market.as_deref(), Some("Neville Horizons"));
+        assert_eq!(trade.tier.as_deref(), Some("Tier 1"));
+        assert_eq!(trade.contribution, 562);
+        assert_eq!(trade.percentile_band, Some(50));
+        assert!(trade.is_current(now));
+        assert!(!trade.is_current(datetime!(2024-09-19 06:00 UTC)));
+
+        assert!(goals.goals[&727].discarded);
+        assert!(!goals.goals[&727].is_current(now));
+        assert_eq!(goals.goals[&700].reward, Some(1000000));
+        assert!(goals.goals[&700].complete);
+    }
+}
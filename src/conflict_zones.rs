@@ -0,0 +1,169 @@
+//! Conflict zones fought in, for documenting war contributions to the BGS.
+//!
+//! The journal has no event for entering or leaving a conflict zone. Space
+//! zones are told by dropping at a warzone signal, zones on the ground by
+//! combat bonds earned on foot. A zone lasts until the commander leaves
+//! for supercruise, jumps, docks, dies or quits.
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+#[derive(Debug, Clone)]
+pub struct ConflictZone {
+    pub start: OffsetDateTime,
+    /// Time of the last bond, or the start if none were earned
+    pub end: OffsetDateTime,
+    pub system: Option<String>,
+    /// "High", "Medium", "Low", "Ground" or "Unknown"
+    pub intensity: &'static str,
+    /// Settlement approached before a ground zone
+    pub settlement: Option<String>,
+    /// Bonds by awarding faction
+    pub factions: Vec<(String, u64)>,
+    pub kills: u32,
+    pub bonds: u64,
+}
+
+impl ConflictZone {
+    /// Faction awarding the most bonds, the one fought for
+    pub fn faction(&self) -> Option<&str> {
+        self.factions
+            .iter()
+            .max_by_key(|(_, bonds)| *bonds)
+            .map(|(faction, _)| faction.as_str())
+    }
+}
+
+/// Intensity of a warzone signal like "$Warzone_PointRace_High:#index=1;"
+fn intensity(signal: &str) -> Option<&'static str> {
+    let kind = signal.strip_prefix("$Warzone_PointRace_")?;
+    Some(match kind.split(':').next() {
+        Some("High") => "High",
+        Some("Med") => "Medium",
+        Some("Low") => "Low",
+        _ => "Unknown",
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct ConflictZones {
+    pub zones: Vec<ConflictZone>,
+    active: bool,
+    system: Option<String>,
+    settlement: Option<String>,
+    on_foot: bool,
+}
+
+impl ConflictZones {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump { star_system, .. } | Event::Location { star_system, .. } => {
+                self.active = false;
+                self.system = Some(star_system.clone());
+            }
+            Event::SupercruiseDestinationDrop {
+                timestamp, r#type, ..
+            } => {
+                self.active = false;
+                if let Some(intensity) = intensity(r#type) {
+                    self.start(*timestamp, intensity);
+                }
+            }
+            Event::ApproachSettlement {
+                name,
+                name_localised,
+                ..
+            } => self.settlement = Some(name_localised.as_ref().unwrap_or(name).clone()),
+            Event::Disembark { .. } => self.on_foot = true,
+            Event::Embark { .. } => self.on_foot = false,
+            Event::FactionKillBond {
+                timestamp,
+                reward,
+                awarding_faction,
+                awarding_faction_localised,
+                ..
+            } => {
+                if !self.active {
+                    let intensity = if self.on_foot { "Ground" } else { "Unknown" };
+                    self.start(*timestamp, intensity);
+                }
+                let Some(zone) = self.zones.last_mut() else {
+                    return;
+                };
+                let faction = awarding_faction_localised
+                    .as_ref()
+                    .unwrap_or(awarding_faction);
+                match zone.factions.iter_mut().find(|(f, _)| f == faction) {
+                    Some((_, bonds)) => *bonds += reward,
+                    None => zone.factions.push((faction.clone(), *reward)),
+                }
+                zone.end = *timestamp;
+                zone.kills += 1;
+                zone.bonds += reward;
+            }
+            Event::SupercruiseEntry { .. }
+            | Event::Docked { .. }
+            | Event::Died { .. }
+            | Event::Shutdown { .. }
+            | Event::Fileheader { .. } => self.active = false,
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, timestamp: OffsetDateTime, intensity: &'static str) {
+        let settlement = match intensity {
+            "Ground" => self.settlement.clone(),
+            _ => None,
+        };
+        self.zones.push(ConflictZone {
+            start: timestamp,
+            end: timestamp,
+            system: self.system.clone(),
+            intensity,
+            settlement,
+            factions: Vec::new(),
+            kills: 0,
+            bonds: 0,
+        });
+        self.active = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_conflict_zones() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"FSDJump", "StarSystem":"Ross 128", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":10.0, "FuelUsed":1.0, "FuelLevel":10.0 }
+{ "timestamp":"2024-09-15T12:05:00Z", "event":"SupercruiseDestinationDrop", "Type":"$Warzone_PointRace_High:#index=1;", "Type_Localised":"Conflict Zone [High Intensity]", "Threat":4 }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"FactionKillBond", "Reward":80000, "AwardingFaction":"Ross 128 Blue Vision", "VictimFaction":"Ross 128 Crimson Travel" }
+{ "timestamp":"2024-09-15T12:12:00Z", "event":"FactionKillBond", "Reward":60000, "AwardingFaction":"Ross 128 Blue Vision", "VictimFaction":"Ross 128 Crimson Travel" }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"SupercruiseEntry", "StarSystem":"Ross 128" }
+{ "timestamp":"2024-09-15T12:40:00Z", "event":"ApproachSettlement", "Name":"Kirk Base", "SystemAddress":1, "BodyID":3, "BodyName":"Ross 128 2" }
+{ "timestamp":"2024-09-15T12:45:00Z", "event":"Disembark", "SRV":false, "Taxi":false, "Multicrew":false, "StarSystem":"Ross 128", "SystemAddress":1, "Body":"Ross 128 2", "BodyID":3, "OnStation":false, "OnPlanet":true }
+{ "timestamp":"2024-09-15T12:50:00Z", "event":"FactionKillBond", "Reward":20000, "AwardingFaction":"Ross 128 Crimson Travel", "VictimFaction":"Ross 128 Blue Vision" }
+"#,
+        )
+        .unwrap();
+        let mut zones = ConflictZones::default();
+        for event in &events {
+            zones.update(event);
+        }
+
+        assert_eq!(zones.zones.len(), 2);
+        let space = &zones.zones[0];
+        assert_eq!(space.intensity, "High");
+        assert_eq!(space.system.as_deref(), Some("Ross 128"));
+        assert_eq!((space.kills, space.bonds), (2, 140000));
+        assert_eq!(space.faction(), Some("Ross 128 Blue Vision"));
+        let ground = &zones.zones[1];
+        assert_eq!(ground.intensity, "Ground");
+        assert_eq!(ground.settlement.as_deref(), Some("Kirk Base"));
+        assert_eq!(ground.faction(), Some("Ross 128 Crimson Travel"));
+    }
+}
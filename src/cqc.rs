@@ -0,0 +1,222 @@
+use time::{Duration, OffsetDateTime};
+
+use crate::journal::{CqcStatistics, Event};
+
+/// CQC ranks by number
+pub const CQC_RANKS: [&str; 9] = [
+    "Helpless",
+    "Mostly Helpless",
+    "Amateur",
+    "Semi Professional",
+    "Professional",
+    "Champion",
+    "Hero",
+    "Legend",
+    "Elite",
+];
+
+/// Tells which events were logged while playing CQC. CQC is started from
+/// the main menu and logs a LoadGame without a GameMode, in a journal file
+/// of its own.
+#[derive(Debug, Default)]
+pub struct CqcSession {
+    active: bool,
+}
+
+impl CqcSession {
+    /// Whether the event belongs to a CQC session
+    pub fn update(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Fileheader { .. } => self.active = false,
+            Event::LoadGame { game_mode, .. } => self.active = game_mode.is_none(),
+            Event::Music { music_track, .. } => match music_track.as_str() {
+                "CQC" | "CQCMenu" => self.active = true,
+                "MainMenu" => self.active = false,
+                _ => {}
+            },
+            // Closes the session for the other trackers too
+            Event::Shutdown { .. } => self.active = false,
+            _ => {}
+        }
+        self.active
+    }
+}
+
+/// The events that weren't logged while playing CQC, for the trackers of
+/// the rest of the game
+pub fn without_cqc(events: Vec<Event>) -> Vec<Event> {
+    let mut session = CqcSession::default();
+    events
+        .into_iter()
+        .filter(|event| !session.update(event))
+        .collect()
+}
+
+/// Time the CQC music was playing
+#[derive(Debug, Clone)]
+pub struct CqcMatch {
+    pub start: OffsetDateTime,
+    /// Missing while the match is still going on
+    pub end: Option<OffsetDateTime>,
+}
+
+impl CqcMatch {
+    pub fn duration(&self) -> Option<Duration> {
+        self.end.map(|end| end - self.start)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CqcRank {
+    pub timestamp: OffsetDateTime,
+    pub rank: u32,
+    /// Percent towards the next rank
+    pub progress: u32,
+}
+
+/// Close Quarters Combat matches and progress.
+/// The game logs no event per match, so matches are told apart by the CQC
+/// music and kills come from the Statistics written at login. Deaths are
+/// the Died events of CQC sessions, as Statistics has no count of them.
+#[derive(Debug, Default)]
+pub struct Cqc {
+    pub matches: Vec<CqcMatch>,
+    /// Times died in CQC sessions
+    pub deaths: u64,
+    session: CqcSession,
+    /// Every change in rank or progress
+    pub ranks: Vec<CqcRank>,
+    /// First and last Statistics seen
+    pub first: Option<CqcStatistics>,
+    pub last: Option<CqcStatistics>,
+}
+
+impl Cqc {
+    pub fn update(&mut self, event: &Event) {
+        let in_session = self.session.update(event);
+        match event {
+            Event::Died { .. } if in_session => self.deaths += 1,
+            Event::Music {
+                timestamp,
+                music_track,
+            } => match music_track.as_str() {
+                "CQC" if !self.in_match() => self.matches.push(CqcMatch {
+                    start: *timestamp,
+                    end: None,
+                }),
+                "CQCMenu" | "MainMenu" => self.end_match(*timestamp),
+                _ => {}
+            },
+            Event::Shutdown { timestamp } | Event::Fileheader { timestamp, .. } => {
+                self.end_match(*timestamp)
+            }
+            Event::Rank { timestamp, cqc, .. } => {
+                let progress = self.ranks.last().map_or(0, |r| r.progress);
+                self.set_rank(*timestamp, *cqc, progress);
+            }
+            Event::Progress { timestamp, cqc, .. } => {
+                let rank = self.ranks.last().map_or(0, |r| r.rank);
+                self.set_rank(*timestamp, rank, *cqc);
+            }
+            Event::Statistics { cqc, .. } => {
+                if self.first.is_none() {
+                    self.first = Some(cqc.clone());
+                }
+                self.last = Some(cqc.clone());
+            }
+            _ => {}
+        }
+    }
+
+    fn in_match(&self) -> bool {
+        self.matches.last().is_some_and(|m| m.end.is_none())
+    }
+
+    fn end_match(&mut self, timestamp: OffsetDateTime) {
+        if let Some(last) = self.matches.last_mut().filter(|m| m.end.is_none()) {
+            last.end = Some(timestamp);
+        }
+    }
+
+    fn set_rank(&mut self, timestamp: OffsetDateTime, rank: u32, progress: u32) {
+        let changed = self
+            .ranks
+            .last()
+            .is_none_or(|r| (r.rank, r.progress) != (rank, progress));
+        if changed {
+            self.ranks.push(CqcRank {
+                timestamp,
+                rank,
+                progress,
+            });
+        }
+    }
+
+    /// Time spent in finished matches
+    pub fn match_time(&self) -> Duration {
+        self.matches.iter().filter_map(CqcMatch::duration).sum()
+    }
+
+    /// Kills between the first and last Statistics
+    pub fn kills(&self) -> u64 {
+        match (&self.first, &self.last) {
+            (Some(first), Some(last)) => last.kills.saturating_sub(first.kills),
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_cqc() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Odyssey":true, "Ship":"Python", "ShipID":1, "GameMode":"Solo", "Credits":1000000, "Loan":0 }
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Rank", "Combat":3, "Trade":2, "Explore":1, "Empire":0, "Federation":0, "CQC":1 }
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Progress", "Combat":10, "Trade":20, "Explore":30, "Empire":0, "Federation":0, "CQC":90 }
+{ "timestamp":"2024-09-15T12:00:01Z", "event":"Statistics", "Bank_Account":{ "Current_Wealth":0, "Spent_On_Ships":0, "Spent_On_Outfitting":0, "Spent_On_Repairs":0, "Spent_On_Fuel":0, "Spent_On_Ammo_Consumables":0, "Insurance_Claims":0, "Spent_On_Insurance":0, "Owned_Ship_Count":1 }, "CQC":{ "CQC_Credits_Earned":1000, "CQC_Time_Played":600, "CQC_KD":2.0, "CQC_Kills":10, "CQC_WL":0.5 } }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"Music", "MusicTrack":"MainMenu" }
+{ "timestamp":"2024-09-15T12:01:30Z", "event":"Shutdown" }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Odyssey":true, "Credits":1000000, "Loan":0 }
+{ "timestamp":"2024-09-15T12:02:01Z", "event":"Music", "MusicTrack":"CQCMenu" }
+{ "timestamp":"2024-09-15T12:03:00Z", "event":"Music", "MusicTrack":"CQC" }
+{ "timestamp":"2024-09-15T12:08:00Z", "event":"Died" }
+{ "timestamp":"2024-09-15T12:15:00Z", "event":"Music", "MusicTrack":"CQCMenu" }
+{ "timestamp":"2024-09-15T12:16:00Z", "event":"Shutdown" }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Odyssey":true, "Ship":"Python", "ShipID":1, "GameMode":"Solo", "Credits":1000000, "Loan":0 }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"Rank", "Combat":3, "Trade":2, "Explore":1, "Empire":0, "Federation":0, "CQC":2 }
+{ "timestamp":"2024-09-16T12:00:01Z", "event":"Statistics", "Bank_Account":{ "Current_Wealth":0, "Spent_On_Ships":0, "Spent_On_Outfitting":0, "Spent_On_Repairs":0, "Spent_On_Fuel":0, "Spent_On_Ammo_Consumables":0, "Insurance_Claims":0, "Spent_On_Insurance":0, "Owned_Ship_Count":1 }, "CQC":{ "CQC_Credits_Earned":3000, "CQC_Time_Played":1320, "CQC_KD":2.5, "CQC_Kills":15, "CQC_WL":0.6 } }
+{ "timestamp":"2024-09-16T12:30:00Z", "event":"Died" }
+"#,
+        )
+        .unwrap();
+        let mut cqc = Cqc::default();
+        for event in &events {
+            cqc.update(event);
+        }
+
+        assert_eq!(cqc.matches.len(), 1);
+        assert_eq!(cqc.match_time(), Duration::minutes(12));
+        assert_eq!(cqc.kills(), 5);
+        assert_eq!(cqc.deaths, 1);
+        assert_eq!(cqc.ranks.len(), 3);
+        assert_eq!(cqc.ranks[2].rank, 2);
+
+        // The CQC journal from its LoadGame up to its Shutdown
+        let rest = without_cqc(events);
+        assert_eq!(rest.len(), 14);
+        assert_eq!(
+            rest.iter()
+                .filter(|e| matches!(e, Event::Died { .. }))
+                .count(),
+            1
+        );
+    }
+}
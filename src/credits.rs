@@ -0,0 +1,255 @@
+use time::OffsetDateTime;
+
+use crate::journal::{Event, VoucherType};
+
+/// What credits were earned or spent on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CreditCategory {
+    /// Bounties and combat bonds cashed in
+    Combat,
+    /// Trade, settlement and other vouchers cashed in
+    Vouchers,
+    Missions,
+    CommunityGoals,
+    Trade,
+    Exploration,
+    Exobiology,
+    Fines,
+    /// Repairs, refuelling, ammo, limpets and restocking vehicles
+    Upkeep,
+    Ships,
+    Modules,
+    /// On-foot suits and weapons bought, sold and upgraded
+    Suits,
+    /// Transfers to and from the commander's fleet carrier, and buying one
+    Carrier,
+    Crew,
+    Rebuys,
+    Data,
+    /// Difference between the credits reported at login and the ones
+    /// reconstructed from events
+    Unaccounted,
+}
+
+/// What an event did to the commander's credits.
+/// Only events the ledger knows about are counted.
+pub fn classify(event: &Event) -> Option<(CreditCategory, i64)> {
+    use CreditCategory::*;
+
+    let gain = |category, amount: u64| Some((category, amount as i64));
+    let loss = |category, amount: u64| Some((category, -(amount as i64)));
+
+    match event {
+        Event::MarketBuy { total_cost, .. } => loss(Trade, *total_cost),
+        Event::MarketSell { total_sale, .. } => gain(Trade, *total_sale),
+        Event::MultiSellExplorationData { total_earnings, .. } => {
+            gain(Exploration, *total_earnings)
+        }
+        Event::SellExplorationData {
+            base_value,
+            bonus,
+            total_earnings,
+            ..
+        } => gain(Exploration, total_earnings.unwrap_or(base_value + bonus)),
+        Event::SellOrganicData { bio_data, .. } => gain(
+            Exobiology,
+            bio_data.iter().map(|sale| sale.value + sale.bonus).sum(),
+        ),
+        Event::MissionCompleted {
+            reward: Some(reward),
+            ..
+        } => gain(Missions, *reward),
+        Event::CommunityGoalReward { reward, .. } => gain(CommunityGoals, *reward),
+        Event::RedeemVoucher {
+            voucher_type: VoucherType::Bounty | VoucherType::CombatBond,
+            amount,
+            ..
+        } => gain(Combat, *amount),
+        Event::RedeemVoucher { amount, .. } => gain(Vouchers, *amount),
+        Event::PayBounties { amount, .. }
+        | Event::PayFines { amount, .. }
+        | Event::PayLegacyFines { amount, .. } => loss(Fines, *amount),
+        Event::RefuelAll { cost, .. }
+        | Event::RefuelPartial { cost, .. }
+        | Event::Repair { cost, .. }
+        | Event::RepairAll { cost, .. }
+        | Event::BuyAmmo { cost, .. }
+        | Event::RestockVehicle { cost, .. } => loss(Upkeep, *cost),
+        Event::BuyDrones { total_cost, .. } => loss(Upkeep, *total_cost),
+        Event::SellDrones { total_sale, .. } => gain(Upkeep, *total_sale),
+        Event::ShipyardBuy {
+            ship_price,
+            sell_price,
+            ..
+        } => Some((Ships, sell_price.unwrap_or(0) as i64 - *ship_price as i64)),
+        Event::ShipyardSell { ship_price, .. } | Event::SellShipOnRebuy { ship_price, .. } => {
+            gain(Ships, *ship_price)
+        }
+        Event::ShipyardTransfer { transfer_price, .. } => loss(Ships, *transfer_price),
+        Event::ModuleBuy {
+            buy_price,
+            sell_price,
+            ..
+        } => Some((Modules, sell_price.unwrap_or(0) as i64 - *buy_price as i64)),
+        Event::ModuleSell { sell_price, .. } | Event::ModuleSellRemote { sell_price, .. } => {
+            gain(Modules, *sell_price)
+        }
+        Event::FetchRemoteModule { transfer_cost, .. } => loss(Modules, *transfer_cost),
+        Event::ModuleStore { cost, .. } | Event::ModuleRetrieve { cost, .. } if *cost > 0 => {
+            loss(Modules, *cost)
+        }
+        Event::BuySuit { price, .. } | Event::BuyWeapon { price, .. } => loss(Suits, *price),
+        Event::SellSuit { price, .. } | Event::SellWeapon { price, .. } => gain(Suits, *price),
+        Event::UpgradeSuit { cost, .. } | Event::UpgradeWeapon { cost, .. } => loss(Suits, *cost),
+        Event::CarrierBankTransfer {
+            deposit, withdraw, ..
+        } => Some((
+            Carrier,
+            withdraw.unwrap_or(0) as i64 - deposit.unwrap_or(0) as i64,
+        )),
+        Event::CarrierBuy { price, .. } => loss(Carrier, *price),
+        Event::CrewHire { cost, .. } => loss(Crew, *cost),
+        Event::Resurrect { cost, .. } => loss(Rebuys, *cost),
+        Event::BuyExplorationData { cost, .. } | Event::BuyTradeData { cost, .. } => {
+            loss(Data, *cost)
+        }
+        _ => None,
+    }
+}
+
+/// How much an event changed the commander's credits
+pub fn credit_change(event: &Event) -> Option<i64> {
+    classify(event).map(|(_, change)| change)
+}
+
+/// Credits reconstructed from the last LoadGame and the changes since
+#[derive(Debug, Default)]
+pub struct Balance {
+    pub credits: Option<i64>,
+}
+
+impl Balance {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::LoadGame { credits, .. } => self.credits = Some(*credits as i64),
+            event => {
+                if let (Some(credits), Some(change)) = (&mut self.credits, credit_change(event)) {
+                    *credits += change;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LedgerEntry {
+    pub timestamp: OffsetDateTime,
+    pub category: CreditCategory,
+    pub change: i64,
+    /// Credits after the change, once a LoadGame was seen
+    pub balance: Option<i64>,
+}
+
+/// Every change to the commander's credits in order.
+/// Each LoadGame reports the actual credits; any difference to the
+/// reconstructed balance is booked as unaccounted.
+#[derive(Debug, Default)]
+pub struct CreditLedger {
+    pub entries: Vec<LedgerEntry>,
+    balance: Option<i64>,
+}
+
+impl CreditLedger {
+    pub fn update(&mut self, event: &Event) {
+        let timestamp = event.timestamp();
+
+        if let Event::LoadGame { credits, .. } = event {
+            let credits = *credits as i64;
+            if let Some(balance) = self.balance.filter(|b| *b != credits) {
+                self.entries.push(LedgerEntry {
+                    timestamp,
+                    category: CreditCategory::Unaccounted,
+                    change: credits - balance,
+                    balance: Some(credits),
+                });
+            }
+            self.balance = Some(credits);
+            return;
+        }
+
+        if let Some((category, change)) = classify(event) {
+            if change == 0 {
+                return;
+            }
+            self.balance = self.balance.map(|b| b + change);
+            self.entries.push(LedgerEntry {
+                timestamp,
+                category,
+                change,
+                balance: self.balance,
+            });
+        }
+    }
+
+    /// Credits as of the last entry
+    pub fn balance(&self) -> Option<i64> {
+        self.balance
+    }
+
+    /// Income and expenses per category
+    pub fn totals(&self) -> Vec<(CreditCategory, i64, i64)> {
+        let mut totals: Vec<(CreditCategory, i64, i64)> = Vec::new();
+        for entry in &self.entries {
+            let i = match totals.binary_search_by_key(&entry.category, |t| t.0) {
+                Ok(i) => i,
+                Err(i) => {
+                    totals.insert(i, (entry.category, 0, 0));
+                    i
+                }
+            };
+            match entry.change > 0 {
+                true => totals[i].1 += entry.change,
+                false => totals[i].2 -= entry.change,
+            }
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_credit_ledger() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Ship":"Anaconda", "ShipID":1, "GameMode":"Open", "Credits":1000000, "Loan":0 }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"RedeemVoucher", "Type":"bounty", "Amount":250000 }
+{ "timestamp":"2024-09-15T12:11:00Z", "event":"RepairAll", "Cost":50000 }
+{ "timestamp":"2024-09-15T12:12:00Z", "event":"CarrierBankTransfer", "CarrierID":1, "Deposit":200000, "PlayerBalance":1000000, "CarrierBalance":200000 }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Ship":"Anaconda", "ShipID":1, "GameMode":"Open", "Credits":1100000, "Loan":0 }
+"#,
+        )
+        .unwrap();
+        let mut ledger = CreditLedger::default();
+        for event in &events {
+            ledger.update(event);
+        }
+
+        assert_eq!(ledger.entries.len(), 4);
+        assert_eq!(ledger.entries[2].balance, Some(1_000_000));
+        // 100k were earned by events the ledger doesn't know
+        let unaccounted = &ledger.entries[3];
+        assert_eq!(unaccounted.category, CreditCategory::Unaccounted);
+        assert_eq!(unaccounted.change, 100_000);
+        assert_eq!(ledger.balance(), Some(1_100_000));
+        assert!(ledger
+            .totals()
+            .contains(&(CreditCategory::Upkeep, 0, 50_000)));
+        assert!(ledger
+            .totals()
+            .contains(&(CreditCategory::Combat, 250_000, 0)));
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::{
+    credits::{classify, CreditCategory},
+    journal::Event,
+};
+
+/// Combat ranks by number
+pub const COMBAT_RANKS: [&str; 9] = [
+    "Harmless",
+    "Mostly Harmless",
+    "Novice",
+    "Competent",
+    "Expert",
+    "Master",
+    "Dangerous",
+    "Deadly",
+    "Elite",
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct CrewMember {
+    pub faction: String,
+    pub hired: Option<OffsetDateTime>,
+    pub fired: Option<OffsetDateTime>,
+    pub hire_cost: u64,
+    /// Share of earnings paid out
+    pub wages: u64,
+    /// Combat rank when hired and every promotion after
+    pub ranks: Vec<(OffsetDateTime, u32)>,
+}
+
+impl CrewMember {
+    pub fn rank(&self) -> Option<u32> {
+        self.ranks.last().map(|(_, rank)| *rank)
+    }
+}
+
+/// Hired NPC crew, what they cost and how they ranked up.
+/// Crew take a share of the commander's earnings, which are counted as
+/// well to see how much of them went to the crew.
+#[derive(Debug, Default)]
+pub struct NpcCrew {
+    /// Keyed by name, as older journals have no crew IDs
+    pub members: BTreeMap<String, CrewMember>,
+    /// Earnings the crew get a share of
+    pub earnings: u64,
+}
+
+impl NpcCrew {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::CrewHire {
+                timestamp,
+                name,
+                faction,
+                cost,
+                combat_rank,
+                ..
+            } => {
+                let member = self.members.entry(name.clone()).or_default();
+                member.faction = faction.clone();
+                member.hired = Some(*timestamp);
+                member.fired = None;
+                member.hire_cost += cost;
+                member.ranks.push((*timestamp, *combat_rank));
+            }
+            Event::CrewFire {
+                timestamp, name, ..
+            } => {
+                self.members.entry(name.clone()).or_default().fired = Some(*timestamp);
+            }
+            Event::NpcCrewPaidWage {
+                npc_crew_name,
+                amount,
+                ..
+            } => {
+                self.members.entry(npc_crew_name.clone()).or_default().wages += amount;
+            }
+            Event::NpcCrewRank {
+                timestamp,
+                npc_crew_name,
+                rank_combat,
+                ..
+            } => {
+                let member = self.members.entry(npc_crew_name.clone()).or_default();
+                member.ranks.push((*timestamp, *rank_combat));
+            }
+            event => {
+                use CreditCategory::*;
+                if let Some((
+                    Combat | Vouchers | Missions | CommunityGoals | Trade | Exploration
+                    | Exobiology,
+                    change,
+                )) = classify(event)
+                {
+                    self.earnings += change.max(0) as u64;
+                }
+            }
+        }
+    }
+
+    pub fn wages(&self) -> u64 {
+        self.members.values().map(|m| m.wages).sum()
+    }
+
+    /// Fraction of the earnings paid as wages
+    pub fn share(&self) -> f64 {
+        match self.earnings {
+            0 => 0.0,
+            earnings => self.wages() as f64 / earnings as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_npc_crew() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"CrewHire", "Name":"Kim Ahmed", "CrewID":1, "Faction":"Pilots' Federation", "Cost":15000, "CombatRank":1 }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"RedeemVoucher", "Type":"bounty", "Amount":1000000 }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"NpcCrewPaidWage", "NpcCrewName":"Kim Ahmed", "NpcCrewId":1, "Amount":120000 }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"NpcCrewRank", "NpcCrewName":"Kim Ahmed", "NpcCrewId":1, "RankCombat":2 }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"CrewFire", "Name":"Kim Ahmed", "CrewID":1 }
+"#,
+        )
+        .unwrap();
+        let mut crew = NpcCrew::default();
+        for event in &events {
+            crew.update(event);
+        }
+
+        let kim = &crew.members["Kim Ahmed"];
+        assert_eq!(kim.hire_cost, 15000);
+        assert_eq!(kim.wages, 120000);
+        assert_eq!(kim.rank(), Some(2));
+        assert!(kim.fired.is_some());
+        assert_eq!(crew.share(), 0.12);
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+#[derive(Debug)]
+pub struct Offense {
+    pub timestamp: OffsetDateTime,
+    pub crime_type: String,
+    /// Faction the crime was committed against
+    pub faction: String,
+    pub victim: Option<String>,
+    pub fine: u64,
+    pub bounty: u64,
+}
+
+/// Outstanding fines and bounties with a single faction
+#[derive(Debug, Default)]
+pub struct Jurisdiction {
+    pub fines: u64,
+    pub bounties: u64,
+}
+
+/// Crimes committed by and against the commander
+#[derive(Debug, Default)]
+pub struct CrimeLedger {
+    pub offenses: Vec<Offense>,
+    /// Crimes committed against the commander
+    pub suffered: Vec<Offense>,
+    /// Keyed by faction name
+    pub outstanding: BTreeMap<String, Jurisdiction>,
+    pub fines_paid: u64,
+    pub bounties_paid: u64,
+    pub legacy_fines_paid: u64,
+    /// Part of the fines and bounties paid through interstellar factors
+    pub paid_through_brokers: u64,
+    pub impounds_cleared: u32,
+}
+
+impl CrimeLedger {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::CommitCrime {
+                timestamp,
+                crime_type,
+                faction,
+                victim,
+                victim_localised,
+                fine,
+                bounty,
+            } => {
+                let offense = Offense {
+                    timestamp: *timestamp,
+                    crime_type: crime_type.to_owned(),
+                    faction: faction.to_owned(),
+                    victim: victim_localised.as_ref().or(victim.as_ref()).cloned(),
+                    fine: fine.unwrap_or(0),
+                    bounty: bounty.unwrap_or(0),
+                };
+
+                let jurisdiction = self.outstanding.entry(faction.to_owned()).or_default();
+                jurisdiction.fines += offense.fine;
+                jurisdiction.bounties += offense.bounty;
+
+                self.offenses.push(offense);
+            }
+            Event::CrimeVictim {
+                timestamp,
+                offender,
+                offender_localised,
+                crime_type,
+                fine,
+                bounty,
+            } => self.suffered.push(Offense {
+                timestamp: *timestamp,
+                crime_type: crime_type.to_owned(),
+                faction: offender_localised.as_ref().unwrap_or(offender).to_owned(),
+                victim: None,
+                fine: fine.unwrap_or(0),
+                bounty: bounty.unwrap_or(0),
+            }),
+            Event::PayFines {
+                amount,
+                all_fines,
+                faction,
+                faction_localised,
+                broker_percentage,
+                ..
+            } => {
+                self.fines_paid += amount;
+                if broker_percentage.is_some() {
+                    self.paid_through_brokers += amount;
+                }
+                let factions = faction.iter().chain(faction_localised).collect::<Vec<_>>();
+                self.settle(*all_fines, &factions, |j| j.fines = 0);
+            }
+            Event::PayBounties {
+                amount,
+                all_fines,
+                faction,
+                faction_localised,
+                broker_percentage,
+                ..
+            } => {
+                self.bounties_paid += amount;
+                if broker_percentage.is_some() {
+                    self.paid_through_brokers += amount;
+                }
+                let factions = faction.iter().chain(faction_localised).collect::<Vec<_>>();
+                self.settle(*all_fines, &factions, |j| j.bounties = 0);
+            }
+            Event::PayLegacyFines {
+                amount,
+                broker_percentage,
+                ..
+            } => {
+                self.legacy_fines_paid += amount;
+                if broker_percentage.is_some() {
+                    self.paid_through_brokers += amount;
+                }
+            }
+            Event::ClearImpound { .. } => self.impounds_cleared += 1,
+            _ => {}
+        }
+    }
+
+    /// Apply a payment to all factions or the given ones. A payment naming
+    /// no faction leaves them all alone, as it can't be told what it paid.
+    fn settle(&mut self, all: bool, factions: &[&String], pay: impl Fn(&mut Jurisdiction)) {
+        for (name, jurisdiction) in self.outstanding.iter_mut() {
+            if all || factions.contains(&name) {
+                pay(jurisdiction);
+            }
+        }
+
+        self.outstanding
+            .retain(|_, j| j.fines > 0 || j.bounties > 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_crime_ledger() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"CommitCrime", "CrimeType":"collidedAtSpeedInNoFireZone", "Faction":"Mother Gaia", "Fine":400 }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"CommitCrime", "CrimeType":"assault", "Faction":"Sol Workers' Party", "Victim":"Jameson", "Bounty":500 }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"CommitCrime", "CrimeType":"fireInNoFireZone", "Faction":"Sol Workers' Party", "Fine":100 }
+{ "timestamp":"2024-09-15T12:03:00Z", "event":"CrimeVictim", "Offender":"Jameson", "CrimeType":"assault", "Bounty":200 }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"PayFines", "Amount":400, "AllFines":false, "Faction":"Mother Gaia", "ShipID":1 }
+{ "timestamp":"2024-09-15T12:11:00Z", "event":"PayFines", "Amount":100, "AllFines":false, "ShipID":1 }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"PayBounties", "Amount":500, "Faction":"$faction_SolWorkersParty;", "Faction_Localised":"Sol Workers' Party", "ShipID":1, "BrokerPercentage":25.0 }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"PayLegacyFines", "Amount":1000 }
+"#,
+        )
+        .unwrap();
+        let mut ledger = CrimeLedger::default();
+        for event in &events[..6] {
+            ledger.update(event);
+        }
+
+        assert_eq!(ledger.offenses.len(), 3);
+        assert_eq!(ledger.offenses[1].victim.as_deref(), Some("Jameson"));
+        assert_eq!(ledger.suffered[0].faction, "Jameson");
+        // Mother Gaia paid off, the fine paid for no faction left alone
+        assert!(!ledger.outstanding.contains_key("Mother Gaia"));
+        let swp = &ledger.outstanding["Sol Workers' Party"];
+        assert_eq!((swp.fines, swp.bounties), (100, 500));
+
+        for event in &events[6..] {
+            ledger.update(event);
+        }
+        let swp = &ledger.outstanding["Sol Workers' Party"];
+        assert_eq!((swp.fines, swp.bounties), (100, 0));
+        assert_eq!(ledger.fines_paid, 500);
+        assert_eq!(ledger.bounties_paid, 500);
+        assert_eq!(ledger.legacy_fines_paid, 1000);
+        assert_eq!(ledger.paid_through_brokers, 500);
+
+        ledger.update(
+            &parse_events(r#"{ "timestamp":"2024-09-15T12:40:00Z", "event":"PayFines", "Amount":100, "AllFines":true, "ShipID":1 }"#)
+                .unwrap()[0],
+        );
+        assert!(ledger.outstanding.is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+use time::{macros::time, Duration, OffsetDateTime, UtcOffset, Weekday};
+
+/// Start of the weekly game cycle containing `timestamp`.
+/// Thargoid war progress and Powerplay both tick over on Thursdays
+/// at 07:00 UTC.
+pub fn week_start(timestamp: OffsetDateTime) -> OffsetDateTime {
+    let timestamp = timestamp.to_offset(UtcOffset::UTC);
+    let days_since_thursday = (timestamp.weekday().number_days_from_monday() + 7
+        - Weekday::Thursday.number_days_from_monday())
+        % 7;
+    let start = (timestamp.date() - Duration::days(days_since_thursday.into()))
+        .with_time(time!(07:00))
+        .assume_utc();
+
+    if start > timestamp {
+        start - Duration::weeks(1)
+    } else {
+        start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_week_start() {
+        assert_eq!(
+            week_start(datetime!(2024-09-15 12:00 UTC)),
+            datetime!(2024-09-12 07:00 UTC)
+        );
+        assert_eq!(
+            week_start(datetime!(2024-09-12 06:59 UTC)),
+            datetime!(2024-09-05 07:00 UTC)
+        );
+        assert_eq!(
+            week_start(datetime!(2024-09-12 07:00 UTC)),
+            datetime!(2024-09-12 07:00 UTC)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Client for the system API of <https://www.edsm.net>
+
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use ureq::Agent;
+
+const API: &str = "https://www.edsm.net/api-system-v1";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    pub name: String,
+    /// "Star" or "Planet"
+    #[serde(rename = "type")]
+    pub body_type: String,
+    /// In the form of "K (Yellow-Orange) Star", "High metal content world" etc.
+    #[serde(default)]
+    pub sub_type: Option<String>,
+    /// Distance from the main star in light seconds
+    pub distance_to_arrival: f64,
+    #[serde(default)]
+    pub is_landable: Option<bool>,
+    #[serde(default)]
+    pub terraforming_state: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemBodies {
+    pub name: String,
+    /// Number of bodies in the system as far as EDSM knows
+    #[serde(default)]
+    pub body_count: Option<u32>,
+    pub bodies: Vec<Body>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValuableBody {
+    pub body_name: String,
+    pub distance: f64,
+    /// Value with first discovery and mapping bonuses
+    pub value_max: u64,
+}
+
+/// Cartographics value of scanning all known bodies of a system
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimatedValue {
+    pub name: String,
+    /// Value of scanning every body
+    pub estimated_value: u64,
+    /// Value of also mapping every body with probes
+    pub estimated_value_mapped: u64,
+    #[serde(default)]
+    pub valuable_bodies: Vec<ValuableBody>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    agent: Agent,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Client {
+            agent: Agent::config_builder()
+                .user_agent("elite-rs")
+                .timeout_global(Some(Duration::from_secs(30)))
+                .build()
+                .into(),
+        }
+    }
+}
+
+impl Client {
+    /// EDSM answers with an empty object or array for systems it doesn't know
+    fn system_query<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        system: &str,
+    ) -> Result<Option<T>, ureq::Error> {
+        let response: Value = self
+            .agent
+            .get(format!("{API}/{endpoint}"))
+            .query("systemName", system)
+            .call()?
+            .body_mut()
+            .read_json()?;
+
+        match response {
+            Value::Object(ref fields) if !fields.is_empty() => {
+                Ok(Some(serde_json::from_value(response)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub fn system_bodies(&self, system: &str) -> Result<Option<SystemBodies>, ureq::Error> {
+        self.system_query("bodies", system)
+    }
+
+    pub fn estimated_value(&self, system: &str) -> Result<Option<EstimatedValue>, ureq::Error> {
+        self.system_query("estimated-value", system)
+    }
+}
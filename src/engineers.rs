@@ -0,0 +1,412 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    credits::Balance,
+    journal::{Event, Vessel},
+    materials::MaterialInventory,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankKind {
+    Combat,
+    Trade,
+    Explore,
+    Empire,
+    Federation,
+}
+
+/// One condition for getting invited by or unlocking an engineer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    /// Referral from another engineer, given once they are unlocked
+    Referral(&'static str),
+    Rank(RankKind, u32, &'static str),
+    /// Commodity symbol as in Cargo, count and display name
+    Commodity(&'static str, u32, &'static str),
+    /// Material symbol as in Materials, count and display name
+    Material(&'static str, u32, &'static str),
+    Credits(u64),
+    /// Anything the journal can't tell, like reputation or distance travelled
+    Other(&'static str),
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Requirement::Referral(engineer) => write!(f, "Referral from {engineer}"),
+            Requirement::Rank(kind, _, name) => write!(f, "{kind:?} rank {name}"),
+            Requirement::Commodity(_, count, name) | Requirement::Material(_, count, name) => {
+                write!(f, "{count} {name}")
+            }
+            Requirement::Credits(credits) => write!(f, "{credits} CR"),
+            Requirement::Other(text) => f.write_str(text),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Engineer {
+    pub name: &'static str,
+    pub system: &'static str,
+    /// Needed to get invited to the workshop
+    pub invite: &'static [Requirement],
+    /// Needed to unlock the engineer once invited
+    pub unlock: &'static [Requirement],
+}
+
+use RankKind::*;
+use Requirement::*;
+
+/// Ship engineers in the bubble and Colonia
+pub const ENGINEERS: &[Engineer] = &[
+    Engineer {
+        name: "Felicity Farseer",
+        system: "Deciat",
+        invite: &[Rank(Explore, 2, "Scout")],
+        unlock: &[Commodity("metaalloys", 1, "Meta-Alloys")],
+    },
+    Engineer {
+        name: "Elvira Martuuk",
+        system: "Khun",
+        invite: &[Other("Travel 300 ly from the starting system")],
+        unlock: &[Commodity("soontillrelics", 3, "Soontill Relics")],
+    },
+    Engineer {
+        name: "The Dweller",
+        system: "Wyrd",
+        invite: &[Other("Deal with 5 black markets")],
+        unlock: &[Credits(500_000)],
+    },
+    Engineer {
+        name: "Liz Ryder",
+        system: "Eurybia",
+        invite: &[Other("Friendly with Eurybia Blue Mafia")],
+        unlock: &[Commodity("landmines", 200, "Landmines")],
+    },
+    Engineer {
+        name: "Tod 'The Blaster' McQuinn",
+        system: "Wolf 397",
+        invite: &[Other("Earn 15 bounty vouchers")],
+        unlock: &[Other("Hand in 100,000 CR of bounty vouchers")],
+    },
+    Engineer {
+        name: "Zacariah Nemo",
+        system: "Yoru",
+        invite: &[
+            Referral("Elvira Martuuk"),
+            Other("Invitation from the Party of Yoru"),
+        ],
+        unlock: &[Commodity(
+            "xihecompanions",
+            25,
+            "Xihe Biomorphic Companions",
+        )],
+    },
+    Engineer {
+        name: "Lei Cheung",
+        system: "Laksak",
+        invite: &[Referral("The Dweller"), Other("Trade at 50 markets")],
+        unlock: &[Commodity("gold", 200, "Gold")],
+    },
+    Engineer {
+        name: "Hera Tani",
+        system: "Kuwemaki",
+        invite: &[Referral("Liz Ryder"), Rank(Empire, 1, "Outsider")],
+        unlock: &[Commodity("kamitracigars", 50, "Kamitra Cigars")],
+    },
+    Engineer {
+        name: "Juri Ishmaak",
+        system: "Giryak",
+        invite: &[Referral("Felicity Farseer"), Other("Earn 50 combat bonds")],
+        unlock: &[Other("Hand in 100,000 CR of combat bonds")],
+    },
+    Engineer {
+        name: "Selene Jean",
+        system: "Kuk",
+        invite: &[
+            Referral("Tod 'The Blaster' McQuinn"),
+            Other("Mine 500 tons of ore"),
+        ],
+        unlock: &[Commodity("painite", 10, "Painite")],
+    },
+    Engineer {
+        name: "Marco Qwent",
+        system: "Sirius",
+        invite: &[
+            Referral("Elvira Martuuk"),
+            Other("Invitation from Sirius Corporation"),
+        ],
+        unlock: &[Commodity("modularterminals", 25, "Modular Terminals")],
+    },
+    Engineer {
+        name: "Ram Tah",
+        system: "Meene",
+        invite: &[Referral("Lei Cheung"), Rank(Explore, 3, "Surveyor")],
+        unlock: &[Material("scandatabanks", 50, "Classified Scan Databanks")],
+    },
+    Engineer {
+        name: "Broo Tarquin",
+        system: "Muang",
+        invite: &[Referral("Hera Tani"), Rank(Combat, 3, "Competent")],
+        unlock: &[Commodity("fujintea", 50, "Fujin Tea")],
+    },
+    Engineer {
+        name: "Colonel Bris Dekker",
+        system: "Sol",
+        invite: &[
+            Referral("Juri Ishmaak"),
+            Other("Friendly with the Federation"),
+        ],
+        unlock: &[Other("Hand in 1,000,000 CR of combat bonds")],
+    },
+    Engineer {
+        name: "Didi Vatermann",
+        system: "Leesti",
+        invite: &[Referral("Selene Jean"), Rank(Trade, 4, "Merchant")],
+        unlock: &[Commodity("lavianbrandy", 50, "Lavian Brandy")],
+    },
+    Engineer {
+        name: "Professor Palin",
+        system: "Arque",
+        invite: &[
+            Referral("Marco Qwent"),
+            Other("Travel 5,000 ly from the starting system"),
+        ],
+        unlock: &[Other("25 Sensor Fragments")],
+    },
+    Engineer {
+        name: "Lori Jameson",
+        system: "Shinrarta Dezhra",
+        invite: &[Referral("Marco Qwent"), Rank(Combat, 6, "Dangerous")],
+        unlock: &[Commodity("konggaale", 25, "Konnga Ale")],
+    },
+    Engineer {
+        name: "Tiana Fortune",
+        system: "Achenar",
+        invite: &[Referral("Hera Tani"), Other("Friendly with the Empire")],
+        unlock: &[Material("decodedemissiondata", 50, "Decoded Emission Data")],
+    },
+    Engineer {
+        name: "The Sarge",
+        system: "Beta-3 Tucani",
+        invite: &[Referral("Juri Ishmaak"), Rank(Federation, 3, "Midshipman")],
+        unlock: &[Material(
+            "shieldpatternanalysis",
+            50,
+            "Aberrant Shield Pattern Analysis",
+        )],
+    },
+    Engineer {
+        name: "Bill Turner",
+        system: "Alioth",
+        invite: &[Referral("Selene Jean"), Other("Friendly with the Alliance")],
+        unlock: &[Commodity("bromellite", 50, "Bromellite")],
+    },
+    Engineer {
+        name: "Etienne Dorn",
+        system: "Los",
+        invite: &[Referral("Liz Ryder"), Rank(Trade, 3, "Dealer")],
+        unlock: &[Commodity("occupiedcryopod", 25, "Occupied Escape Pods")],
+    },
+    Engineer {
+        name: "Marsha Hicks",
+        system: "Tir",
+        invite: &[Referral("The Dweller"), Rank(Explore, 3, "Surveyor")],
+        unlock: &[Commodity("osmium", 10, "Osmium")],
+    },
+    Engineer {
+        name: "Mel Brandon",
+        system: "Luchtaine",
+        invite: &[
+            Referral("Elvira Martuuk"),
+            Other("Invitation from the Colonia Council"),
+        ],
+        unlock: &[Other("Hand in 100,000 CR of bounty vouchers")],
+    },
+    Engineer {
+        name: "Petra Olmanova",
+        system: "Asura",
+        invite: &[
+            Referral("Tod 'The Blaster' McQuinn"),
+            Rank(Combat, 4, "Expert"),
+        ],
+        unlock: &[Commodity("progenitorcells", 200, "Progenitor Cells")],
+    },
+    Engineer {
+        name: "Chloe Sedesi",
+        system: "Shenve",
+        invite: &[
+            Referral("Marco Qwent"),
+            Other("Travel 5,000 ly from the starting system"),
+        ],
+        unlock: &[Other("25 Sensor Fragments")],
+    },
+];
+
+/// Whether a requirement is met, as far as the journal can tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Met,
+    /// Count still missing, or 1 for requirements without a count
+    Missing(u64),
+    Unknown,
+}
+
+/// What the commander has towards the engineer requirements
+#[derive(Debug, Default)]
+pub struct EngineerAdvisor {
+    /// Progress like "Invited" or "Unlocked" by engineer name
+    pub progress: HashMap<String, String>,
+    /// Combat, trade, explore, empire and federation ranks
+    pub ranks: Option<[u32; 5]>,
+    pub cargo: HashMap<String, u32>,
+    pub materials: MaterialInventory,
+    pub balance: Balance,
+}
+
+impl EngineerAdvisor {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::EngineerProgress {
+                engineers,
+                engineer,
+                progress,
+                ..
+            } => {
+                for state in engineers {
+                    self.progress
+                        .insert(state.engineer.clone(), state.progress.clone());
+                }
+                if let (Some(engineer), Some(progress)) = (engineer, progress) {
+                    self.progress.insert(engineer.clone(), progress.clone());
+                }
+            }
+            Event::Rank {
+                combat,
+                trade,
+                explore,
+                empire,
+                federation,
+                ..
+            } => self.ranks = Some([*combat, *trade, *explore, *empire, *federation]),
+            Event::Promotion {
+                combat,
+                trade,
+                explore,
+                empire,
+                federation,
+                ..
+            } => {
+                if let Some(ranks) = &mut self.ranks {
+                    for (rank, promotion) in ranks
+                        .iter_mut()
+                        .zip([combat, trade, explore, empire, federation])
+                    {
+                        *rank = promotion.unwrap_or(*rank);
+                    }
+                }
+            }
+            Event::Cargo {
+                vessel: Vessel::Ship,
+                inventory,
+                ..
+            } => {
+                self.cargo = inventory
+                    .iter()
+                    .map(|entry| (entry.name.to_lowercase(), entry.count))
+                    .collect();
+            }
+            _ => {}
+        }
+        self.materials.update(event);
+        self.balance.update(event);
+    }
+
+    pub fn progress(&self, engineer: &str) -> Option<&str> {
+        self.progress.get(engineer).map(String::as_str)
+    }
+
+    pub fn check(&self, requirement: &Requirement) -> Check {
+        let missing = |have: u64, need: u64| match have >= need {
+            true => Check::Met,
+            false => Check::Missing(need - have),
+        };
+
+        match requirement {
+            Requirement::Referral(engineer) => match self.progress(engineer) {
+                Some("Unlocked") => Check::Met,
+                _ => Check::Missing(1),
+            },
+            Requirement::Rank(kind, rank, _) => match self.ranks {
+                Some(ranks) => missing(u64::from(ranks[*kind as usize]), u64::from(*rank)),
+                None => Check::Unknown,
+            },
+            Requirement::Commodity(symbol, count, _) => missing(
+                u64::from(self.cargo.get(*symbol).copied().unwrap_or(0)),
+                u64::from(*count),
+            ),
+            Requirement::Material(symbol, count, _) => {
+                missing(u64::from(self.materials.count(symbol)), u64::from(*count))
+            }
+            Requirement::Credits(credits) => match self.balance.credits {
+                Some(balance) => missing(balance.max(0) as u64, *credits),
+                None => Check::Unknown,
+            },
+            Requirement::Other(_) => Check::Unknown,
+        }
+    }
+
+    /// Requirements still in the way of unlocking an engineer with what
+    /// is missing of each, empty once unlocked
+    pub fn missing(&self, engineer: &Engineer) -> Vec<(Requirement, Check)> {
+        let requirements: Vec<&Requirement> = match self.progress(engineer.name) {
+            Some("Unlocked") => return Vec::new(),
+            Some("Invited") | Some("Acquainted") => engineer.unlock.iter().collect(),
+            _ => engineer.invite.iter().chain(engineer.unlock).collect(),
+        };
+        requirements
+            .into_iter()
+            .map(|r| (*r, self.check(r)))
+            .filter(|(_, check)| *check != Check::Met)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_engineer_advisor() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"EngineerProgress", "Engineers":[ { "Engineer":"Felicity Farseer", "EngineerID":300100, "Progress":"Invited" }, { "Engineer":"Hera Tani", "EngineerID":300090, "Progress":"Known" }, { "Engineer":"Liz Ryder", "EngineerID":300080, "Progress":"Unlocked", "RankProgress":0, "Rank":5 } ] }
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Rank", "Combat":3, "Trade":2, "Explore":1, "Empire":0, "Federation":0, "CQC":0 }
+{ "timestamp":"2024-09-15T12:00:01Z", "event":"Cargo", "Vessel":"Ship", "Count":20, "Inventory":[ { "Name":"kamitracigars", "Count":20, "Stolen":0 } ] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"Promotion", "Empire":1 }
+"#,
+        )
+        .unwrap();
+        let mut advisor = EngineerAdvisor::default();
+        for event in &events {
+            advisor.update(event);
+        }
+
+        let engineer = |name| ENGINEERS.iter().find(|e| e.name == name).unwrap();
+        // Invited, so only the unlock is left
+        assert_eq!(
+            advisor.missing(engineer("Felicity Farseer")),
+            vec![(Commodity("metaalloys", 1, "Meta-Alloys"), Check::Missing(1))]
+        );
+        // Referral and rank are there, 30 cigars are not
+        assert_eq!(
+            advisor.missing(engineer("Hera Tani")),
+            vec![(
+                Commodity("kamitracigars", 50, "Kamitra Cigars"),
+                Check::Missing(30)
+            )]
+        );
+        assert!(advisor.missing(engineer("Liz Ryder")).is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::{signal_count, Event, OrganicScanType, SignalKind};
+
+/// A species sampled on a specific body
+#[derive(Debug, Clone)]
+pub struct Sample {
+    /// Internal species name in the form of "$Codex_Ent_Stratum_07_Name;"
+    pub species: String,
+    /// Localised species name if available
+    pub name: String,
+    pub system_address: u64,
+    pub body: u32,
+    pub timestamp: OffsetDateTime,
+}
+
+#[derive(Debug, Default)]
+pub struct SpeciesSummary {
+    pub genus: String,
+    /// Number of completed samples
+    pub analysed: u32,
+    pub sold: u32,
+    pub value: u64,
+    /// First logged bonus
+    pub bonus: u64,
+}
+
+/// Tracks organic scans from the first log until the sample is sold
+#[derive(Debug, Default)]
+pub struct Exobiology {
+    /// Keyed by localised species name
+    pub species: BTreeMap<String, SpeciesSummary>,
+    /// Sample currently being collected and how far along it is
+    pub in_progress: Option<(Sample, OrganicScanType)>,
+    /// Completed samples that haven't been sold yet.
+    /// These are lost if the commander dies.
+    pub unsold: Vec<Sample>,
+    /// Completed samples that were lost by dying
+    pub lost: Vec<Sample>,
+    /// Number of biological signals, keyed by body name
+    pub bio_signals: BTreeMap<String, u32>,
+    /// Genera found by mapping, keyed by body name
+    pub genuses: BTreeMap<String, Vec<String>>,
+}
+
+impl Exobiology {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSSBodySignals {
+                body_name, signals, ..
+            } => {
+                let count = signal_count(signals, SignalKind::Biological);
+                if count > 0 {
+                    self.bio_signals.insert(body_name.to_owned(), count);
+                }
+            }
+            Event::SAASignalsFound {
+                body_name,
+                signals,
+                genuses,
+                ..
+            } => {
+                let count = signal_count(signals, SignalKind::Biological);
+                if count > 0 {
+                    self.bio_signals.insert(body_name.to_owned(), count);
+                }
+                if !genuses.is_empty() {
+                    let names = genuses
+                        .iter()
+                        .map(|g| g.genus_localised.as_ref().unwrap_or(&g.genus).to_owned());
+                    self.genuses.insert(body_name.to_owned(), names.collect());
+                }
+            }
+            Event::ScanOrganic {
+                timestamp,
+                scan_type,
+                genus,
+                genus_localised,
+                species,
+                species_localised,
+                system_address,
+                body,
+                ..
+            } => {
+                let sample = Sample {
+                    species: species.to_owned(),
+                    name: species_localised.as_ref().unwrap_or(species).to_owned(),
+                    system_address: *system_address,
+                    body: *body,
+                    timestamp: *timestamp,
+                };
+
+                match scan_type {
+                    OrganicScanType::Analyse => {
+                        let summary = self.species.entry(sample.name.clone()).or_default();
+                        summary.genus = genus_localised.as_ref().unwrap_or(genus).to_owned();
+                        summary.analysed += 1;
+
+                        self.unsold.push(sample);
+                        self.in_progress = None;
+                    }
+                    _ => self.in_progress = Some((sample, *scan_type)),
+                }
+            }
+            Event::SellOrganicData { bio_data, .. } => {
+                for sale in bio_data {
+                    let name = sale.species_localised.as_ref().unwrap_or(&sale.species);
+                    let summary = self.species.entry(name.to_owned()).or_default();
+                    summary.genus = sale
+                        .genus_localised
+                        .as_ref()
+                        .unwrap_or(&sale.genus)
+                        .to_owned();
+                    summary.sold += 1;
+                    summary.value += sale.value;
+                    summary.bonus += sale.bonus;
+
+                    if let Some(i) = self.unsold.iter().position(|s| s.species == sale.species) {
+                        self.unsold.remove(i);
+                    }
+                }
+            }
+            Event::Died { .. } => {
+                self.lost.append(&mut self.unsold);
+                self.in_progress = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Total credits earned from selling samples, including bonuses
+    pub fn credits(&self) -> u64 {
+        self.species.values().map(|s| s.value + s.bonus).sum()
+    }
+
+    /// Bodies with at least `min` biological signals and their signal count
+    pub fn bodies_with_bio_signals(&self, min: u32) -> impl Iterator<Item = (&str, u32)> {
+        self.bio_signals
+            .iter()
+            .filter(move |(_, count)| **count >= min)
+            .map(|(body, count)| (body.as_str(), *count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(scan_type: &str) -> Event {
+        serde_json::from_str(&format!(
+            r#"{{ "timestamp":"2024-09-15T12:00:00Z", "event":"ScanOrganic", "ScanType":"{scan_type}",
+                "Genus":"$Codex_Ent_Stratum_Genus_Name;", "Genus_Localised":"Stratum",
+                "Species":"$Codex_Ent_Stratum_07_Name;", "Species_Localised":"Stratum Tectonicas",
+                "SystemAddress":10477373803, "Body":12 }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sample_lifecycle() {
+        let mut exobiology = Exobiology::default();
+
+        exobiology.update(&scan("Log"));
+        exobiology.update(&scan("Sample"));
+        assert!(matches!(
+            exobiology.in_progress,
+            Some((_, OrganicScanType::Sample))
+        ));
+
+        exobiology.update(&scan("Analyse"));
+        assert!(exobiology.in_progress.is_none());
+        assert_eq!(exobiology.unsold.len(), 1);
+
+        let sell: Event = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-16T12:00:00Z", "event":"SellOrganicData", "MarketID":3223343616,
+                "BioData":[ { "Genus":"$Codex_Ent_Stratum_Genus_Name;", "Genus_Localised":"Stratum",
+                "Species":"$Codex_Ent_Stratum_07_Name;", "Species_Localised":"Stratum Tectonicas",
+                "Value":19010800, "Bonus":76043200 } ] }"#,
+        )
+        .unwrap();
+        exobiology.update(&sell);
+
+        assert!(exobiology.unsold.is_empty());
+        assert_eq!(exobiology.credits(), 19010800 + 76043200);
+        assert_eq!(exobiology.species["Stratum Tectonicas"].analysed, 1);
+    }
+
+    #[test]
+    fn test_bio_signals() {
+        let mut exobiology = Exobiology::default();
+        let found: Event = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"SAASignalsFound", "BodyName":"Col 285 Sector AB-C d1 3", "SystemAddress":10477373803, "BodyID":12,
+                "Signals":[ { "Type":"$SAA_SignalType_Biological;", "Type_Localised":"Biological", "Count":3 }, { "Type":"$SAA_SignalType_Geological;", "Type_Localised":"Geological", "Count":2 } ],
+                "Genuses":[ { "Genus":"$Codex_Ent_Bacterial_Genus_Name;", "Genus_Localised":"Bacterium" } ] }"#,
+        )
+        .unwrap();
+        exobiology.update(&found);
+
+        assert_eq!(
+            exobiology.bodies_with_bio_signals(3).collect::<Vec<_>>(),
+            [("Col 285 Sector AB-C d1 3", 3)]
+        );
+        assert_eq!(exobiology.bodies_with_bio_signals(4).count(), 0);
+        assert_eq!(
+            exobiology.genuses["Col 285 Sector AB-C d1 3"],
+            ["Bacterium"]
+        );
+    }
+}
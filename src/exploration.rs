@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use crate::journal::Event;
+
+/// What the cartographics value of a scanned body depends on
+#[derive(Debug, Clone)]
+pub struct ScannedBody {
+    pub system: String,
+    /// Spectral class for stars
+    pub star_type: Option<String>,
+    /// Class for planets
+    pub planet_class: Option<String>,
+    pub terraformable: bool,
+    /// Solar masses for stars, earth masses for planets
+    pub mass: f64,
+    pub first_discovered: bool,
+    pub first_mapped: bool,
+    /// Whether the body was mapped within the efficiency target, if mapped
+    pub mapped: Option<bool>,
+}
+
+impl ScannedBody {
+    /// Estimated value using the formulas worked out by the community.
+    /// Odyssey pays an extra bonus for mapped bodies.
+    pub fn value(&self, odyssey: bool) -> u64 {
+        let value = match (&self.star_type, &self.planet_class) {
+            (Some(star_type), _) => {
+                let k = match star_type.as_str() {
+                    "N" | "H" => 22628.0,
+                    "SupermassiveBlackHole" => 33.5678,
+                    t if t.starts_with('D') => 14057.0,
+                    _ => 1200.0,
+                };
+                k + self.mass * k / 66.25
+            }
+            (None, Some(planet_class)) => {
+                let (k, terraform_bonus) = match planet_class.as_str() {
+                    "Metal rich body" => (21790.0, 65631.0),
+                    "Ammonia world" => (96932.0, 0.0),
+                    "Sudarsky class I gas giant" => (1656.0, 0.0),
+                    "Sudarsky class II gas giant" => (9654.0, 0.0),
+                    "High metal content body" => (9654.0, 100677.0),
+                    "Water world" => (64831.0, 116295.0),
+                    "Earthlike body" => (64831.0, 116295.0),
+                    _ => (300.0, 93328.0),
+                };
+                let terraformable = self.terraformable || planet_class == "Earthlike body";
+                let k = k + if terraformable { terraform_bonus } else { 0.0 };
+                let mut value = k + k * 0.56591828 * self.mass.powf(0.2);
+
+                if let Some(efficient) = self.mapped {
+                    let mut multiplier = match (self.first_discovered, self.first_mapped) {
+                        (true, true) => 3.699622554,
+                        (false, true) => 8.0956,
+                        _ => 10.0 / 3.0,
+                    };
+                    if efficient {
+                        multiplier *= 1.25;
+                    }
+                    value *= multiplier;
+                }
+                value = value.max(500.0);
+                if odyssey && self.mapped.is_some() {
+                    value += (value * 0.3).max(555.0);
+                }
+                value
+            }
+            (None, None) => return 0,
+        };
+
+        let value = value.max(500.0).round();
+        match self.first_discovered {
+            true => (value * 2.6).round() as u64,
+            false => value as u64,
+        }
+    }
+}
+
+/// Scan data held until it's sold at Universal Cartographics
+#[derive(Debug, Default)]
+pub struct Exploration {
+    /// Number of scanned bodies keyed by system name.
+    /// These are lost if the commander dies.
+    pub unsold: BTreeMap<String, u32>,
+    /// Scanned stars and planets not sold yet, keyed by body name
+    pub unsold_bodies: BTreeMap<String, ScannedBody>,
+    /// Whether the last journal was written by Odyssey
+    pub odyssey: bool,
+    /// Credits earned from selling data, including bonuses
+    pub earned: u64,
+    /// Systems whose data was lost by dying
+    pub lost: Vec<String>,
+    current_system: Option<String>,
+}
+
+impl Exploration {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump { star_system, .. } | Event::Location { star_system, .. } => {
+                self.current_system = Some(star_system.to_owned());
+            }
+            Event::Fileheader { odyssey, .. } => self.odyssey = *odyssey,
+            Event::Scan {
+                scan_type,
+                body_name,
+                star_type,
+                planet_class,
+                terraform_state,
+                stellar_mass,
+                mass_em,
+                was_discovered,
+                was_mapped,
+                ..
+            } => {
+                let Some(system) = &self.current_system else {
+                    return;
+                };
+                *self.unsold.entry(system.to_owned()).or_default() += 1;
+
+                // Nav beacon data isn't sold, belt clusters are worth next to nothing
+                if scan_type.starts_with("NavBeacon")
+                    || (star_type.is_none() && planet_class.is_none())
+                {
+                    return;
+                }
+                self.unsold_bodies
+                    .entry(body_name.to_owned())
+                    .or_insert_with(|| ScannedBody {
+                        system: system.to_owned(),
+                        star_type: star_type.clone(),
+                        planet_class: planet_class.clone(),
+                        terraformable: terraform_state
+                            .as_ref()
+                            .is_some_and(|t| t == "Terraformable" || t == "Terraforming"),
+                        mass: stellar_mass.or(*mass_em).unwrap_or(0.0),
+                        first_discovered: *was_discovered == Some(false),
+                        first_mapped: *was_mapped == Some(false),
+                        mapped: None,
+                    });
+            }
+            Event::SAAScanComplete {
+                body_name,
+                probes_used,
+                efficiency_target,
+                ..
+            } => {
+                if let Some(body) = self.unsold_bodies.get_mut(body_name) {
+                    body.mapped = Some(probes_used <= efficiency_target);
+                }
+            }
+            Event::MultiSellExplorationData {
+                discovered,
+                total_earnings,
+                ..
+            } => {
+                for system in discovered {
+                    self.unsold.remove(&system.system_name);
+                    self.unsold_bodies
+                        .retain(|_, body| body.system != system.system_name);
+                }
+                self.earned += total_earnings;
+            }
+            Event::SellExplorationData {
+                systems,
+                base_value,
+                bonus,
+                total_earnings,
+                ..
+            } => {
+                for system in systems {
+                    self.unsold.remove(system);
+                    self.unsold_bodies.retain(|_, body| body.system != *system);
+                }
+                self.earned += total_earnings.unwrap_or(base_value + bonus);
+            }
+            Event::Died { .. } => {
+                self.lost
+                    .extend(std::mem::take(&mut self.unsold).into_keys());
+                self.unsold_bodies.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Exploration {
+    /// Estimated value of the unsold data of a system
+    pub fn unsold_value(&self, system: &str) -> u64 {
+        self.unsold_bodies
+            .values()
+            .filter(|body| body.system == system)
+            .map(|body| body.value(self.odyssey))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_unsold_systems() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"FSDJump", "StarSystem":"Pru Euq AA-A g1", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":50.0, "FuelUsed":5.0, "FuelLevel":20.0 }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"Pru Euq AA-A g1 A" }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"Pru Euq AA-A g1 1" }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"FSDJump", "StarSystem":"Pru Euq AA-A g2", "SystemAddress":2, "StarPos":[0.0,0.0,50.0], "JumpDist":50.0, "FuelUsed":5.0, "FuelLevel":15.0 }
+{ "timestamp":"2024-09-15T12:11:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"Pru Euq AA-A g2 A" }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"MultiSellExplorationData", "Discovered":[ { "SystemName":"Pru Euq AA-A g1", "NumBodies":2 } ], "BaseValue":10000, "Bonus":5000, "TotalEarnings":15000 }
+"#,
+        )
+        .unwrap();
+
+        let mut exploration = Exploration::default();
+        for event in &events {
+            exploration.update(event);
+        }
+
+        assert_eq!(exploration.unsold.len(), 1);
+        assert_eq!(exploration.unsold.get("Pru Euq AA-A g2"), Some(&1));
+        assert_eq!(exploration.earned, 15000);
+    }
+
+    #[test]
+    fn test_body_value() {
+        let body = |planet_class: &str, terraformable, mass| ScannedBody {
+            system: "Sol".to_owned(),
+            star_type: None,
+            planet_class: Some(planet_class.to_owned()),
+            terraformable,
+            mass,
+            first_discovered: false,
+            first_mapped: false,
+            mapped: None,
+        };
+
+        // Earth like worlds always get the terraformable bonus
+        let mut earth = body("Earthlike body", false, 1.0);
+        assert_eq!(earth.value(false), 283629);
+        earth.mapped = Some(true);
+        assert!(earth.value(true) > 1_000_000);
+
+        let rock = body("Rocky body", false, 0.01);
+        assert_eq!(rock.value(false), 500);
+    }
+}
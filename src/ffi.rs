@@ -0,0 +1,132 @@
+//! C API for embedding the parser in other languages
+//!
+//! Strings returned by these functions are owned by the caller and must be
+//! released with [`elite_string_free`].
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr,
+};
+
+use serde::Serialize;
+
+use crate::{journal, state::GameState};
+
+/// Serialize to a newly allocated C string, null on failure
+fn to_c_json(value: &impl Serialize) -> *mut c_char {
+    serde_json::to_string(value)
+        .ok()
+        .and_then(|json| CString::new(json).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// # Safety
+///
+/// `line` must be null or a valid nul-terminated string
+unsafe fn parse_c_line(line: *const c_char) -> Option<journal::Event> {
+    if line.is_null() {
+        return None;
+    }
+    let line = CStr::from_ptr(line).to_str().ok()?;
+    journal::parse_line(line).ok()
+}
+
+/// Parse a journal line and return the normalized event as JSON, or null if
+/// the line couldn't be parsed
+///
+/// # Safety
+///
+/// `line` must be null or a valid nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn elite_parse_line(line: *const c_char) -> *mut c_char {
+    parse_c_line(line).map_or(ptr::null_mut(), |event| to_c_json(&event))
+}
+
+/// Free a string returned by this library
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that hasn't been
+/// freed yet
+#[no_mangle]
+pub unsafe extern "C" fn elite_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Create an empty game state, free it with [`elite_state_free`]
+#[no_mangle]
+pub extern "C" fn elite_state_new() -> *mut GameState {
+    Box::into_raw(Box::default())
+}
+
+/// # Safety
+///
+/// `state` must be null or a state returned by [`elite_state_new`] that
+/// hasn't been freed yet
+#[no_mangle]
+pub unsafe extern "C" fn elite_state_free(state: *mut GameState) {
+    if !state.is_null() {
+        drop(Box::from_raw(state));
+    }
+}
+
+/// Update the state with a journal line, returns false if the line couldn't
+/// be parsed
+///
+/// # Safety
+///
+/// `state` must be a valid state from [`elite_state_new`] and `line` null or
+/// a valid nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn elite_state_update(state: *mut GameState, line: *const c_char) -> bool {
+    let (Some(state), Some(event)) = (state.as_mut(), parse_c_line(line)) else {
+        return false;
+    };
+    state.update(&event);
+    true
+}
+
+/// Current state as JSON, or null if `state` is null
+///
+/// # Safety
+///
+/// `state` must be null or a valid state from [`elite_state_new`]
+#[no_mangle]
+pub unsafe extern "C" fn elite_state_json(state: *const GameState) -> *mut c_char {
+    state.as_ref().map_or(ptr::null_mut(), to_c_json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_roundtrip() {
+        let line = CString::new(
+            r#"{ "timestamp":"2024-09-15T12:00:05Z", "event":"Commander", "FID":"F123", "Name":"Tester" }"#,
+        )
+        .unwrap();
+
+        unsafe {
+            let event = elite_parse_line(line.as_ptr());
+            assert!(CStr::from_ptr(event)
+                .to_str()
+                .unwrap()
+                .contains(r#""Name":"Tester""#));
+            elite_string_free(event);
+
+            let state = elite_state_new();
+            assert!(elite_state_update(state, line.as_ptr()));
+            assert!(!elite_state_update(state, c"not json".as_ptr()));
+            let json = elite_state_json(state);
+            assert!(CStr::from_ptr(json)
+                .to_str()
+                .unwrap()
+                .contains(r#""commander":"Tester""#));
+            elite_string_free(json);
+            elite_state_free(state);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+/// Body nobody had sold discovery or mapping data for before the commander
+#[derive(Debug, Clone)]
+pub struct First {
+    pub body: String,
+    pub system: Option<String>,
+    pub scanned: OffsetDateTime,
+    pub discovered: bool,
+    pub mapped: Option<OffsetDateTime>,
+    /// Probes used and the efficiency target when mapped
+    pub probes: Option<(u32, u32)>,
+    pub sold: Option<OffsetDateTime>,
+}
+
+/// Sale of exploration data that included firsts
+#[derive(Debug, Clone)]
+pub struct FirstsSale {
+    pub timestamp: OffsetDateTime,
+    /// Number of firsts sold
+    pub bodies: usize,
+    /// Bonus for first discoveries and mappings of the whole sale
+    pub bonus: u64,
+}
+
+/// Ledger of first discoveries and first mappings
+#[derive(Debug, Default)]
+pub struct Firsts {
+    /// Keyed by body name
+    pub bodies: BTreeMap<String, First>,
+    pub sales: Vec<FirstsSale>,
+    /// Bodies someone else discovered but nobody mapped yet, which are
+    /// only a first once mapped
+    discovered_unmapped: BTreeMap<String, (Option<String>, OffsetDateTime)>,
+}
+
+impl Firsts {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::Scan {
+                timestamp,
+                scan_type,
+                body_name,
+                star_system,
+                was_discovered,
+                was_mapped,
+                ..
+            } if !scan_type.starts_with("NavBeacon") => {
+                if self.bodies.contains_key(body_name) {
+                    return;
+                }
+                match (was_discovered, was_mapped) {
+                    (Some(false), _) => {
+                        self.bodies.insert(
+                            body_name.to_owned(),
+                            First {
+                                body: body_name.to_owned(),
+                                system: star_system.clone(),
+                                scanned: *timestamp,
+                                discovered: true,
+                                mapped: None,
+                                probes: None,
+                                sold: None,
+                            },
+                        );
+                    }
+                    (Some(true), Some(false)) => {
+                        self.discovered_unmapped
+                            .insert(body_name.to_owned(), (star_system.clone(), *timestamp));
+                    }
+                    _ => {}
+                }
+            }
+            Event::SAAScanComplete {
+                timestamp,
+                body_name,
+                probes_used,
+                efficiency_target,
+                ..
+            } => {
+                let first = match self.discovered_unmapped.remove(body_name) {
+                    Some((system, scanned)) => self
+                        .bodies
+                        .entry(body_name.to_owned())
+                        .or_insert_with(|| First {
+                            body: body_name.to_owned(),
+                            system,
+                            scanned,
+                            discovered: false,
+                            mapped: None,
+                            probes: None,
+                            sold: None,
+                        }),
+                    None => match self.bodies.get_mut(body_name) {
+                        Some(first) => first,
+                        None => return,
+                    },
+                };
+                first.mapped = Some(*timestamp);
+                first.probes = Some((*probes_used, *efficiency_target));
+            }
+            Event::MultiSellExplorationData {
+                timestamp,
+                discovered,
+                bonus,
+                ..
+            } => {
+                let systems: Vec<&str> =
+                    discovered.iter().map(|s| s.system_name.as_str()).collect();
+                self.sell(*timestamp, &systems, *bonus);
+            }
+            Event::SellExplorationData {
+                timestamp,
+                systems,
+                bonus,
+                ..
+            } => {
+                let systems: Vec<&str> = systems.iter().map(String::as_str).collect();
+                self.sell(*timestamp, &systems, *bonus);
+            }
+            // Unsold data is lost, someone else may get there first now
+            Event::Died { .. } => {
+                self.bodies.retain(|_, first| first.sold.is_some());
+                self.discovered_unmapped.clear();
+            }
+            _ => {}
+        }
+    }
+
+    fn sell(&mut self, timestamp: OffsetDateTime, systems: &[&str], bonus: u64) {
+        let mut bodies = 0;
+        for first in self.bodies.values_mut() {
+            let in_sale = first
+                .system
+                .as_deref()
+                .is_some_and(|system| systems.contains(&system));
+            if first.sold.is_none() && in_sale {
+                first.sold = Some(timestamp);
+                bodies += 1;
+            }
+        }
+
+        if bodies > 0 {
+            self.sales.push(FirstsSale {
+                timestamp,
+                bodies,
+                bonus,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_firsts() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"Pru Euq AA-A g1 1", "StarSystem":"Pru Euq AA-A g1", "SystemAddress":1, "WasDiscovered":false, "WasMapped":false }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"Pru Euq AA-A g1 2", "StarSystem":"Pru Euq AA-A g1", "SystemAddress":1, "WasDiscovered":true, "WasMapped":false }
+{ "timestamp":"2024-09-15T12:03:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"Pru Euq AA-A g1 3", "StarSystem":"Pru Euq AA-A g1", "SystemAddress":1, "WasDiscovered":true, "WasMapped":true }
+{ "timestamp":"2024-09-15T12:05:00Z", "event":"SAAScanComplete", "BodyName":"Pru Euq AA-A g1 2", "SystemAddress":1, "BodyID":2, "ProbesUsed":5, "EfficiencyTarget":6 }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"MultiSellExplorationData", "Discovered":[ { "SystemName":"Pru Euq AA-A g1", "NumBodies":3 } ], "BaseValue":10000, "Bonus":5000, "TotalEarnings":15000 }
+"#,
+        )
+        .unwrap();
+
+        let mut firsts = Firsts::default();
+        for event in &events {
+            firsts.update(event);
+        }
+
+        assert_eq!(firsts.bodies.len(), 2);
+        assert!(firsts.bodies["Pru Euq AA-A g1 1"].discovered);
+        let mapped = &firsts.bodies["Pru Euq AA-A g1 2"];
+        assert!(!mapped.discovered);
+        assert_eq!(mapped.probes, Some((5, 6)));
+        assert!(mapped.sold.is_some());
+        assert_eq!(firsts.sales[0].bodies, 2);
+        assert_eq!(firsts.sales[0].bonus, 5000);
+    }
+}
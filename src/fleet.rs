@@ -0,0 +1,246 @@
+//! Every ship and stored module of the commander and where it's parked.
+//!
+//! The lists come from StoredShips and StoredModules, written when the
+//! shipyard and outfitting are opened, and are kept up to date with the
+//! ships bought, sold, swapped and sent for in between.
+
+use std::collections::BTreeMap;
+
+use time::{Duration, OffsetDateTime};
+
+use crate::journal::{Event, StoredModule};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FleetShip {
+    pub ship_id: u32,
+    /// Symbol like "krait_mkii"
+    pub ship_type: String,
+    /// User defined ship name
+    pub name: Option<String>,
+    /// Hull and modules
+    pub value: u64,
+    pub hot: bool,
+    /// Where the ship is parked, or headed while in transit. Missing for
+    /// the ship in use and while unknown
+    pub system: Option<String>,
+    pub station: Option<String>,
+    pub in_transit: bool,
+    /// When a ship sent for arrives, if it was sent for in the journals
+    /// read
+    pub arrival: Option<OffsetDateTime>,
+    /// Credits to have it sent to the station the shipyard was last
+    /// opened at
+    pub transfer_price: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct Fleet {
+    /// Ships by ID
+    pub ships: BTreeMap<u32, FleetShip>,
+    /// ID of the ship in use, missing on foot in a taxi or while unknown
+    pub current_ship: Option<u32>,
+    /// As of the last time outfitting was opened
+    pub modules: Vec<StoredModule>,
+    /// System and station the commander is docked at
+    docked: Option<(String, String)>,
+}
+
+impl Fleet {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::Docked {
+                star_system,
+                station_name,
+                taxi: false,
+                ..
+            } => self.docked = Some((star_system.clone(), station_name.clone())),
+            Event::Location {
+                star_system,
+                docked: true,
+                station_name: Some(station),
+                ..
+            } => self.docked = Some((star_system.clone(), station.clone())),
+            Event::Undocked { .. } | Event::Location { docked: false, .. } => self.docked = None,
+            Event::Loadout {
+                ship,
+                ship_id,
+                ship_name,
+                hull_value,
+                modules_value,
+                hot,
+                ..
+            } => {
+                self.current_ship = Some(*ship_id);
+                self.ships.insert(
+                    *ship_id,
+                    FleetShip {
+                        ship_id: *ship_id,
+                        ship_type: ship.to_lowercase(),
+                        name: Some(ship_name.clone()).filter(|name| !name.is_empty()),
+                        value: u64::from(*hull_value) + u64::from(*modules_value),
+                        hot: *hot,
+                        system: None,
+                        station: None,
+                        in_transit: false,
+                        arrival: None,
+                        transfer_price: None,
+                    },
+                );
+            }
+            Event::StoredShips {
+                station_name,
+                star_system,
+                ships_here,
+                ships_remote,
+                ..
+            } => {
+                self.ships.retain(|id, _| Some(*id) == self.current_ship);
+                for (ship, here) in ships_here
+                    .iter()
+                    .map(|ship| (ship, true))
+                    .chain(ships_remote.iter().map(|ship| (ship, false)))
+                {
+                    self.ships.insert(
+                        ship.ship_id,
+                        FleetShip {
+                            ship_id: ship.ship_id,
+                            ship_type: ship.ship_type.to_lowercase(),
+                            name: ship.name.clone().filter(|name| !name.is_empty()),
+                            value: ship.value,
+                            hot: ship.hot,
+                            system: match here {
+                                true => star_system.clone(),
+                                false => ship.star_system.clone(),
+                            },
+                            station: station_name.clone().filter(|_| here),
+                            in_transit: ship.in_transit,
+                            arrival: None,
+                            transfer_price: ship.transfer_price,
+                        },
+                    );
+                }
+            }
+            Event::ShipyardBuy {
+                store_ship_id,
+                sell_ship_id,
+                ..
+            }
+            | Event::ShipyardSwap {
+                store_ship_id,
+                sell_ship_id,
+                ..
+            } => {
+                if let Some(id) = sell_ship_id {
+                    self.ships.remove(id);
+                }
+                if let Some(ship) = store_ship_id.and_then(|id| self.ships.get_mut(&id)) {
+                    ship.system = self.docked.as_ref().map(|(system, _)| system.clone());
+                    ship.station = self.docked.as_ref().map(|(_, station)| station.clone());
+                }
+                if let Event::ShipyardSwap { ship_id, .. } = event {
+                    self.current_ship = Some(*ship_id);
+                }
+            }
+            Event::ShipyardNew {
+                ship_type,
+                new_ship_id,
+                ..
+            } => {
+                self.current_ship = Some(*new_ship_id);
+                self.ships.entry(*new_ship_id).or_insert(FleetShip {
+                    ship_id: *new_ship_id,
+                    ship_type: ship_type.to_lowercase(),
+                    name: None,
+                    value: 0,
+                    hot: false,
+                    system: None,
+                    station: None,
+                    in_transit: false,
+                    arrival: None,
+                    transfer_price: None,
+                });
+            }
+            Event::ShipyardSell { sell_ship_id, .. } => {
+                self.ships.remove(sell_ship_id);
+            }
+            Event::ShipyardTransfer {
+                timestamp,
+                ship_id,
+                transfer_time,
+                ..
+            } => {
+                if let Some(ship) = self.ships.get_mut(ship_id) {
+                    ship.system = self.docked.as_ref().map(|(system, _)| system.clone());
+                    ship.station = self.docked.as_ref().map(|(_, station)| station.clone());
+                    ship.in_transit = true;
+                    ship.arrival = Some(*timestamp + Duration::seconds(*transfer_time as i64));
+                    ship.transfer_price = None;
+                }
+            }
+            Event::StoredModules { items, .. } => self.modules = items.clone(),
+            Event::ModuleSellRemote {
+                storage_slot: Some(slot),
+                ..
+            } => self.modules.retain(|module| module.storage_slot != *slot),
+            Event::FetchRemoteModule { storage_slot, .. } => {
+                if let Some(module) = self
+                    .modules
+                    .iter_mut()
+                    .find(|module| module.storage_slot == *storage_slot)
+                {
+                    module.star_system = self.docked.as_ref().map(|(system, _)| system.clone());
+                    module.in_transit = true;
+                    module.transfer_cost = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Ships not in use, with the one in use first
+    pub fn ships(&self) -> impl Iterator<Item = &FleetShip> {
+        let current = self.current_ship.and_then(|id| self.ships.get(&id));
+        current.into_iter().chain(
+            self.ships
+                .values()
+                .filter(move |ship| Some(ship.ship_id) != self.current_ship),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_fleet() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:05Z", "event":"Loadout", "Ship":"anaconda", "ShipID":1, "ShipName":"Hoarder", "ShipIdent":"", "HullValue":140000000, "ModulesValue":60000000, "Rebuy":10000000, "Modules":[] }
+{ "timestamp":"2024-09-15T12:00:30Z", "event":"Docked", "StationName":"Jameson Memorial", "StationType":"Orbis", "StarSystem":"Shinrarta Dezhra", "SystemAddress":3932277478106, "MarketID":128666762 }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"StoredShips", "StationName":"Jameson Memorial", "MarketID":128666762, "StarSystem":"Shinrarta Dezhra", "ShipsHere":[ { "ShipID":2, "ShipType":"sidewinder", "Value":30000, "Hot":false } ], "ShipsRemote":[ { "ShipID":3, "ShipType":"krait_mkii", "StarSystem":"Sol", "ShipMarketID":128016640, "TransferPrice":1000, "TransferTime":600, "Value":50000000, "Hot":false } ] }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"StoredModules", "MarketID":128666762, "StationName":"Jameson Memorial", "StarSystem":"Shinrarta Dezhra", "Items":[ { "Name":"$int_hyperdrive_size5_class5_name;", "StorageSlot":1, "StarSystem":"Sol", "MarketID":128016640, "TransferCost":1200, "TransferTime":600, "BuyPrice":5000000, "Hot":false, "EngineerModifications":"FSD_LongRange", "Level":5, "Quality":1.0 } ] }
+{ "timestamp":"2024-09-15T12:03:00Z", "event":"ShipyardTransfer", "ShipType":"krait_mkii", "ShipID":3, "System":"Sol", "ShipMarketID":128016640, "Distance":49.1, "TransferPrice":1000, "TransferTime":600, "MarketID":128666762 }
+{ "timestamp":"2024-09-15T12:04:00Z", "event":"ShipyardSwap", "ShipType":"sidewinder", "ShipID":2, "StoreOldShip":"Anaconda", "StoreShipID":1, "MarketID":128666762 }
+"#,
+        )
+        .unwrap();
+        let mut fleet = Fleet::default();
+        for event in &events {
+            fleet.update(event);
+        }
+
+        let ships: Vec<&FleetShip> = fleet.ships().collect();
+        assert_eq!(ships.len(), 3);
+        assert_eq!(ships[0].ship_type, "sidewinder");
+        assert_eq!(ships[1].name.as_deref(), Some("Hoarder"));
+        assert_eq!(ships[1].station.as_deref(), Some("Jameson Memorial"));
+        assert_eq!(ships[2].system.as_deref(), Some("Shinrarta Dezhra"));
+        assert!(ships[2].in_transit);
+        assert_eq!(ships[2].arrival, Some(datetime!(2024-09-15 12:13 UTC)));
+        assert_eq!(fleet.modules[0].level, Some(5));
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::{Event, FriendStatus};
+
+#[derive(Debug)]
+pub struct Sighting {
+    pub timestamp: OffsetDateTime,
+    pub name: String,
+    pub status: FriendStatus,
+}
+
+#[derive(Debug)]
+pub struct FriendSummary {
+    pub times_online: u32,
+    pub last_online: Option<OffsetDateTime>,
+    pub last_status: FriendStatus,
+}
+
+/// Friend status changes across the journal history
+#[derive(Debug, Default)]
+pub struct FriendsLog {
+    pub sightings: Vec<Sighting>,
+    /// Keyed by friend name
+    pub friends: BTreeMap<String, FriendSummary>,
+}
+
+impl FriendsLog {
+    pub fn update(&mut self, event: &Event) {
+        if let Event::Friends {
+            timestamp,
+            status,
+            name,
+        } = event
+        {
+            let summary = self
+                .friends
+                .entry(name.to_owned())
+                .or_insert(FriendSummary {
+                    times_online: 0,
+                    last_online: None,
+                    last_status: *status,
+                });
+            summary.last_status = *status;
+            if *status == FriendStatus::Online {
+                summary.times_online += 1;
+                summary.last_online = Some(*timestamp);
+            }
+
+            self.sightings.push(Sighting {
+                timestamp: *timestamp,
+                name: name.to_owned(),
+                status: *status,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_friends_log() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Friends", "Status":"Requested", "Name":"Jameson" }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"Friends", "Status":"Added", "Name":"Jameson" }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"Friends", "Status":"Online", "Name":"Jameson" }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"Friends", "Status":"Offline", "Name":"Jameson" }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"Friends", "Status":"Online", "Name":"Jameson" }
+{ "timestamp":"2024-09-16T12:05:00Z", "event":"Friends", "Status":"Declined", "Name":"Arissa" }
+"#,
+        )
+        .unwrap();
+        let mut log = FriendsLog::default();
+        for event in &events {
+            log.update(event);
+        }
+
+        assert_eq!(log.sightings.len(), 6);
+        let jameson = &log.friends["Jameson"];
+        assert_eq!(jameson.times_online, 2);
+        assert_eq!(jameson.last_online, Some(datetime!(2024-09-16 12:00 UTC)));
+        assert_eq!(jameson.last_status, FriendStatus::Online);
+        let arissa = &log.friends["Arissa"];
+        assert_eq!(arissa.times_online, 0);
+        assert_eq!(arissa.last_status, FriendStatus::Declined);
+    }
+}
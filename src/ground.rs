@@ -0,0 +1,170 @@
+//! On-foot sessions at planetary settlements, from disembarking to getting
+//! back in, with the loot, bonds and missions of each.
+
+use std::collections::HashMap;
+
+use time::{Duration, OffsetDateTime};
+
+use crate::journal::Event;
+
+#[derive(Debug, Clone)]
+pub struct GroundSession {
+    pub start: OffsetDateTime,
+    /// Missing while still on foot
+    pub end: Option<OffsetDateTime>,
+    pub system: String,
+    pub body: String,
+    /// Last settlement approached on the same body, if any
+    pub settlement: Option<String>,
+    /// Items picked up by symbol in lower case
+    pub loot: HashMap<String, u32>,
+    pub kills: u32,
+    /// Credits of combat bonds earned
+    pub bonds: u64,
+    pub missions_completed: u32,
+}
+
+impl GroundSession {
+    pub fn duration(&self) -> Option<Duration> {
+        self.end.map(|end| end - self.start)
+    }
+
+    pub fn loot_count(&self) -> u32 {
+        self.loot.values().sum()
+    }
+}
+
+/// Ground combat and looting on planets, not counting stations
+#[derive(Debug, Default)]
+pub struct GroundCombat {
+    pub sessions: Vec<GroundSession>,
+    /// Last settlement approached with its body
+    approached: Option<(String, String)>,
+}
+
+impl GroundCombat {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::ApproachSettlement {
+                name,
+                name_localised,
+                body_name,
+                ..
+            } => {
+                let name = name_localised.as_ref().unwrap_or(name);
+                self.approached = Some((name.clone(), body_name.clone()));
+            }
+            Event::Disembark {
+                timestamp,
+                star_system,
+                body,
+                on_planet: true,
+                on_station: false,
+                ..
+            } => {
+                self.end_session(*timestamp);
+                let settlement = self
+                    .approached
+                    .as_ref()
+                    .filter(|(_, settlement_body)| settlement_body == body)
+                    .map(|(name, _)| name.clone());
+                self.sessions.push(GroundSession {
+                    start: *timestamp,
+                    end: None,
+                    system: star_system.clone(),
+                    body: body.clone(),
+                    settlement,
+                    loot: HashMap::new(),
+                    kills: 0,
+                    bonds: 0,
+                    missions_completed: 0,
+                });
+            }
+            Event::Embark { timestamp, .. }
+            | Event::Died { timestamp, .. }
+            | Event::Shutdown { timestamp }
+            | Event::Fileheader { timestamp, .. } => self.end_session(*timestamp),
+            Event::BackpackChange { added, .. } => {
+                if let Some(session) = self.current() {
+                    for item in added {
+                        *session.loot.entry(item.name.to_lowercase()).or_default() += item.count;
+                    }
+                }
+            }
+            Event::FactionKillBond { reward, .. } => {
+                if let Some(session) = self.current() {
+                    session.kills += 1;
+                    session.bonds += reward;
+                }
+            }
+            Event::MissionCompleted { .. } => {
+                if let Some(session) = self.current() {
+                    session.missions_completed += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn current(&mut self) -> Option<&mut GroundSession> {
+        self.sessions.last_mut().filter(|s| s.end.is_none())
+    }
+
+    fn end_session(&mut self, timestamp: OffsetDateTime) {
+        if let Some(session) = self.current() {
+            session.end = Some(timestamp);
+        }
+    }
+
+    /// Sessions by settlement, or by body where no settlement was approached
+    pub fn by_settlement(&self) -> Vec<(String, Vec<&GroundSession>)> {
+        let mut settlements: Vec<(String, Vec<&GroundSession>)> = Vec::new();
+        for session in &self.sessions {
+            let name = match &session.settlement {
+                Some(settlement) => format!("{settlement} ({})", session.body),
+                None => session.body.clone(),
+            };
+            match settlements.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, sessions)) => sessions.push(session),
+                None => settlements.push((name, vec![session])),
+            }
+        }
+        settlements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_ground_sessions() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"ApproachSettlement", "Name":"$Ancient_Tiny_003:#index=1;", "Name_Localised":"Guardian Structure", "SystemAddress":1, "BodyID":5, "BodyName":"Synuefe XR-H d11-102 1 B", "Latitude":-20.1, "Longitude":120.4 }
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"ApproachSettlement", "Name":"Hauser Relay", "MarketID":3900000001, "SystemAddress":2, "BodyID":3, "BodyName":"HIP 100 A 2", "Latitude":10.0, "Longitude":20.0 }
+{ "timestamp":"2024-09-15T12:05:00Z", "event":"Disembark", "SRV":false, "Taxi":false, "Multicrew":false, "ID":1, "StarSystem":"HIP 100", "SystemAddress":2, "Body":"HIP 100 A 2", "BodyID":3, "OnStation":false, "OnPlanet":true }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"FactionKillBond", "Reward":40000, "AwardingFaction":"Alliance", "VictimFaction":"Empire" }
+{ "timestamp":"2024-09-15T12:11:00Z", "event":"FactionKillBond", "Reward":40000, "AwardingFaction":"Alliance", "VictimFaction":"Empire" }
+{ "timestamp":"2024-09-15T12:15:00Z", "event":"BackpackChange", "Added":[ { "Name":"surveillanceequipment", "OwnerID":0, "Count":2, "Type":"Item" }, { "Name":"graphene", "OwnerID":0, "Count":1, "Type":"Component" } ] }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"Embark", "SRV":false, "Taxi":false, "Multicrew":false, "ID":1, "StarSystem":"HIP 100", "SystemAddress":2, "Body":"HIP 100 A 2", "BodyID":3, "OnStation":false, "OnPlanet":true }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"Disembark", "SRV":false, "Taxi":false, "Multicrew":false, "ID":1, "StarSystem":"Sol", "SystemAddress":10477373803, "Body":"Abraham Lincoln", "BodyID":20, "OnStation":true, "OnPlanet":false, "StationName":"Abraham Lincoln", "StationType":"Orbis", "MarketID":128016640 }
+"#,
+        )
+        .unwrap();
+        let mut combat = GroundCombat::default();
+        for event in &events {
+            combat.update(event);
+        }
+
+        // Walking around a station is no ground session
+        assert_eq!(combat.sessions.len(), 1);
+        let session = &combat.sessions[0];
+        assert_eq!(session.settlement.as_deref(), Some("Hauser Relay"));
+        assert_eq!(session.duration(), Some(Duration::minutes(25)));
+        assert_eq!((session.kills, session.bonds), (2, 80000));
+        assert_eq!(session.loot_count(), 3);
+        assert_eq!(combat.by_settlement()[0].0, "Hauser Relay (HIP 100 A 2)");
+    }
+}
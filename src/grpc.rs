@@ -0,0 +1,359 @@
+use std::{
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{atomic::AtomicBool, Arc, Mutex},
+    thread,
+};
+
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tonic::{
+    transport::{Channel, Server},
+    Request, Response, Status,
+};
+
+use crate::{
+    journal::{self, Event, JournalSource, JournalTail, LocalSource},
+    processor::{self, EventProcessor, ProcessorResult, Processors, RunOptions},
+    state::GameState,
+};
+
+/// Bytes of a journal sent per message, well below the 4 MB message limit
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Types generated from proto/elite.proto
+pub mod proto {
+    tonic::include_proto!("elite");
+}
+
+use proto::{
+    journal_client::JournalClient,
+    journal_server::{Journal, JournalServer},
+};
+
+impl From<&Event> for proto::Event {
+    fn from(event: &Event) -> Self {
+        let json = serde_json::to_value(event).unwrap_or_default();
+        proto::Event {
+            timestamp: Some(prost_types::Timestamp {
+                seconds: event.timestamp().unix_timestamp(),
+                nanos: event.timestamp().nanosecond() as i32,
+            }),
+            event: json["event"].as_str().unwrap_or_default().to_owned(),
+            json: json.to_string(),
+        }
+    }
+}
+
+impl From<&GameState> for proto::GameState {
+    fn from(state: &GameState) -> Self {
+        proto::GameState {
+            commander: state.commander.clone(),
+            fid: state.fid.clone(),
+            game_version: state.game_version.clone(),
+            odyssey: state.odyssey,
+            credits: state.credits,
+            ship: state.ship.clone(),
+            ship_id: state.ship_id,
+            ship_name: state.ship_name.clone(),
+            ship_ident: state.ship_ident.clone(),
+            system: state.system.clone(),
+            system_address: state.system_address,
+            star_pos: state.star_pos.map(|[x, y, z]| proto::StarPos { x, y, z }),
+            body: state.body.clone(),
+            docked: state.docked,
+            station: state.station.clone(),
+            market_id: state.market_id,
+            fuel_level: state.fuel_level,
+        }
+    }
+}
+
+/// Serves live events and state from the latest journal
+#[derive(Debug)]
+pub struct JournalService {
+    events: broadcast::Sender<proto::Event>,
+    state: Arc<Mutex<GameState>>,
+    journals: LocalSource,
+}
+
+/// Passes events on to the clients of a [JournalService]
+struct Broadcast {
+    events: broadcast::Sender<proto::Event>,
+    state: Arc<Mutex<GameState>>,
+}
+
+impl EventProcessor for Broadcast {
+    fn name(&self) -> &str {
+        "grpc"
+    }
+
+    fn on_event(&mut self, event: &Event) -> ProcessorResult {
+        self.state.lock().unwrap().update(event);
+        // Nobody listening isn't an error
+        let _ = self.events.send(event.into());
+        Ok(())
+    }
+}
+
+impl JournalService {
+    /// Start following the latest journal on a background thread
+    pub fn new() -> Result<Self, std::io::Error> {
+        Self::with_processors(Processors::default())
+    }
+
+    /// Like [JournalService::new] but also feeding the events to processors
+    /// on the same thread
+    pub fn with_processors(mut processors: Processors) -> Result<Self, std::io::Error> {
+        let mut tail = JournalTail::new()?;
+        let (events, _) = broadcast::channel(256);
+        let state = Arc::new(Mutex::new(GameState::default()));
+
+        // Catch up on the state from earlier in the session
+        for event in journal::read_events(&[tail.path().to_owned()])? {
+            state.lock().unwrap().update(&event);
+        }
+
+        processors.register(Broadcast {
+            events: events.clone(),
+            state: state.clone(),
+        });
+        thread::spawn(move || {
+            let running = AtomicBool::new(true);
+            let options = RunOptions::default();
+            loop {
+                if let Err(e) =
+                    processor::run(&mut tail, &mut processors, &options, &running, |e| {
+                        eprintln!("{e}")
+                    })
+                {
+                    eprintln!("Failed to read journal: {e}");
+                }
+                thread::sleep(options.interval);
+            }
+        });
+
+        Ok(JournalService {
+            events,
+            state,
+            journals: LocalSource::default(),
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl Journal for JournalService {
+    type StreamEventsStream = Pin<Box<dyn Stream<Item = Result<proto::Event, Status>> + Send>>;
+
+    async fn stream_events(
+        &self,
+        request: Request<proto::StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let kinds = request.into_inner().kinds;
+        // Clients that fall behind miss events rather than blocking the others
+        let stream = BroadcastStream::new(self.events.subscribe()).filter_map(move |event| {
+            event
+                .ok()
+                .filter(|e| kinds.is_empty() || kinds.contains(&e.event))
+                .map(Ok)
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_state(
+        &self,
+        _: Request<proto::GetStateRequest>,
+    ) -> Result<Response<proto::GameState>, Status> {
+        let state = self.state.lock().unwrap();
+        Ok(Response::new((&*state).into()))
+    }
+
+    async fn list_journals(
+        &self,
+        _: Request<proto::ListJournalsRequest>,
+    ) -> Result<Response<proto::ListJournalsResponse>, Status> {
+        let names = self
+            .journals
+            .journal_names()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(proto::ListJournalsResponse { names }))
+    }
+
+    type ReadJournalStream =
+        Pin<Box<dyn Stream<Item = Result<proto::JournalChunk, Status>> + Send>>;
+
+    async fn read_journal(
+        &self,
+        request: Request<proto::ReadJournalRequest>,
+    ) -> Result<Response<Self::ReadJournalStream>, Status> {
+        let contents = self
+            .journals
+            .read_journal(&request.into_inner().name)
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => Status::not_found(e.to_string()),
+                io::ErrorKind::InvalidInput => Status::invalid_argument(e.to_string()),
+                _ => Status::internal(e.to_string()),
+            })?;
+        let chunks: Vec<proto::JournalChunk> = contents
+            .as_bytes()
+            .chunks(CHUNK_SIZE)
+            .map(|chunk| proto::JournalChunk {
+                contents: chunk.to_vec(),
+            })
+            .collect();
+
+        Ok(Response::new(Box::pin(tokio_stream::iter(
+            chunks.into_iter().map(Ok),
+        ))))
+    }
+}
+
+/// Journals of another machine running the gRPC server
+#[derive(Debug)]
+pub struct GrpcSource {
+    runtime: tokio::runtime::Runtime,
+    client: JournalClient<Channel>,
+}
+
+impl GrpcSource {
+    /// Connect to a server at a URL like "http://192.168.1.10:50051"
+    pub fn connect(url: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let runtime = tokio::runtime::Runtime::new()?;
+        let client = runtime.block_on(JournalClient::connect(url.to_owned()))?;
+
+        Ok(GrpcSource { runtime, client })
+    }
+}
+
+impl JournalSource for GrpcSource {
+    fn journal_names(&self) -> Result<Vec<String>, io::Error> {
+        let mut client = self.client.clone();
+        let response = self
+            .runtime
+            .block_on(client.list_journals(proto::ListJournalsRequest {}))
+            .map_err(io::Error::other)?;
+        Ok(response.into_inner().names)
+    }
+
+    fn read_journal(&self, name: &str) -> Result<String, io::Error> {
+        let mut client = self.client.clone();
+        let contents = self.runtime.block_on(async {
+            let mut chunks = client
+                .read_journal(proto::ReadJournalRequest {
+                    name: name.to_owned(),
+                })
+                .await
+                .map_err(io::Error::other)?
+                .into_inner();
+            let mut contents = Vec::new();
+            while let Some(chunk) = chunks.message().await.map_err(io::Error::other)? {
+                contents.extend(chunk.contents);
+            }
+            Ok::<_, io::Error>(contents)
+        })?;
+        String::from_utf8(contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Run the gRPC server until it fails
+pub async fn serve(addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
+    serve_with(addr, Processors::default()).await
+}
+
+/// Like [serve] with processors of other crates following the journal too
+pub async fn serve_with(
+    addr: SocketAddr,
+    processors: Processors,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Server::builder()
+        .add_service(JournalServer::new(JournalService::with_processors(
+            processors,
+        )?))
+        .serve(addr)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, net::TcpListener, time::Duration};
+
+    use super::*;
+    use crate::journal::parse_line;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = std::env::temp_dir().join(format!("elite-grpc-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let load_game = r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Odyssey":true, "Ship":"Python", "ShipID":1, "ShipName":"", "ShipIdent":"", "FuelLevel":32, "FuelCapacity":32, "GameMode":"Solo", "Credits":1000000, "Loan":0 }"#;
+        let name = "Journal.2024-09-15T120000.01.log";
+        fs::write(dir.join(name), load_game).unwrap();
+
+        let (events, _) = broadcast::channel(16);
+        let service = JournalService {
+            events: events.clone(),
+            state: Default::default(),
+            journals: LocalSource { dir: dir.clone() },
+        };
+        service
+            .state
+            .lock()
+            .unwrap()
+            .update(&parse_line(load_game).unwrap());
+
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let server = tokio::runtime::Runtime::new().unwrap();
+        server.spawn(
+            Server::builder()
+                .add_service(JournalServer::new(service))
+                .serve(addr),
+        );
+        let source = loop {
+            match GrpcSource::connect(&format!("http://{addr}")) {
+                Ok(source) => break source,
+                Err(_) => thread::sleep(Duration::from_millis(10)),
+            }
+        };
+
+        let names = source.journal_names().unwrap();
+        let contents = source.read_journal(name).unwrap();
+        let escape = source.read_journal("../Status.json");
+        let mut client = source.client.clone();
+        let state = source
+            .runtime
+            .block_on(client.get_state(proto::GetStateRequest {}))
+            .unwrap()
+            .into_inner();
+        let mut stream = source
+            .runtime
+            .block_on(client.stream_events(proto::StreamEventsRequest {
+                kinds: vec!["FSDJump".to_owned()],
+            }))
+            .unwrap()
+            .into_inner();
+        for line in [
+            r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"Music", "MusicTrack":"NoTrack" }"#,
+            r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "JumpDist":10.2, "FuelUsed":4.0, "FuelLevel":28.0 }"#,
+        ] {
+            events.send((&parse_line(line).unwrap()).into()).unwrap();
+        }
+        let event = source.runtime.block_on(stream.message()).unwrap().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(names, [name]);
+        assert_eq!(contents, load_game);
+        assert!(escape.is_err());
+        assert_eq!(state.commander.as_deref(), Some("Jameson"));
+        assert_eq!(state.credits, Some(1000000));
+        // The Music event is filtered out
+        assert_eq!(event.event, "FSDJump");
+        assert_eq!(event.timestamp.unwrap().seconds, 1726401900);
+        assert!(event.json.contains(r#""StarSystem":"Sol""#));
+    }
+}
@@ -0,0 +1,397 @@
+//! Plain HTTP endpoints for tools that poll, like Stream Deck plugins and
+//! AutoHotkey scripts, served by `elite serve`.
+//!
+//! | Path               | Response                                  |
+//! |--------------------|-------------------------------------------|
+//! | `/system`          | Name of the current star system           |
+//! | `/docked`          | `true` or `false`                         |
+//! | `/fuel`            | Fill of the main tank in whole percent    |
+//! | `/bounty`          | Credits of the latest bounty              |
+//! | `/state`           | All of the above and more as JSON         |
+//! | `/events`          | WebSocket sending the JSON of every event |
+//! | `/overlay`         | Page for an OBS browser source            |
+//! | `/journals`        | Names of the journal files, one per line  |
+//! | `/journals/<name>` | Contents of a journal file                |
+//!
+//! Everything but `/state` is plain text, empty while unknown. The overlay
+//! shows the system, the ship and a ticker of recent events and is only
+//! served when asked for with [HttpOptions::overlay], the journals only
+//! with [HttpOptions::journals]. Pages on other origins may fetch all but
+//! `/state` and the journals, which have the commander's name and chat.
+
+use std::{
+    error::Error,
+    io::Cursor,
+    net::SocketAddr,
+    sync::{
+        atomic::AtomicBool,
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use serde::Serialize;
+use tiny_http::{Header, Request, Response, Server};
+use tungstenite::{handshake::derive_accept_key, protocol::Role, Message, WebSocket};
+
+use crate::{
+    journal::{self, Event, JournalSource, JournalTail, LocalSource, Status},
+    processor::{self, EventProcessor, ProcessorResult, Processors, RunOptions},
+    ships::ship_name,
+    state::GameState,
+};
+
+const OVERLAY: &str = include_str!("overlay.html");
+
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+    /// Serve the page for OBS at `/overlay`
+    pub overlay: bool,
+    /// Serve the journal files at `/journals`, for reading them from
+    /// another machine
+    pub journals: bool,
+    /// Origin like `https://example.com` whose pages may open `/events`
+    /// besides the pages served here
+    pub allow_origin: Option<String>,
+}
+
+/// JSON of new events for the open `/events` connections
+type Subscribers = Arc<Mutex<Vec<Sender<String>>>>;
+
+/// What the endpoints answer with
+#[derive(Debug, Default, Serialize)]
+pub struct Snapshot {
+    #[serde(flatten)]
+    pub state: GameState,
+    /// Tons the main tank holds
+    pub fuel_capacity: Option<f64>,
+    /// Tons in the main tank, live from Status.json while in game
+    pub fuel_main: Option<f64>,
+    pub last_bounty: Option<u64>,
+}
+
+impl Snapshot {
+    pub fn update(&mut self, event: &Event) {
+        self.state.update(event);
+
+        match event {
+            Event::LoadGame { fuel_capacity, .. } => {
+                self.fuel_capacity = *fuel_capacity;
+                self.fuel_main = self.state.fuel_level;
+            }
+            Event::Loadout { fuel_capacity, .. } => {
+                self.fuel_capacity = Some(fuel_capacity.main.into());
+            }
+            Event::FSDJump { fuel_level, .. } => self.fuel_main = Some(*fuel_level),
+            Event::Bounty {
+                total_reward,
+                reward,
+                ..
+            } => self.last_bounty = Some(reward.unwrap_or(*total_reward)),
+            _ => {}
+        }
+    }
+
+    pub fn update_status(&mut self, status: &Status) {
+        if let Some(fuel) = status.fuel {
+            self.fuel_main = Some(fuel.fuel_main);
+        }
+    }
+
+    /// Fill of the main tank in percent
+    pub fn fuel_percent(&self) -> Option<f64> {
+        match (self.fuel_main, self.fuel_capacity) {
+            (Some(main), Some(capacity)) if capacity > 0.0 => Some(main / capacity * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Keeps the [Snapshot] of a server up to date and passes events on to
+/// its WebSocket connections
+struct Live {
+    snapshot: Arc<Mutex<Snapshot>>,
+    subscribers: Subscribers,
+}
+
+impl EventProcessor for Live {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    fn on_event(&mut self, event: &Event) -> ProcessorResult {
+        self.snapshot.lock().unwrap().update(event);
+
+        let json = serde_json::to_string(event)?;
+        // Closed connections drop their receiver
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.send(json.clone()).is_ok());
+        Ok(())
+    }
+
+    fn on_status(&mut self, status: &Status) -> ProcessorResult {
+        self.snapshot.lock().unwrap().update_status(status);
+        Ok(())
+    }
+}
+
+/// Content type and body for a path, `None` if there's nothing there
+fn route(path: &str, snapshot: &Snapshot, options: &HttpOptions) -> Option<(&'static str, String)> {
+    let text =
+        |value: Option<String>| Some(("text/plain; charset=utf-8", value.unwrap_or_default()));
+
+    match path {
+        "/system" => text(snapshot.state.system.clone()),
+        "/docked" => text(Some(snapshot.state.docked.to_string())),
+        "/fuel" => text(snapshot.fuel_percent().map(|p| format!("{p:.0}"))),
+        "/bounty" => text(snapshot.last_bounty.map(|b| b.to_string())),
+        "/state" => {
+            let mut json = serde_json::to_value(snapshot).ok()?;
+            json["fuel_percent"] = snapshot.fuel_percent().into();
+            json["ship_type"] = snapshot.state.ship.as_deref().map(ship_name).into();
+            Some(("application/json", json.to_string()))
+        }
+        "/overlay" if options.overlay => Some(("text/html; charset=utf-8", OVERLAY.to_owned())),
+        "/journals" if options.journals => text(Some(
+            LocalSource::default().journal_names().ok()?.join("\n"),
+        )),
+        _ => {
+            let name = path
+                .strip_prefix("/journals/")
+                .filter(|_| options.journals)?;
+            text(Some(LocalSource::default().read_journal(name).ok()?))
+        }
+    }
+}
+
+/// Response to a GET of `path`
+fn response(path: &str, snapshot: &Snapshot, options: &HttpOptions) -> Response<Cursor<Vec<u8>>> {
+    let Some((content_type, body)) = route(path, snapshot, options) else {
+        return Response::from_string("Not found").with_status_code(404);
+    };
+    let content_type = Header::from_bytes("Content-Type", content_type).unwrap();
+    let response = Response::from_string(body).with_header(content_type);
+
+    // Lets pages on other origins, like browser plugins, fetch the endpoints.
+    // Not the state and the journals though, any website could read the
+    // commander's name, ID and chat from those.
+    if path == "/state" || path.starts_with("/journals") {
+        return response;
+    }
+    response.with_header(Header::from_bytes("Access-Control-Allow-Origin", "*").unwrap())
+}
+
+/// Whether a WebSocket may be opened from a page at `origin`. Browsers
+/// don't apply CORS to WebSockets, so without this any website could
+/// follow the events, chat included.
+fn origin_allowed(origin: Option<&str>, host: Option<&str>, options: &HttpOptions) -> bool {
+    // Only browsers send an origin
+    let Some(origin) = origin else {
+        return true;
+    };
+    let served = origin
+        .split_once("://")
+        .zip(host)
+        .is_some_and(|((_, authority), host)| authority.eq_ignore_ascii_case(host));
+
+    served || options.allow_origin.as_deref() == Some(origin)
+}
+
+/// Take over the connection of a WebSocket request and send it events
+/// on a thread of its own
+fn upgrade(request: Request, key: String, subscribers: &Subscribers) {
+    let accept =
+        Header::from_bytes("Sec-WebSocket-Accept", derive_accept_key(key.as_bytes())).unwrap();
+    let stream = request.upgrade("websocket", Response::empty(101).with_header(accept));
+
+    let (sender, receiver) = mpsc::channel::<String>();
+    subscribers.lock().unwrap().push(sender);
+    thread::spawn(move || {
+        let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+        for json in receiver {
+            if socket.send(Message::text(json)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn respond(
+    request: Request,
+    snapshot: &Mutex<Snapshot>,
+    subscribers: &Subscribers,
+    options: &HttpOptions,
+) -> Result<(), std::io::Error> {
+    let path = request.url().split('?').next().unwrap_or_default();
+    let header = |name| {
+        request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv(name))
+            .map(|h| h.value.to_string())
+    };
+    let key = header("Sec-WebSocket-Key");
+    let allowed = origin_allowed(
+        header("Origin").as_deref(),
+        header("Host").as_deref(),
+        options,
+    );
+    match (request.method(), path, key) {
+        (tiny_http::Method::Get, "/events", Some(_)) if !allowed => {
+            request.respond(Response::from_string("Forbidden").with_status_code(403))
+        }
+        (tiny_http::Method::Get, "/events", Some(key)) => {
+            upgrade(request, key, subscribers);
+            Ok(())
+        }
+        (tiny_http::Method::Get, path, _) => {
+            let response = response(path, &snapshot.lock().unwrap(), options);
+            request.respond(response)
+        }
+        _ => request.respond(Response::from_string("Not found").with_status_code(404)),
+    }
+}
+
+/// Run the HTTP server until it fails
+pub fn serve(addr: SocketAddr, options: &HttpOptions) -> Result<(), Box<dyn Error>> {
+    serve_with(addr, options, Processors::default())
+}
+
+/// Like [serve] with processors of other crates following the journal too
+pub fn serve_with(
+    addr: SocketAddr,
+    options: &HttpOptions,
+    mut processors: Processors,
+) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(addr).map_err(std::io::Error::other)?;
+    let mut tail = JournalTail::new()?;
+    let snapshot = Arc::new(Mutex::new(Snapshot::default()));
+    let subscribers = Subscribers::default();
+
+    // Catch up on the state from earlier in the session
+    for event in journal::read_events(&[tail.path().to_owned()])? {
+        snapshot.lock().unwrap().update(&event);
+    }
+
+    processors.register(Live {
+        snapshot: snapshot.clone(),
+        subscribers: subscribers.clone(),
+    });
+    thread::spawn(move || {
+        let running = AtomicBool::new(true);
+        let options = RunOptions::default();
+        loop {
+            if let Err(e) = processor::run(&mut tail, &mut processors, &options, &running, |e| {
+                eprintln!("{e}")
+            }) {
+                eprintln!("Failed to read journal: {e}");
+            }
+            thread::sleep(options.interval);
+        }
+    });
+
+    for request in server.incoming_requests() {
+        if let Err(e) = respond(request, &snapshot, &subscribers, options) {
+            eprintln!("Failed to respond: {e}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_line;
+    use std::fs;
+
+    #[test]
+    fn test_routes() {
+        let mut snapshot = Snapshot::default();
+        let options = HttpOptions::default();
+        for line in [
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Odyssey":true, "Ship":"Python", "ShipID":1, "ShipName":"", "ShipIdent":"", "FuelLevel":32, "FuelCapacity":32, "GameMode":"Solo", "Credits":1000000, "Loan":0 }"#,
+            r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "JumpDist":10.2, "FuelUsed":4.0, "FuelLevel":28.0 }"#,
+            r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Alliance", "Reward":150000 } ], "Target":"viper", "TotalReward":150000, "VictimFaction":"Pirates" }"#,
+        ] {
+            snapshot.update(&parse_line(line).unwrap());
+        }
+
+        assert_eq!(route("/system", &snapshot, &options).unwrap().1, "Sol");
+        assert_eq!(route("/docked", &snapshot, &options).unwrap().1, "false");
+        assert_eq!(route("/fuel", &snapshot, &options).unwrap().1, "88");
+        assert_eq!(route("/bounty", &snapshot, &options).unwrap().1, "150000");
+        let (content_type, state) = route("/state", &snapshot, &options).unwrap();
+        assert_eq!(content_type, "application/json");
+        assert!(state.contains(r#""fuel_percent":87.5"#));
+        assert!(state.contains(r#""ship_type":"Python""#));
+        assert!(route("/nothing", &snapshot, &options).is_none());
+        assert!(route("/overlay", &snapshot, &options).is_none());
+        let overlay = HttpOptions {
+            overlay: true,
+            ..Default::default()
+        };
+        assert!(route("/overlay", &snapshot, &overlay).is_some());
+        assert!(route("/journals", &snapshot, &options).is_none());
+        assert!(route(
+            "/journals/Journal.2024-09-15T120000.01.log",
+            &snapshot,
+            &options
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_cross_origin() {
+        let dir = std::env::temp_dir().join(format!("elite-http-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Journal.2024-09-15T120000.01.log"), "").unwrap();
+        journal::set_journals_path(dir.clone()).unwrap();
+
+        let snapshot = Snapshot::default();
+        let options = HttpOptions {
+            journals: true,
+            ..Default::default()
+        };
+        let allows_any = |path| {
+            let response = response(path, &snapshot, &options);
+            assert_eq!(response.status_code(), 200, "{path}");
+            response
+                .headers()
+                .iter()
+                .any(|h| h.field.equiv("Access-Control-Allow-Origin"))
+        };
+        assert!(allows_any("/system"));
+        assert!(!allows_any("/state"));
+        assert!(!allows_any("/journals"));
+        assert!(!allows_any("/journals/Journal.2024-09-15T120000.01.log"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_origin_allowed() {
+        let host = Some("127.0.0.1:8080");
+        let mut options = HttpOptions::default();
+
+        assert!(origin_allowed(None, host, &options));
+        assert!(origin_allowed(
+            Some("http://127.0.0.1:8080"),
+            host,
+            &options
+        ));
+        assert!(!origin_allowed(Some("https://example.com"), host, &options));
+        assert!(!origin_allowed(
+            Some("http://127.0.0.1:8080"),
+            None,
+            &options
+        ));
+        assert!(!origin_allowed(Some("null"), host, &options));
+
+        options.allow_origin = Some("https://example.com".to_owned());
+        assert!(origin_allowed(Some("https://example.com"), host, &options));
+        assert!(!origin_allowed(Some("https://example.org"), host, &options));
+    }
+}
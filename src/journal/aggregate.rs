@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use super::FieldPath;
+
+/// Count, sum, minimum and maximum of the numbers a field had
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FieldStats {
+    /// Events where the field was a number
+    pub count: u64,
+    pub sum: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl FieldStats {
+    pub fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+    }
+
+    pub fn avg(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Group {
+    pub events: u64,
+    /// Stats of every aggregated field in the order they were given
+    pub fields: Vec<FieldStats>,
+}
+
+/// Events grouped by the values of some fields with stats of others, like
+/// the credits from MarketSell per commodity
+#[derive(Debug, Clone)]
+pub struct Aggregation {
+    group_by: Vec<FieldPath>,
+    fields: Vec<FieldPath>,
+    /// Keyed by the text of the grouping fields, empty where missing
+    pub groups: BTreeMap<Vec<String>, Group>,
+}
+
+impl Aggregation {
+    pub fn new(group_by: Vec<FieldPath>, fields: Vec<FieldPath>) -> Self {
+        Aggregation {
+            group_by,
+            fields,
+            groups: BTreeMap::new(),
+        }
+    }
+
+    /// Add the raw JSON of an event
+    pub fn add(&mut self, event: &Value) {
+        let key = self
+            .group_by
+            .iter()
+            .map(|path| match path.get(event) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+            })
+            .collect();
+        let group = self.groups.entry(key).or_insert_with(|| Group {
+            events: 0,
+            fields: vec![FieldStats::default(); self.fields.len()],
+        });
+
+        group.events += 1;
+        for (path, stats) in self.fields.iter().zip(&mut group.fields) {
+            if let Some(value) = path.get(event).and_then(Value::as_f64) {
+                stats.add(value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregation() {
+        let mut aggregation = Aggregation::new(
+            vec!["Type".parse().unwrap()],
+            vec!["TotalSale".parse().unwrap()],
+        );
+        for line in [
+            r#"{ "event":"MarketSell", "Type":"gold", "Count":10, "TotalSale":100000 }"#,
+            r#"{ "event":"MarketSell", "Type":"gold", "Count":20, "TotalSale":300000 }"#,
+            r#"{ "event":"MarketSell", "Type":"tritium", "Count":5, "TotalSale":250000 }"#,
+            r#"{ "event":"MarketSell", "Count":1 }"#,
+        ] {
+            aggregation.add(&serde_json::from_str(line).unwrap());
+        }
+
+        let gold = &aggregation.groups[&vec!["gold".to_owned()]];
+        assert_eq!(gold.events, 2);
+        assert_eq!(gold.fields[0].sum, 400000.0);
+        assert_eq!(gold.fields[0].avg(), Some(200000.0));
+        assert_eq!(gold.fields[0].min, Some(100000.0));
+        let missing = &aggregation.groups[&vec![String::new()]];
+        assert_eq!((missing.events, missing.fields[0].avg()), (1, None));
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+/// Fields naming another player, by event
+const PLAYER_FIELDS: [(&str, &str); 16] = [
+    ("Commander", "Name"),
+    ("NewCommander", "Name"),
+    ("ClearSavedGame", "Name"),
+    ("LoadGame", "Commander"),
+    ("Friends", "Name"),
+    ("WingAdd", "Name"),
+    ("WingInvite", "Name"),
+    ("WingJoin", "Others"),
+    ("CrewMemberJoins", "Crew"),
+    ("CrewMemberQuits", "Crew"),
+    ("CrewMemberRoleChange", "Crew"),
+    ("CrewLaunchFighter", "Crew"),
+    ("KickCrewMember", "Crew"),
+    ("JoinACrew", "Captain"),
+    ("QuitACrew", "Captain"),
+    ("PVPKill", "Victim"),
+];
+
+/// Fields naming the commander's ships and fleet carrier, by event, with
+/// the prefix of their pseudonyms
+const OWNED_FIELDS: [(&str, &str, &str); 11] = [
+    ("LoadGame", "ShipName", "Ship "),
+    ("LoadGame", "ShipIdent", "ID"),
+    ("Loadout", "ShipName", "Ship "),
+    ("Loadout", "ShipIdent", "ID"),
+    ("SetUserShipName", "UserShipName", "Ship "),
+    ("SetUserShipName", "UserShipId", "ID"),
+    ("CarrierBuy", "Callsign", "XXX-"),
+    ("CarrierStats", "Name", "Carrier "),
+    ("CarrierStats", "Callsign", "XXX-"),
+    ("CarrierNameChange", "Name", "Carrier "),
+    ("CarrierNameChange", "Callsign", "XXX-"),
+];
+
+/// Fields holding a name that may be a player's, like an interdictor or a
+/// killer. Players are told by the `$cmdr_decorate` prefix or the event
+/// saying so.
+const PILOT_FIELDS: [&str; 4] = ["PilotName", "Interdictor", "Interdicted", "KillerName"];
+
+/// Channels whose messages and senders are players, not NPCs or the game
+const PLAYER_CHANNELS: [&str; 6] = ["wing", "local", "voicechat", "friend", "player", "squadron"];
+
+/// Replaces everything identifying the commander and the players they met
+/// with pseudonyms, so journals can be shared in bug reports.
+///
+/// The same name always gets the same pseudonym, across files too if the
+/// same anonymizer is used for all of them. Events are rewritten as JSON
+/// values, so fields the parser doesn't know are kept as they are.
+#[derive(Debug, Default)]
+pub struct Anonymizer {
+    players: HashMap<String, String>,
+    fids: HashMap<String, String>,
+    squadrons: HashMap<String, String>,
+    /// Ship and carrier names by the prefix of their pseudonyms
+    owned: HashMap<&'static str, HashMap<String, String>>,
+}
+
+impl Anonymizer {
+    /// Anonymize one journal line
+    pub fn line(&mut self, line: &str) -> Result<String, serde_json::Error> {
+        let mut value: Value = serde_json::from_str(line)?;
+        if let Value::Object(fields) = &mut value {
+            self.event(fields);
+        }
+        serde_json::to_string(&value)
+    }
+
+    fn event(&mut self, fields: &mut Map<String, Value>) {
+        let event = fields
+            .get("event")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+
+        for (kind, field) in PLAYER_FIELDS {
+            if event == kind {
+                self.players_in(fields, field);
+            }
+        }
+        for (kind, field, prefix) in OWNED_FIELDS {
+            if let (true, Some(Value::String(name))) = (event == kind, fields.get_mut(field)) {
+                *name = pseudonym(self.owned.entry(prefix).or_default(), name, prefix);
+            }
+        }
+        let is_player = fields.get("IsPlayer").and_then(Value::as_bool) == Some(true);
+        for field in PILOT_FIELDS {
+            self.pilot_in(fields, field, is_player);
+        }
+        if let Some(Value::Array(killers)) = fields.get_mut("Killers") {
+            for killer in killers.iter_mut().filter_map(Value::as_object_mut) {
+                self.pilot_in(killer, "Name", false);
+            }
+        }
+
+        match event.as_str() {
+            "SendText" => {
+                let to = fields.get("To").and_then(Value::as_str);
+                if to.is_some_and(|to| !PLAYER_CHANNELS.contains(&to)) {
+                    self.players_in(fields, "To");
+                }
+                fields.insert("Message".to_owned(), "[removed]".into());
+            }
+            "ReceiveText" => {
+                let channel = fields.get("Channel").and_then(Value::as_str);
+                if channel.is_some_and(|c| PLAYER_CHANNELS.contains(&c)) {
+                    self.players_in(fields, "From");
+                    fields.insert("Message".to_owned(), "[removed]".into());
+                    fields.remove("From_Localised");
+                }
+            }
+            _ => {}
+        }
+
+        for (key, value) in fields.iter_mut() {
+            match (key.as_str(), value) {
+                ("FID", Value::String(fid)) => {
+                    *fid = pseudonym(&mut self.fids, fid, "F");
+                }
+                ("SquadronName" | "SquadronID", value) if !value.is_null() => {
+                    let name = match &*value {
+                        Value::String(name) => name.clone(),
+                        other => other.to_string(),
+                    };
+                    *value = pseudonym(&mut self.squadrons, &name, "Squadron ").into();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Replace a field holding one player name or a list of them
+    fn players_in(&mut self, fields: &mut Map<String, Value>, field: &str) {
+        match fields.get_mut(field) {
+            Some(Value::String(name)) => *name = self.player(name),
+            Some(Value::Array(names)) => {
+                for name in names {
+                    if let Value::String(name) = name {
+                        *name = self.player(name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace a pilot name if it is a player's, along with its localised
+    /// version
+    fn pilot_in(&mut self, fields: &mut Map<String, Value>, field: &str, is_player: bool) {
+        let Some(Value::String(name)) = fields.get_mut(field) else {
+            return;
+        };
+        let pseudonym = match name.strip_prefix("$cmdr_decorate:#name=") {
+            Some(decorated) => {
+                let player = self.player(decorated.trim_end_matches(';'));
+                *name = format!("$cmdr_decorate:#name={player};");
+                player
+            }
+            None if is_player => {
+                *name = self.player(name);
+                name.clone()
+            }
+            None => return,
+        };
+        if let Some(localised) = fields.get_mut(&format!("{field}_Localised")) {
+            *localised = format!("CMDR {pseudonym}").into();
+        }
+    }
+
+    fn player(&mut self, name: &str) -> String {
+        pseudonym(&mut self.players, name, "Cmdr ")
+    }
+}
+
+/// Pseudonym for `name`, numbered in the order names are first seen
+fn pseudonym(known: &mut HashMap<String, String>, name: &str, prefix: &str) -> String {
+    if name.is_empty() {
+        return String::new();
+    }
+    let next = known.len() + 1;
+    known
+        .entry(name.to_owned())
+        .or_insert_with(|| format!("{prefix}{next}"))
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymize() {
+        let mut anonymizer = Anonymizer::default();
+        let mut line = |line: &str| -> Value {
+            serde_json::from_str(&anonymizer.line(line).unwrap()).unwrap()
+        };
+
+        let commander = line(
+            r#"{ "timestamp":"2024-09-15T12:00:05Z", "event":"Commander", "FID":"F1234", "Name":"Jameson" }"#,
+        );
+        assert_eq!(commander["Name"], "Cmdr 1");
+        assert_eq!(commander["FID"], "F1");
+
+        let load = line(
+            r#"{ "timestamp":"2024-09-15T12:00:06Z", "event":"LoadGame", "FID":"F1234", "Commander":"Jameson", "Ship":"CobraMkIII", "ShipID":1, "GameMode":"Open", "Credits":1000, "Loan":0 }"#,
+        );
+        assert_eq!(
+            (&load["Commander"], &load["FID"]),
+            (&"Cmdr 1".into(), &"F1".into())
+        );
+        assert_eq!(load["Credits"], 1000);
+
+        let new = line(
+            r#"{ "timestamp":"2024-09-15T12:00:07Z", "event":"NewCommander", "FID":"F1234", "Name":"Jameson", "Package":"ImperialBountyHunter" }"#,
+        );
+        assert_eq!(new["Name"], "Cmdr 1");
+        let cleared = line(
+            r#"{ "timestamp":"2024-09-15T12:00:07Z", "event":"ClearSavedGame", "FID":"F1234", "Name":"Jameson" }"#,
+        );
+        assert_eq!(cleared["Name"], "Cmdr 1");
+
+        let load = line(
+            r#"{ "timestamp":"2024-09-15T12:00:06Z", "event":"LoadGame", "FID":"F1234", "Commander":"Jameson", "Ship":"CobraMkIII", "ShipID":1, "ShipName":"Jameson's Pride", "ShipIdent":"JM-01", "GameMode":"Open", "Credits":1000, "Loan":0 }"#,
+        );
+        assert_eq!(
+            (&load["ShipName"], &load["ShipIdent"]),
+            (&"Ship 1".into(), &"ID1".into())
+        );
+        let loadout = line(
+            r#"{ "timestamp":"2024-09-15T12:00:08Z", "event":"Loadout", "Ship":"cobramkiii", "ShipID":1, "ShipName":"Jameson's Pride", "ShipIdent":"JM-01", "Modules":[] }"#,
+        );
+        assert_eq!(
+            (&loadout["ShipName"], &loadout["ShipIdent"]),
+            (&"Ship 1".into(), &"ID1".into())
+        );
+
+        let carrier = line(
+            r#"{ "timestamp":"2024-09-15T12:00:09Z", "event":"CarrierStats", "CarrierID":3700000000, "Callsign":"K7Z-N0Q", "Name":"JAMESON'S HOLD", "DockingAccess":"all", "AllowNotorious":false, "FuelLevel":800 }"#,
+        );
+        assert_eq!(
+            (&carrier["Name"], &carrier["Callsign"]),
+            (&"Carrier 1".into(), &"XXX-1".into())
+        );
+        assert_eq!(carrier["CarrierID"], 3700000000u64);
+
+        let friend = line(
+            r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"Friends", "Status":"Online", "Name":"Salomé" }"#,
+        );
+        assert_eq!(friend["Name"], "Cmdr 2");
+
+        let chat = line(
+            r#"{ "timestamp":"2024-09-15T12:02:00Z", "event":"ReceiveText", "From":"Salomé", "Message":"o7 Jameson", "Channel":"friend" }"#,
+        );
+        assert_eq!(
+            (&chat["From"], &chat["Message"]),
+            (&"Cmdr 2".into(), &"[removed]".into())
+        );
+
+        let npc = line(
+            r#"{ "timestamp":"2024-09-15T12:03:00Z", "event":"ReceiveText", "From":"$npc_name_decorate:#name=Pirate;", "Message":"$Pirate_OnStartScanCargo07;", "Channel":"npc" }"#,
+        );
+        assert_eq!(npc["Message"], "$Pirate_OnStartScanCargo07;");
+
+        let target = line(
+            r#"{ "timestamp":"2024-09-15T12:04:00Z", "event":"ShipTargeted", "TargetLocked":true, "Ship":"anaconda", "ScanStage":1, "PilotName":"$cmdr_decorate:#name=Salomé;", "PilotName_Localised":"CMDR Salomé" }"#,
+        );
+        assert_eq!(target["PilotName"], "$cmdr_decorate:#name=Cmdr 2;");
+        assert_eq!(target["PilotName_Localised"], "CMDR Cmdr 2");
+
+        let squadron = line(
+            r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"SquadronStartup", "SquadronName":"The Dark Wheel", "CurrentRank":2 }"#,
+        );
+        assert_eq!(squadron["SquadronName"], "Squadron 1");
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::Event;
+
+/// Events the game writes as a snapshot of the current state at the start of
+/// every session, whether anything changed or not
+pub const SNAPSHOT_EVENTS: [&str; 11] = [
+    "Cargo",
+    "EngineerProgress",
+    "Loadout",
+    "Materials",
+    "Missions",
+    "Powerplay",
+    "Progress",
+    "Rank",
+    "Reputation",
+    "ShipLocker",
+    "Statistics",
+];
+
+/// Drops snapshot events that are identical to the previous snapshot of the
+/// same kind, so aggregations don't count the same state more than once
+#[derive(Debug, Default)]
+pub struct SnapshotDedup {
+    /// Last snapshot of each kind without its timestamp
+    last: HashMap<String, Value>,
+}
+
+impl SnapshotDedup {
+    /// Whether the event is new information and should be kept
+    pub fn keep(&mut self, event: &Event) -> bool {
+        let Ok(Value::Object(mut fields)) = serde_json::to_value(event) else {
+            return true;
+        };
+        let Some(kind) = fields
+            .get("event")
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+        else {
+            return true;
+        };
+        if !SNAPSHOT_EVENTS.contains(&kind.as_str()) {
+            return true;
+        }
+
+        fields.remove("timestamp");
+        let snapshot = Value::Object(fields);
+        if self.last.get(&kind) == Some(&snapshot) {
+            return false;
+        }
+        self.last.insert(kind, snapshot);
+        true
+    }
+}
+
+/// Events with repeated snapshots removed, see [SnapshotDedup]
+pub fn dedup_snapshots(events: Vec<Event>) -> Vec<Event> {
+    let mut dedup = SnapshotDedup::default();
+    events.into_iter().filter(|e| dedup.keep(e)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_dedup_snapshots() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Rank", "Combat":3, "Trade":2, "Explore":1, "Empire":0, "Federation":0, "CQC":0 }
+{ "timestamp":"2024-09-15T12:00:01Z", "event":"Shutdown" }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"Rank", "Combat":3, "Trade":2, "Explore":1, "Empire":0, "Federation":0, "CQC":0 }
+{ "timestamp":"2024-09-15T13:00:01Z", "event":"Shutdown" }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"Rank", "Combat":4, "Trade":2, "Explore":1, "Empire":0, "Federation":0, "CQC":0 }
+"#,
+        )
+        .unwrap();
+
+        let kept = dedup_snapshots(events);
+        assert_eq!(kept.len(), 4);
+        assert!(matches!(kept[2], Event::Shutdown { .. }));
+    }
+}
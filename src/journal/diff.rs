@@ -0,0 +1,135 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde_json::Value;
+
+/// Difference between two journals in one event
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventDiff {
+    /// Only in the second journal
+    Added(Value),
+    /// Only in the first journal
+    Removed(Value),
+    /// Same kind of event at the same time with different fields
+    Changed {
+        before: Value,
+        after: Value,
+        /// Names of the fields that differ or are only in one of them
+        fields: Vec<String>,
+    },
+}
+
+impl EventDiff {
+    /// The event in the second journal, or the first if it was removed
+    pub fn event(&self) -> &Value {
+        match self {
+            EventDiff::Added(event) | EventDiff::Removed(event) => event,
+            EventDiff::Changed { after, .. } => after,
+        }
+    }
+}
+
+/// Events are matched by timestamp and kind, in the order they appear
+fn key(event: &Value) -> (String, String) {
+    let field = |name| {
+        event
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned()
+    };
+    (field("timestamp"), field("event"))
+}
+
+fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    let (Value::Object(before), Value::Object(after)) = (before, after) else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields.dedup();
+    fields
+}
+
+/// Added, removed and changed events going from `a` to `b`, sorted by time.
+/// Works on raw JSON so fields the parser doesn't know show up too.
+pub fn diff_events(a: &[Value], b: &[Value]) -> Vec<EventDiff> {
+    let mut unmatched: HashMap<(String, String), VecDeque<usize>> = HashMap::new();
+    for (i, event) in b.iter().enumerate() {
+        unmatched.entry(key(event)).or_default().push_back(i);
+    }
+
+    let mut matched = vec![false; b.len()];
+    let mut diffs = Vec::new();
+    for before in a {
+        let Some(i) = unmatched
+            .get_mut(&key(before))
+            .and_then(VecDeque::pop_front)
+        else {
+            diffs.push(EventDiff::Removed(before.clone()));
+            continue;
+        };
+        matched[i] = true;
+        let after = &b[i];
+        if before != after {
+            diffs.push(EventDiff::Changed {
+                before: before.clone(),
+                after: after.clone(),
+                fields: changed_fields(before, after),
+            });
+        }
+    }
+    diffs.extend(
+        b.iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(event, _)| EventDiff::Added(event.clone())),
+    );
+
+    // Timestamps are all UTC so they sort as strings
+    diffs.sort_by_key(|diff| key(diff.event()).0);
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_events() {
+        let parse = |contents: &str| -> Vec<Value> {
+            contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        };
+        let a = parse(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Music", "MusicTrack":"MainMenu" }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"Cargo", "Vessel":"Ship", "Count":0 }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"Shutdown" }
+"#,
+        );
+        let b = parse(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Music", "MusicTrack":"MainMenu" }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"Cargo", "Vessel":"Ship", "Count":4, "Inventory":[] }
+{ "timestamp":"2024-09-15T12:01:30Z", "event":"Music", "MusicTrack":"NoTrack" }
+"#,
+        );
+
+        let diffs = diff_events(&a, &b);
+        assert_eq!(diffs.len(), 3);
+        assert!(matches!(
+            &diffs[0],
+            EventDiff::Changed { fields, .. } if *fields == ["Count", "Inventory"]
+        ));
+        assert!(matches!(&diffs[1], EventDiff::Added(event) if event["MusicTrack"] == "NoTrack"));
+        assert!(matches!(&diffs[2], EventDiff::Removed(event) if event["event"] == "Shutdown"));
+    }
+}
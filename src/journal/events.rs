@@ -1,6 +1,74 @@
-use serde::{de, Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use time::OffsetDateTime;
 
+/// RFC 3339 timestamps for `#[serde(with)]`.
+/// A type rather than a module so schemars can describe the fields using it.
+pub(super) struct Rfc3339;
+
+impl Rfc3339 {
+    pub(super) fn serialize<S: Serializer>(
+        value: &OffsetDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        time::serde::rfc3339::serialize(value, serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<OffsetDateTime, D::Error> {
+        time::serde::rfc3339::deserialize(deserializer)
+    }
+}
+
+pub(super) struct Rfc3339Option;
+
+impl Rfc3339Option {
+    pub(super) fn serialize<S: Serializer>(
+        value: &Option<OffsetDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        time::serde::rfc3339::option::serialize(value, serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<OffsetDateTime>, D::Error> {
+        time::serde::rfc3339::option::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Rfc3339 {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Rfc3339".into()
+    }
+
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string", "format": "date-time" })
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Rfc3339Option {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Rfc3339Option".into()
+    }
+
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": ["string", "null"], "format": "date-time" })
+    }
+}
+
 fn bool_from_int<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: Deserializer<'de>,
@@ -15,18 +83,100 @@ where
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(tag = "event", rename_all_fields = "PascalCase")]
+/// Credits the game writes as text in some places, like donations
+fn credits_from_text<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Credits {
+        Number(u64),
+        Text(String),
+    }
+
+    match Option::<Credits>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Credits::Number(credits)) => Ok(Some(credits)),
+        Some(Credits::Text(text)) => text.trim().parse().map(Some).map_err(de::Error::custom),
+    }
+}
+
+/// Before 3.0 ingredients were an object of counts keyed by name
+fn ingredients_compat<'de, D>(deserializer: D) -> Result<Vec<Material>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Compat {
+        Current(Vec<Material>),
+        Legacy(BTreeMap<String, u32>),
+    }
+
+    Ok(match Compat::deserialize(deserializer)? {
+        Compat::Current(materials) => materials,
+        Compat::Legacy(counts) => counts
+            .into_iter()
+            .map(|(name, count)| Material { name, count })
+            .collect(),
+    })
+}
+
+fn full_health() -> f32 {
+    1.0
+}
+
+/// Flags only written since they can be false
+fn default_true() -> bool {
+    true
+}
+
+/// Before 3.0 the fuel capacity was a single number for the main tank
+fn fuel_capacity_compat<'de, D>(deserializer: D) -> Result<FuelCapacity, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Compat {
+        Current(FuelCapacity),
+        Legacy(f32),
+    }
+
+    Ok(match Compat::deserialize(deserializer)? {
+        Compat::Current(capacity) => capacity,
+        Compat::Legacy(main) => FuelCapacity { main, reserve: 0.0 },
+    })
+}
+
+/// Fields shared by every journal entry.
+/// Cheaper to parse than a full [Event] when only the kind or time matters.
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EventHeader {
+    #[serde(with = "Rfc3339")]
+    pub timestamp: OffsetDateTime,
+    pub event: String,
+}
+
+/// Deserialize and Serialize are derived as functions of their own, see
+/// the impls below for what they add
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(remote = "Self", tag = "event", rename_all_fields = "PascalCase")]
 pub enum Event {
     /// First event in every journal file
     Fileheader {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         /// Part number of this journal.
         /// When a journal reaches 500k lines it will contain an Event::Continue
         /// pointing to the next part.
         #[serde(rename = "part")]
         part: u32,
+        /// Missing before Odyssey was released
+        #[serde(default)]
         odyssey: bool,
         /// Language code in the form of "German/DE", "French/FR" etc.
         #[serde(rename = "language")]
@@ -38,42 +188,44 @@ pub enum Event {
     },
 
     // [[Startup]]
-
     /// Cargo information, written at startup.
     /// After startup other cargo events will be empty and signify that the
     /// Cargo.json file was updated.
     Cargo {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
+        /// Missing before 3.3, where cargo was only written for the ship
+        #[serde(default)]
         vessel: Vessel,
+        #[serde(default)]
         count: u32,
         #[serde(default)]
         inventory: Vec<CargoEntry>,
     },
     /// Save was cleared (who would ever do that?)
     ClearSavedGame {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         /// Player name
         name: String,
-        /// Player ID
-        #[serde(rename = "FID")]
+        /// Player ID, missing before 3.3
+        #[serde(rename = "FID", default)]
         fid: String,
     },
     /// Written at start of load game process
     Commander {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         /// Player name
         name: String,
-        /// Player ID
-        #[serde(rename = "FID")]
+        /// Player ID, missing before 3.3
+        #[serde(rename = "FID", default)]
         fid: String,
     },
     /// Written when loading from main menu, switching ship,
     /// changing ship in outfitting, or docking SRV back in ship.
     Loadout {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         /// Current ship type
         ship: String,
@@ -83,29 +235,36 @@ pub enum Event {
         ship_name: String,
         /// User defined ship ID
         ship_ident: String,
+        #[serde(default)]
         hull_value: u32,
         #[serde(default)]
         modules_value: u32,
+        #[serde(default = "full_health")]
         hull_health: f32,
         /// Mass of hull and modules excluding cargo and fuel
+        #[serde(default)]
         unladen_mass: f32,
+        #[serde(default, deserialize_with = "fuel_capacity_compat")]
         fuel_capacity: FuelCapacity,
+        #[serde(default)]
         cargo_capacity: u32,
+        #[serde(default)]
         max_jump_range: f32,
+        #[serde(default)]
         rebuy: u32,
         #[serde(default)]
         hot: bool,
         modules: Vec<Module>,
     },
     Materials {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         raw: Vec<Material>,
         manufactured: Vec<Material>,
         encoded: Vec<Material>,
     },
     Missions {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         active: Vec<Mission>,
         failed: Vec<Mission>,
@@ -113,48 +272,75 @@ pub enum Event {
     },
     /// Created a new commander
     NewCommander {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         /// Player name
         name: String,
-        /// Player ID
-        #[serde(rename = "FID")]
+        /// Player ID, missing before 3.3
+        #[serde(rename = "FID", default)]
         fid: String,
         /// Selected starter package
         package: String,
     },
     LoadGame {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         /// Player name
         commander: String,
-        /// Player ID
-        #[serde(rename = "FID")]
+        /// Player ID, missing before 3.3
+        #[serde(rename = "FID", default)]
         fid: String,
+        #[serde(default)]
         horizons: bool,
+        #[serde(default)]
         odyssey: bool,
+        /// Missing when loading the game on foot
+        #[serde(default)]
+        ship: Option<String>,
+        #[serde(rename = "ShipID", default)]
+        ship_id: Option<u32>,
+        #[serde(default)]
+        ship_name: Option<String>,
+        #[serde(default)]
+        ship_ident: Option<String>,
+        #[serde(default)]
+        fuel_level: Option<f64>,
+        #[serde(default)]
+        fuel_capacity: Option<f64>,
+        /// "Open", "Solo" or "Group", missing in CQC
+        #[serde(default)]
+        game_mode: Option<String>,
+        credits: u64,
+        #[serde(default)]
+        loan: u64,
     },
     Passengers {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         manifest: Vec<PassengerRecord>,
     },
     Powerplay {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         power: String,
         rank: u32,
         merits: u32,
+        /// Removed with Powerplay 2.0
+        #[serde(default)]
         votes: u32,
         time_pledged: u32,
     },
     Progress {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         combat: u32,
         trade: u32,
         explore: u32,
+        /// Missing before Odyssey was released
+        #[serde(default)]
         soldier: u32,
+        /// Missing before Odyssey was released
+        #[serde(default)]
         exobiologist: u32,
         empire: u32,
         federation: u32,
@@ -162,12 +348,16 @@ pub enum Event {
         cqc: u32,
     },
     Rank {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         combat: u32,
         trade: u32,
         explore: u32,
+        /// Missing before Odyssey was released
+        #[serde(default)]
         soldier: u32,
+        /// Missing before Odyssey was released
+        #[serde(default)]
         exobiologist: u32,
         empire: u32,
         federation: u32,
@@ -176,7 +366,7 @@ pub enum Event {
     },
     /// Players reputation with superpowers,
     /// written at startup and after rank and progress
-    /// 
+    ///
     /// Thresholds:
     /// hostile: -100..-90
     /// unfriendly: -90..-35
@@ -185,276 +375,2380 @@ pub enum Event {
     /// friendly: 35..90
     /// allied: 90..100
     Reputation {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
+        #[serde(default)]
         empire: f32,
+        #[serde(default)]
         federation: f32,
+        #[serde(default)]
         independent: f32,
+        #[serde(default)]
         alliance: f32,
     },
     Statistics {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         #[serde(rename = "Bank_Account")]
         bank_account: BankAccountStatistics,
+        #[serde(default)]
         combat: CombatStatistics,
+        #[serde(default)]
         crime: CrimeStatistics,
+        #[serde(default)]
         smuggling: SmugglingStatistics,
+        #[serde(default)]
         trading: TradingStatistics,
+        #[serde(default)]
         mining: MiningStatistics,
+        #[serde(default)]
         exploration: ExplorationStatistics,
+        #[serde(default)]
         passengers: PassengersStatistics,
-        #[serde(rename = "Search_And_Rescue")]
+        #[serde(rename = "Search_And_Rescue", default)]
         search_and_rescue: SearchAndRescueStatistics,
+        #[serde(default)]
         crafting: CraftingStatistics,
+        #[serde(default)]
         crew: CrewStatistics,
+        #[serde(default)]
         multicrew: MulticrewStatistics,
+        #[serde(rename = "CQC", default)]
+        cqc: CqcStatistics,
     },
 
     // [[Travel]]
-    ApproachBody,
-    Docked,
-    DockingCancelled,
-    DockingDenied,
-    DockingGranted,
-    DockingRequested,
-    DockingTimeout,
-    FSDJump,
-    FSDTarget,
-    LeaveBody,
-    Liftoff,
-    Location,
-    StartJump,
-    SupercruiseEntry,
-    SupercruiseExit,
-    Touchdown,
-    Undocked,
-    NavRoute,
-    NavRouteClear,
+    /// Entered orbital cruise around a body in supercruise
+    ApproachBody {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        star_system: String,
+        system_address: u64,
+        body: String,
+        #[serde(rename = "BodyID")]
+        body_id: u32,
+    },
+    Docked {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        station_name: String,
+        #[serde(default)]
+        station_type: Option<String>,
+        star_system: String,
+        #[serde(default)]
+        system_address: u64,
+        #[serde(rename = "MarketID", default)]
+        market_id: u64,
+        /// Distance from the arrival star in light seconds
+        #[serde(rename = "DistFromStarLS", default)]
+        dist_from_star_ls: Option<f64>,
+        #[serde(default)]
+        taxi: bool,
+        #[serde(default)]
+        multicrew: bool,
+    },
+    DockingCancelled {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    DockingDenied {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    DockingGranted {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    DockingRequested {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    DockingTimeout {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Hyperspace jump to another system completed
+    FSDJump {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        star_system: String,
+        /// Missing before 2.4
+        #[serde(default)]
+        system_address: u64,
+        /// Galactic coordinates in light years relative to Sol
+        star_pos: [f64; 3],
+        #[serde(default)]
+        body: Option<String>,
+        /// Distance jumped in light years
+        jump_dist: f64,
+        fuel_used: f64,
+        fuel_level: f64,
+        /// Jumped in a taxi or dropship
+        #[serde(default)]
+        taxi: bool,
+        /// Jumped as a crew member in someone else's ship
+        #[serde(default)]
+        multicrew: bool,
+        /// Minor factions present, missing in unpopulated systems
+        #[serde(default)]
+        factions: Vec<SystemFactionState>,
+        #[serde(default)]
+        system_faction: Option<ControllingFaction>,
+    },
+    FSDTarget {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Left orbital cruise around a body
+    LeaveBody {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        star_system: String,
+        system_address: u64,
+        body: String,
+        #[serde(rename = "BodyID")]
+        body_id: u32,
+    },
+    /// Ship lifted off from a planet surface
+    Liftoff {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(flatten)]
+        landing: Landing,
+    },
+    /// Written at startup or when respawning
+    Location {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        star_system: String,
+        /// Missing before 2.4
+        #[serde(default)]
+        system_address: u64,
+        /// Galactic coordinates in light years relative to Sol
+        star_pos: [f64; 3],
+        #[serde(default)]
+        body: Option<String>,
+        #[serde(default)]
+        docked: bool,
+        #[serde(default)]
+        station_name: Option<String>,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        #[serde(default)]
+        taxi: bool,
+        #[serde(default)]
+        multicrew: bool,
+        /// Minor factions present, missing in unpopulated systems
+        #[serde(default)]
+        factions: Vec<SystemFactionState>,
+        #[serde(default)]
+        system_faction: Option<ControllingFaction>,
+    },
+    /// FSD started charging for a jump
+    StartJump {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        jump_type: JumpType,
+        #[serde(default)]
+        taxi: bool,
+        /// Target of hyperspace jumps
+        #[serde(default)]
+        star_system: Option<String>,
+        #[serde(default)]
+        system_address: Option<u64>,
+        /// Spectral class of the main star of the target, e.g. "K" or "DA"
+        #[serde(default)]
+        star_class: Option<String>,
+    },
+    SupercruiseEntry {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        star_system: String,
+        /// Missing before 2.4
+        #[serde(default)]
+        system_address: u64,
+        #[serde(default)]
+        taxi: bool,
+        #[serde(default)]
+        multicrew: bool,
+    },
+    SupercruiseExit {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        star_system: String,
+        /// Missing before 2.4
+        #[serde(default)]
+        system_address: u64,
+        /// Body or station dropped at
+        #[serde(default)]
+        body: Option<String>,
+        #[serde(rename = "BodyID", default)]
+        body_id: Option<u32>,
+        /// Like "Star", "Planet", "Station" or "PlanetaryRing"
+        #[serde(default)]
+        body_type: Option<String>,
+        #[serde(default)]
+        taxi: bool,
+        #[serde(default)]
+        multicrew: bool,
+    },
+    /// Ship landed on a planet surface
+    Touchdown {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(flatten)]
+        landing: Landing,
+    },
+    Undocked {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        station_name: String,
+        #[serde(default)]
+        station_type: Option<String>,
+        #[serde(rename = "MarketID", default)]
+        market_id: u64,
+        #[serde(default)]
+        taxi: bool,
+        #[serde(default)]
+        multicrew: bool,
+    },
+    /// A route was plotted.
+    /// The journal only announces it, the route itself is in NavRoute.json
+    /// which has the same layout.
+    NavRoute {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        route: Vec<RouteEntry>,
+    },
+    NavRouteClear {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
 
     // [[Combat]]
-    Bounty,
-    CapShipBond,
-    Died,
-    EscapeInterdiction,
-    FactionKillBond,
-    FighterDestroyed,
-    HeatDamage,
-    HeatWarning,
-    HullDamage,
-    Interdicted,
-    Interdiction,
-    PVPKill,
-    ShieldState,
-    ShipTargeted,
-    SRVDestroyed,
-    UnderAttack,
+    /// Bounty for destroying a wanted ship, shared out between the factions
+    /// in Rewards. Skimmers and the like pay a single Faction instead.
+    Bounty {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        target: String,
+        #[serde(rename = "Target_Localised", default)]
+        target_localised: Option<String>,
+        #[serde(default)]
+        rewards: Vec<BountyReward>,
+        #[serde(default)]
+        total_reward: u64,
+        #[serde(default)]
+        faction: Option<String>,
+        #[serde(default)]
+        reward: Option<u64>,
+        victim_faction: String,
+        #[serde(rename = "VictimFaction_Localised", default)]
+        victim_faction_localised: Option<String>,
+    },
+    CapShipBond {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Commander was killed.
+    /// A single killer is written to the Killer fields, a wing of killers
+    /// to the Killers list.
+    Died {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        killer_name: Option<String>,
+        #[serde(rename = "KillerName_Localised", default)]
+        killer_name_localised: Option<String>,
+        #[serde(default)]
+        killer_ship: Option<String>,
+        #[serde(default)]
+        killer_rank: Option<String>,
+        #[serde(default)]
+        killers: Vec<Killer>,
+    },
+    /// Commander escaped an interdiction
+    EscapeInterdiction {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        interdictor: Option<String>,
+        #[serde(rename = "Interdictor_Localised", default)]
+        interdictor_localised: Option<String>,
+        is_player: bool,
+        #[serde(default)]
+        is_thargoid: bool,
+    },
+    /// Combat bond awarded for a kill in a conflict zone or against Thargoids
+    FactionKillBond {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        reward: u64,
+        awarding_faction: String,
+        #[serde(rename = "AwardingFaction_Localised", default)]
+        awarding_faction_localised: Option<String>,
+        victim_faction: String,
+        #[serde(rename = "VictimFaction_Localised", default)]
+        victim_faction_localised: Option<String>,
+    },
+    FighterDestroyed {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Ship took damage from overheating
+    HeatDamage {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Ship heat rose above 100%
+    HeatWarning {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Hull health dropped below a multiple of 20%
+    HullDamage {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Hull health from 0 to 1
+        health: f32,
+        #[serde(default)]
+        player_pilot: bool,
+        /// Damage was done to the commander's fighter
+        #[serde(default)]
+        fighter: bool,
+    },
+    /// Commander was interdicted
+    Interdicted {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Whether the commander submitted to the interdiction
+        submitted: bool,
+        #[serde(default)]
+        interdictor: Option<String>,
+        #[serde(rename = "Interdictor_Localised", default)]
+        interdictor_localised: Option<String>,
+        is_player: bool,
+        #[serde(default)]
+        is_thargoid: bool,
+        /// Combat rank of a player interdictor from 0 for Harmless
+        #[serde(default)]
+        combat_rank: Option<u8>,
+        #[serde(default)]
+        faction: Option<String>,
+        #[serde(default)]
+        power: Option<String>,
+    },
+    /// Commander interdicted someone
+    Interdiction {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        success: bool,
+        /// Name of the pilot interdicted
+        #[serde(default)]
+        interdicted: Option<String>,
+        #[serde(rename = "Interdicted_Localised", default)]
+        interdicted_localised: Option<String>,
+        is_player: bool,
+        /// Combat rank of a player interdicted from 0 for Harmless
+        #[serde(default)]
+        combat_rank: Option<u8>,
+        #[serde(default)]
+        faction: Option<String>,
+        #[serde(default)]
+        power: Option<String>,
+    },
+    PVPKill {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Shields went down or came back up
+    ShieldState {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        shields_up: bool,
+    },
+    /// Target was locked or the scan of the target progressed.
+    /// Each stage of the scan adds fields to the ones before.
+    ShipTargeted {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        target_locked: bool,
+        /// Stage 0 on, while locked
+        #[serde(default)]
+        ship: Option<String>,
+        #[serde(rename = "Ship_Localised", default)]
+        ship_localised: Option<String>,
+        /// From 0 to 3, missing when the lock was lost
+        #[serde(default)]
+        scan_stage: Option<u8>,
+        /// Stage 1 on, like "$npc_name_decorate:#name=Kaleb Haas;"
+        #[serde(default)]
+        pilot_name: Option<String>,
+        #[serde(rename = "PilotName_Localised", default)]
+        pilot_name_localised: Option<String>,
+        /// Combat rank like "Competent" or "Elite"
+        #[serde(default)]
+        pilot_rank: Option<String>,
+        #[serde(rename = "SquadronID", default)]
+        squadron_id: Option<String>,
+        /// Stage 2 on, from 0 to 100
+        #[serde(default)]
+        shield_health: Option<f64>,
+        #[serde(default)]
+        hull_health: Option<f64>,
+        /// Stage 3
+        #[serde(default)]
+        faction: Option<String>,
+        /// Like "Clean", "Wanted" or "Lawless"
+        #[serde(default)]
+        legal_status: Option<String>,
+        /// Credits on the target's head
+        #[serde(default)]
+        bounty: Option<u64>,
+        #[serde(default)]
+        power: Option<String>,
+        /// Module targeted, if any
+        #[serde(default)]
+        subsystem: Option<String>,
+        #[serde(rename = "Subsystem_Localised", default)]
+        subsystem_localised: Option<String>,
+        #[serde(default)]
+        subsystem_health: Option<f64>,
+    },
+    SRVDestroyed {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Someone is shooting at the commander or what they look after
+    UnderAttack {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// "You", "Fighter", "Mothership" or "Wingman"
+        #[serde(default)]
+        target: Option<String>,
+    },
 
     // [[Exploration]]
-    CodexEntry,
-    DiscoveryScan,
-    Scan,
-    FSSAllBodiesFound,
-    FSSBodySignals,
-    FSSDiscoveryScan,
-    FSSSignalDiscovered,
-    MaterialCollected,
-    MaterialDiscarded,
-    MaterialDiscovered,
-    MultiSellExplorationData,
-    NavBeaconScan,
-    BuyExplorationData,
-    SAAScanComplete,
-    SAASignalsFound,
-    ScanBaryCentre,
-    SellExplorationData,
-    Screenshot,
-
-    // [[Trade]]
-    AsteroidCracked,
-    BuyTradeData,
-    CollectCargo,
-    EjectCargo,
-    MarketBuy,
-    MarketSell,
-    MiningRefined,
-
-    // [[Station Services]]
-    BuyAmmo,
-    BuyDrones,
-    CargoDepot,
-    CommunityGoal,
-    CommunityGoalDiscard,
-    CommunityGoalJoin,
-    CommunityGoalReward,
-    CrewAssign,
-    CrewFire,
-    CrewHire,
-    EngineerApply,
-    EngineerContribution,
-    EngineerCraft,
-    EngineerLegacyConvert,
-    EngineerProgress,
-    FetchRemoteModule,
-    Market,
-    MassModuleStore,
-    MaterialTrade,
-    MissionAbandoned,
-    MissionAccepted,
-    MissionCompleted,
-    MissionFailed,
-    MissionRedirected,
-    ModuleBuy,
-    ModuleRetrieve,
-    ModuleSell,
-    ModuleSellRemote,
-    ModuleStore,
-    ModuleSwap,
-    Outfitting,
-    PayBounties,
-    PayFines,
-    PayLegacyFines,
-    RedeemVoucher,
-    RefuelAll,
-    RefuelPartial,
-    Repair,
-    RepairAll,
-    RestockVehicle,
-    ScientificResearch,
-    SearchAndRescue,
-    SellDrones,
-    SellShipOnRebuy,
-    SetUserShipName,
-    Shipyard,
-    ShipyardBuy,
-    ShipyardNew,
-    ShipyardSell,
-    ShipyardTransfer,
-    ShipyardSwap,
-    StoredModules,
-    StoredShips,
-    TechnologyBroker,
-    ClearImpound,
-
-    // [[Powerplay]]
-    PowerplayCollect,
-    PowerplayDefect,
-    PowerplayDeliver,
-    PowerplayFastTrack,
-    PowerplayJoin,
-    PowerplayLeave,
-    PowerplaySalary,
-    PowerplayVote,
-    PowerplayVoucher,
-
-    // [[Squadrons]]
-    AppliedToSquadron,
-    DisbandedSquadron,
-    InvitedToSquadron,
-    JoinedSquadron,
-    KickedFromSquadron,
-    LeftSquadron,
-    SharedBookmarkToSquadron,
-    SquadronCreated,
-    SquadronDemotion,
-    SquadronPromotion,
-    SquadronStartup,
-    WonATrophyForSquadron,
-
-    // [[Fleet Carriers]]
-    CarrierJump,
-    CarrierBuy,
-    CarrierStats,
-    CarrierJumpRequest,
-    CarrierDecommission,
-    CarrierCancelDecommission,
-    CarrierBankTransfer,
-    CarrierDepositFuel,
-    CarrierCrewServices,
-    CarrierFinance,
-    CarrierShipPack,
-    CarrierModulePack,
-    CarrierTradeOrder,
-    CarrierDockingPermission,
-    CarrierNameChanged,
-    CarrierJumpCancelled,
-
-    // [[New in Odyssey]]
-    Backpack,
-    BackpackChange,
-    BackpackMaterials,
-    BookDropship,
-    BookTaxi,
-    BuyMicroResources,
-    BuySuit,
-    BuyWeapon,
-    CancelDropship,
-    CancelTaxi,
-    CollectItems,
-    CreateSuitLoadout,
-    DeleteSuitLoadout,
-    Disembark,
-    DropItems,
-    DropShipDeploy,
-    Embark,
-    FCMaterials,
-    LoadoutEquipModule,
-    LoadoutRemoveModule,
-    RenameSuitLoadout,
-    Resupply,
-    ScanOrganic,
-    SellMicroResources,
-    SellOrganicData,
-    SellSuit,
-    SellWeapon,
-    ShipLocker,
-    SuitLoadout,
-    SwitchSuitLoadout,
-    TransferMicroResources,
-    TradeMicroResources,
-    UpgradeSuit,
-    UpgradeWeapon,
-    UseConsumable,
-
-    // [[Other Events]]
-    AfmuRepairs,
-    ApproachSettlement,
-    ChangeCrewRole,
-    CockpitBreached,
-    CommitCrime,
-    Continued,
-    CrewLaunchFighter,
-    CrewMemberJoins,
-    CrewMemberQuits,
-    CrewMemberRoleChange,
-    CrimeVictim,
-    DatalinkScan,
-    DatalinkVoucher,
-    DataScanned,
-    DockFighter,
-    DockSRV,
-    EndCrewSession,
-    FighterRebuilt,
-    FuelScoop,
-    Friends,
-    JetConeBoost,
-    JetConeDamage,
-    JoinACrew,
-    KickCrewMember,
-    LaunchDrone,
-    LaunchFighter,
-    LaunchSRV,
-    ModuleInfo,
-    Music,
-    NpcCrewPaidWage,
-    NpcCrewRank,
-    Promotion,
-    ProspectedAsteroid,
-    QuitACrew,
-    RebootRepair,
+    /// Something was logged in the codex
+    CodexEntry {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "EntryID")]
+        entry_id: u64,
+        /// Symbol in the form of "$Codex_Ent_Stratum_07_Name;"
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        /// Symbol like "$Codex_SubCategory_Organic_Structures;"
+        sub_category: String,
+        #[serde(rename = "SubCategory_Localised", default)]
+        sub_category_localised: Option<String>,
+        /// Symbol like "$Codex_Category_Biology;"
+        category: String,
+        #[serde(rename = "Category_Localised", default)]
+        category_localised: Option<String>,
+        /// Symbol like "$Codex_RegionName_18;"
+        region: String,
+        #[serde(rename = "Region_Localised", default)]
+        region_localised: Option<String>,
+        system: String,
+        system_address: u64,
+        #[serde(rename = "BodyID", default)]
+        body_id: Option<u32>,
+        /// Symbol of the closest body or station, like
+        /// "$SAA_Unknown_Signal:#type=$SAA_SignalType_Geological;:#index=3;"
+        #[serde(default)]
+        nearest_destination: Option<String>,
+        #[serde(rename = "NearestDestination_Localised", default)]
+        nearest_destination_localised: Option<String>,
+        /// Position when logged on a planet surface
+        #[serde(default)]
+        latitude: Option<f64>,
+        #[serde(default)]
+        longitude: Option<f64>,
+        /// First entry of its kind in the region for the commander
+        #[serde(default)]
+        is_new_entry: bool,
+        #[serde(default)]
+        new_traits_discovered: bool,
+        /// Symbols of the traits newly discovered
+        #[serde(default)]
+        traits: Vec<String>,
+        /// Credits for the voucher handed out for new entries
+        #[serde(default)]
+        voucher_amount: Option<u64>,
+    },
+    DiscoveryScan {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Body was scanned.
+    /// Which fields are present depends on the body and scan type.
+    Scan {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// "AutoScan", "Detailed", "Basic", "NavBeacon" or "NavBeaconDetail"
+        #[serde(default)]
+        scan_type: String,
+        body_name: String,
+        #[serde(rename = "BodyID", default)]
+        body_id: Option<u32>,
+        /// Missing before 3.3
+        #[serde(default)]
+        star_system: Option<String>,
+        /// Missing before 3.3
+        #[serde(default)]
+        system_address: Option<u64>,
+        #[serde(rename = "DistanceFromArrivalLS", default)]
+        distance_from_arrival_ls: Option<f64>,
+        /// Spectral class for stars, e.g. "K" or "DA"
+        #[serde(default)]
+        star_type: Option<String>,
+        /// Class for planets, e.g. "Earthlike body" or "High metal content body"
+        #[serde(default)]
+        planet_class: Option<String>,
+        #[serde(default)]
+        terraform_state: Option<String>,
+        /// Mass of stars in solar masses
+        #[serde(default)]
+        stellar_mass: Option<f64>,
+        /// Mass of planets in earth masses
+        #[serde(rename = "MassEM", default)]
+        mass_em: Option<f64>,
+        /// Someone else already sold data of this body, missing before 3.3
+        #[serde(default)]
+        was_discovered: Option<bool>,
+        /// Someone else already mapped this body, missing before 3.3
+        #[serde(default)]
+        was_mapped: Option<bool>,
+    },
+    /// Every body in the system was found with the FSS
+    FSSAllBodiesFound {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        system_name: String,
+        system_address: u64,
+        count: u32,
+    },
+    /// Signals found on a body by the FSS
+    FSSBodySignals {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        body_name: String,
+        #[serde(rename = "BodyID")]
+        body_id: u32,
+        system_address: u64,
+        signals: Vec<Signal>,
+    },
+    /// Honk with the discovery scanner
+    FSSDiscoveryScan {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Fraction of the system's signals found, from 0 to 1
+        progress: f64,
+        /// Stars and planets in the system
+        body_count: u32,
+        non_body_count: u32,
+        /// Missing before 3.3
+        #[serde(default)]
+        system_name: Option<String>,
+        #[serde(default)]
+        system_address: Option<u64>,
+    },
+    FSSSignalDiscovered {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    MaterialCollected {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// "Raw", "Manufactured" or "Encoded"
+        category: String,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        count: u32,
+    },
+    MaterialDiscarded {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        category: String,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        count: u32,
+    },
+    /// Material collected for the first time
+    MaterialDiscovered {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        category: String,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        /// Number of materials of the category discovered so far
+        #[serde(default)]
+        discovery_number: u32,
+    },
+    /// Exploration data sold at Universal Cartographics
+    MultiSellExplorationData {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        discovered: Vec<DiscoveredSystem>,
+        base_value: u64,
+        bonus: u64,
+        total_earnings: u64,
+    },
+    /// Nav beacon was scanned, revealing the bodies of the system
+    NavBeaconScan {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Missing before 2.4
+        #[serde(default)]
+        system_address: u64,
+        num_bodies: u32,
+    },
+    BuyExplorationData {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        system: String,
+        cost: u64,
+    },
+    /// Body was fully mapped with probes
+    SAAScanComplete {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        body_name: String,
+        #[serde(rename = "BodyID")]
+        body_id: u32,
+        system_address: u64,
+        probes_used: u32,
+        /// Mapping with no more probes than this earns the efficiency bonus
+        efficiency_target: u32,
+    },
+    /// Signals found by mapping a body
+    SAASignalsFound {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        body_name: String,
+        #[serde(rename = "BodyID")]
+        body_id: u32,
+        system_address: u64,
+        signals: Vec<Signal>,
+        /// Genera of the biological signals, missing before Odyssey
+        #[serde(default)]
+        genuses: Vec<Genus>,
+    },
+    ScanBaryCentre {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Exploration data sold before 3.3
+    SellExplorationData {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        systems: Vec<String>,
+        /// Bodies that were first discovered
+        discovered: Vec<String>,
+        base_value: u64,
+        bonus: u64,
+        /// Missing before 3.0
+        #[serde(default)]
+        total_earnings: Option<u64>,
+    },
+    Screenshot {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Path in the form of "\ED_Pictures\Screenshot_0000.bmp",
+        /// relative to the screenshots directory
+        filename: String,
+        width: u32,
+        height: u32,
+        system: String,
+        /// Missing when not near a body
+        #[serde(default)]
+        body: Option<String>,
+        /// Coordinates, only present near a planet surface
+        #[serde(default)]
+        latitude: Option<f64>,
+        #[serde(default)]
+        longitude: Option<f64>,
+        #[serde(default)]
+        altitude: Option<f64>,
+        #[serde(default)]
+        heading: Option<i32>,
+    },
+
+    // [[Trade]]
+    /// Motherlode asteroid was split open with charges
+    AsteroidCracked {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Ring the asteroid is in
+        body: String,
+    },
+    BuyTradeData {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        system: String,
+        cost: u64,
+    },
+    CollectCargo {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    EjectCargo {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Commodity symbol like "tritium"
+        #[serde(rename = "Type")]
+        commodity: String,
+        #[serde(rename = "Type_Localised", default)]
+        commodity_localised: Option<String>,
+        count: u32,
+        /// Dropped by abandoning a delivery mission
+        #[serde(default)]
+        abandoned: bool,
+    },
+    MarketBuy {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        /// Commodity symbol like "tritium"
+        #[serde(rename = "Type")]
+        commodity: String,
+        #[serde(rename = "Type_Localised", default)]
+        commodity_localised: Option<String>,
+        count: u32,
+        /// Price per ton
+        buy_price: u64,
+        total_cost: u64,
+    },
+    MarketSell {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        /// Commodity symbol like "tritium"
+        #[serde(rename = "Type")]
+        commodity: String,
+        #[serde(rename = "Type_Localised", default)]
+        commodity_localised: Option<String>,
+        count: u32,
+        /// Price per ton
+        sell_price: u64,
+        total_sale: u64,
+        /// Average price per ton paid for the goods sold
+        #[serde(default)]
+        avg_price_paid: u64,
+        #[serde(default)]
+        illegal_goods: bool,
+        #[serde(default)]
+        stolen_goods: bool,
+        #[serde(default)]
+        black_market: bool,
+    },
+    /// Ton of a mined commodity was refined
+    MiningRefined {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Symbol like "$painite_name;"
+        r#type: String,
+        #[serde(rename = "Type_Localised", default)]
+        type_localised: Option<String>,
+    },
+
+    // [[Station Services]]
+    BuyAmmo {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        cost: u64,
+    },
+    BuyDrones {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        count: u32,
+        buy_price: u64,
+        total_cost: u64,
+    },
+    CargoDepot {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Progress of all community goals the commander is signed up for
+    CommunityGoal {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        current_goals: Vec<CommunityGoalStatus>,
+    },
+    CommunityGoalDiscard {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "CGID")]
+        cgid: u32,
+        name: String,
+        system: String,
+    },
+    CommunityGoalJoin {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "CGID")]
+        cgid: u32,
+        name: String,
+        system: String,
+    },
+    CommunityGoalReward {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "CGID")]
+        cgid: u32,
+        name: String,
+        system: String,
+        reward: u64,
+    },
+    /// NPC crew member was given a role, or made inactive
+    CrewAssign {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "CrewID", default)]
+        crew_id: Option<u64>,
+        role: String,
+    },
+    CrewFire {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "CrewID", default)]
+        crew_id: Option<u64>,
+    },
+    CrewHire {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "CrewID", default)]
+        crew_id: Option<u64>,
+        #[serde(default)]
+        faction: String,
+        cost: u64,
+        #[serde(default)]
+        combat_rank: u32,
+    },
+    EngineerApply {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    EngineerContribution {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Blueprint was applied to a module
+    EngineerCraft {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        engineer: String,
+        #[serde(rename = "EngineerID", default)]
+        engineer_id: Option<u64>,
+        /// Slot of the module, missing before 3.0
+        #[serde(default)]
+        slot: Option<String>,
+        /// Symbol of the module like "hpt_pulselaser_fixed_medium"
+        #[serde(default)]
+        module: Option<String>,
+        /// Name like "Weapon_Overcharged", "Blueprint" before 3.0
+        #[serde(rename = "BlueprintName", alias = "Blueprint")]
+        blueprint: String,
+        #[serde(rename = "BlueprintID", default)]
+        blueprint_id: Option<u64>,
+        /// Grade from 1 to 5
+        level: u32,
+        /// Progress towards the next grade from 0 to 1
+        #[serde(default)]
+        quality: Option<f32>,
+        /// Experimental effect applied with this craft
+        #[serde(default)]
+        apply_experimental_effect: Option<String>,
+        /// Experimental effect the module has
+        #[serde(default)]
+        experimental_effect: Option<String>,
+        #[serde(rename = "ExperimentalEffect_Localised", default)]
+        experimental_effect_localised: Option<String>,
+        /// Materials used up
+        #[serde(default, deserialize_with = "ingredients_compat")]
+        ingredients: Vec<Material>,
+        /// Stats of the module after the craft
+        #[serde(default)]
+        modifiers: Vec<EngineeringModifiers>,
+    },
+    EngineerLegacyConvert {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Progress with every engineer at startup, or with one that changed
+    EngineerProgress {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        engineers: Vec<EngineerState>,
+        #[serde(default)]
+        engineer: Option<String>,
+        #[serde(rename = "EngineerID", default)]
+        engineer_id: Option<u64>,
+        /// Like "Known", "Invited", "Acquainted", "Unlocked" or "Barred"
+        #[serde(default)]
+        progress: Option<String>,
+        #[serde(default)]
+        rank: Option<u32>,
+        /// Percent towards the next grade
+        #[serde(default)]
+        rank_progress: Option<u32>,
+    },
+    /// Stored module was sent for to the current station
+    FetchRemoteModule {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        storage_slot: u32,
+        stored_item: String,
+        #[serde(rename = "StoredItem_Localised", default)]
+        stored_item_localised: Option<String>,
+        #[serde(default)]
+        server_id: u64,
+        transfer_cost: u64,
+        /// Seconds until the module arrives
+        #[serde(default)]
+        transfer_time: u64,
+        #[serde(default)]
+        ship: Option<String>,
+        #[serde(rename = "ShipId", default)]
+        ship_id: Option<u32>,
+    },
+    /// Commodity market was opened.
+    /// The prices are only in Market.json, which has the same layout.
+    Market {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        station_name: String,
+        #[serde(default)]
+        station_type: String,
+        star_system: String,
+        #[serde(default)]
+        items: Vec<MarketItem>,
+    },
+    /// Every module of a ship in storage at once
+    MassModuleStore {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        ship: String,
+        #[serde(rename = "ShipID")]
+        ship_id: u32,
+        #[serde(default)]
+        items: Vec<MassStoredModule>,
+    },
+    /// Materials were exchanged at a material trader
+    MaterialTrade {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        trader_type: String,
+        paid: TradedMaterial,
+        received: TradedMaterial,
+    },
+    MissionAbandoned {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(default)]
+        localised_name: Option<String>,
+        #[serde(rename = "MissionID")]
+        mission_id: u32,
+        #[serde(default)]
+        fine: Option<u64>,
+    },
+    MissionAccepted {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Faction offering the mission
+        faction: String,
+        name: String,
+        #[serde(default)]
+        localised_name: Option<String>,
+        #[serde(rename = "MissionID")]
+        mission_id: u32,
+        #[serde(with = "Rfc3339Option", default)]
+        expiry: Option<OffsetDateTime>,
+        /// Offered to the whole wing
+        #[serde(default)]
+        wing: bool,
+        /// One to five pluses of influence the mission is worth
+        #[serde(default)]
+        influence: Option<String>,
+        /// One to five pluses of reputation the mission is worth
+        #[serde(default)]
+        reputation: Option<String>,
+        /// Commodity to deliver, collect or source
+        #[serde(default)]
+        commodity: Option<String>,
+        #[serde(rename = "Commodity_Localised", default)]
+        commodity_localised: Option<String>,
+        /// Tons of the commodity
+        #[serde(default)]
+        count: Option<u32>,
+        /// Credits asked for by donation missions
+        #[serde(default, deserialize_with = "credits_from_text")]
+        donation: Option<u64>,
+        /// Person or ship to kill, rescue or scan
+        #[serde(default)]
+        target: Option<String>,
+        #[serde(rename = "Target_Localised", default)]
+        target_localised: Option<String>,
+        #[serde(default)]
+        target_type: Option<String>,
+        #[serde(rename = "TargetType_Localised", default)]
+        target_type_localised: Option<String>,
+        #[serde(default)]
+        target_faction: Option<String>,
+        /// Ships to destroy for massacre missions
+        #[serde(default)]
+        kill_count: Option<u32>,
+        #[serde(default)]
+        destination_system: Option<String>,
+        #[serde(default)]
+        destination_station: Option<String>,
+        #[serde(default)]
+        destination_settlement: Option<String>,
+        #[serde(default)]
+        reward: Option<u64>,
+        #[serde(default)]
+        passenger_count: Option<u32>,
+        #[serde(rename = "PassengerVIPs", default)]
+        passenger_vips: bool,
+        #[serde(default)]
+        passenger_wanted: bool,
+        /// Passenger type in the form of "Tourist", "Refugee" etc.
+        #[serde(default)]
+        passenger_type: Option<String>,
+    },
+    MissionCompleted {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        faction: String,
+        name: String,
+        #[serde(default)]
+        localised_name: Option<String>,
+        #[serde(rename = "MissionID")]
+        mission_id: u32,
+        /// Commodity delivered, collected or sourced
+        #[serde(default)]
+        commodity: Option<String>,
+        #[serde(rename = "Commodity_Localised", default)]
+        commodity_localised: Option<String>,
+        #[serde(default)]
+        count: Option<u32>,
+        #[serde(default)]
+        target: Option<String>,
+        #[serde(rename = "Target_Localised", default)]
+        target_localised: Option<String>,
+        #[serde(default)]
+        target_type: Option<String>,
+        #[serde(rename = "TargetType_Localised", default)]
+        target_type_localised: Option<String>,
+        #[serde(default)]
+        target_faction: Option<String>,
+        #[serde(default)]
+        kill_count: Option<u32>,
+        #[serde(default)]
+        destination_system: Option<String>,
+        #[serde(default)]
+        destination_station: Option<String>,
+        #[serde(default)]
+        destination_settlement: Option<String>,
+        #[serde(default)]
+        reward: Option<u64>,
+        /// Credits asked for by donation missions
+        #[serde(default, deserialize_with = "credits_from_text")]
+        donation: Option<u64>,
+        /// Credits donated
+        #[serde(default)]
+        donated: Option<u64>,
+        /// Systems the mission gave a permit for
+        #[serde(default)]
+        permits_awarded: Vec<String>,
+        #[serde(default)]
+        commodity_reward: Vec<CommodityReward>,
+        #[serde(default)]
+        materials_reward: Vec<MaterialReward>,
+        /// Effects on the standing of the factions involved
+        #[serde(default)]
+        faction_effects: Vec<FactionEffect>,
+    },
+    MissionFailed {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(default)]
+        localised_name: Option<String>,
+        #[serde(rename = "MissionID")]
+        mission_id: u32,
+        #[serde(default)]
+        fine: Option<u64>,
+    },
+    /// The mission was done and now has to be handed in elsewhere, usually
+    /// back where it was accepted
+    MissionRedirected {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MissionID")]
+        mission_id: u32,
+        name: String,
+        #[serde(default)]
+        localised_name: Option<String>,
+        new_destination_station: String,
+        new_destination_system: String,
+        #[serde(default)]
+        old_destination_station: String,
+        #[serde(default)]
+        old_destination_system: String,
+    },
+    ModuleBuy {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        slot: String,
+        buy_item: String,
+        #[serde(rename = "BuyItem_Localised", default)]
+        buy_item_localised: Option<String>,
+        buy_price: u64,
+        #[serde(default)]
+        ship: Option<String>,
+        #[serde(rename = "ShipID", default)]
+        ship_id: Option<u32>,
+        /// Module sold to make room, if any
+        #[serde(default)]
+        sell_item: Option<String>,
+        #[serde(rename = "SellItem_Localised", default)]
+        sell_item_localised: Option<String>,
+        #[serde(default)]
+        sell_price: Option<u64>,
+        /// Module put in storage to make room, if any
+        #[serde(default)]
+        stored_item: Option<String>,
+        #[serde(rename = "StoredItem_Localised", default)]
+        stored_item_localised: Option<String>,
+    },
+    /// Stored module was fitted
+    ModuleRetrieve {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        slot: String,
+        ship: String,
+        #[serde(rename = "ShipID")]
+        ship_id: u32,
+        retrieved_item: String,
+        #[serde(rename = "RetrievedItem_Localised", default)]
+        retrieved_item_localised: Option<String>,
+        #[serde(default)]
+        hot: bool,
+        /// Blueprint the module was engineered with
+        #[serde(default)]
+        engineer_modifications: Option<String>,
+        #[serde(default)]
+        level: Option<u32>,
+        #[serde(default)]
+        quality: Option<f32>,
+        /// Module put in storage in its place, if any
+        #[serde(default)]
+        swap_out_item: Option<String>,
+        #[serde(rename = "SwapOutItem_Localised", default)]
+        swap_out_item_localised: Option<String>,
+        #[serde(default)]
+        cost: u64,
+    },
+    ModuleSell {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        slot: String,
+        sell_item: String,
+        #[serde(rename = "SellItem_Localised", default)]
+        sell_item_localised: Option<String>,
+        sell_price: u64,
+        #[serde(default)]
+        ship: Option<String>,
+        #[serde(rename = "ShipID", default)]
+        ship_id: Option<u32>,
+    },
+    /// Stored module was sold
+    ModuleSellRemote {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        storage_slot: Option<u32>,
+        sell_item: String,
+        #[serde(rename = "SellItem_Localised", default)]
+        sell_item_localised: Option<String>,
+        #[serde(default)]
+        server_id: Option<u64>,
+        sell_price: u64,
+        #[serde(default)]
+        ship: Option<String>,
+        #[serde(rename = "ShipId", default)]
+        ship_id: Option<u32>,
+    },
+    /// Fitted module was put in storage
+    ModuleStore {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        slot: String,
+        ship: String,
+        #[serde(rename = "ShipID")]
+        ship_id: u32,
+        stored_item: String,
+        #[serde(rename = "StoredItem_Localised", default)]
+        stored_item_localised: Option<String>,
+        #[serde(default)]
+        hot: bool,
+        /// Blueprint the module was engineered with, kept in storage
+        #[serde(default)]
+        engineer_modifications: Option<String>,
+        #[serde(default)]
+        level: Option<u32>,
+        #[serde(default)]
+        quality: Option<f32>,
+        /// Module fitted in its place, if any
+        #[serde(default)]
+        replacement_item: Option<String>,
+        #[serde(rename = "ReplacementItem_Localised", default)]
+        replacement_item_localised: Option<String>,
+        #[serde(default)]
+        cost: u64,
+    },
+    /// Two fitted modules swapped slots, or one moved to an empty slot
+    ModuleSwap {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        from_slot: String,
+        to_slot: String,
+        from_item: String,
+        #[serde(rename = "FromItem_Localised", default)]
+        from_item_localised: Option<String>,
+        /// "Null" when the module moved to an empty slot
+        to_item: String,
+        #[serde(rename = "ToItem_Localised", default)]
+        to_item_localised: Option<String>,
+        ship: String,
+        #[serde(rename = "ShipID")]
+        ship_id: u32,
+    },
+    Outfitting {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    PayBounties {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        amount: u64,
+        #[serde(default)]
+        all_fines: bool,
+        #[serde(default)]
+        faction: Option<String>,
+        #[serde(rename = "Faction_Localised", default)]
+        faction_localised: Option<String>,
+        /// Missing before 3.0
+        #[serde(rename = "ShipID", default)]
+        ship_id: u32,
+        /// Percentage kept by an interstellar factor, if one was used
+        #[serde(default)]
+        broker_percentage: Option<f32>,
+    },
+    PayFines {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        amount: u64,
+        /// Whether fines were paid for all factions at once
+        #[serde(default)]
+        all_fines: bool,
+        #[serde(default)]
+        faction: Option<String>,
+        #[serde(rename = "Faction_Localised", default)]
+        faction_localised: Option<String>,
+        /// Missing before 3.0
+        #[serde(rename = "ShipID", default)]
+        ship_id: u32,
+        /// Percentage kept by an interstellar factor, if one was used
+        #[serde(default)]
+        broker_percentage: Option<f32>,
+    },
+    PayLegacyFines {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        amount: u64,
+        /// Percentage kept by an interstellar factor, if one was used
+        #[serde(default)]
+        broker_percentage: Option<f32>,
+    },
+    /// Bounty, combat bond or other vouchers were cashed in
+    RedeemVoucher {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "Type")]
+        voucher_type: VoucherType,
+        amount: u64,
+        /// Faction paying out a combat bond or trade voucher
+        #[serde(default)]
+        faction: Option<String>,
+        /// Factions paying out bounty vouchers
+        #[serde(default)]
+        factions: Vec<VoucherFaction>,
+        /// Percentage kept by an interstellar factor, if one was used
+        #[serde(default)]
+        broker_percentage: Option<f32>,
+    },
+    RefuelAll {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        cost: u64,
+        /// Tons of fuel bought
+        amount: f64,
+    },
+    RefuelPartial {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        cost: u64,
+        /// Tons of fuel bought
+        amount: f64,
+    },
+    Repair {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Module or "Wear", "Hull" and "Paint" repaired, before 4.0
+        #[serde(default)]
+        item: Option<String>,
+        /// Everything repaired at once, since 4.0
+        #[serde(default)]
+        items: Vec<String>,
+        cost: u64,
+    },
+    RepairAll {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        cost: u64,
+    },
+    /// SRVs or fighters were restocked
+    RestockVehicle {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Vehicle type like "testbuggy" or "empire_fighter"
+        #[serde(rename = "Type")]
+        vehicle_type: String,
+        /// Variant like "starter" or "gelid"
+        #[serde(default)]
+        loadout: Option<String>,
+        cost: u64,
+        count: u32,
+    },
+    ScientificResearch {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    SearchAndRescue {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    SellDrones {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        count: u32,
+        sell_price: u64,
+        total_sale: u64,
+    },
+    /// Stored ship was sold to afford a rebuy
+    SellShipOnRebuy {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        ship_type: String,
+        ship_price: u64,
+    },
+    SetUserShipName {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    Shipyard {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    ShipyardBuy {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        ship_type: String,
+        #[serde(rename = "ShipType_Localised", default)]
+        ship_type_localised: Option<String>,
+        ship_price: u64,
+        /// Type of the old ship, if it was stored
+        #[serde(default)]
+        store_old_ship: Option<String>,
+        #[serde(rename = "StoreShipID", default)]
+        store_ship_id: Option<u32>,
+        /// Type of the old ship, if it was sold
+        #[serde(default)]
+        sell_old_ship: Option<String>,
+        #[serde(rename = "SellShipID", default)]
+        sell_ship_id: Option<u32>,
+        /// Price the old ship was sold for, if it was sold
+        #[serde(default)]
+        sell_price: Option<u64>,
+    },
+    /// Ship bought with [Event::ShipyardBuy] was taken out
+    ShipyardNew {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        ship_type: String,
+        #[serde(rename = "ShipType_Localised", default)]
+        ship_type_localised: Option<String>,
+        #[serde(rename = "NewShipID")]
+        new_ship_id: u32,
+    },
+    ShipyardSell {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        ship_type: String,
+        #[serde(rename = "ShipType_Localised", default)]
+        ship_type_localised: Option<String>,
+        #[serde(rename = "SellShipID")]
+        sell_ship_id: u32,
+        ship_price: u64,
+        /// System the ship was stored in, when sold remotely
+        #[serde(default)]
+        system: Option<String>,
+        /// Market the ship was stored at, when sold remotely
+        #[serde(rename = "ShipMarketID", default)]
+        ship_market_id: Option<u64>,
+    },
+    ShipyardTransfer {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Market the ship is sent to
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        ship_type: String,
+        #[serde(rename = "ShipType_Localised", default)]
+        ship_type_localised: Option<String>,
+        #[serde(rename = "ShipID")]
+        ship_id: u32,
+        /// System the ship is sent from
+        #[serde(default)]
+        system: Option<String>,
+        /// Market the ship is sent from
+        #[serde(rename = "ShipMarketID", default)]
+        ship_market_id: Option<u64>,
+        /// Light years between the two
+        #[serde(default)]
+        distance: Option<f64>,
+        transfer_price: u64,
+        /// Seconds until the ship arrives
+        #[serde(default)]
+        transfer_time: u64,
+    },
+    /// Switched to a ship stored at the same station
+    ShipyardSwap {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        ship_type: String,
+        #[serde(rename = "ShipType_Localised", default)]
+        ship_type_localised: Option<String>,
+        #[serde(rename = "ShipID")]
+        ship_id: u32,
+        /// Type of the old ship, if it was stored
+        #[serde(default)]
+        store_old_ship: Option<String>,
+        #[serde(rename = "StoreShipID", default)]
+        store_ship_id: Option<u32>,
+        /// Type of the old ship, if it was sold
+        #[serde(default)]
+        sell_old_ship: Option<String>,
+        #[serde(rename = "SellShipID", default)]
+        sell_ship_id: Option<u32>,
+    },
+    /// Modules in storage, written when opening outfitting
+    StoredModules {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Station the list was written at
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        #[serde(default)]
+        station_name: Option<String>,
+        #[serde(default)]
+        star_system: Option<String>,
+        #[serde(default)]
+        items: Vec<StoredModule>,
+    },
+    /// Ships in storage, written when opening the shipyard
+    StoredShips {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Station the list was written at, where the ships here are
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        #[serde(default)]
+        station_name: Option<String>,
+        #[serde(default)]
+        star_system: Option<String>,
+        #[serde(default)]
+        ships_here: Vec<StoredShip>,
+        #[serde(default)]
+        ships_remote: Vec<StoredShip>,
+    },
+    /// Items were unlocked at a Guardian or human technology broker
+    TechnologyBroker {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// "guardian" or "human"
+        broker_type: String,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        items_unlocked: Vec<UnlockedItem>,
+        #[serde(default)]
+        commodities: Vec<Material>,
+        #[serde(default)]
+        materials: Vec<Material>,
+    },
+    /// Impounded ship was recovered
+    ClearImpound {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        ship_type: String,
+        #[serde(rename = "ShipType_Localised", default)]
+        ship_type_localised: Option<String>,
+        #[serde(rename = "ShipID")]
+        ship_id: u32,
+        #[serde(rename = "ShipMarketID", default)]
+        ship_market_id: Option<u64>,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+    },
+
+    // [[Powerplay]]
+    PowerplayCollect {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    PowerplayDefect {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    PowerplayDeliver {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    PowerplayFastTrack {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    PowerplayJoin {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    PowerplayLeave {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Merits earned for the pledged power, new in Powerplay 2.0
+    PowerplayMerits {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        power: String,
+        merits_gained: u64,
+        total_merits: u64,
+    },
+    /// Rank with the pledged power went up, new in Powerplay 2.0
+    PowerplayRank {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        power: String,
+        rank: u32,
+    },
+    PowerplaySalary {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    PowerplayVote {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    PowerplayVoucher {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+
+    // [[Squadrons]]
+    AppliedToSquadron {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    DisbandedSquadron {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    InvitedToSquadron {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    JoinedSquadron {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    KickedFromSquadron {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    LeftSquadron {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    SharedBookmarkToSquadron {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    SquadronCreated {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    SquadronDemotion {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    SquadronPromotion {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    SquadronStartup {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    WonATrophyForSquadron {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+
+    // [[Fleet Carriers]]
+    CarrierJump {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierBuy {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "CarrierID")]
+        carrier_id: u64,
+        price: u64,
+    },
+    /// Carrier management screen was opened
+    CarrierStats {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "CarrierID")]
+        carrier_id: u64,
+        callsign: String,
+        #[serde(default)]
+        name: String,
+        finance: CarrierFinances,
+    },
+    /// Fleet carrier jump was scheduled
+    CarrierJumpRequest {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "CarrierID")]
+        carrier_id: u64,
+        system_name: String,
+        system_address: u64,
+        #[serde(default)]
+        body: Option<String>,
+        /// The carrier locks down ten minutes before departure
+        #[serde(with = "Rfc3339Option", default)]
+        departure_time: Option<OffsetDateTime>,
+    },
+    CarrierDecommission {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierCancelDecommission {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Credits moved between the commander and their carrier
+    CarrierBankTransfer {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "CarrierID")]
+        carrier_id: u64,
+        #[serde(default)]
+        deposit: Option<u64>,
+        #[serde(default)]
+        withdraw: Option<u64>,
+        player_balance: u64,
+        carrier_balance: u64,
+    },
+    CarrierDepositFuel {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierCrewServices {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierFinance {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierShipPack {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierModulePack {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierTradeOrder {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierDockingPermission {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierNameChanged {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CarrierJumpCancelled {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+
+    // [[New in Odyssey]]
+    /// Contents of the backpack, at startup or written to Backpack.json
+    Backpack {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Missing when the contents are only in Backpack.json
+        #[serde(default)]
+        items: Option<Vec<MicroResource>>,
+        #[serde(default)]
+        components: Option<Vec<MicroResource>>,
+        #[serde(default)]
+        consumables: Option<Vec<MicroResource>>,
+        #[serde(default)]
+        data: Option<Vec<MicroResource>>,
+    },
+    /// Items were added to or removed from the backpack
+    BackpackChange {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        added: Vec<MicroResource>,
+        #[serde(default)]
+        removed: Vec<MicroResource>,
+    },
+    BackpackMaterials {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    BookDropship {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    BookTaxi {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    BuyMicroResources {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    BuySuit {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Suit type and grade, e.g. "UtilitySuit_Class1"
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        price: u64,
+        #[serde(rename = "SuitID")]
+        suit_id: u64,
+    },
+    BuyWeapon {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        price: u64,
+        #[serde(rename = "SuitModuleID")]
+        suit_module_id: u64,
+        /// Grade from 1 to 5
+        class: u32,
+    },
+    CancelDropship {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CancelTaxi {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CollectItems {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CreateSuitLoadout {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    DeleteSuitLoadout {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Commander left the ship, SRV or taxi on foot
+    Disembark {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "SRV", default)]
+        srv: bool,
+        #[serde(default)]
+        taxi: bool,
+        #[serde(default)]
+        multicrew: bool,
+        star_system: String,
+        system_address: u64,
+        body: String,
+        #[serde(rename = "BodyID")]
+        body_id: u32,
+        #[serde(default)]
+        on_station: bool,
+        #[serde(default)]
+        on_planet: bool,
+        #[serde(default)]
+        station_name: Option<String>,
+    },
+    DropItems {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    DropShipDeploy {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Commander got back into the ship, SRV or taxi
+    Embark {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "SRV", default)]
+        srv: bool,
+        #[serde(default)]
+        taxi: bool,
+        #[serde(default)]
+        multicrew: bool,
+        star_system: String,
+        system_address: u64,
+        body: String,
+        #[serde(rename = "BodyID")]
+        body_id: u32,
+        #[serde(default)]
+        on_station: bool,
+        #[serde(default)]
+        on_planet: bool,
+        #[serde(default)]
+        station_name: Option<String>,
+    },
+    FCMaterials {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    LoadoutEquipModule {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    LoadoutRemoveModule {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    RenameSuitLoadout {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    Resupply {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Organic was scanned with the genetic sampler.
+    /// It takes three scans of the same species to complete a sample.
+    ScanOrganic {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        scan_type: OrganicScanType,
+        genus: String,
+        #[serde(rename = "Genus_Localised", default)]
+        genus_localised: Option<String>,
+        species: String,
+        #[serde(rename = "Species_Localised", default)]
+        species_localised: Option<String>,
+        #[serde(default)]
+        variant: Option<String>,
+        #[serde(rename = "Variant_Localised", default)]
+        variant_localised: Option<String>,
+        system_address: u64,
+        /// Body ID within the system
+        body: u32,
+    },
+    SellMicroResources {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Completed organic samples were sold at Vista Genomics
+    SellOrganicData {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        bio_data: Vec<OrganicSale>,
+    },
+    SellSuit {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        price: u64,
+        #[serde(rename = "SuitID")]
+        suit_id: u64,
+    },
+    SellWeapon {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        price: u64,
+        #[serde(rename = "SuitModuleID")]
+        suit_module_id: u64,
+    },
+    /// Contents of the ship locker, at startup or written to ShipLocker.json
+    ShipLocker {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Missing when the contents are only in ShipLocker.json
+        #[serde(default)]
+        items: Option<Vec<MicroResource>>,
+        #[serde(default)]
+        components: Option<Vec<MicroResource>>,
+        #[serde(default)]
+        consumables: Option<Vec<MicroResource>>,
+        #[serde(default)]
+        data: Option<Vec<MicroResource>>,
+    },
+    /// Suit and weapons of the loadout in use, at startup and when switching
+    SuitLoadout {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "SuitID")]
+        suit_id: u64,
+        /// Suit type and grade, e.g. "utilitysuit_class3"
+        suit_name: String,
+        #[serde(rename = "SuitName_Localised", default)]
+        suit_name_localised: Option<String>,
+        #[serde(rename = "LoadoutID")]
+        loadout_id: u64,
+        loadout_name: String,
+        #[serde(default)]
+        modules: Vec<SuitModule>,
+    },
+    SwitchSuitLoadout {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    TransferMicroResources {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    TradeMicroResources {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    UpgradeSuit {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        #[serde(rename = "SuitID")]
+        suit_id: u64,
+        /// Grade after the upgrade
+        class: u32,
+        cost: u64,
+        #[serde(default)]
+        resources: Vec<MicroResource>,
+    },
+    UpgradeWeapon {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        #[serde(rename = "SuitModuleID")]
+        suit_module_id: u64,
+        /// Grade after the upgrade
+        class: u32,
+        cost: u64,
+        #[serde(default)]
+        resources: Vec<MicroResource>,
+    },
+    UseConsumable {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+
+    // [[Colonisation]]
+    /// Architect placed the colonisation beacon in a claimed system
+    ColonisationBeaconDeployed {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Progress of a construction site, written when docking at its depot
+    ColonisationConstructionDepot {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        /// From 0 to 1
+        construction_progress: f64,
+        construction_complete: bool,
+        construction_failed: bool,
+        #[serde(default)]
+        resources_required: Vec<ConstructionResource>,
+    },
+    /// Commodities delivered to a construction site
+    ColonisationContribution {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(rename = "MarketID")]
+        market_id: u64,
+        contributions: Vec<ColonisationContribution>,
+    },
+    /// Commander claimed a system to colonise
+    ColonisationSystemClaim {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        star_system: String,
+        system_address: u64,
+    },
+    /// Commander gave up their claim
+    ColonisationSystemClaimRelease {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        star_system: String,
+        system_address: u64,
+    },
+
+    // [[Other Events]]
+    /// Auto field-maintenance unit finished repairing a module
+    AfmuRepairs {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Symbol like "$modularcargobaydoor_name;"
+        module: String,
+        #[serde(rename = "Module_Localised", default)]
+        module_localised: Option<String>,
+        fully_repaired: bool,
+        /// Module health from 0 to 1 after the repairs
+        health: f32,
+    },
+    /// Ship came close to a planetary settlement
+    ApproachSettlement {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+        #[serde(rename = "Name_Localised", default)]
+        name_localised: Option<String>,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+        system_address: u64,
+        #[serde(rename = "BodyID")]
+        body_id: u32,
+        body_name: String,
+        /// Position of the settlement, missing for some surface points of
+        /// interest
+        #[serde(default)]
+        latitude: Option<f64>,
+        #[serde(default)]
+        longitude: Option<f64>,
+    },
+    /// Commander changed their role while in someone else's crew
+    ChangeCrewRole {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Role in the form of "Idle", "FireCon", "FighterCon"
+        role: String,
+    },
+    /// Canopy broke and life support is on its reserve
+    CockpitBreached {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Commander committed a crime
+    CommitCrime {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Crime type in the form of "assault", "murder", "collidedAtSpeedInNoFireZone" etc.
+        crime_type: String,
+        /// Faction the crime was committed against
+        faction: String,
+        #[serde(default)]
+        victim: Option<String>,
+        #[serde(rename = "Victim_Localised", default)]
+        victim_localised: Option<String>,
+        #[serde(default)]
+        fine: Option<u64>,
+        #[serde(default)]
+        bounty: Option<u64>,
+    },
+    Continued {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Crew member launched a fighter
+    CrewLaunchFighter {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        crew: String,
+    },
+    /// Another player joined the commander's crew
+    CrewMemberJoins {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        crew: String,
+    },
+    CrewMemberQuits {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        crew: String,
+    },
+    CrewMemberRoleChange {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        crew: String,
+        role: String,
+    },
+    /// Commander was the victim of a crime
+    CrimeVictim {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        offender: String,
+        #[serde(rename = "Offender_Localised", default)]
+        offender_localised: Option<String>,
+        crime_type: String,
+        #[serde(default)]
+        fine: Option<u64>,
+        #[serde(default)]
+        bounty: Option<u64>,
+    },
+    /// Data link of a ship was scanned
+    DatalinkScan {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        message: String,
+        #[serde(rename = "Message_Localised", default)]
+        message_localised: Option<String>,
+    },
+    /// Voucher for scanning the data link of a ship
+    DatalinkVoucher {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        reward: u64,
+        victim_faction: String,
+        payee_faction: String,
+    },
+    /// Data point, listening post, wreck or the like was scanned
+    DataScanned {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Like "DataLink", "DataPoint", "ListeningPost" or "AbandonedDataLog"
+        r#type: String,
+        #[serde(rename = "Type_Localised", default)]
+        type_localised: Option<String>,
+    },
+    DockFighter {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    DockSRV {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Commander ended their multicrew session
+    EndCrewSession {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Session ended because of a crime committed by a crew member
+        #[serde(default)]
+        on_crime: bool,
+    },
+    FighterRebuilt {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    FuelScoop {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Status of a friend changed
+    Friends {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        status: FriendStatus,
+        name: String,
+    },
+    JetConeBoost {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    JetConeDamage {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Commander joined another player's crew
+    JoinACrew {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        captain: String,
+    },
+    KickCrewMember {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        crew: String,
+        #[serde(default)]
+        on_crime: bool,
+    },
+    /// Limpet was launched
+    LaunchDrone {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// "Prospector", "Collection", "Hatchbreaker", "FuelTransfer",
+        /// "Repair", "Research" or "Decontamination"
+        r#type: String,
+    },
+    LaunchFighter {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    LaunchSRV {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    ModuleInfo {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Background music changed, which tells what the game is showing
+    Music {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Like "Supercruise", "CQC" or "CQCMenu"
+        music_track: String,
+    },
+    /// Share of earnings paid to a hired crew member
+    NpcCrewPaidWage {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        npc_crew_name: String,
+        #[serde(rename = "NpcCrewId", default)]
+        npc_crew_id: Option<u64>,
+        amount: u64,
+    },
+    /// Combat rank of an NPC crew member went up
+    NpcCrewRank {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        npc_crew_name: String,
+        #[serde(rename = "NpcCrewId", default)]
+        npc_crew_id: Option<u64>,
+        rank_combat: u32,
+    },
+    /// Rank went up, only the rank that changed is present
+    Promotion {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        combat: Option<u32>,
+        #[serde(default)]
+        trade: Option<u32>,
+        #[serde(default)]
+        explore: Option<u32>,
+        #[serde(default)]
+        soldier: Option<u32>,
+        #[serde(default)]
+        exobiologist: Option<u32>,
+        #[serde(default)]
+        empire: Option<u32>,
+        #[serde(default)]
+        federation: Option<u32>,
+        #[serde(rename = "CQC", default)]
+        cqc: Option<u32>,
+    },
+    /// Prospector limpet reached an asteroid
+    ProspectedAsteroid {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        materials: Vec<ProspectedMaterial>,
+        /// Core material, only for motherlodes
+        #[serde(default)]
+        motherlode_material: Option<String>,
+        #[serde(rename = "MotherlodeMaterial_Localised", default)]
+        motherlode_material_localised: Option<String>,
+        /// "$AsteroidMaterialContent_High;", "..._Medium;" or "..._Low;"
+        content: String,
+        #[serde(rename = "Content_Localised", default)]
+        content_localised: Option<String>,
+        /// Percent of the asteroid left to mine
+        remaining: f64,
+    },
+    QuitACrew {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        captain: String,
+    },
+    /// Reboot and repair sequence ran
+    RebootRepair {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Slots of the modules repaired
+        #[serde(default)]
+        modules: Vec<String>,
+    },
     /// Text message was received from another player or npc
     ReceiveText {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         #[serde(rename = "From")]
         from: String,
@@ -463,34 +2757,441 @@ pub enum Event {
         #[serde(rename = "Channel")]
         channel: Channel,
     },
-    RepairDrone,
-    ReservoirReplenished,
-    Resurrect,
-    Scanned,
-    SelfDestruct,
+    /// Repair limpet restored hull or canopy, amounts from 0 to 1
+    RepairDrone {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        #[serde(default)]
+        hull_repaired: Option<f32>,
+        #[serde(default)]
+        cockpit_repaired: Option<f32>,
+        #[serde(default)]
+        corrosion_repaired: Option<f32>,
+    },
+    ReservoirReplenished {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Commander chose how to continue after dying
+    Resurrect {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// "rebuy", "recover" or "rejoin"
+        option: String,
+        cost: u64,
+        /// Couldn't afford the rebuy and took a loan
+        bankrupt: bool,
+    },
+    Scanned {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    SelfDestruct {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
     /// Text message was sent to another player
     SendText {
-        #[serde(with = "time::serde::iso8601", rename = "timestamp")]
+        #[serde(with = "Rfc3339", rename = "timestamp")]
         timestamp: OffsetDateTime,
         #[serde(rename = "To")]
         to: String,
         #[serde(rename = "Message")]
         message: String,
     },
-    Shutdown,
-    Synthesis,
-    SystemsShutdown,
-    USSDrop,
-    VehicleSwitch,
-    WingAdd,
-    WingInvite,
-    WingJoin,
-    WingLeave,
-    CargoTransfer,
-    SupercruiseDestinationDrop,
+    /// Game was shut down
+    Shutdown {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Materials were used to synthesise ammo, fuel, repairs and the like
+    Synthesis {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Recipe and grade, e.g. "FSD Basic"
+        name: String,
+        materials: Vec<Material>,
+    },
+    SystemsShutdown {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Ship dropped out of supercruise at an unidentified signal source
+    USSDrop {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Like "$USS_Type_Salvage;" or "$USS_Type_ValuableSalvage;"
+        #[serde(rename = "USSType")]
+        uss_type: String,
+        #[serde(rename = "USSType_Localised", default)]
+        uss_type_localised: Option<String>,
+        /// From 0 up, missing in older journals
+        #[serde(rename = "USSThreat", default)]
+        uss_threat: u32,
+    },
+    VehicleSwitch {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Another player joined the commander's wing
+    WingAdd {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+    },
+    /// Commander invited another player to their wing
+    WingInvite {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        name: String,
+    },
+    /// Commander joined a wing
+    WingJoin {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Players already in the wing
+        #[serde(default)]
+        others: Vec<String>,
+    },
+    /// Commander left their wing
+    WingLeave {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    CargoTransfer {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+    },
+    /// Ship dropped out of supercruise at a signal source or station
+    SupercruiseDestinationDrop {
+        #[serde(with = "Rfc3339", rename = "timestamp")]
+        timestamp: OffsetDateTime,
+        /// Like "$Warzone_PointRace_High:#index=1;" for a conflict zone
+        r#type: String,
+        #[serde(rename = "Type_Localised", default)]
+        type_localised: Option<String>,
+        #[serde(default)]
+        threat: Option<u32>,
+        #[serde(rename = "MarketID", default)]
+        market_id: Option<u64>,
+    },
+
+    /// Any event this crate doesn't know about,
+    /// e.g. ones retired from or newly added to the game
+    #[serde(skip)]
+    Unknown {
+        timestamp: OffsetDateTime,
+        /// Kind of event, e.g. "FSDJump"
+        event: String,
+    },
 }
 
-#[derive(Debug, Deserialize)]
+// Names of the variants of Event, sorted, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/event_names.rs"));
+
+/// Kinds of events that aren't variants of [Event] become
+/// [Event::Unknown { .. }], keeping their kind and time
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let header = EventHeader::deserialize(&value).map_err(de::Error::custom)?;
+
+        if EVENT_NAMES.binary_search(&header.event.as_str()).is_err() {
+            return Ok(Event::Unknown {
+                timestamp: header.timestamp,
+                event: header.event,
+            });
+        }
+        Event::deserialize(value).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Event::Unknown { timestamp, event } => EventHeader {
+                timestamp: *timestamp,
+                event: event.clone(),
+            }
+            .serialize(serializer),
+            event => Event::serialize(event, serializer),
+        }
+    }
+}
+
+impl Event {
+    /// Time the event was written
+    pub fn timestamp(&self) -> OffsetDateTime {
+        match self {
+            Event::Fileheader { timestamp, .. }
+            | Event::Cargo { timestamp, .. }
+            | Event::ClearSavedGame { timestamp, .. }
+            | Event::Commander { timestamp, .. }
+            | Event::Loadout { timestamp, .. }
+            | Event::Materials { timestamp, .. }
+            | Event::Missions { timestamp, .. }
+            | Event::NewCommander { timestamp, .. }
+            | Event::LoadGame { timestamp, .. }
+            | Event::Passengers { timestamp, .. }
+            | Event::Powerplay { timestamp, .. }
+            | Event::Progress { timestamp, .. }
+            | Event::Rank { timestamp, .. }
+            | Event::Reputation { timestamp, .. }
+            | Event::Statistics { timestamp, .. }
+            | Event::ApproachBody { timestamp, .. }
+            | Event::Docked { timestamp, .. }
+            | Event::DockingCancelled { timestamp, .. }
+            | Event::DockingDenied { timestamp, .. }
+            | Event::DockingGranted { timestamp, .. }
+            | Event::DockingRequested { timestamp, .. }
+            | Event::DockingTimeout { timestamp, .. }
+            | Event::FSDJump { timestamp, .. }
+            | Event::FSDTarget { timestamp, .. }
+            | Event::LeaveBody { timestamp, .. }
+            | Event::Liftoff { timestamp, .. }
+            | Event::Location { timestamp, .. }
+            | Event::StartJump { timestamp, .. }
+            | Event::SupercruiseEntry { timestamp, .. }
+            | Event::SupercruiseExit { timestamp, .. }
+            | Event::Touchdown { timestamp, .. }
+            | Event::Undocked { timestamp, .. }
+            | Event::NavRoute { timestamp, .. }
+            | Event::NavRouteClear { timestamp, .. }
+            | Event::Bounty { timestamp, .. }
+            | Event::CapShipBond { timestamp, .. }
+            | Event::Died { timestamp, .. }
+            | Event::EscapeInterdiction { timestamp, .. }
+            | Event::FactionKillBond { timestamp, .. }
+            | Event::FighterDestroyed { timestamp, .. }
+            | Event::HeatDamage { timestamp, .. }
+            | Event::HeatWarning { timestamp, .. }
+            | Event::HullDamage { timestamp, .. }
+            | Event::Interdicted { timestamp, .. }
+            | Event::Interdiction { timestamp, .. }
+            | Event::PVPKill { timestamp, .. }
+            | Event::ShieldState { timestamp, .. }
+            | Event::ShipTargeted { timestamp, .. }
+            | Event::SRVDestroyed { timestamp, .. }
+            | Event::UnderAttack { timestamp, .. }
+            | Event::CodexEntry { timestamp, .. }
+            | Event::DiscoveryScan { timestamp, .. }
+            | Event::Scan { timestamp, .. }
+            | Event::FSSAllBodiesFound { timestamp, .. }
+            | Event::FSSBodySignals { timestamp, .. }
+            | Event::FSSDiscoveryScan { timestamp, .. }
+            | Event::FSSSignalDiscovered { timestamp, .. }
+            | Event::MaterialCollected { timestamp, .. }
+            | Event::MaterialDiscarded { timestamp, .. }
+            | Event::MaterialDiscovered { timestamp, .. }
+            | Event::MultiSellExplorationData { timestamp, .. }
+            | Event::NavBeaconScan { timestamp, .. }
+            | Event::BuyExplorationData { timestamp, .. }
+            | Event::SAAScanComplete { timestamp, .. }
+            | Event::SAASignalsFound { timestamp, .. }
+            | Event::ScanBaryCentre { timestamp, .. }
+            | Event::SellExplorationData { timestamp, .. }
+            | Event::Screenshot { timestamp, .. }
+            | Event::AsteroidCracked { timestamp, .. }
+            | Event::BuyTradeData { timestamp, .. }
+            | Event::CollectCargo { timestamp, .. }
+            | Event::EjectCargo { timestamp, .. }
+            | Event::MarketBuy { timestamp, .. }
+            | Event::MarketSell { timestamp, .. }
+            | Event::MiningRefined { timestamp, .. }
+            | Event::BuyAmmo { timestamp, .. }
+            | Event::BuyDrones { timestamp, .. }
+            | Event::CargoDepot { timestamp, .. }
+            | Event::CommunityGoal { timestamp, .. }
+            | Event::CommunityGoalDiscard { timestamp, .. }
+            | Event::CommunityGoalJoin { timestamp, .. }
+            | Event::CommunityGoalReward { timestamp, .. }
+            | Event::CrewAssign { timestamp, .. }
+            | Event::CrewFire { timestamp, .. }
+            | Event::CrewHire { timestamp, .. }
+            | Event::EngineerApply { timestamp, .. }
+            | Event::EngineerContribution { timestamp, .. }
+            | Event::EngineerCraft { timestamp, .. }
+            | Event::EngineerLegacyConvert { timestamp, .. }
+            | Event::EngineerProgress { timestamp, .. }
+            | Event::FetchRemoteModule { timestamp, .. }
+            | Event::Market { timestamp, .. }
+            | Event::MassModuleStore { timestamp, .. }
+            | Event::MaterialTrade { timestamp, .. }
+            | Event::MissionAbandoned { timestamp, .. }
+            | Event::MissionAccepted { timestamp, .. }
+            | Event::MissionCompleted { timestamp, .. }
+            | Event::MissionFailed { timestamp, .. }
+            | Event::MissionRedirected { timestamp, .. }
+            | Event::ModuleBuy { timestamp, .. }
+            | Event::ModuleRetrieve { timestamp, .. }
+            | Event::ModuleSell { timestamp, .. }
+            | Event::ModuleSellRemote { timestamp, .. }
+            | Event::ModuleStore { timestamp, .. }
+            | Event::ModuleSwap { timestamp, .. }
+            | Event::Outfitting { timestamp, .. }
+            | Event::PayBounties { timestamp, .. }
+            | Event::PayFines { timestamp, .. }
+            | Event::PayLegacyFines { timestamp, .. }
+            | Event::RedeemVoucher { timestamp, .. }
+            | Event::RefuelAll { timestamp, .. }
+            | Event::RefuelPartial { timestamp, .. }
+            | Event::Repair { timestamp, .. }
+            | Event::RepairAll { timestamp, .. }
+            | Event::RestockVehicle { timestamp, .. }
+            | Event::ScientificResearch { timestamp, .. }
+            | Event::SearchAndRescue { timestamp, .. }
+            | Event::SellDrones { timestamp, .. }
+            | Event::SellShipOnRebuy { timestamp, .. }
+            | Event::SetUserShipName { timestamp, .. }
+            | Event::Shipyard { timestamp, .. }
+            | Event::ShipyardBuy { timestamp, .. }
+            | Event::ShipyardNew { timestamp, .. }
+            | Event::ShipyardSell { timestamp, .. }
+            | Event::ShipyardTransfer { timestamp, .. }
+            | Event::ShipyardSwap { timestamp, .. }
+            | Event::StoredModules { timestamp, .. }
+            | Event::StoredShips { timestamp, .. }
+            | Event::TechnologyBroker { timestamp, .. }
+            | Event::ClearImpound { timestamp, .. }
+            | Event::PowerplayCollect { timestamp, .. }
+            | Event::PowerplayDefect { timestamp, .. }
+            | Event::PowerplayDeliver { timestamp, .. }
+            | Event::PowerplayFastTrack { timestamp, .. }
+            | Event::PowerplayJoin { timestamp, .. }
+            | Event::PowerplayLeave { timestamp, .. }
+            | Event::PowerplayMerits { timestamp, .. }
+            | Event::PowerplayRank { timestamp, .. }
+            | Event::PowerplaySalary { timestamp, .. }
+            | Event::PowerplayVote { timestamp, .. }
+            | Event::PowerplayVoucher { timestamp, .. }
+            | Event::AppliedToSquadron { timestamp, .. }
+            | Event::DisbandedSquadron { timestamp, .. }
+            | Event::InvitedToSquadron { timestamp, .. }
+            | Event::JoinedSquadron { timestamp, .. }
+            | Event::KickedFromSquadron { timestamp, .. }
+            | Event::LeftSquadron { timestamp, .. }
+            | Event::SharedBookmarkToSquadron { timestamp, .. }
+            | Event::SquadronCreated { timestamp, .. }
+            | Event::SquadronDemotion { timestamp, .. }
+            | Event::SquadronPromotion { timestamp, .. }
+            | Event::SquadronStartup { timestamp, .. }
+            | Event::WonATrophyForSquadron { timestamp, .. }
+            | Event::CarrierJump { timestamp, .. }
+            | Event::CarrierBuy { timestamp, .. }
+            | Event::CarrierStats { timestamp, .. }
+            | Event::CarrierJumpRequest { timestamp, .. }
+            | Event::CarrierDecommission { timestamp, .. }
+            | Event::CarrierCancelDecommission { timestamp, .. }
+            | Event::CarrierBankTransfer { timestamp, .. }
+            | Event::CarrierDepositFuel { timestamp, .. }
+            | Event::CarrierCrewServices { timestamp, .. }
+            | Event::CarrierFinance { timestamp, .. }
+            | Event::CarrierShipPack { timestamp, .. }
+            | Event::CarrierModulePack { timestamp, .. }
+            | Event::CarrierTradeOrder { timestamp, .. }
+            | Event::CarrierDockingPermission { timestamp, .. }
+            | Event::CarrierNameChanged { timestamp, .. }
+            | Event::CarrierJumpCancelled { timestamp, .. }
+            | Event::Backpack { timestamp, .. }
+            | Event::BackpackChange { timestamp, .. }
+            | Event::BackpackMaterials { timestamp, .. }
+            | Event::BookDropship { timestamp, .. }
+            | Event::BookTaxi { timestamp, .. }
+            | Event::BuyMicroResources { timestamp, .. }
+            | Event::BuySuit { timestamp, .. }
+            | Event::BuyWeapon { timestamp, .. }
+            | Event::CancelDropship { timestamp, .. }
+            | Event::CancelTaxi { timestamp, .. }
+            | Event::CollectItems { timestamp, .. }
+            | Event::CreateSuitLoadout { timestamp, .. }
+            | Event::DeleteSuitLoadout { timestamp, .. }
+            | Event::Disembark { timestamp, .. }
+            | Event::DropItems { timestamp, .. }
+            | Event::DropShipDeploy { timestamp, .. }
+            | Event::Embark { timestamp, .. }
+            | Event::FCMaterials { timestamp, .. }
+            | Event::LoadoutEquipModule { timestamp, .. }
+            | Event::LoadoutRemoveModule { timestamp, .. }
+            | Event::RenameSuitLoadout { timestamp, .. }
+            | Event::Resupply { timestamp, .. }
+            | Event::ScanOrganic { timestamp, .. }
+            | Event::SellMicroResources { timestamp, .. }
+            | Event::SellOrganicData { timestamp, .. }
+            | Event::SellSuit { timestamp, .. }
+            | Event::SellWeapon { timestamp, .. }
+            | Event::ShipLocker { timestamp, .. }
+            | Event::SuitLoadout { timestamp, .. }
+            | Event::SwitchSuitLoadout { timestamp, .. }
+            | Event::TransferMicroResources { timestamp, .. }
+            | Event::TradeMicroResources { timestamp, .. }
+            | Event::UpgradeSuit { timestamp, .. }
+            | Event::UpgradeWeapon { timestamp, .. }
+            | Event::UseConsumable { timestamp, .. }
+            | Event::ColonisationBeaconDeployed { timestamp, .. }
+            | Event::ColonisationConstructionDepot { timestamp, .. }
+            | Event::ColonisationContribution { timestamp, .. }
+            | Event::ColonisationSystemClaim { timestamp, .. }
+            | Event::ColonisationSystemClaimRelease { timestamp, .. }
+            | Event::AfmuRepairs { timestamp, .. }
+            | Event::ApproachSettlement { timestamp, .. }
+            | Event::ChangeCrewRole { timestamp, .. }
+            | Event::CockpitBreached { timestamp, .. }
+            | Event::CommitCrime { timestamp, .. }
+            | Event::Continued { timestamp, .. }
+            | Event::CrewLaunchFighter { timestamp, .. }
+            | Event::CrewMemberJoins { timestamp, .. }
+            | Event::CrewMemberQuits { timestamp, .. }
+            | Event::CrewMemberRoleChange { timestamp, .. }
+            | Event::CrimeVictim { timestamp, .. }
+            | Event::DatalinkScan { timestamp, .. }
+            | Event::DatalinkVoucher { timestamp, .. }
+            | Event::DataScanned { timestamp, .. }
+            | Event::DockFighter { timestamp, .. }
+            | Event::DockSRV { timestamp, .. }
+            | Event::EndCrewSession { timestamp, .. }
+            | Event::FighterRebuilt { timestamp, .. }
+            | Event::FuelScoop { timestamp, .. }
+            | Event::Friends { timestamp, .. }
+            | Event::JetConeBoost { timestamp, .. }
+            | Event::JetConeDamage { timestamp, .. }
+            | Event::JoinACrew { timestamp, .. }
+            | Event::KickCrewMember { timestamp, .. }
+            | Event::LaunchDrone { timestamp, .. }
+            | Event::LaunchFighter { timestamp, .. }
+            | Event::LaunchSRV { timestamp, .. }
+            | Event::ModuleInfo { timestamp, .. }
+            | Event::Music { timestamp, .. }
+            | Event::NpcCrewPaidWage { timestamp, .. }
+            | Event::NpcCrewRank { timestamp, .. }
+            | Event::Promotion { timestamp, .. }
+            | Event::ProspectedAsteroid { timestamp, .. }
+            | Event::QuitACrew { timestamp, .. }
+            | Event::RebootRepair { timestamp, .. }
+            | Event::ReceiveText { timestamp, .. }
+            | Event::RepairDrone { timestamp, .. }
+            | Event::ReservoirReplenished { timestamp, .. }
+            | Event::Resurrect { timestamp, .. }
+            | Event::Scanned { timestamp, .. }
+            | Event::SelfDestruct { timestamp, .. }
+            | Event::SendText { timestamp, .. }
+            | Event::Shutdown { timestamp, .. }
+            | Event::Synthesis { timestamp, .. }
+            | Event::SystemsShutdown { timestamp, .. }
+            | Event::USSDrop { timestamp, .. }
+            | Event::VehicleSwitch { timestamp, .. }
+            | Event::WingAdd { timestamp, .. }
+            | Event::WingInvite { timestamp, .. }
+            | Event::WingJoin { timestamp, .. }
+            | Event::WingLeave { timestamp, .. }
+            | Event::CargoTransfer { timestamp, .. }
+            | Event::SupercruiseDestinationDrop { timestamp, .. }
+            | Event::Unknown { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Channel {
     Wing,
@@ -503,13 +3204,195 @@ pub enum Channel {
     StarSystem,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum VoucherType {
+    #[serde(rename = "bounty")]
+    Bounty,
+    CombatBond,
+    #[serde(rename = "trade")]
+    Trade,
+    /// Handed out for on-foot work at settlements
+    #[serde(rename = "settlement")]
+    Settlement,
+    /// Data sold from scanned data points
+    #[serde(rename = "scannable")]
+    Scannable,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct VoucherFaction {
+    /// Empty for the bounties of the Pilots' Federation
+    pub faction: String,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum JumpType {
+    /// To another system
+    Hyperspace,
+    /// Into supercruise within the system
+    Supercruise,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum FriendStatus {
+    Requested,
+    Declined,
+    Added,
+    Lost,
+    Offline,
+    Online,
+    /// Added in a later version of the game
+    #[serde(other)]
+    Other,
+}
+
+/// Minor faction in a system, from FSDJump and Location
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct SystemFactionState {
+    pub name: String,
+    /// Like "None", "War" or "Boom"
+    pub faction_state: String,
+    pub government: String,
+    /// Share of influence in the system from 0 to 1
+    pub influence: f64,
+    pub allegiance: String,
+    #[serde(default)]
+    pub happiness: Option<String>,
+    #[serde(rename = "Happiness_Localised", default)]
+    pub happiness_localised: Option<String>,
+    /// Commander's reputation from -100 to 100, missing before 3.3
+    #[serde(default)]
+    pub my_reputation: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct ControllingFaction {
+    pub name: String,
+    #[serde(default)]
+    pub faction_state: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct CommodityReward {
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct MaterialReward {
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    /// "$MICRORESOURCE_CATEGORY_Encoded;" and the like, "Raw" in older
+    /// journals
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(rename = "Category_Localised", default)]
+    pub category_localised: Option<String>,
+    pub count: u32,
+}
+
+/// What a completed mission did for one faction
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct FactionEffect {
+    /// Empty for effects on no faction in particular
+    pub faction: String,
+    #[serde(default)]
+    pub effects: Vec<MissionEffect>,
+    #[serde(default)]
+    pub influence: Vec<InfluenceEffect>,
+    /// "UpGood", "DownBad" and the like
+    #[serde(default)]
+    pub reputation_trend: Option<String>,
+    /// One to five pluses, or empty for none
+    #[serde(default)]
+    pub reputation: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct MissionEffect {
+    pub effect: String,
+    #[serde(rename = "Effect_Localised", default)]
+    pub effect_localised: Option<String>,
+    pub trend: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct InfluenceEffect {
+    pub system_address: u64,
+    /// "UpGood", "DownBad" and the like
+    pub trend: String,
+    /// One to five pluses, or empty for none
+    pub influence: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct TradedMaterial {
+    pub material: String,
+    #[serde(rename = "Material_Localised", default)]
+    pub material_localised: Option<String>,
+    /// "Raw", "Manufactured" or "Encoded", like
+    /// "$MICRORESOURCE_CATEGORY_Encoded;" in older journals
+    pub category: String,
+    #[serde(rename = "Category_Localised", default)]
+    pub category_localised: Option<String>,
+    pub quantity: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct EngineerState {
+    pub engineer: String,
+    #[serde(rename = "EngineerID")]
+    pub engineer_id: u64,
+    pub progress: String,
+    /// Grade unlocked, once the engineer is unlocked
+    #[serde(default)]
+    pub rank: Option<u32>,
+    /// Percent towards the next grade
+    #[serde(default)]
+    pub rank_progress: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Vessel {
+    #[default]
     Ship,
     SRV,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct CargoEntry {
     pub name: String,
@@ -519,14 +3402,208 @@ pub struct CargoEntry {
     pub mission_id: Option<u32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct ConstructionResource {
+    /// Symbol like "$steel_name;"
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    pub required_amount: u32,
+    pub provided_amount: u32,
+    /// Credits paid per ton delivered
+    pub payment: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct ColonisationContribution {
+    /// Symbol like "$steel_name;"
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct MassStoredModule {
+    pub slot: String,
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    #[serde(default)]
+    pub hot: bool,
+    /// Blueprint the module was engineered with
+    #[serde(default)]
+    pub engineer_modifications: Option<String>,
+    #[serde(default)]
+    pub level: Option<u32>,
+    #[serde(default)]
+    pub quality: Option<f32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct StoredModule {
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    pub storage_slot: u32,
+    /// Missing while the module is in transit
+    #[serde(default)]
+    pub star_system: Option<String>,
+    #[serde(rename = "MarketID", default)]
+    pub market_id: Option<u64>,
+    /// Credits to have it sent to the station the list was written at
+    #[serde(default)]
+    pub transfer_cost: Option<u64>,
+    /// Seconds it would take to arrive
+    #[serde(default)]
+    pub transfer_time: Option<u64>,
+    pub buy_price: u64,
+    #[serde(default)]
+    pub hot: bool,
+    #[serde(default)]
+    pub in_transit: bool,
+    /// Blueprint the module was engineered with
+    #[serde(default)]
+    pub engineer_modifications: Option<String>,
+    #[serde(default)]
+    pub level: Option<u32>,
+    #[serde(default)]
+    pub quality: Option<f32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct SuitModule {
+    pub slot_name: String,
+    #[serde(rename = "SuitModuleID")]
+    pub suit_module_id: u64,
+    pub module_name: String,
+    #[serde(rename = "ModuleName_Localised", default)]
+    pub module_name_localised: Option<String>,
+    /// Grade from 1 to 5
+    pub class: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct UnlockedItem {
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct StoredShip {
+    #[serde(rename = "ShipID")]
+    pub ship_id: u32,
+    pub ship_type: String,
+    #[serde(rename = "ShipType_Localised", default)]
+    pub ship_type_localised: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub value: u64,
+    #[serde(default)]
+    pub hot: bool,
+    /// Only for ships stored elsewhere, missing while in transit
+    #[serde(default)]
+    pub star_system: Option<String>,
+    /// Market of the station the ship is stored at, only for ships
+    /// stored elsewhere
+    #[serde(rename = "ShipMarketID", default)]
+    pub ship_market_id: Option<u64>,
+    /// Credits to have it sent to the station the list was written at
+    #[serde(default)]
+    pub transfer_price: Option<u64>,
+    /// Seconds it would take to arrive
+    #[serde(default)]
+    pub transfer_time: Option<u64>,
+    #[serde(default)]
+    pub in_transit: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct CarrierFinances {
+    pub carrier_balance: u64,
+    pub reserve_balance: u64,
+    pub available_balance: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct MarketItem {
+    #[serde(rename = "id")]
+    pub id: u64,
+    /// Symbol like "$tritium_name;"
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    pub category: String,
+    /// Price the market sells for
+    pub buy_price: u64,
+    /// Price the market buys for
+    pub sell_price: u64,
+    pub mean_price: u64,
+    pub stock: u64,
+    pub demand: u64,
+    #[serde(default)]
+    pub rare: bool,
+}
+
+impl MarketItem {
+    /// Name without the symbol decoration, e.g. "tritium"
+    pub fn symbol(&self) -> String {
+        self.name
+            .trim_start_matches('$')
+            .trim_end_matches("_name;")
+            .to_lowercase()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct ProspectedMaterial {
+    /// Commodity like "platinum"
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    /// Percent of the asteroid
+    pub proportion: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct BountyReward {
+    pub faction: String,
+    pub reward: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct FuelCapacity {
     pub main: f32,
     pub reserve: f32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct Module {
     pub slot: String,
@@ -534,6 +3611,7 @@ pub struct Module {
     pub on: bool,
     /// Power priority
     pub priority: u32,
+    #[serde(default = "full_health")]
     pub health: f32,
     #[serde(default)]
     pub value: u32,
@@ -546,16 +3624,17 @@ pub struct Module {
     pub engineering: Option<Engineering>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct Engineering {
-    #[serde(rename = "EngineerID")]
+    #[serde(rename = "EngineerID", default)]
     pub engineer_id: u32,
     /// Engineers name
     /// TODO: find out why this can be empty
     #[serde(default)]
     pub engineer: String,
-    #[serde(rename = "BlueprintID")]
+    #[serde(rename = "BlueprintID", default)]
     pub blueprint_id: u32,
     pub blueprint_name: String,
     pub level: u32,
@@ -565,25 +3644,48 @@ pub struct Engineering {
     pub modifiers: Vec<EngineeringModifiers>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct EngineeringModifiers {
     pub label: String,
     #[serde(default)]
     pub value: Option<f32>,
-    pub original_value: f32,
-    #[serde(deserialize_with = "bool_from_int")]
+    /// Text value for modifiers that aren't numeric
+    #[serde(default)]
+    pub value_str: Option<String>,
+    /// Missing for modifiers that aren't numeric
+    #[serde(default)]
+    pub original_value: Option<f32>,
+    #[serde(deserialize_with = "bool_from_int", default)]
+    #[cfg_attr(feature = "schema", schemars(with = "u8"))]
     pub less_is_good: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct Material {
     pub name: String,
     pub count: u32,
 }
 
-#[derive(Debug, Deserialize)]
+/// Item, component, consumable or data carried on foot
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct MicroResource {
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+    pub count: u32,
+    /// "Item", "Component", "Consumable" or "Data", only in BackpackChange
+    #[serde(default)]
+    pub r#type: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct Mission {
     #[serde(rename = "MissionID")]
@@ -594,7 +3696,46 @@ pub struct Mission {
     pub expires: u32,
 }
 
-#[derive(Debug, Deserialize)]
+/// Where a ship touched down or lifted off, from Touchdown and Liftoff
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct Landing {
+    /// False when the ship was dismissed or recalled without the commander
+    /// on board
+    #[serde(default = "default_true")]
+    pub player_controlled: bool,
+    #[serde(default)]
+    pub taxi: bool,
+    #[serde(default)]
+    pub multicrew: bool,
+    /// Missing before 3.3
+    #[serde(default)]
+    pub star_system: Option<String>,
+    #[serde(default)]
+    pub system_address: Option<u64>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(rename = "BodyID", default)]
+    pub body_id: Option<u32>,
+    #[serde(default)]
+    pub on_station: bool,
+    #[serde(default)]
+    pub on_planet: bool,
+    /// Missing when not player controlled
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    /// Symbol of the closest settlement or point of interest
+    #[serde(default)]
+    pub nearest_destination: Option<String>,
+    #[serde(rename = "NearestDestination_Localised", default)]
+    pub nearest_destination_localised: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct PassengerRecord {
     #[serde(rename = "MissionID")]
@@ -607,7 +3748,159 @@ pub struct PassengerRecord {
     pub count: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct CommunityGoalStatus {
+    #[serde(rename = "CGID")]
+    pub cgid: u32,
+    pub title: String,
+    pub system_name: String,
+    pub market_name: String,
+    #[serde(with = "Rfc3339")]
+    pub expiry: OffsetDateTime,
+    pub is_complete: bool,
+    pub current_total: u64,
+    pub player_contribution: u64,
+    pub num_contributors: u32,
+    /// Percentage band the commander is in, e.g. 10 for the top 10%
+    pub player_percentile_band: u32,
+    #[serde(default)]
+    pub top_tier: Option<CommunityGoalTier>,
+    /// Tier in the form of "Tier 3"
+    #[serde(default)]
+    pub tier_reached: Option<String>,
+    #[serde(default)]
+    pub player_in_top_rank: Option<bool>,
+    #[serde(default)]
+    pub bonus: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct CommunityGoalTier {
+    pub name: String,
+    pub bonus: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct Killer {
+    pub name: String,
+    pub ship: String,
+    pub rank: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct RouteEntry {
+    pub star_system: String,
+    pub system_address: u64,
+    pub star_pos: [f64; 3],
+    /// Spectral class of the main star, e.g. "K" or "DA"
+    pub star_class: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct Signal {
+    /// Signal type in the form of "$SAA_SignalType_Biological;"
+    #[serde(rename = "Type")]
+    pub signal_type: String,
+    #[serde(rename = "Type_Localised", default)]
+    pub type_localised: Option<String>,
+    pub count: u32,
+}
+
+/// Kind of a body signal, told apart by its type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalKind {
+    Biological,
+    Geological,
+    /// Settlements and other signs of human activity
+    Human,
+    Thargoid,
+    Guardian,
+    /// Rings' hotspots, named after the mineral like "Platinum"
+    Other,
+}
+
+impl Signal {
+    pub fn kind(&self) -> SignalKind {
+        match self.signal_type.as_str() {
+            "$SAA_SignalType_Biological;" => SignalKind::Biological,
+            "$SAA_SignalType_Geological;" => SignalKind::Geological,
+            "$SAA_SignalType_Human;" => SignalKind::Human,
+            "$SAA_SignalType_Thargoid;" => SignalKind::Thargoid,
+            "$SAA_SignalType_Guardian;" => SignalKind::Guardian,
+            _ => SignalKind::Other,
+        }
+    }
+}
+
+/// Number of signals of one kind in a list of signals
+pub fn signal_count(signals: &[Signal], kind: SignalKind) -> u32 {
+    signals
+        .iter()
+        .filter(|s| s.kind() == kind)
+        .map(|s| s.count)
+        .sum()
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct Genus {
+    /// Genus in the form of "$Codex_Ent_Bacterial_Genus_Name;"
+    pub genus: String,
+    #[serde(rename = "Genus_Localised", default)]
+    pub genus_localised: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum OrganicScanType {
+    /// First scan of a species
+    Log,
+    /// Second scan of a species
+    Sample,
+    /// Third and final scan, the sample is now complete
+    Analyse,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct DiscoveredSystem {
+    pub system_name: String,
+    pub num_bodies: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct OrganicSale {
+    pub genus: String,
+    #[serde(rename = "Genus_Localised", default)]
+    pub genus_localised: Option<String>,
+    pub species: String,
+    #[serde(rename = "Species_Localised", default)]
+    pub species_localised: Option<String>,
+    #[serde(default)]
+    pub variant: Option<String>,
+    #[serde(rename = "Variant_Localised", default)]
+    pub variant_localised: Option<String>,
+    pub value: u64,
+    /// First logged bonus
+    pub bonus: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BankAccountStatistics {
     #[serde(rename = "Current_Wealth")]
     pub current_wealth: u64,
@@ -643,54 +3936,85 @@ pub struct BankAccountStatistics {
     pub premium_stock_bought: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct CombatStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct CrimeStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct SmugglingStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct TradingStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct MiningStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct ExplorationStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct PassengersStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct SearchAndRescueStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct CraftingStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct CrewStatistics {}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 pub struct MulticrewStatistics {}
 
+/// Close Quarters Combat totals, kept apart from the rest of the game
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CqcStatistics {
+    #[serde(rename = "CQC_Credits_Earned", default)]
+    pub credits_earned: u64,
+    /// Seconds spent in matches
+    #[serde(rename = "CQC_Time_Played", default)]
+    pub time_played: u64,
+    /// Kill to death ratio
+    #[serde(rename = "CQC_KD", default)]
+    pub kd: f64,
+    #[serde(rename = "CQC_Kills", default)]
+    pub kills: u64,
+    /// Win to loss ratio
+    #[serde(rename = "CQC_WL", default)]
+    pub wl: f64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde::Deserialize;
+    use time::macros::datetime;
 
     #[derive(Debug, Deserialize)]
     struct BoolFromInt {
@@ -716,4 +4040,721 @@ mod tests {
         let res_false: Result<BoolFromInt, serde_json::Error> = serde_json::from_str(data_false);
         assert!(res_false.is_ok_and(|bfi| !bfi.val));
     }
+
+    #[test]
+    fn test_legacy_journal_lines() {
+        let lines = [
+            r#"{ "timestamp":"2016-06-10T14:32:03Z", "event":"Fileheader", "part":1, "language":"English\\UK", "gameversion":"2.2", "build":"r113684 " }"#,
+            r#"{ "timestamp":"2016-06-10T14:35:00Z", "event":"LoadGame", "Commander":"HRC1", "Ship":"CobraMkIII", "ShipID":1, "GameMode":"Open", "Credits":600120, "Loan":0 }"#,
+            r#"{ "timestamp":"2016-06-10T14:35:00Z", "event":"Rank", "Combat":0, "Trade":2, "Explore":1, "Empire":0, "Federation":0, "CQC":0 }"#,
+            r#"{ "timestamp":"2016-06-10T14:36:10Z", "event":"FSDJump", "StarSystem":"Shinrarta Dezhra", "StarPos":[55.719,17.594,27.156], "JumpDist":5.230, "FuelUsed":0.355, "FuelLevel":15.645 }"#,
+            r#"{ "timestamp":"2017-04-12T18:35:00Z", "event":"Loadout", "Ship":"CobraMkIII", "ShipID":1, "ShipName":"", "ShipIdent":"", "FuelCapacity":16.0, "Modules":[ { "Slot":"MediumHardpoint1", "Item":"Hpt_PulseLaser_Gimbal_Medium", "On":true, "Priority":0, "Value":35400 } ] }"#,
+            r#"{ "timestamp":"2017-10-12T12:00:00Z", "event":"Cargo", "Inventory":[ { "Name":"tritium", "Count":10, "Stolen":0 } ] }"#,
+            r#"{ "timestamp":"2017-10-12T12:00:00Z", "event":"Commander", "Name":"HRC1" }"#,
+            r#"{ "timestamp":"2016-06-10T14:40:00Z", "event":"EngineerApply", "Engineer":"Felicity Farseer", "Blueprint":"FSD_LongRange", "Level":1 }"#,
+        ];
+
+        for line in lines {
+            let event: Result<Event, serde_json::Error> = serde_json::from_str(line);
+            assert!(event.is_ok(), "{line}: {event:?}");
+        }
+
+        let retired: Event =
+            serde_json::from_str(r#"{ "timestamp":"2016-06-10T14:40:00Z", "event":"Voucher" }"#)
+                .unwrap();
+        assert!(matches!(&retired, Event::Unknown { event, .. } if event == "Voucher"));
+        assert_eq!(retired.timestamp(), datetime!(2016-06-10 14:40 UTC));
+        assert_eq!(
+            serde_json::to_string(&retired).unwrap(),
+            r#"{"timestamp":"2016-06-10T14:40:00Z","event":"Voucher"}"#
+        );
+
+        // A known event that doesn't parse is still an error
+        let broken: Result<Event, _> =
+            serde_json::from_str(r#"{ "timestamp":"2016-06-10T14:40:00Z", "event":"FSDJump" }"#);
+        assert!(broken.is_err());
+        assert!(EVENT_NAMES.contains(&"FSDJump"));
+        assert!(EVENT_NAMES.contains(&"Touchdown"));
+        assert!(!EVENT_NAMES.contains(&"Unknown"));
+    }
+
+    type Check = fn(&Event) -> bool;
+
+    /// One journal line per case and what it must parse to
+    #[test]
+    fn test_event_payloads() {
+        let cases: &[(&str, Check)] = &[
+            // Missions
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"MissionAccepted", "Faction":"Sirius Corporation", "Name":"Mission_Altruism_name", "LocalisedName":"Donate 1,000,000 CR", "Donation":"1000000", "Expiry":"2024-09-16T12:00:00Z", "Wing":false, "Influence":"++", "Reputation":"++", "MissionID":42 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::MissionAccepted {
+                            donation: Some(1000000),
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T13:00:00Z", "event":"MissionCompleted", "Faction":"Sirius Corporation", "Name":"Mission_Delivery_name", "LocalisedName":"Deliver 8 units of Gold", "MissionID":43, "Commodity":"$Gold_Name;", "Commodity_Localised":"Gold", "Count":8, "DestinationSystem":"Sol", "DestinationStation":"Abraham Lincoln", "Reward":250000, "CommodityReward":[ { "Name":"Painite", "Count":2 } ], "MaterialsReward":[ { "Name":"Tellurium", "Category":"$MICRORESOURCE_CATEGORY_Raw;", "Category_Localised":"Raw", "Count":3 } ], "FactionEffects":[ { "Faction":"Sirius Corporation", "Effects":[], "Influence":[ { "SystemAddress":10477373803, "Trend":"UpGood", "Influence":"++" } ], "ReputationTrend":"UpGood", "Reputation":"++" } ] }"#,
+                |e| {
+                    matches!(e, Event::MissionCompleted { count: Some(8), commodity_reward, materials_reward, .. }
+                        if commodity_reward[0].name == "Painite" && materials_reward[0].count == 3)
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:30:00Z", "event":"MissionRedirected", "MissionID":44, "Name":"Mission_Massacre", "LocalisedName":"Kill pirates", "NewDestinationStation":"Jameson Memorial", "NewDestinationSystem":"Shinrarta Dezhra", "OldDestinationStation":"", "OldDestinationSystem":"Wolf 397" }"#,
+                |e| {
+                    matches!(e, Event::MissionRedirected { mission_id: 44, new_destination_station, .. }
+                        if new_destination_station == "Jameson Memorial")
+                },
+            ),
+            // Engineers
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"EngineerCraft", "Slot":"MediumHardpoint1", "Module":"hpt_pulselaser_fixed_medium", "Ingredients":[ { "Name":"carbon", "Count":1 }, { "Name":"chemicalprocessors", "Name_Localised":"Chemical Processors", "Count":1 } ], "Engineer":"The Dweller", "EngineerID":300180, "BlueprintID":128673537, "BlueprintName":"Weapon_Overcharged", "Level":2, "Quality":0.35, "ApplyExperimentalEffect":"special_thermal_conduit", "Modifiers":[ { "Label":"DamagePerSecond", "Value":14.2, "OriginalValue":12.6, "LessIsGood":0 } ] }"#,
+                |e| {
+                    matches!(e, Event::EngineerCraft { blueprint, quality: Some(0.35), ingredients, modifiers, .. }
+                        if blueprint == "Weapon_Overcharged"
+                            && ingredients.len() == 2
+                            && modifiers[0].original_value == Some(12.6))
+                },
+            ),
+            (
+                r#"{ "timestamp":"2016-06-10T14:45:00Z", "event":"EngineerCraft", "Engineer":"Felicity Farseer", "Blueprint":"FSD_LongRange", "Level":1, "Ingredients":{ "iron":1, "nickel":2 } }"#,
+                |e| {
+                    matches!(e, Event::EngineerCraft { blueprint, ingredients, .. }
+                        if blueprint == "FSD_LongRange"
+                            && ingredients[1].name == "nickel"
+                            && ingredients[1].count == 2)
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"EngineerProgress", "Engineer":"The Dweller", "EngineerID":300180, "Progress":"Unlocked", "Rank":3, "RankProgress":45 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::EngineerProgress {
+                            rank: Some(3),
+                            rank_progress: Some(45),
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Materials
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"MaterialDiscovered", "Category":"Manufactured", "Name":"fedcorecomposites", "Name_Localised":"Core Dynamics Composites", "DiscoveryNumber":34 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::MaterialDiscovered {
+                            discovery_number: 34,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"MaterialTrade", "MarketID":3221524992, "TraderType":"encoded", "Paid":{ "Material":"scandatabanks", "Material_Localised":"Classified Scan Databanks", "Category":"$MICRORESOURCE_CATEGORY_Encoded;", "Category_Localised":"Encoded", "Quantity":6 }, "Received":{ "Material":"encodedscandata", "Material_Localised":"Divergent Scan Data", "Category":"$MICRORESOURCE_CATEGORY_Encoded;", "Category_Localised":"Encoded", "Quantity":1 } }"#,
+                |e| {
+                    matches!(e, Event::MaterialTrade { paid, received, .. }
+                        if paid.quantity == 6 && received.category_localised.as_deref() == Some("Encoded"))
+                },
+            ),
+            // Market
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"MarketBuy", "MarketID":128666762, "Type":"gold", "Count":200, "BuyPrice":45000, "TotalCost":9000000 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::MarketBuy {
+                            count: 200,
+                            buy_price: 45000,
+                            total_cost: 9000000,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T13:00:00Z", "event":"MarketSell", "MarketID":3228191232, "Type":"onionheadc", "Type_Localised":"Onionhead Gamma Strain", "Count":16, "SellPrice":9800, "TotalSale":156800, "AvgPricePaid":0, "IllegalGoods":true, "StolenGoods":true, "BlackMarket":true }"#,
+                |e| {
+                    matches!(e, Event::MarketSell {
+                        commodity_localised: Some(name),
+                        avg_price_paid: 0,
+                        illegal_goods: true,
+                        stolen_goods: true,
+                        black_market: true,
+                        ..
+                    } if name == "Onionhead Gamma Strain")
+                },
+            ),
+            // Shipyard
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"ShipyardBuy", "MarketID":128666762, "ShipType":"krait_mkii", "ShipType_Localised":"Krait Mk II", "ShipPrice":44159610, "StoreOldShip":"CobraMkIII", "StoreShipID":1 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::ShipyardBuy {
+                            store_ship_id: Some(1),
+                            sell_ship_id: None,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:05Z", "event":"ShipyardNew", "ShipType":"krait_mkii", "ShipType_Localised":"Krait Mk II", "NewShipID":7 }"#,
+                |e| matches!(e, Event::ShipyardNew { new_ship_id: 7, .. }),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"ShipyardTransfer", "ShipType":"Python", "ShipID":3, "System":"Sol", "ShipMarketID":128016640, "Distance":32.4, "TransferPrice":95000, "TransferTime":1800, "MarketID":128666762 }"#,
+                |e| {
+                    matches!(e, Event::ShipyardTransfer {
+                        system: Some(system),
+                        ship_market_id: Some(128016640),
+                        transfer_time: 1800,
+                        ..
+                    } if system == "Sol")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:20:00Z", "event":"ShipyardSwap", "ShipType":"Python", "ShipID":3, "StoreOldShip":"Krait_MkII", "StoreShipID":7, "MarketID":128666762 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::ShipyardSwap {
+                            ship_id: 3,
+                            store_ship_id: Some(7),
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Outfitting
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"ModuleStore", "MarketID":128666762, "Slot":"FrameShiftDrive", "Ship":"python", "ShipID":3, "StoredItem":"$int_hyperdrive_size5_class5_name;", "StoredItem_Localised":"Frame Shift Drive", "EngineerModifications":"FSD_LongRange", "Level":5, "Quality":1.0, "Hot":false, "ReplacementItem":"$int_hyperdrive_size5_class2_name;", "Cost":0 }"#,
+                |e| {
+                    matches!(e, Event::ModuleStore {
+                        engineer_modifications: Some(blueprint),
+                        level: Some(5),
+                        replacement_item: Some(_),
+                        ..
+                    } if blueprint == "FSD_LongRange")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"ModuleSwap", "MarketID":128666762, "FromSlot":"Slot03_Size4", "ToSlot":"Slot05_Size3", "FromItem":"int_shieldcellbank_size3_class5", "ToItem":"Null", "Ship":"python", "ShipID":3 }"#,
+                |e| matches!(e, Event::ModuleSwap { to_item, .. } if to_item == "Null"),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"MassModuleStore", "MarketID":128666762, "Ship":"python", "ShipID":3, "Items":[ { "Slot":"MediumHardpoint1", "Name":"$hpt_pulselaser_fixed_medium_name;", "Hot":false, "EngineerModifications":"Weapon_Overcharged", "Level":2, "Quality":0.35 }, { "Slot":"MediumHardpoint2", "Name":"$hpt_pulselaser_fixed_medium_name;", "Hot":false } ] }"#,
+                |e| {
+                    matches!(e, Event::MassModuleStore { items, .. }
+                        if items[0].quality == Some(0.35) && items[1].engineer_modifications.is_none())
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:15:00Z", "event":"FetchRemoteModule", "StorageSlot":4, "StoredItem":"$int_shieldgenerator_size5_class5_name;", "StoredItem_Localised":"Shield Generator", "ServerId":128671121, "TransferCost":7420, "TransferTime":1320, "Ship":"python", "ShipId":3 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::FetchRemoteModule {
+                            transfer_time: 1320,
+                            ship_id: Some(3),
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Exploration
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"CodexEntry", "EntryID":2420407, "Name":"$Codex_Ent_Stratum_07_Name;", "Name_Localised":"Stratum Tectonicas - Green", "SubCategory":"$Codex_SubCategory_Organic_Structures;", "Category":"$Codex_Category_Biology;", "Region":"$Codex_RegionName_18;", "System":"Synuefe AA-A c1", "SystemAddress":1, "BodyID":5, "NearestDestination":"$SAA_Unknown_Signal:#type=$SAA_SignalType_Biological;:#index=2;", "Latitude":-12.5, "Longitude":101.25, "IsNewEntry":true, "NewTraitsDiscovered":true, "Traits":[ "$Codex_Trait_Stratum_Green;" ], "VoucherAmount":50000 }"#,
+                |e| {
+                    matches!(e, Event::CodexEntry {
+                        latitude: Some(-12.5),
+                        longitude: Some(101.25),
+                        nearest_destination_localised: None,
+                        traits,
+                        voucher_amount: Some(50000),
+                        ..
+                    } if traits.len() == 1)
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"ApproachBody", "StarSystem":"HIP 100", "SystemAddress":2, "Body":"HIP 100 A 2", "BodyID":3 }"#,
+                |e| matches!(e, Event::ApproachBody { body, body_id: 3, .. } if body == "HIP 100 A 2"),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"ApproachSettlement", "Name":"Hauser Relay", "MarketID":3900000001, "SystemAddress":2, "BodyID":3, "BodyName":"HIP 100 A 2", "Latitude":10.5, "Longitude":-20.25 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::ApproachSettlement {
+                            market_id: Some(3900000001),
+                            latitude: Some(10.5),
+                            longitude: Some(-20.25),
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:30:00Z", "event":"LeaveBody", "StarSystem":"HIP 100", "SystemAddress":2, "Body":"HIP 100 A 2", "BodyID":3 }"#,
+                |e| matches!(e, Event::LeaveBody { .. }),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Touchdown", "PlayerControlled":true, "Taxi":false, "Multicrew":false, "StarSystem":"HIP 100", "SystemAddress":2, "Body":"HIP 100 A 2", "BodyID":3, "OnStation":false, "OnPlanet":true, "Latitude":10.5, "Longitude":-20.25, "NearestDestination":"$SAA_Unknown_Signal:#type=$SAA_SignalType_Geological;:#index=3;", "NearestDestination_Localised":"Surface signal: Geological (3)" }"#,
+                |e| {
+                    matches!(e, Event::Touchdown { landing, .. }
+                        if landing.body.as_deref() == Some("HIP 100 A 2")
+                            && landing.on_planet
+                            && landing.latitude == Some(10.5)
+                            && landing.nearest_destination_localised.is_some())
+                },
+            ),
+            // Ship sent away from the SRV
+            (
+                r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"Liftoff", "PlayerControlled":false, "StarSystem":"HIP 100", "SystemAddress":2, "Body":"HIP 100 A 2", "BodyID":3, "OnStation":false, "OnPlanet":true }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::Liftoff {
+                            landing: Landing {
+                                player_controlled: false,
+                                latitude: None,
+                                ..
+                            },
+                            ..
+                        }
+                    )
+                },
+            ),
+            // From before 3.3
+            (
+                r#"{ "timestamp":"2017-03-01T12:00:00Z", "event":"Touchdown", "Latitude":10.5, "Longitude":-20.25 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::Touchdown {
+                            landing: Landing {
+                                player_controlled: true,
+                                body: None,
+                                ..
+                            },
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Supercruise
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"StartJump", "JumpType":"Hyperspace", "Taxi":false, "StarSystem":"Sirius", "SystemAddress":121569805492, "StarClass":"A" }"#,
+                |e| {
+                    matches!(e, Event::StartJump { jump_type: JumpType::Hyperspace, star_class: Some(class), .. }
+                        if class == "A")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"StartJump", "JumpType":"Supercruise", "Taxi":false }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::StartJump {
+                            jump_type: JumpType::Supercruise,
+                            star_system: None,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"SupercruiseExit", "Taxi":false, "Multicrew":false, "StarSystem":"Sirius", "SystemAddress":121569805492, "Body":"Sirius A 1", "BodyID":5, "BodyType":"Planet" }"#,
+                |e| {
+                    matches!(e, Event::SupercruiseExit { body_id: Some(5), body_type: Some(body_type), .. }
+                        if body_type == "Planet")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"SupercruiseDestinationDrop", "Type":"$USS_Type_Salvage;", "Type_Localised":"Degraded Emissions", "Threat":2 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::SupercruiseDestinationDrop {
+                            threat: Some(2),
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Interdictions
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Interdicted", "Submitted":false, "Interdictor":"Jameson", "IsPlayer":true, "CombatRank":5, "Power":"Zachary Hudson" }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::Interdicted {
+                            is_player: true,
+                            combat_rank: Some(5),
+                            is_thargoid: false,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"Interdiction", "Success":true, "Interdicted":"$npc_name_decorate:#name=Kaleb Haas;", "Interdicted_Localised":"Kaleb Haas", "IsPlayer":false, "Faction":"Pirates" }"#,
+                |e| {
+                    matches!(e, Event::Interdiction {
+                        success: true,
+                        interdicted_localised: Some(name),
+                        faction: Some(faction),
+                        ..
+                    } if name == "Kaleb Haas" && faction == "Pirates")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"EscapeInterdiction", "Interdictor":"Thargoid", "IsPlayer":false, "IsThargoid":true }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::EscapeInterdiction {
+                            is_thargoid: true,
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Targets at each scan stage
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"ShipTargeted", "TargetLocked":true, "Ship":"python", "ScanStage":0 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::ShipTargeted {
+                            scan_stage: Some(0),
+                            pilot_name: None,
+                            hull_health: None,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:05Z", "event":"ShipTargeted", "TargetLocked":true, "Ship":"python", "ScanStage":3, "PilotName":"$npc_name_decorate:#name=Kaleb Haas;", "PilotName_Localised":"Kaleb Haas", "PilotRank":"Dangerous", "ShieldHealth":100.0, "HullHealth":87.5, "Faction":"Pirates", "LegalStatus":"Wanted", "Bounty":120000, "Subsystem":"$int_powerplant_size6_class5_name;", "Subsystem_Localised":"Power Plant", "SubsystemHealth":100.0 }"#,
+                |e| {
+                    matches!(e, Event::ShipTargeted {
+                        pilot_rank: Some(rank),
+                        hull_health: Some(87.5),
+                        legal_status: Some(status),
+                        bounty: Some(120000),
+                        subsystem_localised: Some(subsystem),
+                        ..
+                    } if rank == "Dangerous" && status == "Wanted" && subsystem == "Power Plant")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:30Z", "event":"ShipTargeted", "TargetLocked":false }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::ShipTargeted {
+                            target_locked: false,
+                            scan_stage: None,
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Combat
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"UnderAttack", "Target":"Fighter" }"#,
+                |e| matches!(e, Event::UnderAttack { target: Some(target), .. } if target == "Fighter"),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:05Z", "event":"ShieldState", "ShieldsUp":false }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::ShieldState {
+                            shields_up: false,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:10Z", "event":"HullDamage", "Health":0.79, "PlayerPilot":true, "Fighter":false }"#,
+                |e| matches!(e, Event::HullDamage { .. }),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:15Z", "event":"HeatWarning" }"#,
+                |e| matches!(e, Event::HeatWarning { .. }),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:20Z", "event":"HeatDamage" }"#,
+                |e| matches!(e, Event::HeatDamage { .. }),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:25Z", "event":"CockpitBreached" }"#,
+                |e| matches!(e, Event::CockpitBreached { .. }),
+            ),
+            // Crime
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"CommitCrime", "CrimeType":"assault", "Faction":"Sirius Corporation", "Victim":"$npc_name_decorate:#name=Kaleb Haas;", "Victim_Localised":"Kaleb Haas", "Bounty":400 }"#,
+                |e| {
+                    matches!(e, Event::CommitCrime { bounty: Some(400), fine: None, crime_type, .. }
+                        if crime_type == "assault")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"CrimeVictim", "Offender":"Jameson", "CrimeType":"assault", "Bounty":200 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::CrimeVictim {
+                            offender_localised: None,
+                            bounty: Some(200),
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:30:00Z", "event":"PayFines", "Amount":2500, "AllFines":false, "Faction":"$faction_Pirate;", "Faction_Localised":"Pirates", "ShipID":3, "BrokerPercentage":25.0 }"#,
+                |e| {
+                    matches!(e, Event::PayFines {
+                        faction_localised: Some(faction),
+                        broker_percentage: Some(25.0),
+                        ..
+                    } if faction == "Pirates")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:31:00Z", "event":"PayBounties", "Amount":400, "Faction":"Sirius Corporation", "ShipID":3 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::PayBounties {
+                            broker_percentage: None,
+                            all_fines: false,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:32:00Z", "event":"PayLegacyFines", "Amount":1000 }"#,
+                |e| matches!(e, Event::PayLegacyFines { amount: 1000, .. }),
+            ),
+            // Vouchers
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"RedeemVoucher", "Type":"bounty", "Amount":1200000, "Factions":[ { "Faction":"Sirius Corporation", "Amount":1000000 }, { "Faction":"", "Amount":200000 } ], "BrokerPercentage":25.0 }"#,
+                |e| {
+                    matches!(e, Event::RedeemVoucher {
+                        voucher_type: VoucherType::Bounty,
+                        factions,
+                        broker_percentage: Some(25.0),
+                        ..
+                    } if factions[1].amount == 200000)
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"RedeemVoucher", "Type":"CombatBond", "Amount":80000, "Faction":"Alliance" }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::RedeemVoucher {
+                            voucher_type: VoucherType::CombatBond,
+                            faction: Some(_),
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"RedeemVoucher", "Type":"codex", "Amount":50000 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::RedeemVoucher {
+                            voucher_type: VoucherType::Other,
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Social
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Friends", "Status":"Online", "Name":"Jameson" }"#,
+                |e| matches!(e, Event::Friends { status: FriendStatus::Online, name, .. } if name == "Jameson"),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T13:01:00Z", "event":"Friends", "Status":"Blocked", "Name":"Jameson" }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::Friends {
+                            status: FriendStatus::Other,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"WingInvite", "Name":"Jameson" }"#,
+                |e| matches!(e, Event::WingInvite { name, .. } if name == "Jameson"),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:02:00Z", "event":"WingJoin", "Others":[ "Salomé", "Arissa" ] }"#,
+                |e| matches!(e, Event::WingJoin { others, .. } if others.len() == 2),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:03:00Z", "event":"WingAdd", "Name":"Jameson" }"#,
+                |e| matches!(e, Event::WingAdd { name, .. } if name == "Jameson"),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T13:00:00Z", "event":"WingLeave" }"#,
+                |e| matches!(e, Event::WingLeave { .. }),
+            ),
+            // Signals
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"USSDrop", "USSType":"$USS_Type_ValuableSalvage;", "USSType_Localised":"Encoded emissions", "USSThreat":0 }"#,
+                |e| {
+                    matches!(e, Event::USSDrop { uss_type_localised: Some(name), uss_threat: 0, .. }
+                        if name == "Encoded emissions")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2016-06-10T14:35:00Z", "event":"USSDrop", "USSType":"$USS_Type_Salvage;" }"#,
+                |e| matches!(e, Event::USSDrop { uss_threat: 0, .. }),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"DataScanned", "Type":"$Datascan_ShipUplink;", "Type_Localised":"Ship Uplink" }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::DataScanned {
+                            type_localised: Some(_),
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:02:00Z", "event":"DatalinkScan", "Message":"$DATAPOINT_GAMEPLAY_complete;", "Message_Localised":"Alliance Military Network data retrieved" }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::DatalinkScan {
+                            message_localised: Some(_),
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:03:00Z", "event":"DatalinkVoucher", "Reward":12000, "VictimFaction":"Pirates", "PayeeFaction":"Alliance" }"#,
+                |e| {
+                    matches!(e, Event::DatalinkVoucher { reward: 12000, payee_faction, .. }
+                        if payee_faction == "Alliance")
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"NavBeaconScan", "SystemAddress":10477373803, "NumBodies":12 }"#,
+                |e| matches!(e, Event::NavBeaconScan { num_bodies: 12, .. }),
+            ),
+            // Maintenance
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Repair", "Items":[ "Wear", "Hull", "$int_powerplant_size6_class5_name;" ], "Cost":24610 }"#,
+                |e| matches!(e, Event::Repair { item: None, items, .. } if items.len() == 3),
+            ),
+            (
+                r#"{ "timestamp":"2017-03-01T12:00:00Z", "event":"Repair", "Item":"Paint", "Cost":245 }"#,
+                |e| matches!(e, Event::Repair { item: Some(item), .. } if item == "Paint"),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"RestockVehicle", "Type":"testbuggy", "Loadout":"starter", "Cost":1030, "Count":1 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::RestockVehicle {
+                            loadout: Some(_),
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"AfmuRepairs", "Module":"$modularcargobaydoor_name;", "Module_Localised":"Cargo Hatch", "FullyRepaired":true, "Health":1.0 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::AfmuRepairs {
+                            fully_repaired: true,
+                            ..
+                        }
+                    )
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:06:00Z", "event":"RebootRepair", "Modules":[ "MainEngines", "TinyHardpoint1" ] }"#,
+                |e| matches!(e, Event::RebootRepair { modules, .. } if modules.len() == 2),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:07:00Z", "event":"RepairDrone", "HullRepaired":0.12, "CockpitRepaired":0.05 }"#,
+                |e| {
+                    matches!(
+                        e,
+                        Event::RepairDrone {
+                            hull_repaired: Some(_),
+                            corrosion_repaired: None,
+                            ..
+                        }
+                    )
+                },
+            ),
+            // Mining
+            (
+                r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"ProspectedAsteroid", "Materials":[ { "Name":"Platinum", "Proportion":31.5 }, { "Name":"LowTemperatureDiamond", "Name_Localised":"Low Temperature Diamonds", "Proportion":12.25 } ], "MotherlodeMaterial":"Painite", "Content":"$AsteroidMaterialContent_High;", "Content_Localised":"Material Content: High", "Remaining":100.0 }"#,
+                |e| {
+                    matches!(e, Event::ProspectedAsteroid { materials, motherlode_material: Some(motherlode), remaining, .. }
+                        if materials[1].proportion == 12.25
+                            && materials[1].name_localised.is_some()
+                            && motherlode == "Painite"
+                            && *remaining == 100.0)
+                },
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:01:00Z", "event":"LaunchDrone", "Type":"Prospector" }"#,
+                |e| matches!(e, Event::LaunchDrone { r#type, .. } if r#type == "Prospector"),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:02:00Z", "event":"AsteroidCracked", "Body":"HIP 21991 5 A Ring" }"#,
+                |e| matches!(e, Event::AsteroidCracked { body, .. } if body.ends_with("Ring")),
+            ),
+            (
+                r#"{ "timestamp":"2024-09-15T12:03:00Z", "event":"MiningRefined", "Type":"$painite_name;", "Type_Localised":"Painite" }"#,
+                |e| matches!(e, Event::MiningRefined { type_localised: Some(name), .. } if name == "Painite"),
+            ),
+        ];
+
+        for (line, check) in cases {
+            let event: Event = serde_json::from_str(line).unwrap_or_else(|e| panic!("{line}: {e}"));
+            assert!(check(&event), "{line}: {event:?}");
+        }
+
+        // Flattened fields are written back in place
+        let (line, _) = cases
+            .iter()
+            .find(|(line, _)| line.contains(r#""event":"Touchdown", "PlayerControlled""#))
+            .unwrap();
+        let touchdown = serde_json::to_value(serde_json::from_str::<Event>(line).unwrap()).unwrap();
+        assert_eq!(
+            (&touchdown["event"], &touchdown["BodyID"]),
+            (&"Touchdown".into(), &3.into())
+        );
+    }
 }
@@ -0,0 +1,565 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use directories::UserDirs;
+use serde_json::Value;
+use time::{macros::format_description, OffsetDateTime, PrimitiveDateTime};
+
+use super::{Event, EventHeader, GameVersion, JournalMerge, JournalParser, RouteEntry, Status};
+
+static JOURNALS_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Read journals from `path` instead of the default location.
+/// Can only be set once, returns the path back if it already was.
+pub fn set_journals_path(path: PathBuf) -> Result<(), PathBuf> {
+    JOURNALS_PATH_OVERRIDE.set(path)
+}
+
+pub fn journals_path() -> PathBuf {
+    if let Some(path) = JOURNALS_PATH_OVERRIDE.get() {
+        return path.to_owned();
+    }
+
+    let user_dirs = UserDirs::new().expect("I'm sorry but your OS sucks :(");
+    let home_dir = user_dirs
+        .home_dir()
+        .to_str()
+        .expect("Your home directory name contains some ancient runes you imbecile >:(");
+
+    [
+        home_dir,
+        "Saved Games",
+        "Frontier Developments",
+        "Elite Dangerous",
+    ]
+    .iter()
+    .collect()
+}
+
+/// Where the game saves screenshots, called ED_Pictures in the journal
+pub fn screenshots_path() -> PathBuf {
+    let user_dirs = UserDirs::new().expect("I'm sorry but your OS sucks :(");
+
+    user_dirs
+        .home_dir()
+        .join("Pictures")
+        .join("Frontier Developments")
+        .join("Elite Dangerous")
+}
+
+pub fn journal_files() -> Result<Vec<PathBuf>, io::Error> {
+    journal_files_in(&journals_path())
+}
+
+/// Journal files in `dir` instead of the journals directory
+pub fn journal_files_in(dir: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|res| res.map(|e| e.path()))
+        .filter(|res| match res {
+            Ok(p) => p
+                .file_name()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .starts_with("Journal"),
+            Err(_) => true,
+        })
+        .collect::<Result<Vec<_>, io::Error>>()?;
+
+    paths.sort_by_cached_key(|p| journal_order(p));
+
+    Ok(paths)
+}
+
+/// Journal files whose time span overlaps `start..end`.
+/// A journal is assumed to cover the time from its own start until the
+/// start of the next journal, so files are selected by name only and
+/// never opened.
+pub fn journal_files_between(
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+) -> Result<Vec<PathBuf>, io::Error> {
+    Ok(overlapping(&journal_files()?, start, end))
+}
+
+/// The journals out of `paths`, sorted by time, overlapping `start..end`
+pub(crate) fn overlapping<P: AsRef<Path> + Clone>(
+    paths: &[P],
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+) -> Vec<P> {
+    let mut paths = paths.to_vec();
+    paths.sort_by_cached_key(|p| journal_order(p.as_ref()));
+    let starts: Vec<Option<OffsetDateTime>> = paths
+        .iter()
+        .map(|p| journal_start_time(p.as_ref()))
+        .collect();
+
+    paths
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            let Some(file_start) = starts[*i] else {
+                return true;
+            };
+            let file_end = starts[i + 1..].iter().flatten().next();
+
+            file_start < end && file_end.is_none_or(|e| *e > start)
+        })
+        .map(|(_, p)| p.to_owned())
+        .collect()
+}
+
+/// Start time encoded in a journal file name.
+/// Supports both the current `Journal.2024-09-15T123456.01.log` and the
+/// legacy `Journal.240915123456.01.log` naming schemes.
+pub fn journal_start_time(path: &Path) -> Option<OffsetDateTime> {
+    let stamp = path.file_name()?.to_str()?.split('.').nth(1)?;
+
+    PrimitiveDateTime::parse(
+        stamp,
+        format_description!("[year]-[month]-[day]T[hour][minute][second]"),
+    )
+    .or_else(|_| {
+        PrimitiveDateTime::parse(
+            &format!("20{stamp}"),
+            format_description!("[year][month][day][hour][minute][second]"),
+        )
+    })
+    .ok()
+    .map(PrimitiveDateTime::assume_utc)
+}
+
+/// Where a journal goes among the others, by the start time and part
+/// number in its file name. The names themselves don't sort by time since
+/// the legacy naming scheme has a two digit year.
+pub(crate) fn journal_order(path: &Path) -> (Option<OffsetDateTime>, Option<u32>) {
+    let part = path
+        .file_name()
+        .and_then(|name| name.to_str()?.split('.').nth(2)?.parse().ok());
+
+    (journal_start_time(path), part)
+}
+
+pub fn latest_journal_path() -> Result<PathBuf, io::Error> {
+    let paths = journal_files()?;
+
+    Ok(paths
+        .last()
+        .expect("No journal file found. Please start the game at least once")
+        .to_owned())
+}
+
+pub fn backpack_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("Backpack.json");
+    path
+}
+
+pub fn cargo_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("Cargo.json");
+    path
+}
+
+pub fn market_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("Market.json");
+    path
+}
+
+pub fn modules_info_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("ModulesInfo.json");
+    path
+}
+
+pub fn nav_route_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("NavRoute.json");
+    path
+}
+
+pub fn outfitting_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("Outfitting.json");
+    path
+}
+
+pub fn ship_locker_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("ShipLocker.json");
+    path
+}
+
+pub fn shipyard_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("Shipyard.json");
+    path
+}
+
+pub fn status_path() -> PathBuf {
+    let mut path = journals_path();
+    path.push("Status.json");
+    path
+}
+
+pub fn read_status() -> Result<Status, io::Error> {
+    Ok(serde_json::from_str(&fs::read_to_string(status_path())?)?)
+}
+
+/// Market the commander last opened, with its prices
+pub fn read_market() -> Result<Event, io::Error> {
+    Ok(serde_json::from_str(&fs::read_to_string(market_path())?)?)
+}
+
+/// Route currently plotted in the galaxy map, empty if there is none
+pub fn read_nav_route() -> Result<Vec<RouteEntry>, io::Error> {
+    let json = match fs::read_to_string(nav_route_path()) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        json => json?,
+    };
+    match serde_json::from_str(&json)? {
+        Event::NavRoute { route, .. } => Ok(route),
+        _ => Ok(Vec::new()),
+    }
+}
+
+pub fn all_events() -> Result<Vec<Event>, io::Error> {
+    read_events(&journal_files()?)
+}
+
+/// Events from all journals overlapping `start..end`.
+/// Events outside the range are skipped.
+pub fn events_between(start: OffsetDateTime, end: OffsetDateTime) -> Result<Vec<Event>, io::Error> {
+    events_between_where(start, end, |_| true)
+}
+
+/// Like [events_between] but only the events whose journal line `keep`
+/// returns true for, e.g. to check the raw JSON against a [Filter]
+///
+/// [Filter]: super::Filter
+pub fn events_between_where(
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+    keep: impl Fn(&str) -> bool,
+) -> Result<Vec<Event>, io::Error> {
+    lines_between(start, end, keep)?
+        .iter()
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// Journal lines within `start..end` that `keep` returns true for, for
+/// working with the raw JSON instead of parsed events
+pub fn lines_between(
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+    keep: impl Fn(&str) -> bool,
+) -> Result<Vec<String>, io::Error> {
+    let mut lines = Vec::new();
+
+    for path in journal_files_between(start, end)? {
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            let header: EventHeader = serde_json::from_str(line.as_str())?;
+
+            if start <= header.timestamp && header.timestamp < end && keep(&line) {
+                lines.push(line);
+            }
+        }
+    }
+
+    Ok(lines)
+}
+
+/// Events from the journals in all of `dirs` within `start..end`, e.g. the
+/// journals directory and copies from other machines, as one chronological
+/// stream. Entries found in more than one journal are only kept once, see
+/// [JournalMerge]. Only lines `keep` returns true for are read.
+pub fn merged_events_between(
+    dirs: &[PathBuf],
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+    keep: impl Fn(&str) -> bool,
+) -> Result<Vec<Event>, io::Error> {
+    Ok(merged_journals(dirs, start, end, keep)?.events()?)
+}
+
+/// Like [merged_events_between] but the journal lines
+pub fn merged_lines_between(
+    dirs: &[PathBuf],
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+    keep: impl Fn(&str) -> bool,
+) -> Result<Vec<String>, io::Error> {
+    Ok(merged_journals(dirs, start, end, keep)?.lines())
+}
+
+fn merged_journals(
+    dirs: &[PathBuf],
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+    keep: impl Fn(&str) -> bool,
+) -> Result<JournalMerge, io::Error> {
+    let mut merge = JournalMerge::default();
+
+    for dir in dirs {
+        for path in journal_files_in(dir)? {
+            // Copies of a journal keep its name
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                let header: EventHeader = serde_json::from_str(line.as_str())?;
+
+                if start <= header.timestamp && header.timestamp < end && keep(&line) {
+                    merge.add_line(&name, &line)?;
+                }
+            }
+        }
+    }
+
+    Ok(merge)
+}
+
+pub fn read_events(paths: &[PathBuf]) -> Result<Vec<Event>, io::Error> {
+    let mut events: Vec<Event> = Vec::new();
+
+    for path in paths {
+        for line in BufReader::new(File::open(path)?).lines() {
+            events.push(serde_json::from_str(line?.as_str())?);
+        }
+    }
+
+    Ok(events)
+}
+
+/// Like [read_events] but the entries as written, with the fields [Event]
+/// doesn't have
+pub fn read_raw_events(paths: &[PathBuf]) -> Result<Vec<Value>, io::Error> {
+    let mut events: Vec<Value> = Vec::new();
+
+    for path in paths {
+        for line in BufReader::new(File::open(path)?).lines() {
+            events.push(serde_json::from_str(line?.as_str())?);
+        }
+    }
+
+    Ok(events)
+}
+
+/// A parsed journal file
+#[derive(Debug)]
+pub struct Journal {
+    pub path: PathBuf,
+    /// Game version from the Fileheader, if the file has one
+    pub version: Option<GameVersion>,
+    pub events: Vec<Event>,
+}
+
+pub fn read_journal(path: &Path) -> Result<Journal, io::Error> {
+    let mut parser = JournalParser::default();
+    let mut events: Vec<Event> = Vec::new();
+
+    for line in BufReader::new(File::open(path)?).lines() {
+        events.push(parser.parse_line(line?.as_str())?);
+    }
+
+    Ok(Journal {
+        path: path.to_owned(),
+        version: parser.version().cloned(),
+        events,
+    })
+}
+
+pub fn all_journals() -> Result<Vec<Journal>, io::Error> {
+    journal_files()?.iter().map(|p| read_journal(p)).collect()
+}
+
+/// Only the [EventHeader] of every entry in a journal file
+pub fn read_headers(path: &Path) -> Result<Vec<EventHeader>, io::Error> {
+    let mut headers: Vec<EventHeader> = Vec::new();
+
+    for line in BufReader::new(File::open(path)?).lines() {
+        headers.push(serde_json::from_str(line?.as_str())?);
+    }
+
+    Ok(headers)
+}
+
+/// Part number from the Fileheader at the top of a journal
+fn journal_part(path: &Path) -> Result<Option<u32>, io::Error> {
+    let mut line = String::new();
+    BufReader::new(File::open(path)?).read_line(&mut line)?;
+
+    Ok(match serde_json::from_str(&line) {
+        Ok(Event::Fileheader { part, .. }) => Some(part),
+        _ => None,
+    })
+}
+
+/// Journal files written during one game session.
+/// When a journal reaches 500k lines the game writes Continued and goes on
+/// in a new file whose Fileheader has the next part number.
+#[derive(Debug)]
+pub struct JournalSession {
+    pub paths: Vec<PathBuf>,
+}
+
+impl JournalSession {
+    /// Events of all parts as one stream, without the Continued entries and
+    /// repeated Fileheaders joining the parts
+    pub fn events(&self) -> Result<Vec<Event>, io::Error> {
+        let mut events = Vec::new();
+
+        for (i, path) in self.paths.iter().enumerate() {
+            events.extend(
+                read_events(&[path.to_owned()])?
+                    .into_iter()
+                    .filter(|e| match e {
+                        Event::Continued { .. } => false,
+                        Event::Fileheader { .. } => i == 0,
+                        _ => true,
+                    }),
+            );
+        }
+
+        Ok(events)
+    }
+
+    /// Like [JournalSession::events] but only the [EventHeader] of every entry
+    pub fn headers(&self) -> Result<Vec<EventHeader>, io::Error> {
+        let mut headers = Vec::new();
+
+        for (i, path) in self.paths.iter().enumerate() {
+            headers.extend(
+                read_headers(path)?
+                    .into_iter()
+                    .filter(|h| match h.event.as_str() {
+                        "Continued" => false,
+                        "Fileheader" => i == 0,
+                        _ => true,
+                    }),
+            );
+        }
+
+        Ok(headers)
+    }
+}
+
+/// Group journal files, sorted by time, into the sessions they belong to
+pub fn journal_sessions(paths: &[PathBuf]) -> Result<Vec<JournalSession>, io::Error> {
+    let mut sessions: Vec<JournalSession> = Vec::new();
+    let mut last_part = None;
+
+    for path in paths {
+        let part = journal_part(path)?;
+
+        match (sessions.last_mut(), last_part, part) {
+            (Some(session), Some(last), Some(part)) if part == last + 1 => {
+                session.paths.push(path.to_owned())
+            }
+            _ => sessions.push(JournalSession {
+                paths: vec![path.to_owned()],
+            }),
+        }
+        last_part = part;
+    }
+
+    Ok(sessions)
+}
+
+pub fn all_sessions() -> Result<Vec<JournalSession>, io::Error> {
+    journal_sessions(&journal_files()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_journal_start_time() {
+        assert_eq!(
+            journal_start_time(Path::new("Journal.2024-09-15T123456.01.log")),
+            Some(datetime!(2024-09-15 12:34:56 UTC))
+        );
+        assert_eq!(
+            journal_start_time(Path::new("Journal.170412183402.01.log")),
+            Some(datetime!(2017-04-12 18:34:02 UTC))
+        );
+        assert_eq!(journal_start_time(Path::new("Status.json")), None);
+    }
+
+    #[test]
+    fn test_journal_order() {
+        let mut paths = [
+            Path::new("Journal.2024-09-15T123456.02.log"),
+            Path::new("Journal.170412183402.01.log"),
+            Path::new("Journal.2024-09-15T123456.01.log"),
+            Path::new("Journal.2017-04-13T090000.01.log"),
+        ];
+        paths.sort_by_key(|p| journal_order(p));
+
+        assert_eq!(
+            paths,
+            [
+                Path::new("Journal.170412183402.01.log"),
+                Path::new("Journal.2017-04-13T090000.01.log"),
+                Path::new("Journal.2024-09-15T123456.01.log"),
+                Path::new("Journal.2024-09-15T123456.02.log"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_journal_sessions() {
+        let dir = std::env::temp_dir().join(format!("elite-sessions-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let header = |time: &str, part: u32| {
+            format!(
+                r#"{{ "timestamp":"{time}", "event":"Fileheader", "part":{part}, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }}"#
+            )
+        };
+        let files = [
+            (
+                "Journal.2024-09-15T120000.01.log",
+                header("2024-09-15T12:00:00Z", 1)
+                    + "\n"
+                    + r#"{ "timestamp":"2024-09-15T13:00:00Z", "event":"Continued", "Part":2 }"#,
+            ),
+            (
+                "Journal.2024-09-15T130000.02.log",
+                header("2024-09-15T13:00:00Z", 2)
+                    + "\n"
+                    + r#"{ "timestamp":"2024-09-15T13:10:00Z", "event":"Shutdown" }"#,
+            ),
+            (
+                "Journal.2024-09-16T120000.01.log",
+                header("2024-09-16T12:00:00Z", 1),
+            ),
+        ];
+        let paths: Vec<PathBuf> = files
+            .iter()
+            .map(|(name, contents)| {
+                let path = dir.join(name);
+                fs::write(&path, contents).unwrap();
+                path
+            })
+            .collect();
+
+        let sessions = journal_sessions(&paths).unwrap();
+        let events = sessions[0].events().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].paths, paths[..2]);
+        // Only the first Fileheader and the Shutdown are left
+        assert_eq!(events.len(), 2);
+    }
+}
@@ -0,0 +1,455 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use serde_json::Value;
+
+/// Filter expression evaluated against the raw JSON of an event, like
+/// `event == "Bounty" && TotalReward > 100000`.
+///
+/// Fields are named as in the journal, nested ones with dots and indices
+/// like `Rewards[0].Faction`. A field on its own is true if it exists and
+/// isn't `false` or `null`. Comparisons are `==`, `!=`, `<`, `<=`, `>` and
+/// `>=` between fields, strings, numbers, `true`, `false` and `null`, and
+/// are false when the types don't match, `!=` too. Only `null` compares to
+/// anything, so `Field != null` finds events with the field. Conditions are
+/// combined with `&&`, `||`, `!` and parentheses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    expr: Expr,
+}
+
+impl Filter {
+    pub fn matches(&self, event: &Value) -> bool {
+        self.expr.eval(event)
+    }
+
+    /// Whether a journal line matches, false if it isn't valid JSON
+    pub fn matches_line(&self, line: &str) -> bool {
+        serde_json::from_str(line).is_ok_and(|event| self.matches(&event))
+    }
+}
+
+impl FromStr for Filter {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            next: 0,
+            end: s.len(),
+        };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.next) {
+            None => Ok(Filter { expr }),
+            Some((position, token)) => Err(FilterError {
+                position: *position,
+                message: format!("Unexpected {token:?}"),
+            }),
+        }
+    }
+}
+
+/// Path to a field like `Rewards[0].Faction`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPath(Vec<Segment>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+impl FieldPath {
+    pub fn get<'a>(&self, event: &'a Value) -> Option<&'a Value> {
+        self.0
+            .iter()
+            .try_fold(event, |value, segment| match segment {
+                Segment::Field(name) => value.get(name),
+                Segment::Index(i) => value.get(i),
+            })
+    }
+}
+
+impl FromStr for FieldPath {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            next: 0,
+            end: s.len(),
+        };
+        let path = match parser.advance() {
+            Some(Token::Ident(name)) => parser.path(name)?,
+            _ => return Err(parser.error("Expected a field name")),
+        };
+        match parser.peek() {
+            None => Ok(path),
+            Some(_) => Err(parser.error("Unexpected text after the field")),
+        }
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Field(name) if i == 0 => write!(f, "{name}")?,
+                Segment::Field(name) => write!(f, ".{name}")?,
+                Segment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replace `{Field}` in a template with the field's value, missing ones
+/// with a dash
+pub fn fill_fields(template: &str, payload: &Value) -> String {
+    let mut text = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let field = after.find('}').and_then(|end| {
+            let path: FieldPath = after[..end].parse().ok()?;
+            Some((end, path))
+        });
+        match field {
+            Some((end, path)) => {
+                match path.get(payload) {
+                    None | Some(Value::Null) => text.push('-'),
+                    Some(Value::String(s)) => text.push_str(s),
+                    Some(other) => text.push_str(&other.to_string()),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    text.push_str(rest);
+    text
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterError {
+    /// Byte offset in the expression
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Literal(Value),
+    Field(FieldPath),
+}
+
+impl Operand {
+    fn eval<'a>(&'a self, event: &'a Value) -> Option<&'a Value> {
+        match self {
+            Operand::Literal(value) => Some(value),
+            Operand::Field(path) => path.get(event),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Operand, CmpOp, Operand),
+    Truthy(Operand),
+}
+
+/// Order of two values of the same type, numbers compared as floats
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        (a, b) if a == b => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+impl Expr {
+    fn eval(&self, event: &Value) -> bool {
+        match self {
+            Expr::Or(a, b) => a.eval(event) || b.eval(event),
+            Expr::And(a, b) => a.eval(event) && b.eval(event),
+            Expr::Not(a) => !a.eval(event),
+            Expr::Truthy(operand) => !matches!(
+                operand.eval(event),
+                None | Some(Value::Null | Value::Bool(false))
+            ),
+            Expr::Compare(a, op, b) => {
+                // A missing field is null, so `Field == null` finds events without it
+                let null = Value::Null;
+                let a = a.eval(event).unwrap_or(&null);
+                let b = b.eval(event).unwrap_or(&null);
+                let ordering = compare(a, b);
+                match op {
+                    CmpOp::Eq => ordering == Some(Ordering::Equal),
+                    CmpOp::Ne if a.is_null() || b.is_null() => ordering.is_none(),
+                    CmpOp::Ne => matches!(ordering, Some(Ordering::Less | Ordering::Greater)),
+                    CmpOp::Lt => ordering == Some(Ordering::Less),
+                    CmpOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                    CmpOp::Gt => ordering == Some(Ordering::Greater),
+                    CmpOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Str(String),
+    Num(serde_json::Number),
+    Ident(String),
+    Dot,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    Cmp(CmpOp),
+    And,
+    Or,
+    Not,
+}
+
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    let error = |position, message: &str| FilterError {
+        position,
+        message: message.to_owned(),
+    };
+
+    while let Some((start, c)) = chars.next() {
+        let mut next_is = |expected| chars.next_if(|(_, c)| *c == expected).is_some();
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '.' => Token::Dot,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '&' if next_is('&') => Token::And,
+            '|' if next_is('|') => Token::Or,
+            '=' if next_is('=') => Token::Cmp(CmpOp::Eq),
+            '!' if next_is('=') => Token::Cmp(CmpOp::Ne),
+            '!' => Token::Not,
+            '<' if next_is('=') => Token::Cmp(CmpOp::Le),
+            '<' => Token::Cmp(CmpOp::Lt),
+            '>' if next_is('=') => Token::Cmp(CmpOp::Ge),
+            '>' => Token::Cmp(CmpOp::Gt),
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c)) => string.push(c),
+                            None => return Err(error(start, "Unterminated string")),
+                        },
+                        Some((_, c)) => string.push(c),
+                        None => return Err(error(start, "Unterminated string")),
+                    }
+                }
+                Token::Str(string)
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+                    end = i + c.len_utf8();
+                }
+                let number = s[start..end]
+                    .parse()
+                    .map_err(|_| error(start, "Invalid number"))?;
+                Token::Num(number)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    end = i + c.len_utf8();
+                }
+                Token::Ident(s[start..end].to_owned())
+            }
+            _ => return Err(error(start, &format!("Unexpected '{c}'"))),
+        };
+        tokens.push((start, token));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    /// Length of the input, where errors at its end are
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.next += 1;
+        token
+    }
+
+    /// Error at the last token taken
+    fn error(&self, message: &str) -> FilterError {
+        let position = match self.tokens.get(self.next.saturating_sub(1)) {
+            Some((position, _)) => *position,
+            None => self.end,
+        };
+        FilterError {
+            position,
+            message: message.to_owned(),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, FilterError> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, FilterError> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, FilterError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.next += 1;
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::LParen) => {
+                self.next += 1;
+                let expr = self.or()?;
+                match self.advance() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(self.error("Expected ')'")),
+                }
+            }
+            _ => {
+                let left = self.operand()?;
+                match self.peek() {
+                    Some(Token::Cmp(op)) => {
+                        let op = *op;
+                        self.next += 1;
+                        Ok(Expr::Compare(left, op, self.operand()?))
+                    }
+                    _ => Ok(Expr::Truthy(left)),
+                }
+            }
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, FilterError> {
+        match self.advance() {
+            Some(Token::Str(s)) => Ok(Operand::Literal(Value::String(s))),
+            Some(Token::Num(n)) => Ok(Operand::Literal(Value::Number(n))),
+            Some(Token::Ident(name)) => Ok(match name.as_str() {
+                "true" => Operand::Literal(Value::Bool(true)),
+                "false" => Operand::Literal(Value::Bool(false)),
+                "null" => Operand::Literal(Value::Null),
+                _ => Operand::Field(self.path(name)?),
+            }),
+            _ => Err(self.error("Expected a field or value")),
+        }
+    }
+
+    fn path(&mut self, first: String) -> Result<FieldPath, FilterError> {
+        let mut segments = vec![Segment::Field(first)];
+        loop {
+            match self.peek() {
+                Some(Token::Dot) => {
+                    self.next += 1;
+                    match self.advance() {
+                        Some(Token::Ident(name)) => segments.push(Segment::Field(name)),
+                        _ => return Err(self.error("Expected a field name after '.'")),
+                    }
+                }
+                Some(Token::LBracket) => {
+                    self.next += 1;
+                    let index = match self.advance() {
+                        Some(Token::Num(n)) => n.as_u64(),
+                        _ => None,
+                    };
+                    let (Some(index), Some(Token::RBracket)) = (index, self.advance()) else {
+                        return Err(self.error("Expected an index like [0]"));
+                    };
+                    segments.push(Segment::Index(index as usize));
+                }
+                _ => return Ok(FieldPath(segments)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let bounty: Value = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Alliance", "Reward":150000 } ], "Target":"viper", "TotalReward":150000, "VictimFaction":"Pirates" }"#,
+        )
+        .unwrap();
+        let matches = |filter: &str| filter.parse::<Filter>().unwrap().matches(&bounty);
+
+        assert!(matches(r#"event == "Bounty" && TotalReward > 100000"#));
+        assert!(!matches(r#"event == "Bounty" && TotalReward > 200000.5"#));
+        assert!(matches(r#"Rewards[0].Faction == "Alliance""#));
+        assert!(matches(r#"!(Target == "anaconda" || Target == "python")"#));
+        assert!(matches("!SharedWithOthers && SharedWithOthers == null"));
+        assert!(!matches(r#"TotalReward == "150000""#));
+        assert!(!matches(r#"TotalReward != "150000""#));
+        assert!(matches(r#"Target != "python" && Target != null"#));
+        assert!(!matches("SharedWithOthers != null"));
+
+        let error = "TotalReward >".parse::<Filter>().unwrap_err();
+        assert_eq!(error.position, 13);
+        assert!("event = 1".parse::<Filter>().is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+use time::OffsetDateTime;
+
+use super::{Event, EventHeader, JournalParser};
+
+/// Joins the journals of several machines the same commander plays on into
+/// one chronological stream.
+///
+/// Journals copied back and forth between machines overlap, so entries are
+/// told apart by their timestamp and content. Content is compared as JSON,
+/// so spacing and the order of fields don't matter. The game can write the
+/// same entry twice in a second, so an entry is kept as often as the one
+/// source with the most of it has it.
+#[derive(Debug, Default)]
+pub struct JournalMerge {
+    /// Times each entry was added from each source
+    seen: HashMap<String, HashMap<String, usize>>,
+    entries: Vec<(OffsetDateTime, String)>,
+}
+
+impl JournalMerge {
+    /// Add one journal line of `source`, like the name of the journal file
+    /// it is from. Returns whether it wasn't added from another source
+    /// before.
+    pub fn add_line(&mut self, source: &str, line: &str) -> Result<bool, serde_json::Error> {
+        let value: Value = serde_json::from_str(line)?;
+        let header: EventHeader = serde_json::from_value(value.clone())?;
+
+        // Objects are sorted by key, so this is the same for equal entries
+        let sources = self.seen.entry(value.to_string()).or_default();
+        let kept = sources.values().copied().max().unwrap_or_default();
+        let count = sources.entry(source.to_owned()).or_default();
+        *count += 1;
+        if *count <= kept {
+            return Ok(false);
+        }
+
+        self.entries.push((header.timestamp, line.to_owned()));
+        Ok(true)
+    }
+
+    /// Number of distinct entries added
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All distinct entries sorted by time. Entries with the same timestamp
+    /// keep the order they were added in.
+    pub fn events(self) -> Result<Vec<Event>, serde_json::Error> {
+        let mut parser = JournalParser::default();
+        self.lines()
+            .iter()
+            .map(|line| parser.parse_line(line))
+            .collect()
+    }
+
+    /// Like [JournalMerge::events] but the journal lines
+    pub fn lines(mut self) -> Vec<String> {
+        self.entries.sort_by_key(|(timestamp, _)| *timestamp);
+        self.entries.into_iter().map(|(_, line)| line).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_merge() {
+        let desktop = r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Music", "MusicTrack":"MainMenu" }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"Shutdown" }
+{ "timestamp":"2024-09-16T20:00:00Z", "event":"Music", "MusicTrack":"MainMenu" }
+"#;
+        // A copy of the first session with other spacing and a session of its own
+        let laptop = r#"
+{"timestamp":"2024-09-15T12:00:00Z","MusicTrack":"MainMenu","event":"Music"}
+{"timestamp":"2024-09-15T12:10:00Z","event":"Shutdown"}
+{ "timestamp":"2024-09-16T08:00:00Z", "event":"Music", "MusicTrack":"Exploration" }
+"#;
+
+        let mut merge = JournalMerge::default();
+        let lines = |source, journal: &'static str| {
+            journal
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(move |line| (source, line))
+        };
+        for (source, line) in lines("desktop", desktop).chain(lines("laptop", laptop)) {
+            merge.add_line(source, line).unwrap();
+        }
+        assert_eq!(merge.len(), 4);
+
+        let events = merge.events().unwrap();
+        assert!(matches!(
+            &events[2],
+            Event::Music { music_track, .. } if music_track == "Exploration"
+        ));
+    }
+
+    #[test]
+    fn test_repeated_entries() {
+        let bounty = r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Alliance", "Reward":1000 } ], "Target":"viper", "TotalReward":1000, "VictimFaction":"Pirates" }"#;
+
+        let mut merge = JournalMerge::default();
+        // Two kills in the same second, both in the copy on the laptop
+        assert!(merge.add_line("desktop", bounty).unwrap());
+        assert!(merge.add_line("desktop", bounty).unwrap());
+        assert!(!merge.add_line("laptop", bounty).unwrap());
+        assert!(!merge.add_line("laptop", bounty).unwrap());
+        assert_eq!(merge.len(), 2);
+
+        // One more than any other source has
+        assert!(!merge.add_line("other", bounty).unwrap());
+        assert!(!merge.add_line("other", bounty).unwrap());
+        assert!(merge.add_line("other", bounty).unwrap());
+        assert_eq!(merge.len(), 3);
+    }
+}
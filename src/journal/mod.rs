@@ -1,122 +1,100 @@
-use std::{
-    fs::{self, File},
-    io::{self, BufRead, BufReader},
-    path::PathBuf,
-};
-
-use directories::UserDirs;
-
+pub use aggregate::*;
+pub use anonymize::*;
+pub use dedup::*;
+pub use diff::*;
 pub use events::*;
-
+#[cfg(feature = "fs")]
+pub use files::*;
+pub use filter::*;
+pub use merge::*;
+#[cfg(feature = "fs")]
+pub use source::*;
+pub use status::*;
+#[cfg(feature = "fs")]
+pub use tail::*;
+pub use version::*;
+
+mod aggregate;
+mod anonymize;
+mod dedup;
+mod diff;
 mod events;
-
-pub fn journals_path() -> PathBuf {
-    let user_dirs = UserDirs::new().expect("I'm sorry but your OS sucks :(");
-    let home_dir = user_dirs
-        .home_dir()
-        .to_str()
-        .expect("Your home directory name contains some ancient runes you imbecile >:(");
-
-    [
-        home_dir,
-        "Saved Games",
-        "Frontier Developments",
-        "Elite Dangerous",
-    ]
-    .iter()
-    .collect()
+#[cfg(feature = "fs")]
+mod files;
+mod filter;
+mod merge;
+#[cfg(feature = "fs")]
+mod source;
+mod status;
+#[cfg(feature = "fs")]
+mod tail;
+mod version;
+
+/// Parse a single journal line
+pub fn parse_line(line: &str) -> Result<Event, serde_json::Error> {
+    serde_json::from_str(line)
 }
 
-pub fn journal_files() -> Result<Vec<PathBuf>, io::Error> {
-    let mut paths = fs::read_dir(journals_path())?
-        .map(|res| res.map(|e| e.path()))
-        .filter(|res| match res {
-            Ok(p) => p
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .starts_with("Journal"),
-            Err(_) => true,
-        })
-        .collect::<Result<Vec<_>, io::Error>>()?;
-
-    paths.sort();
-
-    Ok(paths)
-}
-
-pub fn latest_journal_path() -> Result<PathBuf, io::Error> {
-    let paths = journal_files()?;
-
-    Ok(paths
-        .last()
-        .expect("No journal file found. Please start the game at least once")
-        .to_owned())
-}
-
-pub fn backpack_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("Backpack.json");
-    path
-}
-
-pub fn cargo_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("Cargo.json");
-    path
-}
+/// Parse the contents of a whole journal file,
+/// e.g. one that was dropped into a browser
+pub fn parse_events(contents: &str) -> Result<Vec<Event>, serde_json::Error> {
+    let mut parser = JournalParser::default();
 
-pub fn market_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("Market.json");
-    path
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parser.parse_line(line))
+        .collect()
 }
 
-pub fn modules_info_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("ModulesInfo.json");
-    path
-}
-
-pub fn nav_route_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("NavRoute.json");
-    path
-}
-
-pub fn outfitting_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("Outfitting.json");
-    path
-}
-
-pub fn ship_locker_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("ShipLocker.json");
-    path
-}
-
-pub fn shipyard_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("Shipyard.json");
-    path
-}
+/// Only the events from sessions played as the commander with this FID.
+/// Sessions where the commander can't be told, e.g. from journals
+/// written before FIDs were logged, are kept.
+pub fn commander_events(events: Vec<Event>, fid: &str) -> Vec<Event> {
+    let mut sessions: Vec<(Option<String>, Vec<Event>)> = vec![(None, Vec::new())];
+
+    for event in events {
+        match &event {
+            // Continued journals start with a Fileheader too
+            Event::Fileheader { part: 1, .. } => sessions.push((None, Vec::new())),
+            Event::Commander { fid, .. } | Event::LoadGame { fid, .. } if !fid.is_empty() => {
+                sessions
+                    .last_mut()
+                    .unwrap()
+                    .0
+                    .get_or_insert_with(|| fid.to_owned());
+            }
+            _ => {}
+        }
+        sessions.last_mut().unwrap().1.push(event);
+    }
 
-pub fn status_path() -> PathBuf {
-    let mut path = journals_path();
-    path.push("Status.json");
-    path
+    sessions
+        .into_iter()
+        .filter(|(commander, _)| commander.as_deref().is_none_or(|c| c == fid))
+        .flat_map(|(_, events)| events)
+        .collect()
 }
 
-pub fn all_events() -> Result<Vec<Event>, io::Error> {
-    let mut events: Vec<Event> = Vec::new();
-
-    for path in journal_files()? {
-        for line in BufReader::new(File::open(path)?).lines() {
-            events.push(serde_json::from_str(line?.as_str())?);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commander_events() {
+        let contents = r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }
+{ "timestamp":"2024-09-15T12:00:05Z", "event":"Commander", "FID":"F1", "Name":"One" }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"Shutdown" }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }
+{ "timestamp":"2024-09-15T13:00:05Z", "event":"Commander", "FID":"F2", "Name":"Two" }
+{ "timestamp":"2024-09-15T14:00:00Z", "event":"Fileheader", "part":2, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }
+{ "timestamp":"2024-09-15T14:10:00Z", "event":"Shutdown" }
+"#;
+        let events = || parse_events(contents).unwrap();
+
+        assert_eq!(commander_events(events(), "F1").len(), 3);
+        // Continued journals belong to the session before them
+        assert_eq!(commander_events(events(), "F2").len(), 4);
     }
-
-    Ok(events)
 }
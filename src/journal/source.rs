@@ -0,0 +1,141 @@
+use std::{fs, io, path::PathBuf};
+
+use time::OffsetDateTime;
+
+use super::{files::overlapping, journal_files_in, journals_path, Event, EventHeader};
+
+/// Somewhere journal files can be read from, the journals directory of this
+/// machine or a remote one
+pub trait JournalSource {
+    /// Names of the journal files like `Journal.2024-09-15T123456.01.log`,
+    /// oldest first
+    fn journal_names(&self) -> Result<Vec<String>, io::Error>;
+
+    /// Contents of the journal file with this name
+    fn read_journal(&self, name: &str) -> Result<String, io::Error>;
+
+    /// Events from all journals overlapping `start..end`.
+    /// Events outside the range are skipped.
+    fn events_between(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Result<Vec<Event>, io::Error> {
+        self.events_between_where(start, end, &|_| true)
+    }
+
+    /// Like [JournalSource::events_between] but only the events whose
+    /// journal line `keep` returns true for
+    fn events_between_where(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        keep: &dyn Fn(&str) -> bool,
+    ) -> Result<Vec<Event>, io::Error> {
+        self.lines_between_where(start, end, keep)?
+            .iter()
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    /// Journal lines within `start..end` that `keep` returns true for
+    fn lines_between_where(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        keep: &dyn Fn(&str) -> bool,
+    ) -> Result<Vec<String>, io::Error> {
+        let mut lines = Vec::new();
+
+        for name in overlapping(&self.journal_names()?, start, end) {
+            for line in self.read_journal(&name)?.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let header: EventHeader = serde_json::from_str(line)?;
+
+                if start <= header.timestamp && header.timestamp < end && keep(line) {
+                    lines.push(line.to_owned());
+                }
+            }
+        }
+
+        Ok(lines)
+    }
+}
+
+/// Journals in a directory of this machine
+#[derive(Debug, Clone)]
+pub struct LocalSource {
+    pub dir: PathBuf,
+}
+
+impl Default for LocalSource {
+    fn default() -> Self {
+        LocalSource {
+            dir: journals_path(),
+        }
+    }
+}
+
+impl JournalSource for LocalSource {
+    fn journal_names(&self) -> Result<Vec<String>, io::Error> {
+        Ok(journal_files_in(&self.dir)?
+            .iter()
+            .filter_map(|p| Some(p.file_name()?.to_str()?.to_owned()))
+            .collect())
+    }
+
+    fn read_journal(&self, name: &str) -> Result<String, io::Error> {
+        // Only the listed journals, never a path to somewhere else
+        if !self.journal_names()?.iter().any(|n| n == name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Not a journal name: {name}"),
+            ));
+        }
+        fs::read_to_string(self.dir.join(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_local_source() {
+        let dir = std::env::temp_dir().join(format!("elite-source-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Journal.2024-09-15T120000.01.log"),
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Shutdown" }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("Journal.2024-09-16T120000.01.log"),
+            r#"{ "timestamp":"2024-09-16T12:00:00Z", "event":"Shutdown" }"#,
+        )
+        .unwrap();
+        fs::write(dir.join("Status.json"), "{}").unwrap();
+
+        let source = LocalSource { dir: dir.clone() };
+        let names = source.journal_names().unwrap();
+        let events = source
+            .events_between(
+                datetime!(2024-09-16 00:00 UTC),
+                datetime!(2024-09-17 00:00 UTC),
+            )
+            .unwrap();
+        let escape = source.read_journal("../Status.json");
+        let status = source.read_journal("Status.json");
+        let journal = source.read_journal("Journal.2024-09-15T120000.01.log");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(names.len(), 2);
+        assert_eq!(events.len(), 1);
+        assert!(escape.is_err());
+        assert!(status.is_err());
+        assert!(journal.is_ok());
+    }
+}
@@ -0,0 +1,423 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use super::Rfc3339;
+
+/// Live ship status, rewritten by the game to Status.json whenever it changes
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct Status {
+    #[serde(with = "Rfc3339", rename = "timestamp")]
+    pub timestamp: OffsetDateTime,
+    /// Bit field, see the `FLAG_` constants. Zero while not in game
+    #[serde(default)]
+    pub flags: u32,
+    /// Odyssey bit field, mostly about being on foot
+    #[serde(default)]
+    pub flags2: u32,
+    /// Missing while on foot or not in game
+    #[serde(default)]
+    pub fuel: Option<Fuel>,
+    /// Tons of cargo carried
+    #[serde(default)]
+    pub cargo: Option<f64>,
+    /// Power distribution, missing while on foot or not in game
+    #[serde(default)]
+    pub pips: Option<Pips>,
+    /// Selected fire group, counted from 0
+    #[serde(default)]
+    pub fire_group: Option<u32>,
+    /// Screen or panel the player is looking at
+    #[serde(default)]
+    pub gui_focus: Option<GuiFocus>,
+    /// Oxygen left on foot from 0 to 1
+    #[serde(default)]
+    pub oxygen: Option<f64>,
+    /// Health on foot from 0 to 1
+    #[serde(default)]
+    pub health: Option<f64>,
+    /// Temperature around the commander on foot in Kelvin
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    /// Symbol of the weapon or tool in hand, like "$humanoid_fists_name;"
+    #[serde(default)]
+    pub selected_weapon: Option<String>,
+    #[serde(rename = "SelectedWeapon_Localised", default)]
+    pub selected_weapon_localised: Option<String>,
+    /// Gravity in g while on foot or near a planet
+    #[serde(default)]
+    pub gravity: Option<f64>,
+    /// Planet the commander is on or near
+    #[serde(default)]
+    pub body_name: Option<String>,
+    /// Target selected in the galaxy map, system map or HUD
+    #[serde(default)]
+    pub destination: Option<Destination>,
+    /// Meters above the surface, or above the average radius in orbital
+    /// cruise
+    #[serde(default)]
+    pub altitude: Option<f64>,
+    /// Degrees near a planet
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    /// Degrees clockwise from north
+    #[serde(default)]
+    pub heading: Option<f64>,
+    /// Meters
+    #[serde(default)]
+    pub planet_radius: Option<f64>,
+    #[serde(default)]
+    pub legal_state: Option<LegalState>,
+    /// Credits the commander has
+    #[serde(default)]
+    pub balance: Option<u64>,
+}
+
+/// Standing with the authorities where the commander is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LegalState {
+    Clean,
+    IllegalCargo,
+    Speeding,
+    Wanted,
+    Hostile,
+    PassengerWanted,
+    Warrant,
+    /// Added in a later version of the game
+    #[serde(other)]
+    Unknown,
+}
+
+/// Difference between two readings of Status.json worth telling about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusChange {
+    LegalState { from: LegalState, to: LegalState },
+    Balance { from: u64, to: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct Destination {
+    /// Address of the system
+    pub system: u64,
+    /// ID of the body in the system, the star when the destination is a
+    /// system
+    pub body: u32,
+    pub name: String,
+    #[serde(rename = "Name_Localised", default)]
+    pub name_localised: Option<String>,
+}
+
+/// Way across the surface of a planet to a target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceCourse {
+    /// Meters along the surface
+    pub distance: f64,
+    /// Degrees clockwise from north to head in
+    pub bearing: f64,
+}
+
+/// Shortest way over a sphere of `radius` meters between two positions
+/// given as latitude and longitude in degrees
+pub fn surface_course(from: (f64, f64), to: (f64, f64), radius: f64) -> SurfaceCourse {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = lon2 - lon1;
+
+    // Haversine formula, stable for short distances
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    let distance = 2.0 * radius * a.sqrt().atan2((1.0 - a).sqrt());
+
+    let bearing = (dlon.sin() * lat2.cos())
+        .atan2(lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos())
+        .to_degrees();
+    SurfaceCourse {
+        distance,
+        bearing: bearing.rem_euclid(360.0),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct Fuel {
+    /// Tons of fuel in the main tank
+    pub fuel_main: f64,
+    /// Tons of fuel in the reservoir feeding the power plant
+    pub fuel_reservoir: f64,
+}
+
+/// Power distributor pips in halves, so each is 0 to 8 and they add up
+/// to 12. Written as `[sys, eng, wep]` like in Status.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(from = "[u8; 3]", into = "[u8; 3]")]
+pub struct Pips {
+    pub sys: u8,
+    pub eng: u8,
+    pub wep: u8,
+}
+
+impl From<[u8; 3]> for Pips {
+    fn from([sys, eng, wep]: [u8; 3]) -> Self {
+        Pips { sys, eng, wep }
+    }
+}
+
+impl From<Pips> for [u8; 3] {
+    fn from(pips: Pips) -> Self {
+        [pips.sys, pips.eng, pips.wep]
+    }
+}
+
+/// Screen or panel in focus, written as its number in Status.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(from = "u8", into = "u8")]
+pub enum GuiFocus {
+    NoFocus,
+    /// Right hand panel
+    InternalPanel,
+    /// Left hand panel
+    ExternalPanel,
+    /// Top panel
+    CommsPanel,
+    /// Bottom panel
+    RolePanel,
+    StationServices,
+    GalaxyMap,
+    SystemMap,
+    Orrery,
+    /// Full Spectrum System scanner
+    Fss,
+    /// Detailed Surface Scanner, called SAA by the game
+    Saa,
+    Codex,
+    /// Added in a later version of the game
+    Other(u8),
+}
+
+impl From<u8> for GuiFocus {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => GuiFocus::NoFocus,
+            1 => GuiFocus::InternalPanel,
+            2 => GuiFocus::ExternalPanel,
+            3 => GuiFocus::CommsPanel,
+            4 => GuiFocus::RolePanel,
+            5 => GuiFocus::StationServices,
+            6 => GuiFocus::GalaxyMap,
+            7 => GuiFocus::SystemMap,
+            8 => GuiFocus::Orrery,
+            9 => GuiFocus::Fss,
+            10 => GuiFocus::Saa,
+            11 => GuiFocus::Codex,
+            other => GuiFocus::Other(other),
+        }
+    }
+}
+
+impl From<GuiFocus> for u8 {
+    fn from(focus: GuiFocus) -> Self {
+        match focus {
+            GuiFocus::NoFocus => 0,
+            GuiFocus::InternalPanel => 1,
+            GuiFocus::ExternalPanel => 2,
+            GuiFocus::CommsPanel => 3,
+            GuiFocus::RolePanel => 4,
+            GuiFocus::StationServices => 5,
+            GuiFocus::GalaxyMap => 6,
+            GuiFocus::SystemMap => 7,
+            GuiFocus::Orrery => 8,
+            GuiFocus::Fss => 9,
+            GuiFocus::Saa => 10,
+            GuiFocus::Codex => 11,
+            GuiFocus::Other(other) => other,
+        }
+    }
+}
+
+pub const FLAG_DOCKED: u32 = 1 << 0;
+pub const FLAG_LANDED: u32 = 1 << 1;
+pub const FLAG_SUPERCRUISE: u32 = 1 << 4;
+pub const FLAG_SCOOPING_FUEL: u32 = 1 << 11;
+pub const FLAG_FSD_MASS_LOCKED: u32 = 1 << 16;
+pub const FLAG_FSD_CHARGING: u32 = 1 << 17;
+/// Main tank below 25%
+pub const FLAG_LOW_FUEL: u32 = 1 << 19;
+/// Heat above 100%
+pub const FLAG_OVERHEATING: u32 = 1 << 20;
+pub const FLAG_IN_DANGER: u32 = 1 << 22;
+pub const FLAG_BEING_INTERDICTED: u32 = 1 << 23;
+
+pub const FLAG2_ON_FOOT: u32 = 1 << 0;
+pub const FLAG2_IN_TAXI: u32 = 1 << 1;
+pub const FLAG2_IN_MULTICREW: u32 = 1 << 2;
+/// On foot in the concourse or hangar of a station
+pub const FLAG2_ON_FOOT_IN_STATION: u32 = 1 << 3;
+/// On foot on a planet, at a settlement or out in the open
+pub const FLAG2_ON_FOOT_ON_PLANET: u32 = 1 << 4;
+pub const FLAG2_AIM_DOWN_SIGHT: u32 = 1 << 5;
+pub const FLAG2_LOW_OXYGEN: u32 = 1 << 6;
+pub const FLAG2_LOW_HEALTH: u32 = 1 << 7;
+pub const FLAG2_COLD: u32 = 1 << 8;
+pub const FLAG2_HOT: u32 = 1 << 9;
+pub const FLAG2_VERY_COLD: u32 = 1 << 10;
+pub const FLAG2_VERY_HOT: u32 = 1 << 11;
+pub const FLAG2_GLIDE_MODE: u32 = 1 << 12;
+pub const FLAG2_ON_FOOT_IN_HANGAR: u32 = 1 << 13;
+/// On foot in the concourse of a station or settlement
+pub const FLAG2_ON_FOOT_SOCIAL_SPACE: u32 = 1 << 14;
+/// On foot outside, not in any building
+pub const FLAG2_ON_FOOT_EXTERIOR: u32 = 1 << 15;
+pub const FLAG2_BREATHABLE_ATMOSPHERE: u32 = 1 << 16;
+
+impl Status {
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & flag != 0
+    }
+
+    /// Whether an Odyssey flag is set, see the `FLAG2_` constants
+    pub fn has_flag2(&self, flag: u32) -> bool {
+        self.flags2 & flag != 0
+    }
+
+    pub fn on_foot(&self) -> bool {
+        self.has_flag2(FLAG2_ON_FOOT)
+    }
+
+    /// Changes since the `previous` reading, fields missing from either
+    /// aren't compared
+    pub fn changes_since(&self, previous: &Status) -> Vec<StatusChange> {
+        let mut changes = Vec::new();
+
+        if let (Some(from), Some(to)) = (previous.legal_state, self.legal_state) {
+            if from != to {
+                changes.push(StatusChange::LegalState { from, to });
+            }
+        }
+        if let (Some(from), Some(to)) = (previous.balance, self.balance) {
+            if from != to {
+                changes.push(StatusChange::Balance { from, to });
+            }
+        }
+
+        changes
+    }
+
+    /// Way to a target latitude and longitude in degrees on the planet
+    /// below, `None` when not near one
+    pub fn course_to(&self, latitude: f64, longitude: f64) -> Option<SurfaceCourse> {
+        Some(surface_course(
+            (self.latitude?, self.longitude?),
+            (latitude, longitude),
+            self.planet_radius?,
+        ))
+    }
+
+    pub fn low_fuel(&self) -> bool {
+        self.has_flag(FLAG_LOW_FUEL)
+    }
+
+    pub fn overheating(&self) -> bool {
+        self.has_flag(FLAG_OVERHEATING)
+    }
+
+    /// The game is running and a commander is loaded
+    pub fn in_game(&self) -> bool {
+        self.flags != 0 || self.flags2 != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let status: Status = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Status", "Flags":18415880, "Flags2":0, "Pips":[4,8,0], "FireGroup":0, "GuiFocus":0, "Fuel":{ "FuelMain":3.2, "FuelReservoir":0.5 }, "Cargo":12.0, "LegalState":"Clean" }"#,
+        )
+        .unwrap();
+        assert!(status.low_fuel());
+        assert!(status.overheating());
+        assert!(!status.has_flag(FLAG_DOCKED));
+        assert_eq!(status.fuel.unwrap().fuel_main, 3.2);
+        assert_eq!(
+            status.pips,
+            Some(Pips {
+                sys: 4,
+                eng: 8,
+                wep: 0
+            })
+        );
+        assert_eq!(status.gui_focus, Some(GuiFocus::NoFocus));
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["Pips"], serde_json::json!([4, 8, 0]));
+        assert_eq!(json["GuiFocus"], 0);
+
+        let status: Status = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Status", "Flags":16842765, "Pips":[4,4,4], "FireGroup":1, "GuiFocus":9 }"#,
+        )
+        .unwrap();
+        assert_eq!(status.fire_group, Some(1));
+        assert_eq!(status.gui_focus, Some(GuiFocus::Fss));
+
+        let status: Status = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Status", "Flags":0, "Flags2":98321, "Oxygen":1.0, "Health":0.8, "Temperature":293.4, "SelectedWeapon":"$humanoid_fists_name;", "SelectedWeapon_Localised":"Unarmed", "Gravity":0.17, "LegalState":"Clean", "BodyName":"Dav's Hope", "Balance":1000000 }"#,
+        )
+        .unwrap();
+        assert!(status.in_game());
+        assert!(status.on_foot());
+        assert!(status.has_flag2(FLAG2_ON_FOOT_ON_PLANET));
+        assert!(!status.has_flag2(FLAG2_ON_FOOT_IN_STATION));
+        assert!(status.has_flag2(FLAG2_BREATHABLE_ATMOSPHERE));
+        assert_eq!(status.health, Some(0.8));
+        assert_eq!(status.selected_weapon_localised.as_deref(), Some("Unarmed"));
+        assert_eq!(status.body_name.as_deref(), Some("Dav's Hope"));
+        assert!(status.course_to(10.0, 20.0).is_none());
+
+        let wanted: Status = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:00:05Z", "event":"Status", "Flags":0, "Flags2":98321, "LegalState":"Wanted", "Balance":999000 }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            wanted.changes_since(&status),
+            [
+                StatusChange::LegalState {
+                    from: LegalState::Clean,
+                    to: LegalState::Wanted
+                },
+                StatusChange::Balance {
+                    from: 1000000,
+                    to: 999000
+                },
+            ]
+        );
+        assert!(wanted.changes_since(&wanted).is_empty());
+
+        let status: Status = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Status", "Flags":2097152, "Latitude":0.0, "Longitude":0.0, "Heading":90, "Altitude":1500, "BodyName":"Hip 1 A 1", "PlanetRadius":1000000.0, "Destination":{ "System":2869440882065, "Body":12, "Name":"Hip 1 A 1" } }"#,
+        )
+        .unwrap();
+        assert_eq!(status.destination.as_ref().unwrap().body, 12);
+        assert_eq!(status.altitude, Some(1500.0));
+        let north = status.course_to(1.0, 0.0).unwrap();
+        assert!((north.distance - 17453.3).abs() < 0.1);
+        assert!(north.bearing.abs() < 1e-9);
+        let west = status.course_to(0.0, -1.0).unwrap();
+        assert!((west.bearing - 270.0).abs() < 1e-9);
+
+        let status: Status = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Status", "Flags":0 }"#,
+        )
+        .unwrap();
+        assert!(!status.in_game());
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    files::journal_order, journal_files, journal_files_in, latest_journal_path, Event, GameVersion,
+    JournalParser,
+};
+
+/// How far a [JournalTail] got, saved so it can pick up from there after
+/// a restart instead of reading events again
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TailPosition {
+    pub path: PathBuf,
+    /// Bytes of complete lines read
+    pub offset: u64,
+    /// Complete lines read
+    pub line: u64,
+}
+
+impl TailPosition {
+    /// Position saved at `path`, `None` if nothing was saved yet
+    pub fn load(path: &Path) -> Result<Option<Self>, io::Error> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Save to `path`, replacing the previous position at once so a crash
+    /// can't leave half of it behind
+    pub fn save(&self, path: &Path) -> Result<(), io::Error> {
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, serde_json::to_string(self)?)?;
+        fs::rename(temp, path)
+    }
+}
+
+/// Follows the newest journal file as the game writes to it.
+/// Switches over to the next journal file once the game starts one.
+#[derive(Debug)]
+pub struct JournalTail {
+    path: PathBuf,
+    reader: BufReader<File>,
+    parser: JournalParser,
+    /// Line that hasn't been completely written yet
+    partial: String,
+    offset: u64,
+    line: u64,
+}
+
+impl JournalTail {
+    /// Start at the end of the newest journal, skipping events already written
+    pub fn new() -> Result<Self, io::Error> {
+        let mut tail = Self::open(latest_journal_path()?)?;
+
+        // Counted rather than seeked past to know the line number
+        while tail.reader.read_line(&mut tail.partial)? > 0 && tail.partial.ends_with('\n') {
+            tail.offset += tail.partial.len() as u64;
+            tail.line += 1;
+            tail.partial.clear();
+        }
+
+        Ok(tail)
+    }
+
+    /// Continue where a tail left off, see [JournalTail::position]. Events
+    /// written since are returned by the next poll, including the ones in
+    /// journals the game started in the meantime. If the journal is gone
+    /// the next one after it is read from the start, without one it starts
+    /// like [JournalTail::new].
+    pub fn resume(position: &TailPosition) -> Result<Self, io::Error> {
+        if !position.path.exists() {
+            let dir = position.path.parent().unwrap_or(Path::new(""));
+            let saved = journal_order(&position.path);
+            let next = journal_files_in(dir)?
+                .into_iter()
+                .find(|path| journal_order(path) > saved);
+            return match next {
+                Some(path) => Self::open(path),
+                None => Self::new(),
+            };
+        }
+        let mut tail = Self::open(position.path.to_owned())?;
+
+        // A journal shorter than before was replaced and is read from the top
+        if tail.reader.get_ref().metadata()?.len() >= position.offset {
+            tail.reader.seek(SeekFrom::Start(position.offset))?;
+            tail.offset = position.offset;
+            tail.line = position.line;
+        }
+
+        Ok(tail)
+    }
+
+    /// Open a journal at its start, only reading the Fileheader for the
+    /// game version
+    fn open(path: PathBuf) -> Result<Self, io::Error> {
+        let mut reader = BufReader::new(File::open(&path)?);
+
+        let mut parser = JournalParser::default();
+        let mut header = String::new();
+        if reader.read_line(&mut header)? > 0 {
+            parser.parse_line(header.trim_end())?;
+        }
+        reader.seek(SeekFrom::Start(0))?;
+
+        Ok(JournalTail {
+            path,
+            reader,
+            parser,
+            partial: String::new(),
+            offset: 0,
+            line: 0,
+        })
+    }
+
+    /// Path of the journal currently being followed
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Game version of the journal currently being followed
+    pub fn version(&self) -> Option<&GameVersion> {
+        self.parser.version()
+    }
+
+    /// Where the tail is in the journal, to [resume](JournalTail::resume)
+    /// from later
+    pub fn position(&self) -> TailPosition {
+        TailPosition {
+            path: self.path.clone(),
+            offset: self.offset,
+            line: self.line,
+        }
+    }
+
+    /// Events written since the last poll
+    pub fn poll(&mut self) -> Result<Vec<Event>, io::Error> {
+        Ok(self
+            .poll_raw()?
+            .into_iter()
+            .map(|(event, _)| event)
+            .collect())
+    }
+
+    /// Like [JournalTail::poll] but each event with its entry as written to
+    /// the journal, including the fields [Event] doesn't have
+    pub fn poll_raw(&mut self) -> Result<Vec<(Event, Value)>, io::Error> {
+        let mut events = self.read_new()?;
+
+        // Every journal started since, in case there was more than one
+        for path in journal_files()? {
+            if journal_order(&path) > journal_order(&self.path) {
+                *self = Self::open(path)?;
+                events.extend(self.read_new()?);
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn read_new(&mut self) -> Result<Vec<(Event, Value)>, io::Error> {
+        let mut events = Vec::new();
+
+        while self.reader.read_line(&mut self.partial)? > 0 && self.partial.ends_with('\n') {
+            let line = self.partial.trim_end();
+            events.push((self.parser.parse_line(line)?, serde_json::from_str(line)?));
+            self.offset += self.partial.len() as u64;
+            self.line += 1;
+            self.partial.clear();
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume() {
+        let dir = std::env::temp_dir().join(format!("elite-tail-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Journal.2024-09-15T120000.01.log");
+        let lines = [
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }"#,
+            r#"{ "timestamp":"2024-09-15T12:00:05Z", "event":"Music", "MusicTrack":"MainMenu" }"#,
+            r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"Shutdown" }"#,
+        ];
+        fs::write(&path, format!("{}\r\n{}\r\n", lines[0], lines[1])).unwrap();
+
+        let mut tail = JournalTail::open(path.clone()).unwrap();
+        let first = tail.read_new().unwrap();
+        let position = tail.position();
+        let state = dir.join("tail.json");
+        position.save(&state).unwrap();
+
+        // Half of the next line is written when the tail is restarted
+        fs::write(
+            &path,
+            format!("{}\r\n{}\r\n{}", lines[0], lines[1], &lines[2][..20]),
+        )
+        .unwrap();
+        let mut resumed =
+            JournalTail::resume(&TailPosition::load(&state).unwrap().unwrap()).unwrap();
+        let before = resumed.read_new().unwrap();
+        fs::write(
+            &path,
+            format!("{}\r\n{}\r\n{}\r\n", lines[0], lines[1], lines[2]),
+        )
+        .unwrap();
+        let after = resumed.read_new().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[1].1["MusicTrack"], "MainMenu");
+        assert_eq!(position.line, 2);
+        assert!(before.is_empty());
+        assert!(matches!(after[..], [(Event::Shutdown { .. }, _)]));
+        assert!(resumed.version().is_some());
+        assert_eq!(resumed.position().line, 3);
+    }
+
+    #[test]
+    fn test_resume_removed() {
+        let dir = std::env::temp_dir().join(format!("elite-tail-removed-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let lines = [
+            r#"{ "timestamp":"2024-09-16T12:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }"#,
+            r#"{ "timestamp":"2024-09-16T12:00:05Z", "event":"Music", "MusicTrack":"MainMenu" }"#,
+        ];
+        let next = dir.join("Journal.2024-09-16T120000.01.log");
+        fs::write(&next, format!("{}\r\n{}\r\n", lines[0], lines[1])).unwrap();
+        // Sorts after the saved journal by name but was started before it
+        fs::write(dir.join("Journal.240914120000.01.log"), "").unwrap();
+        let position = TailPosition {
+            path: dir.join("Journal.2024-09-15T120000.01.log"),
+            offset: 1000,
+            line: 10,
+        };
+
+        let mut resumed = JournalTail::resume(&position).unwrap();
+        let events = resumed.read_new().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(resumed.path(), &next);
+        assert_eq!(events.len(), 2);
+        assert_eq!(resumed.position().line, 2);
+    }
+}
@@ -0,0 +1,110 @@
+use time::{macros::datetime, OffsetDateTime};
+
+use super::Event;
+
+/// Galaxy split with the 4.0 update.
+/// Clients still on 3.8 after the split play in the Legacy galaxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Galaxy {
+    Live,
+    Legacy,
+}
+
+const GALAXY_SPLIT: OffsetDateTime = datetime!(2022-11-29 00:00 UTC);
+
+/// Game version a journal was written by, taken from its Fileheader
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameVersion {
+    /// Version in the form of "4.0.0.1904"
+    pub gameversion: String,
+    pub build: String,
+    pub odyssey: bool,
+    pub galaxy: Galaxy,
+}
+
+impl GameVersion {
+    pub fn from_fileheader(event: &Event) -> Option<Self> {
+        let Event::Fileheader {
+            timestamp,
+            odyssey,
+            gameversion,
+            build,
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        let mut version = GameVersion {
+            gameversion: gameversion.to_owned(),
+            build: build.trim().to_owned(),
+            odyssey: *odyssey,
+            galaxy: Galaxy::Live,
+        };
+        if *timestamp >= GALAXY_SPLIT && version.major().is_some_and(|m| m < 4) {
+            version.galaxy = Galaxy::Legacy;
+        }
+
+        Some(version)
+    }
+
+    pub fn major(&self) -> Option<u32> {
+        self.gameversion.split('.').next()?.parse().ok()
+    }
+
+    pub fn minor(&self) -> Option<u32> {
+        self.gameversion.split('.').nth(1)?.parse().ok()
+    }
+
+    /// Whether the journal was written by at least version `major.minor`
+    pub fn at_least(&self, major: u32, minor: u32) -> bool {
+        match (self.major(), self.minor()) {
+            (Some(ma), Some(mi)) => (ma, mi) >= (major, minor),
+            (Some(ma), None) => ma > major,
+            _ => false,
+        }
+    }
+}
+
+/// Parses journal lines while remembering the game version of the file
+/// so later events can be interpreted accordingly
+#[derive(Debug, Default)]
+pub struct JournalParser {
+    version: Option<GameVersion>,
+}
+
+impl JournalParser {
+    pub fn parse_line(&mut self, line: &str) -> Result<Event, serde_json::Error> {
+        let event: Event = serde_json::from_str(line)?;
+
+        if let Some(version) = GameVersion::from_fileheader(&event) {
+            self.version = Some(version);
+        }
+
+        Ok(event)
+    }
+
+    /// Version from the last Fileheader, if one was parsed
+    pub fn version(&self) -> Option<&GameVersion> {
+        self.version.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_version() {
+        let mut parser = JournalParser::default();
+        parser
+            .parse_line(r#"{ "timestamp":"2023-01-05T10:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":false, "gameversion":"3.8.0.407", "build":"r286858/r0 " }"#)
+            .unwrap();
+
+        let version = parser.version().unwrap();
+        assert_eq!(version.galaxy, Galaxy::Legacy);
+        assert!(version.at_least(3, 8));
+        assert!(!version.at_least(4, 0));
+        assert_eq!(version.build, "r286858/r0");
+    }
+}
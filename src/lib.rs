@@ -1,3 +1,77 @@
 #![warn(missing_debug_implementations)]
 
+#[cfg(feature = "backup")]
+pub mod backup;
+pub mod bgs;
+pub mod codex;
+pub mod colonisation;
+pub mod community_goals;
+pub mod conflict_zones;
+pub mod cqc;
+pub mod credits;
+pub mod crew;
+pub mod crime;
+pub mod cycle;
+#[cfg(feature = "edsm")]
+pub mod edsm;
+pub mod engineers;
+pub mod exobiology;
+pub mod exploration;
+#[cfg(feature = "ffi")]
+mod ffi;
+pub mod firsts;
+pub mod fleet;
+pub mod friends;
+pub mod ground;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "http")]
+pub mod http;
 pub mod journal;
+#[cfg(feature = "fs")]
+pub mod macro_files;
+pub mod map;
+#[cfg(feature = "market")]
+pub mod market;
+pub mod materials;
+pub mod mining;
+pub mod networth;
+#[cfg(feature = "notifications")]
+pub mod notifications;
+pub mod odyssey;
+pub mod passengers;
+pub mod playtime;
+pub mod powerplay;
+#[cfg(feature = "fs")]
+pub mod processor;
+#[cfg(feature = "python")]
+mod python;
+pub mod ranks;
+pub mod rares;
+pub mod rebuy;
+pub mod regions;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod reputation;
+pub mod route;
+#[cfg(feature = "rules")]
+pub mod rules;
+#[cfg(feature = "screenshots")]
+pub mod screenshots;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod ships;
+pub mod social;
+#[cfg(feature = "spansh")]
+pub mod spansh;
+pub mod state;
+pub mod stats;
+pub mod survey;
+pub mod synthesis;
+pub mod system_states;
+pub mod tech_broker;
+pub mod thargoid;
+#[cfg(feature = "tz")]
+pub mod timezone;
+#[cfg(feature = "twitch")]
+pub mod twitch;
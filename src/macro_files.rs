@@ -0,0 +1,267 @@
+//! Game state and events as `key=value` lines in plain text files, for
+//! macro tools like VoiceAttack that can read files but not JSON.
+//!
+//! `state.txt` holds the [GameState] with snake case keys and the fields of
+//! Status.json prefixed with `status.`, e.g. `status.Fuel.FuelMain=31.2`,
+//! plus `flag.` keys for the known status flags. The latest event of each
+//! selected kind is written to `<Event>.txt` with the field names of the
+//! journal. Nested fields are joined with dots and lists of plain values
+//! with commas. Files are replaced at once, so they're never read half
+//! written.
+//!
+//! Selected events can also be written as one line each to a named pipe,
+//! their fields separated by tabs.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Sender},
+    thread::{self, JoinHandle},
+};
+
+use serde_json::Value;
+
+use crate::{
+    journal::{self, Event, Status},
+    processor::{EventProcessor, ProcessorResult},
+    state::GameState,
+};
+
+/// Status flags written as `flag.<name>=true|false`
+const FLAGS: [(u32, &str); 10] = [
+    (journal::FLAG_DOCKED, "docked"),
+    (journal::FLAG_LANDED, "landed"),
+    (journal::FLAG_SUPERCRUISE, "supercruise"),
+    (journal::FLAG_SCOOPING_FUEL, "scooping_fuel"),
+    (journal::FLAG_FSD_MASS_LOCKED, "fsd_mass_locked"),
+    (journal::FLAG_FSD_CHARGING, "fsd_charging"),
+    (journal::FLAG_LOW_FUEL, "low_fuel"),
+    (journal::FLAG_OVERHEATING, "overheating"),
+    (journal::FLAG_IN_DANGER, "in_danger"),
+    (journal::FLAG_BEING_INTERDICTED, "being_interdicted"),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct MacroFilesConfig {
+    /// Directory for `state.txt` and the event files
+    pub dir: Option<PathBuf>,
+    /// Named pipe or file to write selected events to
+    pub pipe: Option<PathBuf>,
+    /// Kinds of events to write, like "FSDJump"
+    pub events: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct MacroFiles {
+    config: MacroFilesConfig,
+    state: GameState,
+    status: Option<Status>,
+    /// Last contents of `state.txt`, to skip rewriting it unchanged
+    written: String,
+    /// Lines for the pipe, written on a thread of their own as opening a
+    /// pipe waits for a reader
+    pipe: Option<(Sender<String>, JoinHandle<()>)>,
+}
+
+/// Add the `key=value` pairs of a JSON value, dotted below `prefix`
+fn key_values(prefix: &str, value: &Value, pairs: &mut Vec<(String, String)>) {
+    let key = |name: &str| match prefix {
+        "" => name.to_owned(),
+        prefix => format!("{prefix}.{name}"),
+    };
+
+    match value {
+        Value::Object(map) => {
+            for (name, value) in map {
+                key_values(&key(name), value, pairs);
+            }
+        }
+        Value::Array(items) if items.iter().any(|i| i.is_object() || i.is_array()) => {
+            for (i, item) in items.iter().enumerate() {
+                key_values(&key(&i.to_string()), item, pairs);
+            }
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(plain).collect();
+            pairs.push((prefix.to_owned(), items.join(",")));
+        }
+        value => pairs.push((prefix.to_owned(), plain(value))),
+    }
+}
+
+/// Text of a value kept to a single line, empty when missing
+fn plain(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.replace(['\r', '\n', '\t'], " "),
+        other => other.to_string(),
+    }
+}
+
+/// Replace the file at `path` at once
+fn replace(path: &Path, contents: &str) -> Result<(), io::Error> {
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, contents)?;
+    fs::rename(temp, path)
+}
+
+impl MacroFiles {
+    pub fn new(config: MacroFilesConfig) -> Result<Self, io::Error> {
+        if let Some(dir) = &config.dir {
+            fs::create_dir_all(dir)?;
+        }
+
+        let pipe = config.pipe.clone().map(|path| {
+            let (sender, receiver) = mpsc::channel::<String>();
+            let writer = thread::spawn(move || {
+                for line in receiver {
+                    // Lines go missing while nothing reads the pipe
+                    let written = OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(&path)
+                        .and_then(|mut pipe| pipe.write_all(line.as_bytes()));
+                    if let Err(e) = written {
+                        eprintln!("Can't write to {}: {e}", path.display());
+                    }
+                }
+            });
+            (sender, writer)
+        });
+
+        Ok(MacroFiles {
+            config,
+            state: GameState::default(),
+            status: None,
+            written: String::new(),
+            pipe,
+        })
+    }
+
+    /// Update the state without writing anything, e.g. with events from
+    /// earlier in the session
+    pub fn catch_up(&mut self, event: &Event) {
+        self.state.update(event);
+    }
+
+    fn write_state(&mut self) -> Result<(), io::Error> {
+        let Some(dir) = &self.config.dir else {
+            return Ok(());
+        };
+
+        let mut pairs = Vec::new();
+        key_values("", &serde_json::to_value(&self.state)?, &mut pairs);
+        if let Some(status) = &self.status {
+            key_values("status", &serde_json::to_value(status)?, &mut pairs);
+            for (flag, name) in FLAGS {
+                pairs.push((format!("flag.{name}"), status.has_flag(flag).to_string()));
+            }
+        }
+        let contents: String = pairs
+            .iter()
+            .map(|(key, value)| format!("{key}={value}\n"))
+            .collect();
+
+        if contents != self.written {
+            replace(&dir.join("state.txt"), &contents)?;
+            self.written = contents;
+        }
+        Ok(())
+    }
+}
+
+impl EventProcessor for MacroFiles {
+    fn name(&self) -> &str {
+        "files"
+    }
+
+    fn on_event(&mut self, event: &Event) -> ProcessorResult {
+        self.state.update(event);
+        self.write_state()?;
+
+        let json = serde_json::to_value(event)?;
+        let kind = json["event"].as_str().unwrap_or_default();
+        if !self.config.events.iter().any(|e| e == kind) {
+            return Ok(());
+        }
+        let mut pairs = Vec::new();
+        key_values("", &json, &mut pairs);
+
+        if let Some(dir) = &self.config.dir {
+            let contents: String = pairs
+                .iter()
+                .map(|(key, value)| format!("{key}={value}\n"))
+                .collect();
+            replace(&dir.join(format!("{kind}.txt")), &contents)?;
+        }
+        if let Some((sender, _)) = &self.pipe {
+            let fields: Vec<String> = pairs
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect();
+            sender.send(format!("{}\n", fields.join("\t")))?;
+        }
+
+        Ok(())
+    }
+
+    fn on_status(&mut self, status: &Status) -> ProcessorResult {
+        self.status = Some(status.clone());
+        Ok(self.write_state()?)
+    }
+
+    fn on_shutdown(&mut self) -> ProcessorResult {
+        // Lets the writer finish the lines still queued
+        if let Some((sender, writer)) = self.pipe.take() {
+            drop(sender);
+            writer.join().map_err(|_| "Pipe writer panicked")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_line;
+
+    #[test]
+    fn test_macro_files() {
+        let dir = std::env::temp_dir().join(format!("elite-macro-files-{}", std::process::id()));
+        let mut files = MacroFiles::new(MacroFilesConfig {
+            dir: Some(dir.clone()),
+            pipe: Some(dir.join("events.pipe")),
+            events: vec!["FSDJump".to_owned()],
+        })
+        .unwrap();
+
+        for line in [
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Music", "MusicTrack":"MainMenu" }"#,
+            r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "JumpDist":10.2, "FuelUsed":1.1, "FuelLevel":30.0 }"#,
+        ] {
+            files.on_event(&parse_line(line).unwrap()).unwrap();
+        }
+        let status = serde_json::from_str(
+            r#"{ "timestamp":"2024-09-15T12:05:01Z", "event":"Status", "Flags":16, "Fuel":{ "FuelMain":30.0, "FuelReservoir":0.5 } }"#,
+        )
+        .unwrap();
+        files.on_status(&status).unwrap();
+        files.on_shutdown().unwrap();
+
+        let state = fs::read_to_string(dir.join("state.txt")).unwrap();
+        let jump = fs::read_to_string(dir.join("FSDJump.txt")).unwrap();
+        let pipe = fs::read_to_string(dir.join("events.pipe")).unwrap();
+        let music = dir.join("Music.txt").exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(state.contains("\nsystem=Sol\n"));
+        assert!(state.contains("\nstar_pos=0.0,0.0,0.0\n"));
+        assert!(state.contains("\nstatus.Fuel.FuelMain=30.0\n"));
+        assert!(state.contains("\nflag.supercruise=true\n"));
+        assert!(jump.contains("\nStarSystem=Sol\n"));
+        assert!(pipe.contains("\tStarSystem=Sol\t"));
+        assert_eq!(pipe.lines().count(), 1);
+        assert!(!music);
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::journal::Event;
+
+/// Arrival in a system
+#[derive(Debug, Clone)]
+pub struct Visit {
+    pub timestamp: OffsetDateTime,
+    pub system: String,
+    /// Galactic coordinates in light years relative to Sol
+    pub star_pos: [f64; 3],
+}
+
+/// Systems jumped to in order, for drawing maps of the commander's travels
+#[derive(Debug, Default)]
+pub struct TravelMap {
+    pub visits: Vec<Visit>,
+}
+
+impl TravelMap {
+    pub fn update(&mut self, event: &Event) {
+        if let Event::FSDJump {
+            timestamp,
+            star_system,
+            star_pos,
+            ..
+        } = event
+        {
+            self.visits.push(Visit {
+                timestamp: *timestamp,
+                system: star_system.to_owned(),
+                star_pos: *star_pos,
+            });
+        }
+    }
+
+    /// GeoJSON FeatureCollection with the path travelled as a LineString and
+    /// every visit as a Point.
+    /// Coordinates are the galactic x, y and z instead of longitude, latitude
+    /// and altitude.
+    pub fn to_geojson(&self) -> Value {
+        let path = json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "LineString",
+                "coordinates": self.visits.iter().map(|v| v.star_pos).collect::<Vec<_>>(),
+            },
+            "properties": { "name": "Travel history" },
+        });
+        let points = self.visits.iter().map(|visit| {
+            json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": visit.star_pos },
+                "properties": {
+                    "name": visit.system,
+                    "timestamp": visit.timestamp.format(&Rfc3339).ok(),
+                },
+            })
+        });
+
+        json!({
+            "type": "FeatureCollection",
+            "features": std::iter::once(path).chain(points).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Top down view of the galactic plane, x to the right and z up like the
+    /// galaxy map in game
+    pub fn to_svg(&self, size: u32) -> String {
+        const MARGIN: f64 = 10.0;
+
+        let xs = self.visits.iter().map(|v| v.star_pos[0]);
+        let zs = self.visits.iter().map(|v| v.star_pos[2]);
+        let (min_x, max_x) = xs.fold((f64::MAX, f64::MIN), |(lo, hi), x| (lo.min(x), hi.max(x)));
+        let (min_z, max_z) = zs.fold((f64::MAX, f64::MIN), |(lo, hi), z| (lo.min(z), hi.max(z)));
+        // Keep the aspect ratio and avoid dividing by zero for a single system
+        let span = (max_x - min_x).max(max_z - min_z).max(1.0);
+        let scale = (size as f64 - 2.0 * MARGIN) / span;
+        // Center the travels in the square
+        let offset_x = (span - (max_x - min_x)) / 2.0;
+        let offset_z = (span - (max_z - min_z)) / 2.0;
+        let project = |[x, _, z]: [f64; 3]| {
+            (
+                MARGIN + (x - min_x + offset_x) * scale,
+                size as f64 - MARGIN - (z - min_z + offset_z) * scale,
+            )
+        };
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#
+        );
+        svg.push_str(r##"<rect width="100%" height="100%" fill="#000"/>"##);
+
+        let points: Vec<String> = self
+            .visits
+            .iter()
+            .map(|v| {
+                let (x, y) = project(v.star_pos);
+                format!("{x:.1},{y:.1}")
+            })
+            .collect();
+        let _ = write!(
+            svg,
+            r##"<polyline points="{}" fill="none" stroke="#f80" stroke-width="1"/>"##,
+            points.join(" ")
+        );
+
+        for visit in &self.visits {
+            let (x, y) = project(visit.star_pos);
+            let name = visit
+                .system
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            let _ = write!(
+                svg,
+                r##"<circle cx="{x:.1}" cy="{y:.1}" r="2" fill="#fff"><title>{name}</title></circle>"##
+            );
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_geojson() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":5.0, "FuelUsed":1.0, "FuelLevel":20.0 }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"FSDJump", "StarSystem":"Alpha Centauri", "SystemAddress":2, "StarPos":[3.03125,-0.09375,3.15625], "JumpDist":4.4, "FuelUsed":1.0, "FuelLevel":19.0 }
+"#,
+        )
+        .unwrap();
+
+        let mut map = TravelMap::default();
+        for event in &events {
+            map.update(event);
+        }
+        let geojson = map.to_geojson();
+
+        // The path and one point per visit
+        assert_eq!(geojson["features"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            geojson["features"][2]["properties"]["name"],
+            "Alpha Centauri"
+        );
+        assert_eq!(
+            geojson["features"][2]["properties"]["timestamp"],
+            "2024-09-15T12:01:00Z"
+        );
+    }
+}
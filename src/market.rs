@@ -0,0 +1,401 @@
+//! History of commodity prices collected from Market.json
+
+use std::{collections::HashMap, path::Path};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS snapshots (
+    market_id INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    station_name TEXT NOT NULL,
+    star_system TEXT NOT NULL,
+    PRIMARY KEY (market_id, timestamp)
+);
+CREATE TABLE IF NOT EXISTS prices (
+    market_id INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    commodity TEXT NOT NULL,
+    name TEXT NOT NULL,
+    buy_price INTEGER NOT NULL,
+    sell_price INTEGER NOT NULL,
+    mean_price INTEGER NOT NULL,
+    stock INTEGER NOT NULL,
+    demand INTEGER NOT NULL,
+    PRIMARY KEY (market_id, timestamp, commodity),
+    FOREIGN KEY (market_id, timestamp) REFERENCES snapshots (market_id, timestamp)
+);
+CREATE INDEX IF NOT EXISTS prices_commodity ON prices (commodity, timestamp);
+";
+
+/// Prices of a commodity at one market at one time
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricePoint {
+    pub timestamp: OffsetDateTime,
+    pub market_id: u64,
+    pub station_name: String,
+    pub star_system: String,
+    /// Price the market sells for, zero if it doesn't
+    pub buy_price: u64,
+    /// Price the market buys for
+    pub sell_price: u64,
+    pub stock: u64,
+    pub demand: u64,
+}
+
+/// Prices of a commodity at a market in one snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommodityPrice {
+    /// Symbol like "tritium"
+    pub commodity: String,
+    pub name: String,
+    pub buy_price: u64,
+    pub sell_price: u64,
+    pub stock: u64,
+    pub demand: u64,
+}
+
+/// Every price of a market at one time
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketSnapshot {
+    pub timestamp: OffsetDateTime,
+    pub market_id: u64,
+    pub station_name: String,
+    pub star_system: String,
+    pub prices: Vec<CommodityPrice>,
+}
+
+/// Buying a commodity at one station to sell it at another
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeRoute {
+    pub from: String,
+    pub from_system: String,
+    pub to: String,
+    pub to_system: String,
+    pub commodity: String,
+    pub buy_price: u64,
+    pub sell_price: u64,
+    /// Tons to buy, limited by cargo capacity, stock and demand
+    pub units: u64,
+    pub profit: u64,
+    /// Distance between the systems in light years
+    pub distance: f64,
+    /// Jumps needed at the given jump range
+    pub jumps: u32,
+}
+
+/// Most profitable trades between pairs of markets, best first.
+/// Markets in systems without known coordinates are left out.
+pub fn suggest_trades(
+    snapshots: &[MarketSnapshot],
+    positions: &HashMap<String, [f64; 3]>,
+    cargo_capacity: u64,
+    jump_range: f64,
+) -> Vec<TradeRoute> {
+    let mut routes = Vec::new();
+
+    for from in snapshots {
+        let Some(a) = positions.get(&from.star_system) else {
+            continue;
+        };
+        for to in snapshots {
+            let Some(b) = positions.get(&to.star_system) else {
+                continue;
+            };
+            if from.market_id == to.market_id {
+                continue;
+            }
+
+            // Best commodity for this pair
+            let best = from
+                .prices
+                .iter()
+                .filter(|p| p.buy_price > 0 && p.stock > 0)
+                .filter_map(|bought| {
+                    let sold = to
+                        .prices
+                        .iter()
+                        .find(|p| p.commodity == bought.commodity && p.demand > 0)?;
+                    let margin = sold.sell_price.checked_sub(bought.buy_price)?;
+                    let units = cargo_capacity.min(bought.stock).min(sold.demand);
+                    (margin > 0 && units > 0).then_some((bought, sold, units, margin * units))
+                })
+                .max_by_key(|(_, _, _, profit)| *profit);
+
+            if let Some((bought, sold, units, profit)) = best {
+                let distance = a
+                    .iter()
+                    .zip(b)
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+                routes.push(TradeRoute {
+                    from: from.station_name.to_owned(),
+                    from_system: from.star_system.to_owned(),
+                    to: to.station_name.to_owned(),
+                    to_system: to.star_system.to_owned(),
+                    commodity: bought.name.to_owned(),
+                    buy_price: bought.buy_price,
+                    sell_price: sold.sell_price,
+                    units,
+                    profit,
+                    distance,
+                    jumps: match jump_range > 0.0 {
+                        true => (distance / jump_range).ceil() as u32,
+                        false => 0,
+                    },
+                });
+            }
+        }
+    }
+
+    routes.sort_by_key(|route| std::cmp::Reverse(route.profit));
+    routes
+}
+
+/// SQLite database of market snapshots keyed by market ID and time
+#[derive(Debug)]
+pub struct MarketDb {
+    conn: Connection,
+}
+
+impl MarketDb {
+    /// Open or create the database at `path`
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(MarketDb { conn })
+    }
+
+    /// Save the prices of a Market event read from Market.json.
+    /// Returns false for events without prices and snapshots already saved.
+    pub fn record(&mut self, event: &Event) -> rusqlite::Result<bool> {
+        let Event::Market {
+            timestamp,
+            market_id,
+            station_name,
+            star_system,
+            items,
+            ..
+        } = event
+        else {
+            return Ok(false);
+        };
+        if items.is_empty() {
+            return Ok(false);
+        }
+
+        let tx = self.conn.transaction()?;
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO snapshots VALUES (?1, ?2, ?3, ?4)",
+            params![
+                market_id,
+                timestamp.unix_timestamp(),
+                station_name,
+                star_system
+            ],
+        )?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        {
+            let mut insert =
+                tx.prepare("INSERT INTO prices VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)")?;
+            for item in items {
+                insert.execute(params![
+                    market_id,
+                    timestamp.unix_timestamp(),
+                    item.symbol(),
+                    item.name_localised.as_ref().unwrap_or(&item.name),
+                    item.buy_price,
+                    item.sell_price,
+                    item.mean_price,
+                    item.stock,
+                    item.demand,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Prices of a commodity over time, oldest first.
+    /// The commodity is matched against both its symbol like "tritium" and
+    /// its localised name, ignoring case.
+    pub fn price_history(
+        &self,
+        commodity: &str,
+        market_id: Option<u64>,
+    ) -> rusqlite::Result<Vec<PricePoint>> {
+        let mut query = self.conn.prepare(
+            "SELECT p.timestamp, p.market_id, s.station_name, s.star_system,
+                    p.buy_price, p.sell_price, p.stock, p.demand
+             FROM prices p JOIN snapshots s USING (market_id, timestamp)
+             WHERE (p.commodity = lower(?1) OR p.name = ?1 COLLATE NOCASE)
+               AND (?2 IS NULL OR p.market_id = ?2)
+             ORDER BY p.timestamp",
+        )?;
+        let rows = query.query_map(params![commodity, market_id], |row| {
+            Ok(PricePoint {
+                timestamp: OffsetDateTime::from_unix_timestamp(row.get(0)?)
+                    .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+                market_id: row.get(1)?,
+                station_name: row.get(2)?,
+                star_system: row.get(3)?,
+                buy_price: row.get(4)?,
+                sell_price: row.get(5)?,
+                stock: row.get(6)?,
+                demand: row.get(7)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Latest snapshot of every market saved since `since`
+    pub fn latest_snapshots(&self, since: OffsetDateTime) -> rusqlite::Result<Vec<MarketSnapshot>> {
+        let mut query = self.conn.prepare(
+            "SELECT market_id, max(timestamp), station_name, star_system FROM snapshots
+             WHERE timestamp >= ?1 GROUP BY market_id",
+        )?;
+        let mut snapshots = query
+            .query_map([since.unix_timestamp()], |row| {
+                Ok(MarketSnapshot {
+                    market_id: row.get(0)?,
+                    timestamp: OffsetDateTime::from_unix_timestamp(row.get(1)?)
+                        .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+                    station_name: row.get(2)?,
+                    star_system: row.get(3)?,
+                    prices: Vec::new(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut prices = self.conn.prepare(
+            "SELECT commodity, name, buy_price, sell_price, stock, demand FROM prices
+             WHERE market_id = ?1 AND timestamp = ?2",
+        )?;
+        for snapshot in &mut snapshots {
+            snapshot.prices = prices
+                .query_map(
+                    params![snapshot.market_id, snapshot.timestamp.unix_timestamp()],
+                    |row| {
+                        Ok(CommodityPrice {
+                            commodity: row.get(0)?,
+                            name: row.get(1)?,
+                            buy_price: row.get(2)?,
+                            sell_price: row.get(3)?,
+                            stock: row.get(4)?,
+                            demand: row.get(5)?,
+                        })
+                    },
+                )?
+                .collect::<rusqlite::Result<_>>()?;
+        }
+        Ok(snapshots)
+    }
+
+    /// Market ID of the station with this name, from the latest snapshot
+    pub fn find_market(&self, station_name: &str) -> rusqlite::Result<Option<u64>> {
+        self.conn
+            .query_row(
+                "SELECT market_id FROM snapshots WHERE station_name = ?1 COLLATE NOCASE
+                 ORDER BY timestamp DESC LIMIT 1",
+                [station_name],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_line;
+
+    #[test]
+    fn test_price_history() {
+        let mut db = MarketDb::open_in_memory().unwrap();
+        for (timestamp, price) in [
+            ("2024-09-15T12:00:00Z", 50000),
+            ("2024-09-16T12:00:00Z", 52000),
+        ] {
+            let event = parse_line(&format!(
+                r#"{{ "timestamp":"{timestamp}", "event":"Market", "MarketID":3228342528, "StationName":"Jameson Memorial", "StationType":"Orbis", "StarSystem":"Shinrarta Dezhra", "Items":[ {{ "id":128961524, "Name":"$tritium_name;", "Name_Localised":"Tritium", "Category":"$MARKET_category_chemicals;", "BuyPrice":{price}, "SellPrice":48000, "MeanPrice":51000, "StockBracket":2, "DemandBracket":0, "Stock":1000, "Demand":0, "Consumer":false, "Producer":true, "Rare":false }} ] }}"#
+            ))
+            .unwrap();
+            assert!(db.record(&event).unwrap());
+            assert!(!db.record(&event).unwrap());
+        }
+
+        let history = db.price_history("Tritium", None).unwrap();
+        assert_eq!(
+            history.iter().map(|p| p.buy_price).collect::<Vec<_>>(),
+            [50000, 52000]
+        );
+        assert_eq!(
+            db.find_market("jameson memorial").unwrap(),
+            Some(3228342528)
+        );
+        assert!(db.price_history("gold", None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_suggest_trades() {
+        let price = |commodity: &str, buy_price, sell_price, stock, demand| CommodityPrice {
+            commodity: commodity.to_owned(),
+            name: commodity.to_owned(),
+            buy_price,
+            sell_price,
+            stock,
+            demand,
+        };
+        let snapshot = |market_id, system: &str, prices| MarketSnapshot {
+            timestamp: OffsetDateTime::UNIX_EPOCH,
+            market_id,
+            station_name: format!("Station {market_id}"),
+            star_system: system.to_owned(),
+            prices,
+        };
+        let snapshots = [
+            snapshot(
+                1,
+                "A",
+                vec![
+                    price("gold", 9000, 8800, 500, 0),
+                    price("silver", 4000, 3900, 10, 0),
+                ],
+            ),
+            snapshot(
+                2,
+                "B",
+                vec![
+                    price("gold", 0, 10000, 0, 50),
+                    price("silver", 0, 6000, 0, 1000),
+                ],
+            ),
+        ];
+        let positions = HashMap::from([
+            ("A".to_owned(), [0.0; 3]),
+            ("B".to_owned(), [0.0, 0.0, 25.0]),
+        ]);
+
+        let routes = suggest_trades(&snapshots, &positions, 100, 10.0);
+        assert_eq!(routes.len(), 1);
+        // Demand for gold limits it to 50 t, stock of silver to 10 t
+        assert_eq!(routes[0].commodity, "gold");
+        assert_eq!(routes[0].units, 50);
+        assert_eq!(routes[0].profit, 50_000);
+        assert_eq!(routes[0].jumps, 3);
+    }
+}
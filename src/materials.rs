@@ -0,0 +1,247 @@
+//! Engineering materials, the commander's inventory of them and trades at
+//! material traders.
+//!
+//! The materials are read from `materials.csv` next to this file, with the
+//! symbol as written in the journal, the name shown in game, whether it is
+//! raw, manufactured or encoded, the trader category and the grade.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::journal::Event;
+
+const TABLE: &str = include_str!("materials.csv");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaterialType {
+    /// Symbol used in the journal in lower case, e.g. "shieldemitters"
+    pub symbol: &'static str,
+    pub name: &'static str,
+    /// "Raw", "Manufactured" or "Encoded", only traded among each other
+    pub kind: &'static str,
+    pub category: &'static str,
+    pub grade: u32,
+}
+
+/// Every material in the table
+pub fn material_types() -> &'static [MaterialType] {
+    static MATERIALS: OnceLock<Vec<MaterialType>> = OnceLock::new();
+    MATERIALS.get_or_init(|| {
+        TABLE
+            .lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                let [symbol, name, kind, category, grade] = fields[..] else {
+                    panic!("Malformed line in materials.csv: {line}");
+                };
+                MaterialType {
+                    symbol,
+                    name,
+                    kind,
+                    category,
+                    grade: grade.parse().expect("Malformed grade in materials.csv"),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Look up a material by its journal symbol or its name, in any case
+pub fn material_type(material: &str) -> Option<&'static MaterialType> {
+    material_types()
+        .iter()
+        .find(|m| m.symbol.eq_ignore_ascii_case(material) || m.name.eq_ignore_ascii_case(material))
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    match b {
+        0 => a,
+        b => gcd(b, a % b),
+    }
+}
+
+/// How many of `from` a trader takes for how many of `to`.
+/// Trading up a grade costs 6, trading down one gives 3 and changing
+/// category costs another 6. Materials of different kinds can't be traded.
+pub fn trade_rate(from: &MaterialType, to: &MaterialType) -> Option<(u32, u32)> {
+    if from.kind != to.kind || from.symbol == to.symbol {
+        return None;
+    }
+
+    let mut give = 1;
+    let mut receive = 1;
+    if to.grade > from.grade {
+        give *= 6u32.pow(to.grade - from.grade);
+    } else {
+        receive *= 3u32.pow(from.grade - to.grade);
+    }
+    if from.category != to.category {
+        give *= 6;
+    }
+    let divisor = gcd(give, receive);
+    Some((give / divisor, receive / divisor))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaterialTrade {
+    pub give: &'static MaterialType,
+    pub give_count: u32,
+    pub receive: &'static MaterialType,
+    pub receive_count: u32,
+}
+
+/// Materials the commander has, from the Materials snapshot at startup
+/// and every change after it
+#[derive(Debug, Default)]
+pub struct MaterialInventory {
+    /// Count by symbol in lower case
+    pub counts: HashMap<String, u32>,
+}
+
+impl MaterialInventory {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::Materials {
+                raw,
+                manufactured,
+                encoded,
+                ..
+            } => {
+                self.counts = raw
+                    .iter()
+                    .chain(manufactured)
+                    .chain(encoded)
+                    .map(|material| (material.name.to_lowercase(), material.count))
+                    .collect();
+            }
+            Event::MaterialCollected { name, count, .. } => self.add(name, *count),
+            Event::MaterialDiscarded { name, count, .. } => self.remove(name, *count),
+            Event::MaterialTrade { paid, received, .. } => {
+                self.remove(&paid.material, paid.quantity);
+                self.add(&received.material, received.quantity);
+            }
+            Event::EngineerCraft {
+                ingredients: materials,
+                ..
+            }
+            | Event::Synthesis { materials, .. }
+            | Event::TechnologyBroker { materials, .. } => {
+                for material in materials {
+                    self.remove(&material.name, material.count);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add(&mut self, name: &str, count: u32) {
+        *self.counts.entry(name.to_lowercase()).or_default() += count;
+    }
+
+    fn remove(&mut self, name: &str, count: u32) {
+        if let Some(have) = self.counts.get_mut(&name.to_lowercase()) {
+            *have = have.saturating_sub(count);
+        }
+    }
+
+    pub fn count(&self, symbol: &str) -> u32 {
+        self.counts
+            .get(&symbol.to_lowercase())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Trades that get `count` more of `target` giving up as little as
+    /// possible. What a trade costs is measured in grade 1 materials of its
+    /// category, a grade up being worth 6, so trading up is free and down or
+    /// across categories loses value. Returns `None` if the inventory can't
+    /// cover it.
+    pub fn plan(&self, target: &'static MaterialType, count: u32) -> Option<Vec<MaterialTrade>> {
+        let value = |m: &MaterialType, n: u32| u64::from(n) * 6u64.pow(m.grade - 1);
+
+        let mut candidates: Vec<(&'static MaterialType, u32, u32)> = material_types()
+            .iter()
+            .filter(|m| self.count(m.symbol) > 0)
+            .filter_map(|m| trade_rate(m, target).map(|(give, receive)| (m, give, receive)))
+            .collect();
+        // Cheapest per material received first, then the ones there are most of
+        candidates.sort_by_key(|(m, give, receive)| {
+            (
+                value(m, *give) * 1000 / u64::from(*receive),
+                std::cmp::Reverse(self.count(m.symbol)),
+            )
+        });
+
+        let mut trades = Vec::new();
+        let mut missing = count;
+        for (material, give, receive) in candidates {
+            if missing == 0 {
+                break;
+            }
+            let batches = missing
+                .div_ceil(receive)
+                .min(self.count(material.symbol) / give);
+            if batches > 0 {
+                trades.push(MaterialTrade {
+                    give: material,
+                    give_count: batches * give,
+                    receive: target,
+                    receive_count: batches * receive,
+                });
+                missing = missing.saturating_sub(batches * receive);
+            }
+        }
+        (missing == 0).then_some(trades)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_trade_plan() {
+        assert!(material_types().len() > 100);
+        let material = |m| material_type(m).unwrap();
+        assert_eq!(
+            trade_rate(material("carbon"), material("vanadium")),
+            Some((6, 1))
+        );
+        assert_eq!(
+            trade_rate(material("niobium"), material("carbon")),
+            Some((1, 9))
+        );
+        assert_eq!(
+            trade_rate(material("vanadium"), material("iron")),
+            Some((2, 1))
+        );
+        assert_eq!(
+            trade_rate(material("carbon"), material("shieldemitters")),
+            None
+        );
+
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Materials", "Raw":[ { "Name":"carbon", "Count":20 }, { "Name":"iron", "Count":100 } ], "Manufactured":[], "Encoded":[] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"MaterialCollected", "Category":"Raw", "Name":"carbon", "Count":3 }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"MaterialTrade", "MarketID":1, "TraderType":"raw", "Paid":{ "Material":"iron", "Category":"Raw", "Quantity":12 }, "Received":{ "Material":"zinc", "Quantity":2, "Category":"Raw" } }
+"#,
+        )
+        .unwrap();
+        let mut inventory = MaterialInventory::default();
+        for event in &events {
+            inventory.update(event);
+        }
+        assert_eq!(inventory.count("carbon"), 23);
+        assert_eq!(inventory.count("iron"), 88);
+
+        // Carbon is traded up first, the rest comes from iron across categories
+        let plan = inventory.plan(material("vanadium"), 5).unwrap();
+        assert_eq!(plan.len(), 2);
+        assert_eq!((plan[0].give.symbol, plan[0].give_count), ("carbon", 18));
+        assert_eq!((plan[1].give.symbol, plan[1].give_count), ("iron", 72));
+        assert!(inventory.plan(material("vanadium"), 100).is_none());
+    }
+}
@@ -0,0 +1,197 @@
+//! Mining sessions, from the limpets launched to the tons refined.
+//!
+//! A session starts with the first prospector or collector limpet,
+//! prospect, cracked asteroid or refined ton and lasts until the commander
+//! leaves for supercruise, jumps, docks, dies or quits.
+
+use std::collections::BTreeMap;
+
+use time::{Duration, OffsetDateTime};
+
+use crate::journal::Event;
+
+#[derive(Debug, Clone)]
+pub struct MiningSession {
+    pub start: OffsetDateTime,
+    /// Time of the last mining event
+    pub end: OffsetDateTime,
+    pub system: Option<String>,
+    /// Ring dropped at before mining, if any
+    pub ring: Option<String>,
+    pub prospectors: u32,
+    pub collectors: u32,
+    pub prospected: u32,
+    /// Asteroids prospected with high, medium and low content
+    pub content: [u32; 3],
+    /// Motherlodes prospected by core material
+    pub motherlodes: BTreeMap<String, u32>,
+    pub cracked: u32,
+    /// Tons refined by commodity
+    pub refined: BTreeMap<String, u32>,
+}
+
+impl MiningSession {
+    pub fn tons(&self) -> u32 {
+        self.refined.values().sum()
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Tons refined per hour, `None` for sessions too short to tell
+    pub fn tons_per_hour(&self) -> Option<f64> {
+        let hours = self.duration().as_seconds_f64() / 3600.0;
+        (self.duration() >= Duration::minutes(1)).then(|| self.tons() as f64 / hours)
+    }
+}
+
+/// Index into [MiningSession::content] of a symbol like
+/// "$AsteroidMaterialContent_High;"
+fn content_level(symbol: &str) -> Option<usize> {
+    match symbol.strip_prefix("$AsteroidMaterialContent_")? {
+        "High;" => Some(0),
+        "Medium;" => Some(1),
+        "Low;" => Some(2),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Mining {
+    pub sessions: Vec<MiningSession>,
+    active: bool,
+    system: Option<String>,
+    ring: Option<String>,
+}
+
+impl Mining {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump { star_system, .. } | Event::Location { star_system, .. } => {
+                self.active = false;
+                self.system = Some(star_system.clone());
+                self.ring = None;
+            }
+            Event::SupercruiseExit {
+                body, body_type, ..
+            } => {
+                self.ring = body
+                    .clone()
+                    .filter(|_| body_type.as_deref() == Some("PlanetaryRing"));
+            }
+            Event::LaunchDrone { timestamp, r#type } => match r#type.as_str() {
+                "Prospector" => self.session(*timestamp).prospectors += 1,
+                "Collection" => self.session(*timestamp).collectors += 1,
+                _ => {}
+            },
+            Event::ProspectedAsteroid {
+                timestamp,
+                motherlode_material,
+                motherlode_material_localised,
+                content,
+                ..
+            } => {
+                let session = self.session(*timestamp);
+                session.prospected += 1;
+                if let Some(level) = content_level(content) {
+                    session.content[level] += 1;
+                }
+                if let Some(material) = motherlode_material_localised
+                    .as_ref()
+                    .or(motherlode_material.as_ref())
+                {
+                    *session.motherlodes.entry(material.clone()).or_default() += 1;
+                }
+            }
+            Event::AsteroidCracked { timestamp, body } => {
+                let session = self.session(*timestamp);
+                session.cracked += 1;
+                session.ring.get_or_insert_with(|| body.clone());
+            }
+            Event::MiningRefined {
+                timestamp,
+                r#type,
+                type_localised,
+            } => {
+                let name = type_localised.as_ref().unwrap_or(r#type).clone();
+                *self.session(*timestamp).refined.entry(name).or_default() += 1;
+            }
+            Event::SupercruiseEntry { .. }
+            | Event::Docked { .. }
+            | Event::Died { .. }
+            | Event::Shutdown { .. }
+            | Event::Fileheader { .. } => self.active = false,
+            _ => {}
+        }
+    }
+
+    /// Session a mining event belongs to, started by it if none is going on
+    fn session(&mut self, timestamp: OffsetDateTime) -> &mut MiningSession {
+        if !self.active {
+            self.active = true;
+            self.sessions.push(MiningSession {
+                start: timestamp,
+                end: timestamp,
+                system: self.system.clone(),
+                ring: self.ring.clone(),
+                prospectors: 0,
+                collectors: 0,
+                prospected: 0,
+                content: [0; 3],
+                motherlodes: BTreeMap::new(),
+                cracked: 0,
+                refined: BTreeMap::new(),
+            });
+        }
+        let session = self.sessions.last_mut().unwrap();
+        session.end = timestamp;
+        session
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_mining() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"FSDJump", "StarSystem":"HIP 21991", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":10.0, "FuelUsed":1.0, "FuelLevel":30.0 }
+{ "timestamp":"2024-09-15T12:05:00Z", "event":"SupercruiseExit", "StarSystem":"HIP 21991", "SystemAddress":1, "Body":"HIP 21991 5 A Ring", "BodyID":20, "BodyType":"PlanetaryRing" }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"LaunchDrone", "Type":"Prospector" }
+{ "timestamp":"2024-09-15T12:10:05Z", "event":"ProspectedAsteroid", "Materials":[ { "Name":"Platinum", "Proportion":31.5 } ], "Content":"$AsteroidMaterialContent_High;", "Content_Localised":"Material Content: High", "Remaining":100.0 }
+{ "timestamp":"2024-09-15T12:11:00Z", "event":"LaunchDrone", "Type":"Prospector" }
+{ "timestamp":"2024-09-15T12:11:05Z", "event":"ProspectedAsteroid", "Materials":[], "MotherlodeMaterial":"Painite", "Content":"$AsteroidMaterialContent_Low;", "Remaining":100.0 }
+{ "timestamp":"2024-09-15T12:15:00Z", "event":"AsteroidCracked", "Body":"HIP 21991 5 A Ring" }
+{ "timestamp":"2024-09-15T12:16:00Z", "event":"LaunchDrone", "Type":"Collection" }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }
+{ "timestamp":"2024-09-15T12:40:00Z", "event":"MiningRefined", "Type":"$painite_name;", "Type_Localised":"Painite" }
+{ "timestamp":"2024-09-15T12:45:00Z", "event":"SupercruiseEntry", "StarSystem":"HIP 21991" }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"LaunchDrone", "Type":"Repair" }
+"#,
+        )
+        .unwrap();
+        let mut mining = Mining::default();
+        for event in &events {
+            mining.update(event);
+        }
+
+        // Repair limpets don't start a session
+        assert_eq!(mining.sessions.len(), 1);
+        let session = &mining.sessions[0];
+        assert_eq!(session.ring.as_deref(), Some("HIP 21991 5 A Ring"));
+        assert_eq!(session.prospectors, 2);
+        assert_eq!(session.collectors, 1);
+        assert_eq!(session.content, [1, 0, 1]);
+        assert_eq!(session.motherlodes["Painite"], 1);
+        assert_eq!(session.cracked, 1);
+        assert_eq!(session.refined["Platinum"], 2);
+        assert_eq!(session.tons(), 3);
+        // 3 t in half an hour
+        assert_eq!(session.tons_per_hour(), Some(6.0));
+    }
+}
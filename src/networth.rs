@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::{credits::CreditLedger, journal::Event};
+
+/// Assets and credits at one point in time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Assets {
+    pub credits: i64,
+    /// Hull and module value of every owned ship
+    pub ships: u64,
+    /// Purchase price of modules in storage
+    pub stored_modules: u64,
+    /// Credits held by the fleet carrier
+    pub carrier: u64,
+}
+
+impl Assets {
+    pub fn total(&self) -> i64 {
+        self.credits + (self.ships + self.stored_modules + self.carrier) as i64
+    }
+}
+
+/// Credits plus the value of ships, stored modules and the carrier balance.
+/// Ship values come from StoredShips and are replaced by the hull and module
+/// value of each Loadout, so they are only known once the shipyard was opened.
+#[derive(Debug, Default)]
+pub struct NetWorth {
+    pub ledger: CreditLedger,
+    /// Value by ship ID
+    pub ships: BTreeMap<u32, u64>,
+    pub current_ship: Option<u32>,
+    pub stored_modules: u64,
+    pub carrier: u64,
+    /// Net worth after every change, once the credits are known
+    pub history: Vec<(OffsetDateTime, i64)>,
+}
+
+impl NetWorth {
+    pub fn update(&mut self, event: &Event) {
+        let before = self.assets();
+        self.ledger.update(event);
+
+        match event {
+            Event::Loadout {
+                ship_id,
+                hull_value,
+                modules_value,
+                ..
+            } => {
+                self.current_ship = Some(*ship_id);
+                self.ships
+                    .insert(*ship_id, u64::from(*hull_value) + u64::from(*modules_value));
+            }
+            Event::StoredShips {
+                ships_here,
+                ships_remote,
+                ..
+            } => {
+                self.ships.retain(|id, _| Some(*id) == self.current_ship);
+                for ship in ships_here.iter().chain(ships_remote) {
+                    self.ships.insert(ship.ship_id, ship.value);
+                }
+            }
+            Event::StoredModules { items, .. } => {
+                self.stored_modules = items.iter().map(|item| item.buy_price).sum();
+            }
+            Event::ShipyardSell { sell_ship_id, .. } => {
+                self.ships.remove(sell_ship_id);
+            }
+            Event::ShipyardBuy {
+                sell_price: Some(_),
+                ..
+            } => {
+                // The old ship was sold as part of the purchase
+                if let Some(id) = self.current_ship.take() {
+                    self.ships.remove(&id);
+                }
+            }
+            Event::CarrierStats { finance, .. } => {
+                self.carrier = finance.carrier_balance;
+            }
+            Event::CarrierBankTransfer {
+                carrier_balance, ..
+            } => {
+                self.carrier = *carrier_balance;
+            }
+            _ => {}
+        }
+
+        if let Some(assets) = self.assets().filter(|assets| Some(*assets) != before) {
+            self.history.push((event.timestamp(), assets.total()));
+        }
+    }
+
+    /// Current assets, once the credits are known
+    pub fn assets(&self) -> Option<Assets> {
+        Some(Assets {
+            credits: self.ledger.balance()?,
+            ships: self.ships.values().sum(),
+            stored_modules: self.stored_modules,
+            carrier: self.carrier,
+        })
+    }
+
+    pub fn total(&self) -> Option<i64> {
+        self.assets().map(|assets| assets.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_net_worth() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Ship":"Anaconda", "ShipID":1, "GameMode":"Open", "Credits":1000000, "Loan":0 }
+{ "timestamp":"2024-09-15T12:00:05Z", "event":"Loadout", "Ship":"anaconda", "ShipID":1, "ShipName":"", "ShipIdent":"", "HullValue":140000000, "ModulesValue":60000000, "Rebuy":10000000, "Modules":[] }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"StoredShips", "StationName":"Jameson Memorial", "MarketID":128666762, "StarSystem":"Shinrarta Dezhra", "ShipsHere":[ { "ShipID":2, "ShipType":"sidewinder", "Value":30000, "Hot":false } ], "ShipsRemote":[ { "ShipID":3, "ShipType":"krait_mkii", "StarSystem":"Sol", "ShipMarketID":1, "TransferPrice":1000, "TransferTime":600, "Value":50000000, "Hot":false } ] }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"StoredModules", "MarketID":128666762, "StationName":"Jameson Memorial", "StarSystem":"Shinrarta Dezhra", "Items":[ { "Name":"$int_hyperdrive_size5_class5_name;", "StorageSlot":1, "StarSystem":"Shinrarta Dezhra", "MarketID":128666762, "TransferCost":0, "TransferTime":0, "BuyPrice":5000000, "Hot":false } ] }
+{ "timestamp":"2024-09-15T12:03:00Z", "event":"CarrierBankTransfer", "CarrierID":1, "Deposit":500000, "PlayerBalance":500000, "CarrierBalance":2500000 }
+{ "timestamp":"2024-09-15T12:04:00Z", "event":"ShipyardSell", "ShipType":"sidewinder", "SellShipID":2, "ShipPrice":30000, "MarketID":128666762 }
+"#,
+        )
+        .unwrap();
+        let mut net_worth = NetWorth::default();
+        for event in &events {
+            net_worth.update(event);
+        }
+
+        let assets = net_worth.assets().unwrap();
+        assert_eq!(assets.credits, 530_000);
+        assert_eq!(assets.ships, 250_000_000);
+        assert_eq!(assets.stored_modules, 5_000_000);
+        assert_eq!(assets.carrier, 2_500_000);
+        assert_eq!(net_worth.total(), Some(258_030_000));
+        assert_eq!(net_worth.history.len(), 6);
+    }
+}
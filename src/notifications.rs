@@ -0,0 +1,438 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    str::FromStr,
+};
+
+use notify_rust::Notification as DesktopNotification;
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+    credits::credit_change,
+    journal::{Event, Status},
+    rebuy::RebuyMonitor,
+    system_states::SystemStates,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alert {
+    Interdicted,
+    /// Hull health dropped below the configured threshold
+    HullDamage,
+    /// Fleet carrier jump was scheduled or is about to lock down
+    CarrierJump,
+    /// Mission is about to expire
+    MissionExpiry,
+    /// Main tank ran low on fuel
+    LowFuel,
+    Overheating,
+    /// Cargo hold reached the configured limit, e.g. while scooping
+    CargoFull,
+    /// Credits don't cover the configured number of rebuys
+    Rebuy,
+    /// State of a faction changed since the last visit to the system
+    SystemState,
+}
+
+impl Alert {
+    pub const ALL: [Alert; 9] = [
+        Alert::Interdicted,
+        Alert::HullDamage,
+        Alert::CarrierJump,
+        Alert::MissionExpiry,
+        Alert::LowFuel,
+        Alert::Overheating,
+        Alert::CargoFull,
+        Alert::Rebuy,
+        Alert::SystemState,
+    ];
+}
+
+impl FromStr for Alert {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "interdicted" => Ok(Alert::Interdicted),
+            "hull-damage" => Ok(Alert::HullDamage),
+            "carrier-jump" => Ok(Alert::CarrierJump),
+            "mission-expiry" => Ok(Alert::MissionExpiry),
+            "low-fuel" => Ok(Alert::LowFuel),
+            "overheating" => Ok(Alert::Overheating),
+            "cargo-full" => Ok(Alert::CargoFull),
+            "rebuy" => Ok(Alert::Rebuy),
+            "system-state" => Ok(Alert::SystemState),
+            other => Err(format!(
+                "Unknown alert {other:?}, expected one of interdicted, hull-damage, carrier-jump, mission-expiry, low-fuel, overheating, cargo-full, rebuy, system-state"
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NotificationConfig {
+    pub alerts: Vec<Alert>,
+    /// Hull health from 0 to 1 below which damage is reported
+    pub hull_threshold: f32,
+    /// How long before expiry missions are warned about
+    pub mission_warning: Duration,
+    /// Tons in the main tank below which fuel is low.
+    /// Without it the game's own warning at 25% is used.
+    pub fuel_threshold: Option<f64>,
+    /// Tons of cargo at which the hold counts as full
+    pub cargo_limit: Option<f64>,
+    /// Number of rebuys the credits should cover
+    pub min_rebuys: u64,
+    /// How long a condition has to be over before it is notified again
+    pub rearm_after: Duration,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        NotificationConfig {
+            alerts: Alert::ALL.to_vec(),
+            hull_threshold: 0.5,
+            mission_warning: Duration::hours(1),
+            fuel_threshold: None,
+            cargo_limit: None,
+            min_rebuys: 1,
+            rearm_after: Duration::minutes(1),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Notification {
+    pub summary: String,
+    pub body: String,
+}
+
+impl Notification {
+    fn new(summary: &str, body: String) -> Self {
+        Notification {
+            summary: summary.to_owned(),
+            body,
+        }
+    }
+
+    /// Show as a desktop notification
+    pub fn show(&self) -> Result<(), notify_rust::error::Error> {
+        DesktopNotification::new()
+            .appname("Elite Dangerous")
+            .summary(&self.summary)
+            .body(&self.body)
+            .show()
+            .map(|_| ())
+    }
+}
+
+/// Decides which events and deadlines are worth a notification
+#[derive(Debug, Default)]
+pub struct Notifier {
+    pub config: NotificationConfig,
+    /// Mission name and expiry keyed by mission ID
+    missions: BTreeMap<u32, (String, OffsetDateTime)>,
+    warned_missions: HashSet<u32>,
+    /// Destination system and lockdown time of scheduled carrier jumps
+    lockdowns: Vec<(String, OffsetDateTime)>,
+    low_fuel: Latch,
+    overheating: Latch,
+    cargo_full: Latch,
+    rebuy: RebuyMonitor,
+    low_rebuy: Latch,
+    system_states: SystemStates,
+}
+
+/// Fires once when a condition starts and again only after it has been over
+/// for a while, so conditions flickering on and off don't spam
+#[derive(Debug, Default)]
+struct Latch {
+    raised: bool,
+    /// When the condition was last seen to be over while raised
+    cleared: Option<OffsetDateTime>,
+}
+
+impl Latch {
+    /// Whether to notify
+    fn update(&mut self, active: bool, now: OffsetDateTime, rearm_after: Duration) -> bool {
+        if active {
+            self.cleared = None;
+            return !std::mem::replace(&mut self.raised, true);
+        }
+        if self.raised && now - *self.cleared.get_or_insert(now) >= rearm_after {
+            self.raised = false;
+            self.cleared = None;
+        }
+        false
+    }
+}
+
+impl Notifier {
+    pub fn new(config: NotificationConfig) -> Self {
+        Notifier {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Notifications caused directly by an event
+    pub fn check_event(&mut self, event: &Event) -> Option<Notification> {
+        self.rebuy.update(event);
+        let known_changes = self.system_states.changes.len();
+        self.system_states.update(event);
+
+        let notification = match event {
+            Event::Missions {
+                timestamp, active, ..
+            } => {
+                for mission in active {
+                    let expiry = *timestamp + Duration::seconds(mission.expires.into());
+                    self.missions
+                        .insert(mission.mission_id, (mission.name.to_owned(), expiry));
+                }
+                None
+            }
+            Event::MissionAccepted {
+                name,
+                localised_name,
+                mission_id,
+                expiry: Some(expiry),
+                ..
+            } => {
+                let name = localised_name.as_ref().unwrap_or(name).to_owned();
+                self.missions.insert(*mission_id, (name, *expiry));
+                None
+            }
+            Event::MissionCompleted { mission_id, .. }
+            | Event::MissionFailed { mission_id, .. }
+            | Event::MissionAbandoned { mission_id, .. } => {
+                self.missions.remove(mission_id);
+                None
+            }
+            Event::Interdicted {
+                interdictor,
+                interdictor_localised,
+                is_player,
+                ..
+            } if self.enabled(Alert::Interdicted) => {
+                let interdictor = interdictor_localised
+                    .as_ref()
+                    .or(interdictor.as_ref())
+                    .map_or("Unknown", |i| i.as_str());
+                Some(Notification::new(
+                    "Interdicted",
+                    format!(
+                        "You were interdicted by {}{}",
+                        interdictor,
+                        if *is_player { " (player)" } else { "" }
+                    ),
+                ))
+            }
+            Event::HullDamage {
+                health, fighter, ..
+            } if self.enabled(Alert::HullDamage) && *health < self.config.hull_threshold => {
+                Some(Notification::new(
+                    "Hull damage",
+                    format!(
+                        "{} hull at {:.0}%",
+                        if *fighter { "Fighter" } else { "Ship" },
+                        health * 100.0
+                    ),
+                ))
+            }
+            Event::HeatWarning { .. } if self.enabled(Alert::Overheating) => {
+                // Timed by the clock like the status updates clearing the latch
+                let now = OffsetDateTime::now_utc();
+                self.overheating
+                    .update(true, now, self.config.rearm_after)
+                    .then(overheating_notification)
+            }
+            Event::CarrierJumpRequest {
+                timestamp,
+                system_name,
+                departure_time: Some(departure),
+                ..
+            } if self.enabled(Alert::CarrierJump) => {
+                let lockdown = *departure - Duration::minutes(10);
+                self.lockdowns.push((system_name.to_owned(), lockdown));
+                Some(Notification::new(
+                    "Carrier jump scheduled",
+                    format!(
+                        "Jumping to {} in {} minutes",
+                        system_name,
+                        (*departure - *timestamp).whole_minutes()
+                    ),
+                ))
+            }
+            _ => None,
+        };
+        notification
+            .or_else(|| self.check_system_states(known_changes))
+            .or_else(|| self.check_rebuy(event))
+    }
+
+    fn check_system_states(&self, known_changes: usize) -> Option<Notification> {
+        let changes = &self.system_states.changes[known_changes..];
+        let system = &changes.first()?.system;
+        if !self.enabled(Alert::SystemState) {
+            return None;
+        }
+
+        let changes: Vec<String> = changes
+            .iter()
+            .map(|change| match (&change.from, &change.to) {
+                (Some(from), Some(to)) => format!("{} {from} -> {to}", change.faction),
+                (None, _) => format!("{} arrived", change.faction),
+                (_, None) => format!("{} left", change.faction),
+            })
+            .collect();
+        Some(Notification::new(
+            &format!("States changed in {system}"),
+            changes.join("\n"),
+        ))
+    }
+
+    fn check_rebuy(&mut self, event: &Event) -> Option<Notification> {
+        let affects_rebuy =
+            matches!(event, Event::Loadout { .. }) || credit_change(event).is_some();
+        if !self.enabled(Alert::Rebuy) || !affects_rebuy {
+            return None;
+        }
+
+        let affordable = self.rebuy.affordable_rebuys()?;
+        let short = affordable < self.config.min_rebuys;
+        self.low_rebuy
+            .update(short, event.timestamp(), self.config.rearm_after)
+            .then(|| {
+                Notification::new(
+                    "Rebuy",
+                    match affordable {
+                        0 => "You can't afford the rebuy of this ship".to_owned(),
+                        n => format!("You can only afford {n} rebuys of this ship"),
+                    },
+                )
+            })
+    }
+
+    /// Notifications for deadlines that passed since the last check
+    pub fn check_time(&mut self, now: OffsetDateTime) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+
+        if self.enabled(Alert::MissionExpiry) {
+            for (id, (name, expiry)) in &self.missions {
+                if *expiry > now
+                    && *expiry - now < self.config.mission_warning
+                    && self.warned_missions.insert(*id)
+                {
+                    notifications.push(Notification::new(
+                        "Mission expiring",
+                        format!(
+                            "{} expires in {} minutes",
+                            name,
+                            (*expiry - now).whole_minutes()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        self.lockdowns.retain(|(system, lockdown)| {
+            if *lockdown > now {
+                return true;
+            }
+            // Don't bring up lockdowns that are long over
+            if now - *lockdown < Duration::minutes(10) {
+                notifications.push(Notification::new(
+                    "Carrier lockdown",
+                    format!("Carrier is locking down for the jump to {system}"),
+                ));
+            }
+            false
+        });
+
+        notifications
+    }
+
+    /// Notifications for conditions in Status.json.
+    /// Call regularly even when the status hasn't changed, so alerts that
+    /// are over can be raised again.
+    pub fn check_status(&mut self, status: &Status, now: OffsetDateTime) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+        let rearm_after = self.config.rearm_after;
+
+        if self.enabled(Alert::LowFuel) {
+            let low = match (self.config.fuel_threshold, status.fuel) {
+                (Some(threshold), Some(fuel)) => fuel.fuel_main < threshold,
+                (Some(_), None) => false,
+                (None, _) => status.low_fuel(),
+            };
+            if self.low_fuel.update(low, now, rearm_after) {
+                let left = status.fuel.map_or(0.0, |f| f.fuel_main);
+                notifications.push(Notification::new(
+                    "Low fuel",
+                    format!("{left:.1} t of fuel left in the main tank"),
+                ));
+            }
+        }
+
+        if self.enabled(Alert::Overheating)
+            && self
+                .overheating
+                .update(status.overheating(), now, rearm_after)
+        {
+            notifications.push(overheating_notification());
+        }
+
+        if let (true, Some(limit)) = (self.enabled(Alert::CargoFull), self.config.cargo_limit) {
+            let cargo = status.cargo.unwrap_or_default();
+            if self.cargo_full.update(cargo >= limit, now, rearm_after) {
+                notifications.push(Notification::new(
+                    "Cargo full",
+                    format!("Carrying {cargo:.0} t of cargo"),
+                ));
+            }
+        }
+
+        notifications
+    }
+
+    fn enabled(&self, alert: Alert) -> bool {
+        self.config.alerts.contains(&alert)
+    }
+}
+
+fn overheating_notification() -> Notification {
+    Notification::new("Overheating", "Heat is above 100%".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_hysteresis() {
+        let mut notifier = Notifier::new(NotificationConfig {
+            fuel_threshold: Some(4.0),
+            ..Default::default()
+        });
+        let status = |timestamp: &str, fuel: f64| -> Status {
+            serde_json::from_str(&format!(
+                r#"{{ "timestamp":"{timestamp}", "event":"Status", "Flags":16777224, "Fuel":{{ "FuelMain":{fuel}, "FuelReservoir":0.5 }} }}"#
+            ))
+            .unwrap()
+        };
+        let mut check = |timestamp: &str, fuel: f64| {
+            let status = status(timestamp, fuel);
+            notifier.check_status(&status, status.timestamp).len()
+        };
+
+        assert_eq!(check("2024-09-15T12:00:00Z", 5.0), 0);
+        assert_eq!(check("2024-09-15T12:00:10Z", 3.9), 1);
+        // Scooping briefly lifts fuel over the threshold
+        assert_eq!(check("2024-09-15T12:00:20Z", 4.1), 0);
+        assert_eq!(check("2024-09-15T12:00:30Z", 3.9), 0);
+        // Over for longer than a minute
+        assert_eq!(check("2024-09-15T12:01:00Z", 8.0), 0);
+        assert_eq!(check("2024-09-15T12:02:30Z", 8.0), 0);
+        assert_eq!(check("2024-09-15T12:03:00Z", 3.0), 1);
+    }
+}
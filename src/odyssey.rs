@@ -0,0 +1,530 @@
+//! On-foot micro-resources, the suits and weapons owned and the cost of
+//! upgrading them.
+
+use std::collections::HashMap;
+
+use time::OffsetDateTime;
+
+use crate::{
+    credits::Balance,
+    journal::{Event, MicroResource},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GearKind {
+    Suit,
+    Weapon,
+}
+
+/// Cost of upgrading a suit or weapon to a grade.
+/// The game charges about the same for every suit and every weapon type.
+#[derive(Debug)]
+pub struct Upgrade {
+    pub kind: GearKind,
+    /// Grade after the upgrade
+    pub grade: u32,
+    pub credits: u64,
+    /// Micro-resource symbol as in ShipLocker, count and display name
+    pub resources: &'static [(&'static str, u32, &'static str)],
+}
+
+const SUIT_SCHEMATIC: &str = "Suit Schematic";
+const WEAPON_SCHEMATIC: &str = "Weapon Schematic";
+
+pub const UPGRADES: &[Upgrade] = &[
+    Upgrade {
+        kind: GearKind::Suit,
+        grade: 2,
+        credits: 150_000,
+        resources: &[
+            ("suitschematic", 1, SUIT_SCHEMATIC),
+            ("healthmonitor", 2, "Health Monitor"),
+            ("graphene", 3, "Graphene"),
+        ],
+    },
+    Upgrade {
+        kind: GearKind::Suit,
+        grade: 3,
+        credits: 495_000,
+        resources: &[
+            ("suitschematic", 2, SUIT_SCHEMATIC),
+            ("healthmonitor", 4, "Health Monitor"),
+            ("graphene", 5, "Graphene"),
+            ("carbonfibreplating", 3, "Carbon Fibre Plating"),
+        ],
+    },
+    Upgrade {
+        kind: GearKind::Suit,
+        grade: 4,
+        credits: 1_500_000,
+        resources: &[
+            ("suitschematic", 5, SUIT_SCHEMATIC),
+            ("healthmonitor", 6, "Health Monitor"),
+            ("carbonfibreplating", 6, "Carbon Fibre Plating"),
+            ("titaniumplating", 5, "Titanium Plating"),
+        ],
+    },
+    Upgrade {
+        kind: GearKind::Suit,
+        grade: 5,
+        credits: 5_000_000,
+        resources: &[
+            ("suitschematic", 10, SUIT_SCHEMATIC),
+            ("healthmonitor", 10, "Health Monitor"),
+            ("titaniumplating", 10, "Titanium Plating"),
+            ("epoxyadhesive", 8, "Epoxy Adhesive"),
+        ],
+    },
+    Upgrade {
+        kind: GearKind::Weapon,
+        grade: 2,
+        credits: 75_000,
+        resources: &[
+            ("weaponschematic", 1, WEAPON_SCHEMATIC),
+            ("weaponcomponent", 2, "Weapon Component"),
+            ("tungstencarbide", 3, "Tungsten Carbide"),
+        ],
+    },
+    Upgrade {
+        kind: GearKind::Weapon,
+        grade: 3,
+        credits: 250_000,
+        resources: &[
+            ("weaponschematic", 2, WEAPON_SCHEMATIC),
+            ("weaponcomponent", 4, "Weapon Component"),
+            ("tungstencarbide", 5, "Tungsten Carbide"),
+            ("circuitswitch", 3, "Circuit Switch"),
+        ],
+    },
+    Upgrade {
+        kind: GearKind::Weapon,
+        grade: 4,
+        credits: 750_000,
+        resources: &[
+            ("weaponschematic", 5, WEAPON_SCHEMATIC),
+            ("weaponcomponent", 6, "Weapon Component"),
+            ("circuitswitch", 6, "Circuit Switch"),
+            ("electricalwiring", 5, "Electrical Wiring"),
+        ],
+    },
+    Upgrade {
+        kind: GearKind::Weapon,
+        grade: 5,
+        credits: 2_500_000,
+        resources: &[
+            ("weaponschematic", 10, WEAPON_SCHEMATIC),
+            ("weaponcomponent", 10, "Weapon Component"),
+            ("electricalwiring", 10, "Electrical Wiring"),
+            ("microthrusters", 8, "Micro Thrusters"),
+        ],
+    },
+];
+
+/// Upgrade to the grade after `grade`, `None` at grade 5
+pub fn next_upgrade(kind: GearKind, grade: u32) -> Option<&'static Upgrade> {
+    UPGRADES
+        .iter()
+        .find(|upgrade| upgrade.kind == kind && upgrade.grade == grade + 1)
+}
+
+/// Micro-resources in the ship locker and backpack by symbol in lower case.
+/// Both are replaced by every snapshot with contents.
+#[derive(Debug, Default)]
+pub struct MicroResources {
+    pub locker: HashMap<String, u32>,
+    pub backpack: HashMap<String, u32>,
+}
+
+fn snapshot(parts: [&Option<Vec<MicroResource>>; 4]) -> Option<HashMap<String, u32>> {
+    if parts.iter().all(|part| part.is_none()) {
+        return None;
+    }
+    let mut counts = HashMap::new();
+    for resource in parts.into_iter().flatten().flatten() {
+        *counts.entry(resource.name.to_lowercase()).or_default() += resource.count;
+    }
+    Some(counts)
+}
+
+impl MicroResources {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::ShipLocker {
+                items,
+                components,
+                consumables,
+                data,
+                ..
+            } => {
+                if let Some(counts) = snapshot([items, components, consumables, data]) {
+                    self.locker = counts;
+                }
+            }
+            Event::Backpack {
+                items,
+                components,
+                consumables,
+                data,
+                ..
+            } => {
+                if let Some(counts) = snapshot([items, components, consumables, data]) {
+                    self.backpack = counts;
+                }
+            }
+            Event::BackpackChange { added, removed, .. } => {
+                for resource in added {
+                    *self
+                        .backpack
+                        .entry(resource.name.to_lowercase())
+                        .or_default() += resource.count;
+                }
+                for resource in removed {
+                    if let Some(count) = self.backpack.get_mut(&resource.name.to_lowercase()) {
+                        *count = count.saturating_sub(resource.count);
+                    }
+                }
+            }
+            Event::UpgradeSuit { resources, .. } | Event::UpgradeWeapon { resources, .. } => {
+                for resource in resources {
+                    if let Some(count) = self.locker.get_mut(&resource.name.to_lowercase()) {
+                        *count = count.saturating_sub(resource.count);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Count in the locker and backpack together
+    pub fn count(&self, symbol: &str) -> u32 {
+        let symbol = symbol.to_lowercase();
+        self.locker.get(&symbol).copied().unwrap_or(0)
+            + self.backpack.get(&symbol).copied().unwrap_or(0)
+    }
+
+    /// Counts in the locker and backpack together by symbol
+    pub fn totals(&self) -> HashMap<String, u32> {
+        let mut totals = self.locker.clone();
+        for (symbol, count) in &self.backpack {
+            *totals.entry(symbol.clone()).or_default() += count;
+        }
+        totals
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceChange {
+    pub timestamp: OffsetDateTime,
+    /// Symbol in lower case
+    pub name: String,
+    pub change: i64,
+    /// What the change is put down to, like "Sold" or "Upgrade"
+    pub cause: &'static str,
+}
+
+/// Events that explain the next change to the micro-resources
+fn cause(event: &Event) -> Option<&'static str> {
+    match event {
+        Event::BuyMicroResources { .. } => Some("Bought"),
+        Event::SellMicroResources { .. } => Some("Sold"),
+        Event::TradeMicroResources { .. } => Some("Traded"),
+        Event::TransferMicroResources { .. } => Some("Carrier"),
+        Event::CollectItems { .. } => Some("Collected"),
+        Event::DropItems { .. } => Some("Dropped"),
+        Event::UseConsumable { .. } => Some("Used"),
+        Event::UpgradeSuit { .. } | Event::UpgradeWeapon { .. } => Some("Upgrade"),
+        Event::MissionCompleted { .. } => Some("Mission"),
+        Event::Died { .. } => Some("Died"),
+        _ => None,
+    }
+}
+
+/// Gains and losses of every micro-resource, from the differences between
+/// consecutive ShipLocker and Backpack snapshots and from BackpackChange.
+/// Each change is put down to the last event explaining it since the
+/// previous change.
+#[derive(Debug, Default)]
+pub struct MicroResourceHistory {
+    pub resources: MicroResources,
+    pub changes: Vec<ResourceChange>,
+    /// Totals after the last change, `None` before the first snapshot
+    totals: Option<HashMap<String, u32>>,
+    cause: Option<&'static str>,
+}
+
+impl MicroResourceHistory {
+    pub fn update(&mut self, event: &Event) {
+        if let Some(cause) = cause(event) {
+            self.cause = Some(cause);
+        }
+        self.resources.update(event);
+        let timestamp = event.timestamp();
+
+        let snapshot = match event {
+            Event::ShipLocker { .. } | Event::Backpack { .. } => true,
+            Event::BackpackChange { .. }
+            | Event::UpgradeSuit { .. }
+            | Event::UpgradeWeapon { .. } => false,
+            _ => return,
+        };
+        let totals = self.resources.totals();
+        let Some(previous) = self.totals.replace(totals.clone()) else {
+            return;
+        };
+
+        let mut names: Vec<&String> = totals.keys().chain(previous.keys()).collect();
+        names.sort();
+        names.dedup();
+        let cause = match (self.cause, snapshot) {
+            (Some(cause), _) => cause,
+            (None, true) => "Unknown",
+            (None, false) => "Backpack",
+        };
+        let mut changed = false;
+        for name in names {
+            let change = i64::from(totals.get(name).copied().unwrap_or(0))
+                - i64::from(previous.get(name).copied().unwrap_or(0));
+            if change == 0 {
+                continue;
+            }
+            changed = true;
+            // Items moving between backpack and locker show up in two
+            // snapshots written at the same time and cancel out
+            let merged = self
+                .changes
+                .iter_mut()
+                .rev()
+                .take_while(|c| c.timestamp == timestamp)
+                .find(|c| c.name == *name && c.cause == cause);
+            match merged {
+                Some(merged) => merged.change += change,
+                None => self.changes.push(ResourceChange {
+                    timestamp,
+                    name: name.clone(),
+                    change,
+                    cause,
+                }),
+            }
+        }
+        self.changes.retain(|c| c.change != 0);
+        if changed {
+            self.cause = None;
+        }
+    }
+
+    /// Total gained and lost of a micro-resource by cause
+    pub fn summary(&self, name: &str) -> Vec<(&'static str, u64, u64)> {
+        let mut summary: Vec<(&'static str, u64, u64)> = Vec::new();
+        for change in self
+            .changes
+            .iter()
+            .filter(|c| c.name.eq_ignore_ascii_case(name))
+        {
+            let index = match summary
+                .iter()
+                .position(|(cause, ..)| *cause == change.cause)
+            {
+                Some(index) => index,
+                None => {
+                    summary.push((change.cause, 0, 0));
+                    summary.len() - 1
+                }
+            };
+            match change.change {
+                gain @ 1.. => summary[index].1 += gain as u64,
+                loss => summary[index].2 += loss.unsigned_abs(),
+            }
+        }
+        summary
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Gear {
+    pub kind: GearKind,
+    /// Symbol like "utilitysuit" or "wpn_m_assaultrifle_laser_fauto"
+    pub name: String,
+    pub grade: u32,
+}
+
+/// Suit symbol and grade from names like "UtilitySuit_Class3"
+fn suit_grade(name: &str) -> (String, u32) {
+    let name = name.to_lowercase();
+    match name.rsplit_once("_class") {
+        Some((suit, grade)) => (suit.to_owned(), grade.parse().unwrap_or(1)),
+        None => (name, 1),
+    }
+}
+
+/// Suits and weapons owned, the micro-resources and credits to upgrade them
+#[derive(Debug, Default)]
+pub struct Armoury {
+    /// Suits by SuitID and weapons by SuitModuleID
+    pub gear: HashMap<u64, Gear>,
+    pub resources: MicroResources,
+    pub balance: Balance,
+}
+
+impl Armoury {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::BuySuit { name, suit_id, .. } => {
+                let (name, grade) = suit_grade(name);
+                self.gear.insert(
+                    *suit_id,
+                    Gear {
+                        kind: GearKind::Suit,
+                        name,
+                        grade,
+                    },
+                );
+            }
+            Event::BuyWeapon {
+                name,
+                suit_module_id,
+                class,
+                ..
+            } => {
+                self.gear.insert(
+                    *suit_module_id,
+                    Gear {
+                        kind: GearKind::Weapon,
+                        name: name.to_lowercase(),
+                        grade: *class,
+                    },
+                );
+            }
+            Event::SellSuit { suit_id: id, .. }
+            | Event::SellWeapon {
+                suit_module_id: id, ..
+            } => {
+                self.gear.remove(id);
+            }
+            Event::UpgradeSuit {
+                suit_id: id, class, ..
+            }
+            | Event::UpgradeWeapon {
+                suit_module_id: id,
+                class,
+                ..
+            } => {
+                if let Some(gear) = self.gear.get_mut(id) {
+                    gear.grade = *class;
+                }
+            }
+            Event::SuitLoadout {
+                suit_id,
+                suit_name,
+                modules,
+                ..
+            } => {
+                let (name, grade) = suit_grade(suit_name);
+                self.gear.insert(
+                    *suit_id,
+                    Gear {
+                        kind: GearKind::Suit,
+                        name,
+                        grade,
+                    },
+                );
+                for module in modules {
+                    self.gear.insert(
+                        module.suit_module_id,
+                        Gear {
+                            kind: GearKind::Weapon,
+                            name: module.module_name.to_lowercase(),
+                            grade: module.class,
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+        self.resources.update(event);
+        self.balance.update(event);
+    }
+
+    /// Credits and micro-resources still missing for an upgrade by display
+    /// name, empty if it is affordable now
+    pub fn missing(&self, upgrade: &Upgrade) -> Vec<(&'static str, u64)> {
+        let mut missing = Vec::new();
+        let credits = self.balance.credits.unwrap_or(0).max(0) as u64;
+        if credits < upgrade.credits {
+            missing.push(("CR", upgrade.credits - credits));
+        }
+        for (symbol, count, name) in upgrade.resources {
+            let have = self.resources.count(symbol);
+            if have < *count {
+                missing.push((*name, u64::from(count - have)));
+            }
+        }
+        missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_armoury() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Odyssey":true, "Ship":"Python", "ShipID":1, "ShipName":"", "ShipIdent":"", "FuelLevel":32, "FuelCapacity":32, "GameMode":"Solo", "Credits":1000000, "Loan":0 }
+{ "timestamp":"2024-09-15T12:00:01Z", "event":"ShipLocker", "Items":[ { "Name":"suitschematic", "Name_Localised":"Suit Schematic", "OwnerID":0, "Count":3 } ], "Components":[ { "Name":"graphene", "OwnerID":0, "Count":6 }, { "Name":"carbonfibreplating", "Name_Localised":"Carbon Fibre Plating", "OwnerID":0, "Count":1 } ], "Consumables":[], "Data":[] }
+{ "timestamp":"2024-09-15T12:00:02Z", "event":"SuitLoadout", "SuitID":1700000000000001, "SuitName":"utilitysuit_class1", "SuitName_Localised":"Maverick Suit", "SuitMods":[], "LoadoutID":4293000001, "LoadoutName":"Scav", "Modules":[ { "SlotName":"PrimaryWeapon1", "SuitModuleID":1700000000000002, "ModuleName":"wpn_m_assaultrifle_laser_fauto", "ModuleName_Localised":"TK Aphelion", "Class":3, "WeaponMods":[] } ] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"BackpackChange", "Added":[ { "Name":"healthmonitor", "Name_Localised":"Health Monitor", "OwnerID":0, "Count":2, "Type":"Item" } ] }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"ShipLocker" }
+"#,
+        )
+        .unwrap();
+        let mut armoury = Armoury::default();
+        for event in &events {
+            armoury.update(event);
+        }
+
+        // The empty ShipLocker keeps the previous contents
+        assert_eq!(armoury.resources.count("graphene"), 6);
+        assert_eq!(armoury.resources.count("HealthMonitor"), 2);
+
+        let suit = &armoury.gear[&1700000000000001];
+        assert_eq!((suit.name.as_str(), suit.grade), ("utilitysuit", 1));
+        let upgrade = next_upgrade(suit.kind, suit.grade).unwrap();
+        assert!(armoury.missing(upgrade).is_empty());
+
+        let upgrade = next_upgrade(GearKind::Suit, 2).unwrap();
+        assert_eq!(
+            armoury.missing(upgrade),
+            vec![("Health Monitor", 2), ("Carbon Fibre Plating", 2)]
+        );
+        assert!(next_upgrade(GearKind::Weapon, 5).is_none());
+    }
+
+    #[test]
+    fn test_micro_resource_history() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"ShipLocker", "Items":[ { "Name":"surveillanceequipment", "OwnerID":0, "Count":10 } ], "Components":[], "Consumables":[], "Data":[] }
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Backpack", "Items":[], "Components":[], "Consumables":[], "Data":[] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"BackpackChange", "Added":[ { "Name":"surveillanceequipment", "OwnerID":0, "Count":2, "Type":"Item" } ] }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"ShipLocker", "Items":[ { "Name":"surveillanceequipment", "OwnerID":0, "Count":12 } ], "Components":[], "Consumables":[], "Data":[] }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"Backpack", "Items":[], "Components":[], "Consumables":[], "Data":[] }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"SellMicroResources", "TotalCount":8, "Price":40000, "MarketID":1 }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"ShipLocker", "Items":[ { "Name":"surveillanceequipment", "OwnerID":0, "Count":4 } ], "Components":[], "Consumables":[], "Data":[] }
+"#,
+        )
+        .unwrap();
+        let mut history = MicroResourceHistory::default();
+        for event in &events {
+            history.update(event);
+        }
+
+        // Carrying the two to the ship is not a change
+        assert_eq!(history.changes.len(), 2);
+        assert_eq!(
+            history.summary("SurveillanceEquipment"),
+            vec![("Backpack", 2, 0), ("Sold", 0, 8)]
+        );
+    }
+}
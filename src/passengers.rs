@@ -0,0 +1,188 @@
+use std::collections::{BTreeMap, HashMap};
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissionOutcome {
+    Active,
+    Completed,
+    /// Failed before the mission expired, usually by upsetting the passengers
+    Failed,
+    /// Failed because the mission ran out of time
+    Expired,
+    Abandoned,
+}
+
+#[derive(Debug)]
+pub struct PassengerMission {
+    pub mission_id: u32,
+    pub name: String,
+    pub accepted: OffsetDateTime,
+    pub expiry: Option<OffsetDateTime>,
+    /// Passenger type in the form of "Tourist", "Refugee" etc.
+    pub passenger_type: Option<String>,
+    pub count: u32,
+    pub vip: bool,
+    pub wanted: bool,
+    pub origin: Option<String>,
+    pub destination: Option<String>,
+    pub reward: u64,
+    pub outcome: MissionOutcome,
+}
+
+/// Passenger missions from acceptance until they are handed in or failed
+#[derive(Debug, Default)]
+pub struct PassengerMissions {
+    pub missions: BTreeMap<u32, PassengerMission>,
+    current_system: Option<String>,
+    /// Known system coordinates, used for mission distances
+    star_positions: HashMap<String, [f64; 3]>,
+}
+
+impl PassengerMissions {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump {
+                star_system,
+                star_pos,
+                ..
+            }
+            | Event::Location {
+                star_system,
+                star_pos,
+                ..
+            } => {
+                self.star_positions
+                    .insert(star_system.to_owned(), *star_pos);
+                self.current_system = Some(star_system.to_owned());
+            }
+            Event::MissionAccepted {
+                timestamp,
+                name,
+                localised_name,
+                mission_id,
+                expiry,
+                destination_system,
+                reward,
+                passenger_count: Some(count),
+                passenger_vips,
+                passenger_wanted,
+                passenger_type,
+                ..
+            } => {
+                self.missions.insert(
+                    *mission_id,
+                    PassengerMission {
+                        mission_id: *mission_id,
+                        name: localised_name.as_ref().unwrap_or(name).to_owned(),
+                        accepted: *timestamp,
+                        expiry: *expiry,
+                        passenger_type: passenger_type.clone(),
+                        count: *count,
+                        vip: *passenger_vips,
+                        wanted: *passenger_wanted,
+                        origin: self.current_system.clone(),
+                        destination: destination_system.clone(),
+                        reward: reward.unwrap_or(0),
+                        outcome: MissionOutcome::Active,
+                    },
+                );
+            }
+            Event::MissionCompleted {
+                mission_id, reward, ..
+            } => {
+                if let Some(mission) = self.missions.get_mut(mission_id) {
+                    mission.reward = reward.unwrap_or(mission.reward);
+                    mission.outcome = MissionOutcome::Completed;
+                }
+            }
+            Event::MissionFailed {
+                timestamp,
+                mission_id,
+                ..
+            } => {
+                if let Some(mission) = self.missions.get_mut(mission_id) {
+                    mission.outcome = match mission.expiry {
+                        Some(expiry) if expiry <= *timestamp => MissionOutcome::Expired,
+                        _ => MissionOutcome::Failed,
+                    };
+                }
+            }
+            Event::MissionAbandoned { mission_id, .. } => {
+                if let Some(mission) = self.missions.get_mut(mission_id) {
+                    mission.outcome = MissionOutcome::Abandoned;
+                }
+            }
+            Event::Passengers { manifest, .. } => {
+                for record in manifest {
+                    if let Some(mission) = self.missions.get_mut(&record.mission_id) {
+                        mission.vip = record.vip;
+                        mission.wanted = record.wanted;
+                        mission.count = record.count;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Straight line distance between the system a mission was accepted in
+    /// and its destination, if both systems were visited
+    pub fn distance(&self, mission: &PassengerMission) -> Option<f64> {
+        let from = self.star_positions.get(mission.origin.as_ref()?)?;
+        let to = self.star_positions.get(mission.destination.as_ref()?)?;
+
+        Some(
+            from.iter()
+                .zip(to)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_passenger_missions() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Location", "Docked":true, "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0] }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"MissionAccepted", "Faction":"Sol Workers' Party", "Name":"Mission_Sightseeing_name", "LocalisedName":"Take tourists to Barnard's Star", "DestinationSystem":"Barnard's Star", "Expiry":"2024-09-16T12:00:00Z", "Wing":false, "Reward":500000, "PassengerCount":4, "PassengerVIPs":true, "PassengerWanted":false, "PassengerType":"Tourist", "MissionID":1 }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"MissionAccepted", "Faction":"Sol Workers' Party", "Name":"Mission_PassengerBulk_name", "DestinationSystem":"Alpha Centauri", "Expiry":"2024-09-15T13:00:00Z", "Wing":false, "Reward":200000, "PassengerCount":10, "PassengerVIPs":false, "PassengerWanted":false, "PassengerType":"Refugee", "MissionID":2 }
+{ "timestamp":"2024-09-15T12:03:00Z", "event":"MissionAccepted", "Faction":"Sol Workers' Party", "Name":"Mission_Courier_name", "DestinationSystem":"Alpha Centauri", "Wing":false, "Reward":20000, "MissionID":3 }
+{ "timestamp":"2024-09-15T12:04:00Z", "event":"MissionAccepted", "Faction":"Sol Workers' Party", "Name":"Mission_PassengerVIP_name", "DestinationSystem":"Alpha Centauri", "Expiry":"2024-09-16T12:00:00Z", "Wing":false, "Reward":100000, "PassengerCount":1, "PassengerVIPs":true, "PassengerWanted":false, "PassengerType":"Criminal", "MissionID":4 }
+{ "timestamp":"2024-09-15T12:05:00Z", "event":"Passengers", "Manifest":[ { "MissionID":1, "Type":"Tourist", "VIP":true, "Wanted":true, "Count":4 } ] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"FSDJump", "StarSystem":"Barnard's Star", "SystemAddress":1, "StarPos":[-3.0,1.5,4.0], "JumpDist":5.2, "FuelUsed":0.5, "FuelLevel":31.5 }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"MissionCompleted", "Faction":"Sol Workers' Party", "Name":"Mission_Sightseeing_name", "MissionID":1, "Reward":550000 }
+{ "timestamp":"2024-09-15T13:30:00Z", "event":"MissionFailed", "Name":"Mission_PassengerBulk_name", "MissionID":2 }
+{ "timestamp":"2024-09-15T13:31:00Z", "event":"MissionAbandoned", "Name":"Mission_PassengerVIP_name", "MissionID":4 }
+"#,
+        )
+        .unwrap();
+        let mut passengers = PassengerMissions::default();
+        for event in &events {
+            passengers.update(event);
+        }
+
+        // The courier mission carries no passengers
+        assert_eq!(passengers.missions.len(), 3);
+        let tourists = &passengers.missions[&1];
+        assert_eq!(tourists.name, "Take tourists to Barnard's Star");
+        assert_eq!(tourists.origin.as_deref(), Some("Sol"));
+        assert!(tourists.wanted);
+        assert_eq!(tourists.reward, 550000);
+        assert_eq!(tourists.outcome, MissionOutcome::Completed);
+        assert_eq!(passengers.distance(tourists), Some(5.220153254455275));
+
+        assert_eq!(passengers.missions[&2].outcome, MissionOutcome::Expired);
+        assert_eq!(passengers.distance(&passengers.missions[&2]), None);
+        assert_eq!(passengers.missions[&4].outcome, MissionOutcome::Abandoned);
+    }
+}
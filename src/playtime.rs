@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+
+use time::{Date, Duration, OffsetDateTime};
+
+use crate::journal::Event;
+
+/// What the commander was doing, guessed from the journal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Activity {
+    Docked,
+    Supercruise,
+    /// In normal space, landed or in an SRV without doing anything else
+    Flying,
+    OnFoot,
+    Combat,
+    Mining,
+    Exploring,
+}
+
+impl Activity {
+    /// Activity an event is evidence of, if it is more specific than where
+    /// the commander is
+    fn engaged(event: &Event) -> Option<Activity> {
+        match event {
+            Event::UnderAttack { .. }
+            | Event::Bounty { .. }
+            | Event::FactionKillBond { .. }
+            | Event::CapShipBond { .. }
+            | Event::Interdicted { .. }
+            | Event::Interdiction { .. }
+            | Event::EscapeInterdiction { .. }
+            | Event::LaunchFighter { .. } => Some(Activity::Combat),
+            Event::ProspectedAsteroid { .. }
+            | Event::AsteroidCracked { .. }
+            | Event::MiningRefined { .. } => Some(Activity::Mining),
+            Event::Scan { scan_type, .. } => match scan_type.as_str() {
+                // Written for everyone arriving in a system
+                "AutoScan" | "NavBeaconDetail" => None,
+                _ => Some(Activity::Exploring),
+            },
+            Event::FSSDiscoveryScan { .. }
+            | Event::FSSAllBodiesFound { .. }
+            | Event::SAAScanComplete { .. }
+            | Event::SAASignalsFound { .. }
+            | Event::ScanOrganic { .. }
+            | Event::CodexEntry { .. } => Some(Activity::Exploring),
+            _ => None,
+        }
+    }
+}
+
+/// How long an activity lasts after the last event that was evidence of it
+pub const ENGAGED_TIMEOUT: Duration = Duration::minutes(5);
+
+/// Time spent on every activity.
+/// Where the commander is follows docking, supercruise and on-foot events;
+/// combat, mining and exploring events take precedence over that until
+/// [ENGAGED_TIMEOUT] passes without another one or the commander moves on.
+/// Time between a Shutdown and the next Fileheader is not counted.
+#[derive(Debug, Default)]
+pub struct Playtime {
+    /// Times spent on one activity, in order
+    pub spans: Vec<(Activity, OffsetDateTime, OffsetDateTime)>,
+    last: Option<OffsetDateTime>,
+    location: Option<Activity>,
+    /// Where the commander was before disembarking
+    ship: Option<Activity>,
+    engaged: Option<(Activity, OffsetDateTime)>,
+}
+
+impl Playtime {
+    pub fn update(&mut self, event: &Event) {
+        let timestamp = event.timestamp();
+        if let Event::Fileheader { .. } = event {
+            self.last = None;
+            self.location = None;
+            self.engaged = None;
+        }
+
+        if let Some(last) = self.last {
+            self.count(last, timestamp);
+        }
+        self.last = Some(timestamp);
+
+        let location = match event {
+            Event::Docked { .. } | Event::Resurrect { .. } => Some(Activity::Docked),
+            Event::Location { docked: true, .. } => Some(Activity::Docked),
+            Event::Location { .. }
+            | Event::LoadGame { .. }
+            | Event::Undocked { .. }
+            | Event::SupercruiseExit { .. } => Some(Activity::Flying),
+            Event::SupercruiseEntry { .. } | Event::StartJump { .. } | Event::FSDJump { .. } => {
+                Some(Activity::Supercruise)
+            }
+            Event::Disembark { .. } => {
+                self.ship = self.location.filter(|a| *a != Activity::OnFoot);
+                Some(Activity::OnFoot)
+            }
+            Event::Embark { .. } => Some(self.ship.take().unwrap_or(Activity::Flying)),
+            _ => None,
+        };
+        if let Some(location) = location {
+            if self.location != Some(location) {
+                self.engaged = None;
+            }
+            self.location = Some(location);
+        }
+
+        if let Some(activity) = Activity::engaged(event) {
+            self.engaged = Some((activity, timestamp));
+        }
+
+        if let Event::Shutdown { .. } = event {
+            self.last = None;
+        }
+    }
+
+    /// Attribute `start..end` to what the commander was doing
+    fn count(&mut self, start: OffsetDateTime, end: OffsetDateTime) {
+        let Some(location) = self.location else {
+            return;
+        };
+
+        let mut start = start;
+        if let Some((activity, seen)) = self.engaged {
+            let until = (seen + ENGAGED_TIMEOUT).min(end);
+            if until > start {
+                self.add(activity, start, until);
+                start = until;
+            }
+            if until < end {
+                self.engaged = None;
+            }
+        }
+        if end > start {
+            self.add(location, start, end);
+        }
+    }
+
+    fn add(&mut self, activity: Activity, start: OffsetDateTime, end: OffsetDateTime) {
+        match self.spans.last_mut() {
+            Some((last, _, until)) if *last == activity && *until == start => *until = end,
+            _ => self.spans.push((activity, start, end)),
+        }
+    }
+
+    /// Time per activity keyed by the Monday of the week it falls in, with
+    /// days starting at midnight of the times `local` converts to
+    pub fn weeks(
+        &self,
+        local: impl Fn(OffsetDateTime) -> OffsetDateTime,
+    ) -> BTreeMap<Date, BTreeMap<Activity, Duration>> {
+        let mut weeks: BTreeMap<Date, BTreeMap<Activity, Duration>> = BTreeMap::new();
+
+        for (activity, start, end) in &self.spans {
+            // Split at midnight so time lands in the right week
+            let (mut start, end) = (local(*start), local(*end));
+            while start < end {
+                let midnight = start
+                    .date()
+                    .next_day()
+                    .map(|d| d.midnight().assume_offset(start.offset()))
+                    .unwrap_or(end);
+                let until = midnight.min(end);
+                let week =
+                    start.date() - Duration::days(start.weekday().number_days_from_monday() as i64);
+                *weeks.entry(week).or_default().entry(*activity).or_default() += until - start;
+                start = until;
+            }
+        }
+
+        weeks
+    }
+
+    /// Time per activity over all weeks
+    pub fn totals(&self) -> BTreeMap<Activity, Duration> {
+        let mut totals: BTreeMap<Activity, Duration> = BTreeMap::new();
+        for (activity, start, end) in &self.spans {
+            *totals.entry(*activity).or_default() += *end - *start;
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_playtime() {
+        let journal = r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r308767/r0 " }
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Location", "Docked":true, "StationName":"Jameson Memorial", "StarSystem":"Shinrarta Dezhra", "SystemAddress":3932277478106, "StarPos":[55.71875,17.59375,27.15625] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"Undocked", "StationName":"Jameson Memorial", "StationType":"Orbis", "MarketID":128666762 }
+{ "timestamp":"2024-09-15T12:12:00Z", "event":"SupercruiseEntry", "StarSystem":"Shinrarta Dezhra" }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"SupercruiseExit", "StarSystem":"Shinrarta Dezhra", "Body":"Ring", "BodyType":"PlanetaryRing" }
+{ "timestamp":"2024-09-15T12:22:00Z", "event":"ProspectedAsteroid", "Materials":[], "Content":"$AsteroidMaterialContent_High;", "Remaining":100.0 }
+{ "timestamp":"2024-09-15T12:40:00Z", "event":"Shutdown" }
+"#;
+        let mut playtime = Playtime::default();
+        for event in &parse_events(journal).unwrap() {
+            playtime.update(event);
+        }
+
+        let totals = playtime.totals();
+        assert_eq!(totals[&Activity::Docked], Duration::minutes(10));
+        assert_eq!(totals[&Activity::Supercruise], Duration::minutes(8));
+        // Mining until the timeout runs out, then just flying
+        assert_eq!(totals[&Activity::Mining], Duration::minutes(5));
+        assert_eq!(totals[&Activity::Flying], Duration::minutes(17));
+        assert_eq!(playtime.weeks(|t| t).len(), 1);
+    }
+}
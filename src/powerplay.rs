@@ -0,0 +1,168 @@
+//! Powerplay merits earned per weekly cycle and what they were earned for.
+
+use std::{collections::BTreeMap, fmt};
+
+use time::{Duration, OffsetDateTime};
+
+use crate::{cycle::week_start, journal::Event};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MeritActivity {
+    Combat,
+    Trade,
+    Exploration,
+    Exobiology,
+    Missions,
+    /// Collecting and delivering Powerplay commodities
+    Delivery,
+    /// Merits that couldn't be put down to an activity
+    Other,
+}
+
+impl fmt::Display for MeritActivity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Activity an event earns merits for
+fn activity(event: &Event) -> Option<MeritActivity> {
+    use MeritActivity::*;
+
+    match event {
+        Event::Bounty { .. } | Event::FactionKillBond { .. } | Event::CapShipBond { .. } => {
+            Some(Combat)
+        }
+        Event::MarketSell { .. } => Some(Trade),
+        Event::SellExplorationData { .. } | Event::MultiSellExplorationData { .. } => {
+            Some(Exploration)
+        }
+        Event::SellOrganicData { .. } => Some(Exobiology),
+        Event::MissionCompleted { .. } => Some(Missions),
+        Event::PowerplayCollect { .. } | Event::PowerplayDeliver { .. } => Some(Delivery),
+        _ => None,
+    }
+}
+
+/// How long after an activity the merits for it are still put down to it,
+/// the game writes them right after
+const ATTRIBUTION_WINDOW: Duration = Duration::seconds(10);
+
+#[derive(Debug, Default)]
+pub struct PowerplayWeek {
+    pub merits: BTreeMap<MeritActivity, u64>,
+    /// Rank at the end of the week, once known
+    pub rank: Option<u32>,
+    pub promotions: u32,
+}
+
+impl PowerplayWeek {
+    pub fn total_merits(&self) -> u64 {
+        self.merits.values().sum()
+    }
+}
+
+/// Merits grouped by the weekly cycle starting Thursday 07:00 UTC
+#[derive(Debug, Default)]
+pub struct MeritLedger {
+    /// Keyed by the start of the cycle
+    pub weeks: BTreeMap<OffsetDateTime, PowerplayWeek>,
+    pub power: Option<String>,
+    pub rank: Option<u32>,
+    pub total_merits: Option<u64>,
+    /// Last activity that earns merits and when it happened
+    last_activity: Option<(MeritActivity, OffsetDateTime)>,
+}
+
+impl MeritLedger {
+    pub fn update(&mut self, event: &Event) {
+        if let Some(activity) = activity(event) {
+            self.last_activity = Some((activity, event.timestamp()));
+        }
+
+        match event {
+            Event::Powerplay {
+                power,
+                rank,
+                merits,
+                ..
+            } => {
+                self.power = Some(power.clone());
+                self.rank = Some(*rank);
+                self.total_merits = Some(u64::from(*merits));
+            }
+            Event::PowerplayMerits {
+                timestamp,
+                power,
+                merits_gained,
+                total_merits,
+            } => {
+                let activity = match self.last_activity.take() {
+                    Some((activity, at)) if *timestamp - at <= ATTRIBUTION_WINDOW => activity,
+                    _ => MeritActivity::Other,
+                };
+                self.power = Some(power.clone());
+                self.total_merits = Some(*total_merits);
+
+                let week = self.week(*timestamp);
+                *week.merits.entry(activity).or_default() += merits_gained;
+            }
+            Event::PowerplayRank {
+                timestamp,
+                power,
+                rank,
+            } => {
+                self.power = Some(power.clone());
+                self.rank = Some(*rank);
+
+                let week = self.week(*timestamp);
+                week.promotions += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn week(&mut self, timestamp: OffsetDateTime) -> &mut PowerplayWeek {
+        let week = self.weeks.entry(week_start(timestamp)).or_default();
+        week.rank = self.rank;
+        week
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_merit_ledger() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-11-06T12:00:00Z", "event":"Powerplay", "Power":"Edmund Mahon", "Rank":10, "Merits":5000, "TimePledged":100000 }
+{ "timestamp":"2024-11-06T12:10:00Z", "event":"MarketSell", "MarketID":1, "Type":"gold", "Count":100, "SellPrice":50000, "TotalSale":5000000, "AvgPricePaid":40000 }
+{ "timestamp":"2024-11-06T12:10:00Z", "event":"PowerplayMerits", "Power":"Edmund Mahon", "MeritsGained":300, "TotalMerits":5300 }
+{ "timestamp":"2024-11-07T08:00:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Alliance", "Reward":100000 } ], "Target":"viper", "TotalReward":100000, "VictimFaction":"Pirates" }
+{ "timestamp":"2024-11-07T08:00:01Z", "event":"PowerplayMerits", "Power":"Edmund Mahon", "MeritsGained":50, "TotalMerits":5350 }
+{ "timestamp":"2024-11-07T08:00:01Z", "event":"PowerplayRank", "Power":"Edmund Mahon", "Rank":11 }
+{ "timestamp":"2024-11-07T09:00:00Z", "event":"PowerplayMerits", "Power":"Edmund Mahon", "MeritsGained":20, "TotalMerits":5370 }
+"#,
+        )
+        .unwrap();
+        let mut ledger = MeritLedger::default();
+        for event in &events {
+            ledger.update(event);
+        }
+
+        // The cycle turned over on Thursday 07:00
+        assert_eq!(ledger.weeks.len(), 2);
+        let first = &ledger.weeks[&datetime!(2024-10-31 07:00 UTC)];
+        assert_eq!(first.merits[&MeritActivity::Trade], 300);
+        assert_eq!(first.rank, Some(10));
+        let second = &ledger.weeks[&datetime!(2024-11-07 07:00 UTC)];
+        assert_eq!(second.merits[&MeritActivity::Combat], 50);
+        assert_eq!(second.merits[&MeritActivity::Other], 20);
+        assert_eq!((second.rank, second.promotions), (Some(11), 1));
+        assert_eq!(ledger.total_merits, Some(5370));
+    }
+}
@@ -0,0 +1,259 @@
+//! Extension point for running code of other crates on live events, like
+//! analytics or sinks, the way `elite watch` and the gRPC server do.
+//!
+//! A crate depending on this one implements [EventProcessor], registers it
+//! in [Processors] and hands them to [run] or [crate::grpc::serve_with].
+
+use std::{
+    error::Error,
+    fmt, io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde_json::Value;
+
+use crate::journal::{self, Event, JournalTail, Status, StatusChange};
+
+pub type ProcessorResult = Result<(), Box<dyn Error + Send + Sync>>;
+
+/// Shows a notification raised by a processor, as summary and body
+pub type Notify = Arc<dyn Fn(&str, &str) + Send + Sync>;
+
+pub trait EventProcessor: Send {
+    /// Name to tell which processor failed
+    fn name(&self) -> &str;
+
+    /// Called for every event written to the journal
+    fn on_event(&mut self, _event: &Event) -> ProcessorResult {
+        Ok(())
+    }
+
+    /// Called after [EventProcessor::on_event] with the entry as written
+    /// to the journal, for going by fields that [Event] doesn't have
+    fn on_raw_event(&mut self, _event: &Value) -> ProcessorResult {
+        Ok(())
+    }
+
+    /// Called about every second with the current Status.json, whether it
+    /// changed or not
+    fn on_status(&mut self, _status: &Status) -> ProcessorResult {
+        Ok(())
+    }
+
+    /// Called after [EventProcessor::on_status] for each change since the
+    /// last reading, like becoming wanted
+    fn on_status_change(&mut self, _change: &StatusChange) -> ProcessorResult {
+        Ok(())
+    }
+
+    /// Called once when the processors are stopped, e.g. to flush buffers
+    fn on_shutdown(&mut self) -> ProcessorResult {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ProcessorError {
+    pub processor: String,
+    pub error: Box<dyn Error + Send + Sync>,
+}
+
+impl fmt::Display for ProcessorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: {}", self.processor, self.error)
+    }
+}
+
+impl Error for ProcessorError {}
+
+/// Processors called in the order they were registered. One failing
+/// doesn't keep the others from being called.
+#[derive(Default)]
+pub struct Processors {
+    processors: Vec<Box<dyn EventProcessor>>,
+    /// Last status passed on, to tell what changed
+    previous: Option<Status>,
+}
+
+impl fmt::Debug for Processors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.processors.iter().map(|p| p.name()))
+            .finish()
+    }
+}
+
+impl Processors {
+    pub fn register(&mut self, processor: impl EventProcessor + 'static) {
+        self.processors.push(Box::new(processor));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    fn call(
+        &mut self,
+        mut f: impl FnMut(&mut dyn EventProcessor) -> ProcessorResult,
+    ) -> Vec<ProcessorError> {
+        self.processors
+            .iter_mut()
+            .filter_map(|processor| {
+                f(processor.as_mut()).err().map(|error| ProcessorError {
+                    processor: processor.name().to_owned(),
+                    error,
+                })
+            })
+            .collect()
+    }
+
+    pub fn event(&mut self, event: &Event) -> Vec<ProcessorError> {
+        self.call(|p| p.on_event(event))
+    }
+
+    pub fn raw_event(&mut self, event: &Value) -> Vec<ProcessorError> {
+        self.call(|p| p.on_raw_event(event))
+    }
+
+    pub fn status(&mut self, status: &Status) -> Vec<ProcessorError> {
+        let mut errors = self.call(|p| p.on_status(status));
+        let changes = self
+            .previous
+            .replace(status.clone())
+            .map(|previous| status.changes_since(&previous))
+            .unwrap_or_default();
+        for change in &changes {
+            errors.extend(self.call(|p| p.on_status_change(change)));
+        }
+        errors
+    }
+
+    pub fn shutdown(&mut self) -> Vec<ProcessorError> {
+        self.call(|p| p.on_shutdown())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// Time between looking for new events
+    pub interval: Duration,
+    /// File to save the position of the tail to after new events, see
+    /// [journal::TailPosition]
+    pub state: Option<PathBuf>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            interval: Duration::from_secs(1),
+            state: None,
+        }
+    }
+}
+
+/// Feed the processors events from `tail` and the status until `running`
+/// is false, then shut them down. Failing processors are passed to
+/// `on_error`, only failing to read the journal stops early.
+pub fn run(
+    tail: &mut JournalTail,
+    processors: &mut Processors,
+    options: &RunOptions,
+    running: &AtomicBool,
+    mut on_error: impl FnMut(ProcessorError),
+) -> Result<(), io::Error> {
+    while running.load(Ordering::Relaxed) {
+        let events = tail.poll_raw()?;
+        for (event, raw) in &events {
+            processors.event(event).into_iter().for_each(&mut on_error);
+            processors
+                .raw_event(raw)
+                .into_iter()
+                .for_each(&mut on_error);
+        }
+        if let (Some(path), false) = (&options.state, events.is_empty()) {
+            tail.position().save(path)?;
+        }
+
+        // Status.json can be caught halfway through being rewritten,
+        // the next poll will read it whole
+        if let Ok(status) = journal::read_status() {
+            processors
+                .status(&status)
+                .into_iter()
+                .for_each(&mut on_error);
+        }
+
+        thread::sleep(options.interval);
+    }
+
+    processors.shutdown().into_iter().for_each(on_error);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_line;
+
+    struct Counter(u32);
+
+    impl EventProcessor for Counter {
+        fn name(&self) -> &str {
+            "counter"
+        }
+
+        fn on_event(&mut self, _: &Event) -> ProcessorResult {
+            self.0 += 1;
+            Err(format!("seen {}", self.0).into())
+        }
+
+        fn on_status_change(&mut self, change: &StatusChange) -> ProcessorResult {
+            Err(format!("{change:?}").into())
+        }
+    }
+
+    struct Silent;
+
+    impl EventProcessor for Silent {
+        fn name(&self) -> &str {
+            "silent"
+        }
+    }
+
+    #[test]
+    fn test_processors() {
+        let mut processors = Processors::default();
+        processors.register(Counter(0));
+        processors.register(Silent);
+
+        let event =
+            parse_line(r#"{ "timestamp":"2024-09-15T12:10:00Z", "event":"Shutdown" }"#).unwrap();
+        processors.event(&event);
+        let errors = processors.event(&event);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "counter failed: seen 2");
+
+        let status = |legal_state: &str| -> Status {
+            serde_json::from_str(&format!(
+                r#"{{ "timestamp":"2024-09-15T12:10:00Z", "event":"Status", "Flags":16, "LegalState":"{legal_state}" }}"#
+            ))
+            .unwrap()
+        };
+        assert!(processors.status(&status("Clean")).is_empty());
+        assert!(processors.status(&status("Clean")).is_empty());
+        let errors = processors.status(&status("Wanted"));
+        assert_eq!(
+            errors[0].to_string(),
+            "counter failed: LegalState { from: Clean, to: Wanted }"
+        );
+        assert!(processors.shutdown().is_empty());
+        assert_eq!(format!("{processors:?}"), r#"["counter", "silent"]"#);
+    }
+}
@@ -0,0 +1,58 @@
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde::Serialize;
+
+use crate::{journal, state::GameState};
+
+/// Convert anything serializable to plain Python dicts and lists
+fn to_python(py: Python<'_>, value: &impl Serialize) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Parse a single journal line into a dict
+#[pyfunction]
+fn parse_line(py: Python<'_>, line: &str) -> PyResult<PyObject> {
+    let event = journal::parse_line(line).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    to_python(py, &event)
+}
+
+/// All events from all journal files as dicts
+#[cfg(feature = "fs")]
+#[pyfunction]
+fn all_events(py: Python<'_>) -> PyResult<PyObject> {
+    to_python(py, &journal::all_events()?)
+}
+
+#[pyclass(name = "GameState")]
+#[derive(Debug, Default)]
+struct PyGameState(GameState);
+
+#[pymethods]
+impl PyGameState {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the state with a journal line
+    fn update(&mut self, line: &str) -> PyResult<()> {
+        let event = journal::parse_line(line).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.0.update(&event);
+        Ok(())
+    }
+
+    /// Current state as a dict
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.0)
+    }
+}
+
+#[pymodule]
+fn elite(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_line, m)?)?;
+    #[cfg(feature = "fs")]
+    m.add_function(wrap_pyfunction!(all_events, m)?)?;
+    m.add_class::<PyGameState>()?;
+    Ok(())
+}
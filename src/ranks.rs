@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+use crate::{cqc::CQC_RANKS, crew::COMBAT_RANKS, journal::Event};
+
+const TRADE_RANKS: [&str; 9] = [
+    "Penniless",
+    "Mostly Penniless",
+    "Peddler",
+    "Dealer",
+    "Merchant",
+    "Broker",
+    "Entrepreneur",
+    "Tycoon",
+    "Elite",
+];
+
+const EXPLORE_RANKS: [&str; 9] = [
+    "Aimless",
+    "Mostly Aimless",
+    "Scout",
+    "Surveyor",
+    "Trailblazer",
+    "Pathfinder",
+    "Ranger",
+    "Pioneer",
+    "Elite",
+];
+
+const SOLDIER_RANKS: [&str; 9] = [
+    "Defenceless",
+    "Mostly Defenceless",
+    "Rookie",
+    "Soldier",
+    "Gunslinger",
+    "Warrior",
+    "Gladiator",
+    "Deadeye",
+    "Elite",
+];
+
+const EXOBIOLOGIST_RANKS: [&str; 9] = [
+    "Directionless",
+    "Mostly Directionless",
+    "Compiler",
+    "Collector",
+    "Cataloguer",
+    "Taxonomist",
+    "Ecologist",
+    "Geneticist",
+    "Elite",
+];
+
+const EMPIRE_RANKS: [&str; 15] = [
+    "None", "Outsider", "Serf", "Master", "Squire", "Knight", "Lord", "Baron", "Viscount", "Count",
+    "Earl", "Marquis", "Duke", "Prince", "King",
+];
+
+const FEDERATION_RANKS: [&str; 15] = [
+    "None",
+    "Recruit",
+    "Cadet",
+    "Midshipman",
+    "Petty Officer",
+    "Chief Petty Officer",
+    "Warrant Officer",
+    "Ensign",
+    "Lieutenant",
+    "Lieutenant Commander",
+    "Post Commander",
+    "Post Captain",
+    "Rear Admiral",
+    "Vice Admiral",
+    "Admiral",
+];
+
+/// Elite ranks past the first are numbered
+const ELITE_RANKS: [&str; 5] = ["Elite I", "Elite II", "Elite III", "Elite IV", "Elite V"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RankTrack {
+    Combat,
+    Trade,
+    Explore,
+    /// Mercenary rank on foot
+    Soldier,
+    Exobiologist,
+    Empire,
+    Federation,
+    Cqc,
+}
+
+impl RankTrack {
+    pub const ALL: [RankTrack; 8] = [
+        RankTrack::Combat,
+        RankTrack::Trade,
+        RankTrack::Explore,
+        RankTrack::Soldier,
+        RankTrack::Exobiologist,
+        RankTrack::Empire,
+        RankTrack::Federation,
+        RankTrack::Cqc,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RankTrack::Combat => "Combat",
+            RankTrack::Trade => "Trade",
+            RankTrack::Explore => "Exploration",
+            RankTrack::Soldier => "Mercenary",
+            RankTrack::Exobiologist => "Exobiology",
+            RankTrack::Empire => "Empire",
+            RankTrack::Federation => "Federation",
+            RankTrack::Cqc => "CQC",
+        }
+    }
+
+    /// Name of a rank on this track, `None` past the highest one
+    pub fn rank_name(self, rank: u32) -> Option<&'static str> {
+        let ranks: &[&str] = match self {
+            RankTrack::Combat => &COMBAT_RANKS,
+            RankTrack::Trade => &TRADE_RANKS,
+            RankTrack::Explore => &EXPLORE_RANKS,
+            RankTrack::Soldier => &SOLDIER_RANKS,
+            RankTrack::Exobiologist => &EXOBIOLOGIST_RANKS,
+            RankTrack::Empire => &EMPIRE_RANKS,
+            RankTrack::Federation => &FEDERATION_RANKS,
+            RankTrack::Cqc => &CQC_RANKS,
+        };
+        let rank = rank as usize;
+        match self {
+            RankTrack::Empire | RankTrack::Federation | RankTrack::Cqc => ranks.get(rank).copied(),
+            _ => ranks
+                .get(rank)
+                .or_else(|| ELITE_RANKS.get(rank.checked_sub(ranks.len())?))
+                .copied(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Promotion {
+    pub timestamp: OffsetDateTime,
+    pub track: RankTrack,
+    /// Rank reached
+    pub rank: u32,
+}
+
+/// Ranks of the commander and when each was reached
+#[derive(Debug, Default)]
+pub struct RankHistory {
+    pub ranks: BTreeMap<RankTrack, u32>,
+    /// Percent towards the next rank
+    pub progress: BTreeMap<RankTrack, u32>,
+    pub promotions: Vec<Promotion>,
+}
+
+impl RankHistory {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::Rank {
+                combat,
+                trade,
+                explore,
+                soldier,
+                exobiologist,
+                empire,
+                federation,
+                cqc,
+                ..
+            } => {
+                let ranks = [
+                    combat,
+                    trade,
+                    explore,
+                    soldier,
+                    exobiologist,
+                    empire,
+                    federation,
+                    cqc,
+                ];
+                self.ranks = RankTrack::ALL.into_iter().zip(ranks.map(|r| *r)).collect();
+            }
+            Event::Progress {
+                combat,
+                trade,
+                explore,
+                soldier,
+                exobiologist,
+                empire,
+                federation,
+                cqc,
+                ..
+            } => {
+                let progress = [
+                    combat,
+                    trade,
+                    explore,
+                    soldier,
+                    exobiologist,
+                    empire,
+                    federation,
+                    cqc,
+                ];
+                self.progress = RankTrack::ALL
+                    .into_iter()
+                    .zip(progress.map(|p| *p))
+                    .collect();
+            }
+            Event::Promotion {
+                timestamp,
+                combat,
+                trade,
+                explore,
+                soldier,
+                exobiologist,
+                empire,
+                federation,
+                cqc,
+            } => {
+                let ranks = [
+                    combat,
+                    trade,
+                    explore,
+                    soldier,
+                    exobiologist,
+                    empire,
+                    federation,
+                    cqc,
+                ];
+                for (track, rank) in RankTrack::ALL.into_iter().zip(ranks) {
+                    let Some(rank) = *rank else {
+                        continue;
+                    };
+                    self.ranks.insert(track, rank);
+                    self.progress.insert(track, 0);
+                    self.promotions.push(Promotion {
+                        timestamp: *timestamp,
+                        track,
+                        rank,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Latest promotion on a track
+    pub fn last_promotion(&self, track: RankTrack) -> Option<&Promotion> {
+        self.promotions.iter().rev().find(|p| p.track == track)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_rank_history() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Rank", "Combat":8, "Trade":2, "Explore":1, "Soldier":0, "Exobiologist":0, "Empire":14, "Federation":3, "CQC":0 }
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Progress", "Combat":95, "Trade":20, "Explore":30, "Soldier":0, "Exobiologist":0, "Empire":100, "Federation":40, "CQC":0 }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"Promotion", "Combat":9 }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"Promotion", "Trade":3, "Federation":4 }
+{ "timestamp":"2024-09-16T13:00:00Z", "event":"Promotion", "CQC":1 }
+"#,
+        )
+        .unwrap();
+        let mut history = RankHistory::default();
+        for event in &events {
+            history.update(event);
+        }
+
+        assert_eq!(history.promotions.len(), 4);
+        let combat = history.last_promotion(RankTrack::Combat).unwrap();
+        assert_eq!(combat.timestamp, datetime!(2024-09-15 13:00 UTC));
+        assert_eq!(RankTrack::Combat.rank_name(combat.rank), Some("Elite I"));
+        assert_eq!(history.progress[&RankTrack::Combat], 0);
+        assert_eq!(history.progress[&RankTrack::Explore], 30);
+        assert_eq!(history.ranks[&RankTrack::Federation], 4);
+        assert_eq!(RankTrack::Empire.rank_name(14), Some("King"));
+        assert_eq!(
+            history.last_promotion(RankTrack::Cqc).map(|p| p.rank),
+            Some(1)
+        );
+        assert_eq!(RankTrack::Cqc.rank_name(9), None);
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+/// Symbols of the rare goods sold in the live galaxy, as written to the
+/// Type of MarketBuy events
+pub const RARE_GOODS: &[&str] = &[
+    "aepyornisegg",
+    "aganipperush",
+    "alacarakmoskinart",
+    "albinoquechuamammoth",
+    "altairianskin",
+    "alyabodilysoap",
+    "anduligafireworks",
+    "anynacoffee",
+    "aroucaconventualsweets",
+    "azcancriformula42",
+    "baltahsinevacuumkrill",
+    "bankiamphibiousleather",
+    "bastsnakegin",
+    "belalansrayleather",
+    "borasetanipathogenetics",
+    "buckyballbeermats",
+    "burnhambiledistillate",
+    "cd75catcoffee",
+    "centaurimegagin",
+    "ceremonialheiketea",
+    "cetiaepyornisegg",
+    "cetirabbits",
+    "chameleoncloth",
+    "chateaudeaegaeon",
+    "cherbonesbloodcrystals",
+    "chieridanimarinepaste",
+    "coquimspongiformvictuals",
+    "deltaphoenicispalms",
+    "deuringastruffles",
+    "disomacorn",
+    "eleuthermals",
+    "eraninpearlwhisky",
+    "eshuumbrellas",
+    "esusekucaviar",
+    "ethgrezeteabuds",
+    "fujintea",
+    "galactictravelguide",
+    "geawendancedust",
+    "gerasiangueuzebeer",
+    "giantirukamasnails",
+    "giantverrix",
+    "gilyasignatureweapons",
+    "gomanyauponcoffee",
+    "haidneblackbrew",
+    "havasupaidreamcatcher",
+    "helvetitjpearls",
+    "hip10175bushmeat",
+    "hip41181squid",
+    "hiporganophosphates",
+    "holvaduellingblades",
+    "honestypills",
+    "hr7221wheat",
+    "indibourbon",
+    "jaquesquinentianstill",
+    "jaradharrepuzzlebox",
+    "jarouarice",
+    "jotunmookah",
+    "kachiriginleaches",
+    "kamitracigars",
+    "kamorinhistoricweapons",
+    "karetiicouture",
+    "karsukilocusts",
+    "kinagoinstruments",
+    "konggaale",
+    "korrokungpellets",
+    "lavianbrandy",
+    "leestianeviljuice",
+    "lftvoidextractcoffee",
+    "livehecateseaworms",
+    "ltthypersweet",
+    "lyraeweed",
+    "masterchefs",
+    "mechucoshightea",
+    "medbstarlube",
+    "mokojingbeastfeast",
+    "momusbogspaniel",
+    "motronaexperiencejelly",
+    "mukusubiichitinos",
+    "mulachigiantfungus",
+    "neritusberries",
+    "ngadandarifireopals",
+    "ngunamodernantiques",
+    "njangarisaddles",
+    "noneuclidianexotanks",
+    "ochoengchillies",
+    "onionhead",
+    "onionheada",
+    "onionheadb",
+    "ophiuchiexinoartefacts",
+    "orrerianviciousbrew",
+    "pantaaprayersticks",
+    "pavoniseargrubs",
+    "personalgifts",
+    "rajukrustoves",
+    "rapabaosnakeskins",
+    "rusanioldsmokey",
+    "sanumameat",
+    "saxonwine",
+    "shanscharisorchid",
+    "soontillrelics",
+    "sothiscrystallinegold",
+    "tanmarktranquiltea",
+    "taurichimes",
+    "terramaterbloodbores",
+    "thehuttonmug",
+    "thrutiscream",
+    "tiegfriessynthsilk",
+    "tiolcewaste2pasteunits",
+    "toxandjivirocide",
+    "transgeniconionhead",
+    "uszaiantreegrub",
+    "utgaroarmillenialeggs",
+    "uzumokulowgwings",
+    "vanayequirhinofur",
+    "vegaslimweed",
+    "vherculisbodyrub",
+    "voidextractcoffee",
+    "volkhabbeedrones",
+    "watersofshintara",
+    "wheemetewheatcakes",
+    "witchhaulkobebeef",
+    "wolf1301fesh",
+    "wulpahyperboresystems",
+    "wuthielokufroth",
+    "xihecompanions",
+    "yasokondileaf",
+    "zeesszeantglue",
+];
+
+/// Distance from the origin at which rares fetch their best price
+const FULL_PRICE_DISTANCE: f64 = 200.0;
+
+pub fn is_rare(commodity: &str) -> bool {
+    RARE_GOODS.contains(&commodity.to_lowercase().as_str())
+}
+
+/// Rare goods bought in one go
+#[derive(Debug, Clone)]
+pub struct RareLot {
+    pub commodity: String,
+    pub name: String,
+    pub count: u32,
+    pub buy_price: u64,
+    pub bought: OffsetDateTime,
+    pub station: Option<String>,
+    pub system: Option<String>,
+    star_pos: Option<[f64; 3]>,
+}
+
+impl RareLot {
+    /// Distance in light years from where the lot was bought
+    pub fn distance(&self, star_pos: [f64; 3]) -> Option<f64> {
+        let origin = self.star_pos?;
+        Some(
+            origin
+                .iter()
+                .zip(star_pos)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt(),
+        )
+    }
+
+    /// Rough price per ton when sold at `distance` light years from where
+    /// it was bought.
+    /// Rares hardly make a profit close to their origin, the premium grows
+    /// until about 200 ly where they fetch around three times their price.
+    pub fn estimated_price(&self, distance: f64) -> u64 {
+        let premium = 2.0 * (distance / FULL_PRICE_DISTANCE).min(1.0);
+        (self.buy_price as f64 * (1.0 + premium)).round() as u64
+    }
+}
+
+/// Rare goods in the hold and where they came from
+#[derive(Debug, Default)]
+pub struct RareCargo {
+    /// Oldest first, sales and losses take from the oldest lots
+    pub lots: Vec<RareLot>,
+    pub current_system: Option<String>,
+    pub star_pos: Option<[f64; 3]>,
+    station: Option<String>,
+    positions: HashMap<String, [f64; 3]>,
+}
+
+impl RareCargo {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump {
+                star_system,
+                star_pos,
+                ..
+            }
+            | Event::Location {
+                star_system,
+                star_pos,
+                ..
+            } => {
+                self.positions.insert(star_system.to_owned(), *star_pos);
+                self.current_system = Some(star_system.to_owned());
+                self.star_pos = Some(*star_pos);
+                self.station = None;
+            }
+            Event::Docked {
+                station_name,
+                star_system,
+                ..
+            } => {
+                self.station = Some(station_name.to_owned());
+                if let Some(pos) = self.positions.get(star_system) {
+                    self.current_system = Some(star_system.to_owned());
+                    self.star_pos = Some(*pos);
+                }
+            }
+            Event::MarketBuy {
+                timestamp,
+                commodity,
+                commodity_localised,
+                count,
+                buy_price,
+                ..
+            } if is_rare(commodity) => self.lots.push(RareLot {
+                commodity: commodity.to_lowercase(),
+                name: commodity_localised.as_ref().unwrap_or(commodity).to_owned(),
+                count: *count,
+                buy_price: *buy_price,
+                bought: *timestamp,
+                station: self.station.clone(),
+                system: self.current_system.clone(),
+                star_pos: self.star_pos,
+            }),
+            Event::MarketSell {
+                commodity, count, ..
+            }
+            | Event::EjectCargo {
+                commodity, count, ..
+            } if is_rare(commodity) => self.remove(commodity, *count),
+            Event::Died { .. } => self.lots.clear(),
+            _ => {}
+        }
+    }
+
+    fn remove(&mut self, commodity: &str, mut count: u32) {
+        let commodity = commodity.to_lowercase();
+        for lot in self.lots.iter_mut().filter(|l| l.commodity == commodity) {
+            let taken = lot.count.min(count);
+            lot.count -= taken;
+            count -= taken;
+        }
+        self.lots.retain(|lot| lot.count > 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_rare_cargo() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Location", "StarSystem":"Lave", "SystemAddress":1, "StarPos":[75.75,48.75,70.75], "Docked":true }
+{ "timestamp":"2024-09-15T12:00:10Z", "event":"Docked", "StationName":"Lave Station", "StarSystem":"Lave", "MarketID":1 }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"MarketBuy", "MarketID":1, "Type":"lavianbrandy", "Type_Localised":"Lavian Brandy", "Count":10, "BuyPrice":5000, "TotalCost":50000 }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"MarketBuy", "MarketID":1, "Type":"tritium", "Count":10, "BuyPrice":50000, "TotalCost":500000 }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"FSDJump", "StarSystem":"Far", "SystemAddress":2, "StarPos":[75.75,48.75,370.75], "JumpDist":30.0, "FuelUsed":1.0, "FuelLevel":20.0 }
+{ "timestamp":"2024-09-15T12:40:00Z", "event":"MarketSell", "MarketID":2, "Type":"lavianbrandy", "Count":4, "SellPrice":15000, "TotalSale":60000, "AvgPricePaid":5000 }
+"#,
+        )
+        .unwrap();
+        let mut rares = RareCargo::default();
+        for event in &events {
+            rares.update(event);
+        }
+
+        assert_eq!(rares.lots.len(), 1);
+        let lot = &rares.lots[0];
+        assert_eq!(lot.count, 6);
+        assert_eq!(lot.station.as_deref(), Some("Lave Station"));
+        let distance = lot.distance(rares.star_pos.unwrap()).unwrap();
+        assert_eq!(distance, 300.0);
+        assert_eq!(lot.estimated_price(distance), 15000);
+    }
+}
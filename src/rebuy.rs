@@ -0,0 +1,58 @@
+use crate::{credits::Balance, journal::Event};
+
+/// Whether the commander can afford to lose their ship
+#[derive(Debug, Default)]
+pub struct RebuyMonitor {
+    /// Rebuy of the current ship, from the last Loadout
+    pub rebuy: Option<u64>,
+    pub balance: Balance,
+}
+
+impl RebuyMonitor {
+    pub fn update(&mut self, event: &Event) {
+        if let Event::Loadout { rebuy, .. } = event {
+            self.rebuy = Some(u64::from(*rebuy));
+        }
+        self.balance.update(event);
+    }
+
+    /// Number of rebuys the credits cover, if both are known
+    pub fn affordable_rebuys(&self) -> Option<u64> {
+        let credits = self.balance.credits?.max(0) as u64;
+        match self.rebuy? {
+            0 => Some(u64::MAX),
+            rebuy => Some(credits / rebuy),
+        }
+    }
+
+    /// Whether `count` rebuys can be paid, if the credits and rebuy are known
+    pub fn can_afford(&self, count: u64) -> Option<bool> {
+        self.affordable_rebuys().map(|n| n >= count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_rebuy_monitor() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"LoadGame", "FID":"F1", "Commander":"Jameson", "Horizons":true, "Ship":"Anaconda", "ShipID":1, "GameMode":"Open", "Credits":10000000, "Loan":0 }
+{ "timestamp":"2024-09-15T12:00:05Z", "event":"Loadout", "Ship":"anaconda", "ShipID":1, "ShipName":"", "ShipIdent":"", "Rebuy":4000000, "Modules":[] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"MarketBuy", "MarketID":1, "Type":"gold", "Count":100, "BuyPrice":30000, "TotalCost":3000000 }
+"#,
+        )
+        .unwrap();
+        let mut monitor = RebuyMonitor::default();
+        for event in &events {
+            monitor.update(event);
+        }
+
+        assert_eq!(monitor.balance.credits, Some(7_000_000));
+        assert_eq!(monitor.affordable_rebuys(), Some(1));
+        assert_eq!(monitor.can_afford(2), Some(false));
+    }
+}
@@ -0,0 +1,283 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::journal::Event;
+
+/// Names of the galactic regions by ID, as used in "$Codex_RegionName_1;"
+pub const REGIONS: [&str; 42] = [
+    "Galactic Centre",
+    "Empyrean Straits",
+    "Ryker's Hope",
+    "Odin's Hold",
+    "Norma Arm",
+    "Arcadian Stream",
+    "Izanami",
+    "Inner Orion-Perseus Conflux",
+    "Inner Scutum-Centaurus Arm",
+    "Norma Expanse",
+    "Trojan Belt",
+    "The Veils",
+    "Newton's Vault",
+    "The Conduit",
+    "Outer Orion-Perseus Conflux",
+    "Orion-Cygnus Arm",
+    "Temple",
+    "Inner Orion Spur",
+    "Hawking's Gap",
+    "Dryman's Point",
+    "Sagittarius-Carina Arm",
+    "Mare Somnia",
+    "Acheron",
+    "Formorian Frontier",
+    "Hieronymus Delta",
+    "Outer Scutum-Centaurus Arm",
+    "Outer Arm",
+    "Aquila's Halo",
+    "Errant Marches",
+    "Perseus Arm",
+    "Formidine Rift",
+    "Vulcan Gate",
+    "Elysian Shore",
+    "Sanguineous Rim",
+    "Outer Orion Spur",
+    "Achilles's Altar",
+    "Xibalba",
+    "Lyra's Song",
+    "Tenebrae",
+    "The Abyss",
+    "Kepler's Crest",
+    "The Void",
+];
+
+/// Name of the region with the given ID, starting at 1
+pub fn region_name(id: u8) -> Option<&'static str> {
+    REGIONS.get(usize::from(id).checked_sub(1)?).copied()
+}
+
+/// Region ID from a symbol like "$Codex_RegionName_18;"
+pub fn region_id(symbol: &str) -> Option<u8> {
+    symbol
+        .strip_prefix("$Codex_RegionName_")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+        .filter(|id| region_name(*id).is_some())
+}
+
+/// Galactic coordinates of the corner of the region map
+const MAP_ORIGIN: [f64; 2] = [-49985.0, -24105.0];
+/// Light years per cell of the region map
+const MAP_CELL: f64 = 4096.0 / 83.0;
+
+/// Map of the galactic regions as published with the game's codex art,
+/// in the format of `RegionMapData.json` from
+/// <https://github.com/klightspeed/EliteDangerousRegionMap>: rows of
+/// run-length encoded `[length, region ID]` cells along x, one row per
+/// cell along z
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegionMap {
+    #[serde(rename = "regionmap")]
+    rows: Vec<Vec<(u32, u8)>>,
+}
+
+impl RegionMap {
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::parse(&fs::read_to_string(path)?)?)
+    }
+
+    /// Region ID at a star position, `None` outside the galaxy
+    pub fn region(&self, star_pos: [f64; 3]) -> Option<u8> {
+        let [x, _, z] = star_pos;
+        let column = ((x - MAP_ORIGIN[0]) / MAP_CELL).floor();
+        let row = ((z - MAP_ORIGIN[1]) / MAP_CELL).floor();
+        if column < 0.0 || row < 0.0 {
+            return None;
+        }
+
+        let mut end = 0;
+        for (length, id) in self.rows.get(row as usize)? {
+            end += *length as usize;
+            if (column as usize) < end {
+                return Some(*id).filter(|id| region_name(*id).is_some());
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SystemVisit {
+    pub name: String,
+    pub star_pos: [f64; 3],
+    pub jumps: u32,
+    /// Time from arriving until leaving or shutting down the game
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegionSummary {
+    pub systems: u32,
+    pub jumps: u32,
+    pub time: Duration,
+}
+
+/// Jumps and time spent per galactic region.
+/// Codex entries name the region of the system they were made in; every
+/// other visited system is looked up by its position on the [RegionMap].
+#[derive(Debug, Default)]
+pub struct Regions {
+    pub map: Option<RegionMap>,
+    pub visits: BTreeMap<u64, SystemVisit>,
+    /// Region ID by system address, from codex entries
+    pub known: HashMap<u64, u8>,
+    /// System the commander is in and since when
+    stay: Option<(u64, OffsetDateTime)>,
+}
+
+impl Regions {
+    pub fn new(map: RegionMap) -> Self {
+        Self {
+            map: Some(map),
+            ..Default::default()
+        }
+    }
+
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump {
+                timestamp,
+                star_system,
+                system_address,
+                star_pos,
+                ..
+            }
+            | Event::Location {
+                timestamp,
+                star_system,
+                system_address,
+                star_pos,
+                ..
+            } => {
+                self.leave(*timestamp);
+                let visit = self
+                    .visits
+                    .entry(*system_address)
+                    .or_insert_with(|| SystemVisit {
+                        name: star_system.clone(),
+                        star_pos: *star_pos,
+                        jumps: 0,
+                        time: Duration::ZERO,
+                    });
+                if let Event::FSDJump { .. } = event {
+                    visit.jumps += 1;
+                }
+                self.stay = Some((*system_address, *timestamp));
+            }
+            Event::CodexEntry {
+                region,
+                system_address,
+                ..
+            } => {
+                if let Some(id) = region_id(region) {
+                    self.known.insert(*system_address, id);
+                }
+            }
+            Event::Shutdown { timestamp } | Event::Fileheader { timestamp, .. } => {
+                self.leave(*timestamp);
+            }
+            _ => {}
+        }
+    }
+
+    fn leave(&mut self, now: OffsetDateTime) {
+        if let Some((address, since)) = self.stay.take() {
+            if let Some(visit) = self.visits.get_mut(&address) {
+                visit.time += now - since;
+            }
+        }
+    }
+
+    /// Region of a visited system, from its codex entries or the map
+    pub fn region(&self, system_address: u64) -> Option<u8> {
+        if let Some(id) = self.known.get(&system_address) {
+            return Some(*id);
+        }
+        let visit = self.visits.get(&system_address)?;
+        self.map.as_ref()?.region(visit.star_pos)
+    }
+
+    /// Systems, jumps and time by region ID, `None` for unknown regions
+    pub fn summary(&self) -> BTreeMap<Option<u8>, RegionSummary> {
+        let mut summary: BTreeMap<Option<u8>, RegionSummary> = BTreeMap::new();
+        for (address, visit) in &self.visits {
+            let region = summary.entry(self.region(*address)).or_default();
+            region.systems += 1;
+            region.jumps += visit.jumps;
+            region.time += visit.time;
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_regions() {
+        assert_eq!(region_id("$Codex_RegionName_18;"), Some(18));
+        assert_eq!(region_name(18), Some("Inner Orion Spur"));
+        assert_eq!(region_id("$Codex_RegionName_43;"), None);
+
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Location", "Docked":false, "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0] }
+{ "timestamp":"2024-09-15T12:05:00Z", "event":"CodexEntry", "EntryID":1, "Name":"$Codex_Ent_G_Type_Name;", "SubCategory":"$Codex_SubCategory_Stars;", "Category":"$Codex_Category_StellarBodies;", "Region":"$Codex_RegionName_18;", "System":"Sol", "SystemAddress":10477373803 }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"FSDJump", "StarSystem":"Alpha Centauri", "SystemAddress":1, "StarPos":[3.0,0.0,3.0], "JumpDist":4.4, "FuelUsed":0.5, "FuelLevel":31.5 }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"FSDJump", "StarSystem":"Colonia", "SystemAddress":2, "StarPos":[-9530.5,-910.3,19808.1], "JumpDist":60.0, "FuelUsed":5.0, "FuelLevel":26.5 }
+{ "timestamp":"2024-09-15T12:50:00Z", "event":"Shutdown" }
+"#,
+        )
+        .unwrap();
+
+        // Inner Orion Spur around Sol, nothing in any region elsewhere
+        let mut rows = vec![vec![(2048, 0)]; 2048];
+        rows[488] = vec![(1000, 1), (100, 18), (948, 0)];
+        let map = RegionMap::parse(&serde_json::json!({ "regionmap": rows }).to_string()).unwrap();
+        assert_eq!(map.region([0.0, 0.0, 0.0]), Some(18));
+        assert_eq!(map.region([-49000.0, 0.0, 0.0]), Some(1));
+        assert_eq!(map.region([0.0, 0.0, -30000.0]), None);
+        assert_eq!(map.region([-9530.5, -910.3, 19808.1]), None);
+
+        let mut regions = Regions::default();
+        for event in &events {
+            regions.update(event);
+        }
+        assert_eq!(regions.region(10477373803), Some(18));
+        assert_eq!(regions.region(1), None);
+
+        let mut regions = Regions::new(map);
+        for event in &events {
+            regions.update(event);
+        }
+
+        let summary = regions.summary();
+        let spur = summary[&Some(18)];
+        assert_eq!(spur.systems, 2);
+        assert_eq!(spur.jumps, 1);
+        assert_eq!(spur.time, Duration::minutes(20));
+        assert_eq!(summary[&None].time, Duration::minutes(30));
+    }
+}
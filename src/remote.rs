@@ -0,0 +1,181 @@
+//! Journals on another machine shared over WebDAV or by `elite serve
+//! --journals`, for reading the journals of a gaming PC from a laptop.
+//!
+//! Anything serving the journals directory over WebDAV works, e.g.
+//! `rclone serve webdav`, which can also put SFTP servers and S3 buckets
+//! behind WebDAV.
+
+use std::{io, time::Duration};
+
+use ureq::{http::Request, Agent};
+
+use crate::journal::JournalSource;
+
+const LISTING: &str = r#"<?xml version="1.0" encoding="utf-8"?><propfind xmlns="DAV:"><prop><resourcetype/></prop></propfind>"#;
+
+#[derive(Debug, Clone)]
+pub struct WebDavSource {
+    /// URL of the journals directory, ending with a slash
+    url: String,
+    agent: Agent,
+}
+
+impl WebDavSource {
+    pub fn new(url: &str) -> Self {
+        WebDavSource {
+            url: format!("{}/", url.trim_end_matches('/')),
+            agent: Agent::config_builder()
+                .user_agent("elite-rs")
+                .timeout_global(Some(Duration::from_secs(60)))
+                .allow_non_standard_methods(true)
+                .build()
+                .into(),
+        }
+    }
+}
+
+impl JournalSource for WebDavSource {
+    fn journal_names(&self) -> Result<Vec<String>, io::Error> {
+        let request = Request::builder()
+            .method("PROPFIND")
+            .uri(&self.url)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml")
+            .body(LISTING)
+            .map_err(io::Error::other)?;
+        let listing = self
+            .agent
+            .run(request)
+            .map_err(io::Error::other)?
+            .body_mut()
+            .read_to_string()
+            .map_err(io::Error::other)?;
+
+        let mut names: Vec<String> = hrefs(&listing)
+            .filter_map(|href| href.trim_end_matches('/').rsplit('/').next())
+            .map(percent_decode)
+            .filter(|name| name.starts_with("Journal"))
+            .collect();
+        names.sort();
+
+        Ok(names)
+    }
+
+    fn read_journal(&self, name: &str) -> Result<String, io::Error> {
+        self.agent
+            .get(format!("{}{name}", self.url))
+            .call()
+            .map_err(io::Error::other)?
+            .body_mut()
+            // Journals can be larger than the default limit of 10 MB
+            .with_config()
+            .limit(u64::MAX)
+            .read_to_string()
+            .map_err(io::Error::other)
+    }
+}
+
+/// Journals of another machine running `elite serve --journals`
+#[derive(Debug, Clone)]
+pub struct ServeSource {
+    /// URL of the server, without a trailing slash
+    url: String,
+    agent: Agent,
+}
+
+impl ServeSource {
+    /// Source for a server at a URL like "http://192.168.1.10:8080"
+    pub fn new(url: &str) -> Self {
+        ServeSource {
+            url: url.trim_end_matches('/').to_owned(),
+            agent: Agent::config_builder()
+                .user_agent("elite-rs")
+                .timeout_global(Some(Duration::from_secs(60)))
+                .build()
+                .into(),
+        }
+    }
+
+    fn get(&self, path: &str) -> Result<String, io::Error> {
+        self.agent
+            .get(format!("{}{path}", self.url))
+            .call()
+            .map_err(io::Error::other)?
+            .body_mut()
+            .with_config()
+            .limit(u64::MAX)
+            .read_to_string()
+            .map_err(io::Error::other)
+    }
+}
+
+impl JournalSource for ServeSource {
+    fn journal_names(&self) -> Result<Vec<String>, io::Error> {
+        Ok(self
+            .get("/journals")?
+            .lines()
+            .filter(|name| !name.is_empty())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn read_journal(&self, name: &str) -> Result<String, io::Error> {
+        self.get(&format!("/journals/{name}"))
+    }
+}
+
+/// Contents of the `href` elements of a PROPFIND response, whatever prefix
+/// the server uses for the DAV namespace
+fn hrefs(listing: &str) -> impl Iterator<Item = &str> {
+    listing.split('<').filter_map(|tag| {
+        let (name, text) = tag.split_once('>')?;
+        // Not the closing tags
+        (name == "href" || name.ends_with(":href") && !name.starts_with('/')).then_some(text.trim())
+    })
+}
+
+fn percent_decode(s: &str) -> String {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        let hex = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (byte, hex) {
+            (b'%', Some(decoded)) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listing() {
+        let listing = r#"<?xml version="1.0" encoding="UTF-8"?>
+<D:multistatus xmlns:D="DAV:">
+<D:response><D:href>/elite/</D:href><D:propstat><D:prop><D:resourcetype><D:collection/></D:resourcetype></D:prop></D:propstat></D:response>
+<D:response><D:href>/elite/Journal.2024-09-15T120000.01.log</D:href><D:propstat><D:prop><D:resourcetype/></D:prop></D:propstat></D:response>
+<D:response><D:href>/elite/Status%2Ejson</D:href><D:propstat><D:prop><D:resourcetype/></D:prop></D:propstat></D:response>
+</D:multistatus>"#;
+
+        let hrefs: Vec<&str> = hrefs(listing).collect();
+        assert_eq!(hrefs.len(), 3);
+        assert_eq!(
+            percent_decode(hrefs[2].rsplit('/').next().unwrap()),
+            "Status.json"
+        );
+    }
+}
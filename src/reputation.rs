@@ -0,0 +1,151 @@
+//! How the commander's standing with minor factions changed over time.
+//!
+//! Jumps and logins report the actual reputation with every faction in the
+//! system, completed missions only how much it went up in pluses.
+
+use std::collections::HashMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReputationChange {
+    /// Reputation from -100 to 100 seen in a system, with the change since
+    /// it was last seen
+    Seen {
+        system: String,
+        reputation: f64,
+        change: Option<f64>,
+    },
+    /// Pluses from a completed mission
+    Mission { name: String, pluses: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReputationEntry {
+    pub timestamp: OffsetDateTime,
+    pub faction: String,
+    pub change: ReputationChange,
+}
+
+#[derive(Debug, Default)]
+pub struct FactionReputation {
+    pub history: Vec<ReputationEntry>,
+    /// Last reputation seen by faction
+    pub current: HashMap<String, f64>,
+}
+
+impl FactionReputation {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump {
+                timestamp,
+                star_system,
+                factions,
+                ..
+            }
+            | Event::Location {
+                timestamp,
+                star_system,
+                factions,
+                ..
+            } => {
+                for faction in factions {
+                    let Some(reputation) = faction.my_reputation else {
+                        continue;
+                    };
+                    let previous = self.current.insert(faction.name.clone(), reputation);
+                    if previous == Some(reputation) {
+                        continue;
+                    }
+                    self.history.push(ReputationEntry {
+                        timestamp: *timestamp,
+                        faction: faction.name.clone(),
+                        change: ReputationChange::Seen {
+                            system: star_system.clone(),
+                            reputation,
+                            change: previous.map(|previous| reputation - previous),
+                        },
+                    });
+                }
+            }
+            Event::MissionCompleted {
+                timestamp,
+                name,
+                faction_effects,
+                ..
+            } => {
+                for effect in faction_effects {
+                    let pluses = effect.reputation.matches('+').count() as u32;
+                    if effect.faction.is_empty() || pluses == 0 {
+                        continue;
+                    }
+                    self.history.push(ReputationEntry {
+                        timestamp: *timestamp,
+                        faction: effect.faction.clone(),
+                        change: ReputationChange::Mission {
+                            name: name.clone(),
+                            pluses,
+                        },
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// History of one faction, in any case
+    pub fn faction<'a>(&'a self, faction: &'a str) -> impl Iterator<Item = &'a ReputationEntry> {
+        self.history
+            .iter()
+            .filter(move |entry| entry.faction.eq_ignore_ascii_case(faction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_reputation_history() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Location", "StarSystem":"Ross 128", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "Docked":true, "Factions":[ { "Name":"Ross 128 Blue Vision", "FactionState":"None", "Government":"Democracy", "Influence":0.4, "Allegiance":"Federation", "Happiness":"$Faction_HappinessBand2;", "MyReputation":10.0 }, { "Name":"Pilots' Federation Local Branch", "FactionState":"None", "Government":"Democracy", "Influence":0.0, "Allegiance":"PilotsFederation", "Happiness":"", "MyReputation":0.0 } ], "SystemFaction":{ "Name":"Ross 128 Blue Vision" } }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"MissionCompleted", "Faction":"Ross 128 Blue Vision", "Name":"Mission_Delivery_name", "MissionID":1, "Reward":50000, "FactionEffects":[ { "Faction":"Ross 128 Blue Vision", "Effects":[], "Influence":[ { "SystemAddress":1, "Trend":"UpGood", "Influence":"++" } ], "ReputationTrend":"UpGood", "Reputation":"+++" }, { "Faction":"", "Effects":[], "Influence":[], "ReputationTrend":"UpGood", "Reputation":"+" } ] }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"FSDJump", "StarSystem":"Ross 128", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":0.0, "FuelUsed":0.0, "FuelLevel":10.0, "Factions":[ { "Name":"Ross 128 Blue Vision", "FactionState":"Boom", "Government":"Democracy", "Influence":0.42, "Allegiance":"Federation", "MyReputation":14.5 }, { "Name":"Pilots' Federation Local Branch", "FactionState":"None", "Government":"Democracy", "Influence":0.0, "Allegiance":"PilotsFederation", "MyReputation":0.0 } ] }
+"#,
+        )
+        .unwrap();
+        let mut reputation = FactionReputation::default();
+        for event in &events {
+            reputation.update(event);
+        }
+
+        let history: Vec<_> = reputation.faction("ross 128 blue vision").collect();
+        assert_eq!(history.len(), 3);
+        assert_eq!(
+            history[1].change,
+            ReputationChange::Mission {
+                name: "Mission_Delivery_name".to_owned(),
+                pluses: 3
+            }
+        );
+        assert_eq!(
+            history[2].change,
+            ReputationChange::Seen {
+                system: "Ross 128".to_owned(),
+                reputation: 14.5,
+                change: Some(4.5)
+            }
+        );
+        // Unchanged reputation is only recorded the first time
+        assert_eq!(
+            reputation
+                .faction("Pilots' Federation Local Branch")
+                .count(),
+            1
+        );
+    }
+}
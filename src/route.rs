@@ -0,0 +1,165 @@
+use time::{Duration, OffsetDateTime};
+
+use crate::journal::{Event, RouteEntry};
+
+/// How many of the latest jumps the ETA is based on
+const CADENCE_JUMPS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteChange {
+    Plotted,
+    /// A different route was plotted while following one
+    Replotted,
+    Cleared,
+}
+
+/// Whether fuel can be scooped from stars of a spectral class, which
+/// are the main sequence classes KGBFOAM
+pub fn is_scoopable(star_class: &str) -> bool {
+    matches!(star_class, "K" | "G" | "B" | "F" | "O" | "A" | "M")
+}
+
+/// Progress along the route plotted in the galaxy map
+#[derive(Debug, Default)]
+pub struct RouteProgress {
+    /// Every system of the route, starting with the one it was plotted from
+    pub route: Vec<RouteEntry>,
+    /// Index of the system on the route the commander is in
+    pub position: usize,
+    /// Arrival times of the latest jumps
+    jumps: Vec<OffsetDateTime>,
+}
+
+impl RouteProgress {
+    /// Start following a route, usually read from NavRoute.json
+    pub fn set_route(&mut self, route: Vec<RouteEntry>) -> Option<RouteChange> {
+        if route.is_empty() {
+            return self.clear();
+        }
+        if route == self.route {
+            return None;
+        }
+
+        let change = match self.route.is_empty() {
+            true => RouteChange::Plotted,
+            false => RouteChange::Replotted,
+        };
+        self.route = route;
+        self.position = 0;
+        Some(change)
+    }
+
+    fn clear(&mut self) -> Option<RouteChange> {
+        if self.route.is_empty() {
+            return None;
+        }
+        self.route.clear();
+        self.position = 0;
+        Some(RouteChange::Cleared)
+    }
+
+    /// Follow jumps along the route.
+    /// NavRoute events from the journal don't contain the route, pass the
+    /// contents of NavRoute.json to [RouteProgress::set_route] for those.
+    pub fn update(&mut self, event: &Event) -> Option<RouteChange> {
+        match event {
+            Event::FSDJump {
+                timestamp,
+                system_address,
+                ..
+            } => {
+                self.jumps.push(*timestamp);
+                if self.jumps.len() > CADENCE_JUMPS + 1 {
+                    self.jumps.remove(0);
+                }
+
+                if let Some(i) = self
+                    .route
+                    .iter()
+                    .skip(self.position)
+                    .position(|s| s.system_address == *system_address)
+                {
+                    self.position += i;
+                }
+                None
+            }
+            Event::NavRoute { route, .. } if !route.is_empty() => self.set_route(route.clone()),
+            Event::NavRouteClear { .. } => self.clear(),
+            _ => None,
+        }
+    }
+
+    pub fn remaining_jumps(&self) -> usize {
+        self.route.len().saturating_sub(self.position + 1)
+    }
+
+    /// Straight line distance of the remaining jumps in light years
+    pub fn remaining_distance(&self) -> f64 {
+        self.route
+            .iter()
+            .skip(self.position)
+            .zip(self.route.iter().skip(self.position + 1))
+            .map(|(a, b)| {
+                a.star_pos
+                    .iter()
+                    .zip(b.star_pos)
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .sum()
+    }
+
+    /// Next system to jump to
+    pub fn next(&self) -> Option<&RouteEntry> {
+        self.route.get(self.position + 1)
+    }
+
+    /// Average time between the latest jumps
+    pub fn jump_interval(&self) -> Option<Duration> {
+        let (first, last) = (self.jumps.first()?, self.jumps.last()?);
+        let intervals = self.jumps.len() as i32 - 1;
+        (intervals > 0).then(|| (*last - *first) / intervals)
+    }
+
+    /// Time until arrival at the recent jump cadence
+    pub fn eta(&self) -> Option<Duration> {
+        self.jump_interval()
+            .map(|interval| interval * self.remaining_jumps() as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_route_progress() {
+        let mut progress = RouteProgress::default();
+        let route = parse_events(
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"NavRoute", "Route":[ { "StarSystem":"Sol", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "StarClass":"G" }, { "StarSystem":"A", "SystemAddress":2, "StarPos":[0.0,0.0,10.0], "StarClass":"K" }, { "StarSystem":"B", "SystemAddress":3, "StarPos":[0.0,0.0,30.0], "StarClass":"M" } ] }"#,
+        )
+        .unwrap();
+        assert_eq!(progress.update(&route[0]), Some(RouteChange::Plotted));
+        assert_eq!(progress.remaining_jumps(), 2);
+        assert_eq!(progress.remaining_distance(), 30.0);
+
+        let jumps = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"FSDJump", "StarSystem":"A", "SystemAddress":2, "StarPos":[0.0,0.0,10.0], "JumpDist":10.0, "FuelUsed":1.0, "FuelLevel":20.0 }
+{ "timestamp":"2024-09-15T12:01:40Z", "event":"FSDJump", "StarSystem":"X", "SystemAddress":9, "StarPos":[0.0,0.0,10.0], "JumpDist":10.0, "FuelUsed":1.0, "FuelLevel":20.0 }
+"#,
+        )
+        .unwrap();
+        for jump in &jumps {
+            progress.update(jump);
+        }
+        // Systems off the route don't count
+        assert_eq!(progress.remaining_jumps(), 1);
+        assert_eq!(progress.next().unwrap().star_system, "B");
+        assert!(is_scoopable(&progress.next().unwrap().star_class));
+        assert!(!is_scoopable("DA"));
+        assert_eq!(progress.eta(), Some(Duration::seconds(40)));
+    }
+}
@@ -0,0 +1,602 @@
+//! Alerts declared as data instead of code, like the `[[rules]]` in the
+//! config of the CLI:
+//!
+//! ```toml
+//! [[rules]]
+//! name = "Big bounty"
+//! event = "Bounty"
+//! when = "TotalReward >= 1000000"
+//! cooldown = 60
+//! actions = [
+//!     { notify = "{TotalReward} CR on {Target}" },
+//!     { command = ["paplay", "/home/cmdr/sounds/bounty.oga"] },
+//!     { webhook = "http://localhost:8080/bounty" },
+//! ]
+//!
+//! [[rules]]
+//! name = "Carrier jump"
+//! event = "CarrierJump"
+//! actions = [
+//!     { discord = { url = "https://discord.com/api/webhooks/...", description = "Arrived at {Body}", fields = ["StarSystem"] } },
+//! ]
+//! ```
+//!
+//! A rule with an `event` fires on events of that kind, if `when` matches
+//! them. Without one `when` is a condition on the fields of Status.json,
+//! firing each time it becomes true. `mission_expiry` fires that many
+//! minutes before accepted missions expire instead. Conditions are
+//! [Filter] expressions and `{Field}` in the text of actions is replaced
+//! by the value of the field, with the fields as the game wrote them to
+//! the journal. A `discord` action posts an embed to a
+//! Discord webhook, titled with the rule's name unless given a `title`.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    process::Command,
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use ureq::Agent;
+
+use crate::{
+    journal::{fill_fields, FieldPath, Filter, Status},
+    processor::{EventProcessor, Notify, ProcessorResult},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub name: String,
+    /// Kind of event the rule fires on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    /// Condition on the event, or on Status.json without `event`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    /// Minutes before missions expire to fire
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mission_expiry: Option<i64>,
+    /// Seconds to stay quiet after firing
+    #[serde(default)]
+    pub cooldown: u64,
+    pub actions: Vec<Action>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// Notification with this as body and the rule's name as summary
+    Notify(String),
+    /// Program to run and its arguments
+    Command(Vec<String>),
+    /// URL to POST the JSON of the event, status or mission to
+    Webhook(String),
+    /// Embed to post to a Discord webhook
+    Discord(Discord),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Discord {
+    /// Webhook URL from the settings of the Discord channel
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Fields of the event to show below the description
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    /// Color of the embed's edge as RGB, the orange of the HUD by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+}
+
+const HUD_ORANGE: u32 = 0xff8c0d;
+
+/// Body of a Discord webhook with one embed, its fields taken from the
+/// event, status or mission of the firing
+fn discord_message(discord: &Discord, rule: &str, payload: &Value) -> Value {
+    let fields: Vec<Value> = discord
+        .fields
+        .iter()
+        .map(|field| {
+            serde_json::json!({
+                "name": field,
+                "value": fill_fields(&format!("{{{field}}}"), payload),
+                "inline": true,
+            })
+        })
+        .collect();
+    let mut embed = serde_json::json!({
+        "title": discord.title.as_deref().unwrap_or(rule),
+        "color": discord.color.unwrap_or(HUD_ORANGE),
+        "fields": fields,
+    });
+    if let Some(description) = &discord.description {
+        embed["description"] = description.as_str().into();
+    }
+    if let Some(timestamp) = payload.get("timestamp") {
+        embed["timestamp"] = timestamp.clone();
+    }
+    serde_json::json!({ "username": "elite-rs", "embeds": [embed] })
+}
+
+/// Names of the rules [builtin_rule] knows
+pub const BUILTIN_RULES: [&str; 4] = ["interdiction", "hull", "mission-expiry", "wanted"];
+
+/// Rules for the usual alerts, so they don't have to be written out
+pub fn builtin_rule(name: &str) -> Option<Rule> {
+    let rule = |title: &str, event: Option<&str>, when: Option<&str>, notify: &str| Rule {
+        name: title.to_owned(),
+        event: event.map(str::to_owned),
+        when: when.map(str::to_owned),
+        mission_expiry: None,
+        cooldown: 0,
+        actions: vec![Action::Notify(notify.to_owned())],
+    };
+
+    match name {
+        "interdiction" => Some(rule(
+            "Interdiction",
+            Some("Interdicted"),
+            None,
+            "Interdicted by {Interdictor}",
+        )),
+        "hull" => Some(Rule {
+            cooldown: 30,
+            ..rule(
+                "Hull damage",
+                Some("HullDamage"),
+                Some("PlayerPilot && Health < 0.5"),
+                "Hull integrity at {Health}",
+            )
+        }),
+        "mission-expiry" => Some(Rule {
+            mission_expiry: Some(60),
+            ..rule(
+                "Mission expiring",
+                None,
+                None,
+                "{LocalisedName} expires at {Expiry}",
+            )
+        }),
+        "wanted" => Some(rule(
+            "Wanted",
+            None,
+            Some(r#"LegalState == "Wanted""#),
+            "Wanted by the local authorities",
+        )),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleError {
+    pub rule: String,
+    pub message: String,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rule {}: {}", self.rule, self.message)
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// A rule that fired, with the fields filled into its actions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Firing {
+    pub rule: String,
+    pub actions: Vec<Action>,
+    /// Event, status or mission the rule fired on
+    pub payload: Value,
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    rule: Rule,
+    when: Option<Filter>,
+    last_fired: Option<OffsetDateTime>,
+    /// Whether the status condition was true on the last check
+    active: bool,
+    /// Missions already warned about
+    warned: HashSet<u64>,
+}
+
+impl CompiledRule {
+    fn fire(&mut self, payload: &Value, now: OffsetDateTime) -> Option<Firing> {
+        let cooldown = time::Duration::seconds(self.rule.cooldown as i64);
+        if self.last_fired.is_some_and(|last| now < last + cooldown) {
+            return None;
+        }
+        self.last_fired = Some(now);
+
+        let actions = self
+            .rule
+            .actions
+            .iter()
+            .map(|action| match action {
+                Action::Notify(text) => Action::Notify(fill_fields(text, payload)),
+                Action::Command(args) => {
+                    Action::Command(args.iter().map(|arg| fill_fields(arg, payload)).collect())
+                }
+                Action::Webhook(url) => Action::Webhook(url.clone()),
+                Action::Discord(discord) => Action::Discord(Discord {
+                    title: discord
+                        .title
+                        .as_deref()
+                        .map(|title| fill_fields(title, payload)),
+                    description: discord
+                        .description
+                        .as_deref()
+                        .map(|description| fill_fields(description, payload)),
+                    ..discord.clone()
+                }),
+            })
+            .collect();
+        Some(Firing {
+            rule: self.rule.name.clone(),
+            actions,
+            payload: payload.clone(),
+        })
+    }
+}
+
+fn mission_expiry(mission: &Value) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(mission["Expiry"].as_str()?, &Rfc3339).ok()
+}
+
+/// Decides which rules fire on the raw JSON of events and the status
+#[derive(Debug, Default)]
+pub struct RuleEngine {
+    rules: Vec<CompiledRule>,
+    /// MissionAccepted events of missions in progress keyed by mission ID
+    missions: BTreeMap<u64, Value>,
+}
+
+impl RuleEngine {
+    pub fn new(rules: Vec<Rule>) -> Result<Self, RuleError> {
+        let rules = rules
+            .into_iter()
+            .map(|rule| {
+                let error = |message: String| RuleError {
+                    rule: rule.name.clone(),
+                    message,
+                };
+                let when = match &rule.when {
+                    Some(when) => Some(when.parse::<Filter>().map_err(|e| error(e.to_string()))?),
+                    None => None,
+                };
+                match (&rule.event, &when, rule.mission_expiry) {
+                    (_, _, Some(_)) if rule.event.is_some() || when.is_some() => {
+                        return Err(error(
+                            "mission_expiry can't be combined with event or when".to_owned(),
+                        ))
+                    }
+                    (None, None, None) => {
+                        return Err(error("Needs an event, when or mission_expiry".to_owned()))
+                    }
+                    _ => {}
+                }
+                if rule.actions.is_empty() {
+                    return Err(error("Has no actions".to_owned()));
+                }
+                for action in &rule.actions {
+                    if let Action::Discord(discord) = action {
+                        for field in &discord.fields {
+                            field
+                                .parse::<FieldPath>()
+                                .map_err(|e| error(format!("Invalid field {field}: {e}")))?;
+                        }
+                    }
+                }
+
+                Ok(CompiledRule {
+                    rule,
+                    when,
+                    last_fired: None,
+                    active: false,
+                    warned: HashSet::new(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(RuleEngine {
+            rules,
+            missions: BTreeMap::new(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Follow missions without firing, e.g. on events from earlier in the
+    /// session
+    pub fn track(&mut self, event: &Value) {
+        match (event["event"].as_str(), event["MissionID"].as_u64()) {
+            (Some("MissionAccepted"), Some(id)) => {
+                self.missions.insert(id, event.clone());
+            }
+            (Some("MissionCompleted" | "MissionFailed" | "MissionAbandoned"), Some(id)) => {
+                self.missions.remove(&id);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn check_event(&mut self, event: &Value, now: OffsetDateTime) -> Vec<Firing> {
+        self.track(event);
+        let kind = event["event"].as_str();
+        self.rules
+            .iter_mut()
+            .filter(|r| r.rule.event.is_some() && r.rule.event.as_deref() == kind)
+            .filter(|r| r.when.as_ref().is_none_or(|when| when.matches(event)))
+            .filter_map(|r| r.fire(event, now))
+            .collect()
+    }
+
+    /// Rules whose condition on the status became true since the last check
+    pub fn check_status(&mut self, status: &Value, now: OffsetDateTime) -> Vec<Firing> {
+        let mut firings = Vec::new();
+
+        for rule in &mut self.rules {
+            let Some(when) = rule.when.as_ref().filter(|_| rule.rule.event.is_none()) else {
+                continue;
+            };
+            let matches = when.matches(status);
+            if matches && !rule.active {
+                firings.extend(rule.fire(status, now));
+            }
+            rule.active = matches;
+        }
+
+        firings
+    }
+
+    /// Rules for missions about to expire
+    pub fn check_time(&mut self, now: OffsetDateTime) -> Vec<Firing> {
+        self.missions
+            .retain(|_, mission| mission_expiry(mission).is_some_and(|expiry| expiry > now));
+        let mut firings = Vec::new();
+
+        for rule in &mut self.rules {
+            let Some(minutes) = rule.rule.mission_expiry else {
+                continue;
+            };
+            for (id, mission) in &self.missions {
+                let due = mission_expiry(mission)
+                    .is_some_and(|expiry| now >= expiry - time::Duration::minutes(minutes));
+                if due && !rule.warned.contains(id) {
+                    if let Some(firing) = rule.fire(mission, now) {
+                        rule.warned.insert(*id);
+                        firings.push(firing);
+                    }
+                }
+            }
+        }
+
+        firings
+    }
+}
+
+/// Runs the actions of the rules that fire on live events
+pub struct RuleProcessor {
+    engine: RuleEngine,
+    notify: Notify,
+    agent: Agent,
+}
+
+impl fmt::Debug for RuleProcessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuleProcessor")
+            .field("engine", &self.engine)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RuleProcessor {
+    pub fn new(engine: RuleEngine, notify: Notify) -> Self {
+        RuleProcessor {
+            engine,
+            notify,
+            agent: Agent::config_builder()
+                .user_agent("elite-rs")
+                .timeout_global(Some(Duration::from_secs(10)))
+                .build()
+                .into(),
+        }
+    }
+
+    /// Run every action, even after one failed, returning the first failure
+    fn run(&self, firings: Vec<Firing>) -> ProcessorResult {
+        let mut result = Ok(());
+
+        for firing in firings {
+            for action in &firing.actions {
+                let outcome: ProcessorResult = match action {
+                    Action::Notify(text) => {
+                        (self.notify)(&firing.rule, text);
+                        Ok(())
+                    }
+                    Action::Command(args) => match args.split_first() {
+                        Some((program, args)) => Command::new(program)
+                            .args(args)
+                            .spawn()
+                            .map(|mut child| {
+                                // Waited for so it doesn't linger as a zombie
+                                thread::spawn(move || child.wait());
+                            })
+                            .map_err(|e| format!("Can't run {program}: {e}").into()),
+                        None => Err("Empty command".into()),
+                    },
+                    Action::Webhook(url) => self
+                        .agent
+                        .post(url)
+                        .send_json(&firing.payload)
+                        .map(|_| ())
+                        .map_err(|e| format!("Webhook {url} failed: {e}").into()),
+                    Action::Discord(discord) => self
+                        .agent
+                        .post(&discord.url)
+                        .send_json(discord_message(discord, &firing.rule, &firing.payload))
+                        .map(|_| ())
+                        .map_err(|e| format!("Discord webhook failed: {e}").into()),
+                };
+                if let Err(e) = outcome {
+                    result = result.and(Err(format!("{}: {e}", firing.rule).into()));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl EventProcessor for RuleProcessor {
+    fn name(&self) -> &str {
+        "rules"
+    }
+
+    fn on_raw_event(&mut self, event: &Value) -> ProcessorResult {
+        let firings = self.engine.check_event(event, OffsetDateTime::now_utc());
+        self.run(firings)
+    }
+
+    fn on_status(&mut self, status: &Status) -> ProcessorResult {
+        let now = OffsetDateTime::now_utc();
+        let mut firings = self.engine.check_time(now);
+        firings.extend(
+            self.engine
+                .check_status(&serde_json::to_value(status)?, now),
+        );
+        self.run(firings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_rule_engine() {
+        let mut rules: Vec<Rule> = serde_json::from_str(
+            r#"[
+                { "name": "Big bounty", "event": "Bounty", "when": "TotalReward >= 1000000",
+                  "cooldown": 60, "actions": [{ "notify": "{TotalReward} CR on {Target}" }] },
+                { "name": "Low fuel", "when": "Fuel.FuelMain < 4",
+                  "actions": [{ "command": ["say", "Fuel at {Fuel.FuelMain}"] }] }
+            ]"#,
+        )
+        .unwrap();
+        rules.push(builtin_rule("mission-expiry").unwrap());
+        let mut rules = RuleEngine::new(rules).unwrap();
+        let now = datetime!(2024-09-15 12:00 UTC);
+
+        let bounty =
+            serde_json::json!({ "event": "Bounty", "Target": "anaconda", "TotalReward": 1200000 });
+        let firings = rules.check_event(&bounty, now);
+        assert_eq!(
+            firings[0].actions,
+            [Action::Notify("1200000 CR on anaconda".to_owned())]
+        );
+        // Still cooling down
+        assert!(rules
+            .check_event(&bounty, now + time::Duration::seconds(30))
+            .is_empty());
+
+        let status = |fuel: f64| serde_json::json!({ "Flags": 0, "Fuel": { "FuelMain": fuel, "FuelReservoir": 0.5 } });
+        assert!(rules.check_status(&status(8.0), now).is_empty());
+        let firings = rules.check_status(&status(3.5), now);
+        assert_eq!(
+            firings[0].actions,
+            [Action::Command(vec![
+                "say".to_owned(),
+                "Fuel at 3.5".to_owned()
+            ])]
+        );
+        assert!(rules.check_status(&status(3.0), now).is_empty());
+
+        let mission = serde_json::json!({ "event": "MissionAccepted", "MissionID": 42, "LocalisedName": "Deliver 8 units of Gold", "Expiry": "2024-09-15T13:30:00Z" });
+        rules.check_event(&mission, now);
+        assert!(rules.check_time(now).is_empty());
+        let firings = rules.check_time(datetime!(2024-09-15 12:45 UTC));
+        assert_eq!(
+            firings[0].actions,
+            [Action::Notify(
+                "Deliver 8 units of Gold expires at 2024-09-15T13:30:00Z".to_owned()
+            )]
+        );
+        assert!(rules.check_time(datetime!(2024-09-15 12:50 UTC)).is_empty());
+
+        let invalid: Rule = serde_json::from_str(r#"{ "name": "Broken", "actions": [] }"#).unwrap();
+        assert!(RuleEngine::new(vec![invalid]).is_err());
+    }
+
+    #[test]
+    fn test_rule_processor() {
+        let rule: Rule = serde_json::from_str(
+            r#"{ "name": "Carrier jump", "event": "CarrierJump", "when": "Docked",
+                 "actions": [{ "notify": "{StarSystem}, {Body}" }] }"#,
+        )
+        .unwrap();
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let sink = shown.clone();
+        let notify: Notify = Arc::new(move |_: &str, body: &str| {
+            sink.lock().unwrap().push(body.to_owned());
+        });
+        let mut processor = RuleProcessor::new(RuleEngine::new(vec![rule]).unwrap(), notify);
+
+        // Fields the typed event doesn't have are there all the same
+        let jump: Value = serde_json::from_str(r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"CarrierJump", "Docked":true, "StationName":"X7Z-B2K", "StationType":"FleetCarrier", "MarketID":3700000000, "StarSystem":"Colonia", "SystemAddress":3238296097059, "StarPos":[-9530.5,-910.28125,19808.125], "Body":"Colonia 5", "BodyID":17, "BodyType":"Planet" }"#).unwrap();
+        processor.on_raw_event(&jump).unwrap();
+
+        assert_eq!(*shown.lock().unwrap(), ["Colonia, Colonia 5"]);
+    }
+
+    #[test]
+    fn test_discord() {
+        let rule: Rule = serde_json::from_str(
+            r#"{ "name": "Carrier jump", "event": "CarrierJump", "actions": [
+                { "discord": { "url": "https://discord.com/api/webhooks/1/abc",
+                  "description": "Arrived at {Body}", "fields": ["StarSystem", "Docked"] } }
+            ] }"#,
+        )
+        .unwrap();
+        let mut rules = RuleEngine::new(vec![rule]).unwrap();
+
+        // As written to the journal, the typed CarrierJump has none of these
+        let jump: Value = serde_json::from_str(r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"CarrierJump", "Docked":false, "StarSystem":"Colonia", "SystemAddress":3238296097059, "StarPos":[-9530.5,-910.28125,19808.125], "Body":"Colonia 5", "BodyID":17, "BodyType":"Planet" }"#).unwrap();
+        let firings = rules.check_event(&jump, datetime!(2024-09-15 12:00 UTC));
+        let Action::Discord(discord) = &firings[0].actions[0] else {
+            panic!("Expected a Discord action");
+        };
+        assert_eq!(
+            discord_message(discord, &firings[0].rule, &firings[0].payload),
+            serde_json::json!({
+                "username": "elite-rs",
+                "embeds": [{
+                    "title": "Carrier jump",
+                    "description": "Arrived at Colonia 5",
+                    "color": 0xff8c0d,
+                    "timestamp": "2024-09-15T12:00:00Z",
+                    "fields": [
+                        { "name": "StarSystem", "value": "Colonia", "inline": true },
+                        { "name": "Docked", "value": "false", "inline": true },
+                    ],
+                }],
+            })
+        );
+    }
+}
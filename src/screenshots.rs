@@ -0,0 +1,166 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use image::{ImageError, ImageFormat};
+use time::{macros::format_description, Duration, OffsetDateTime};
+
+use crate::journal::Event;
+
+/// How far apart the bitmap's modification time and the event may be
+const WRITE_DELAY: Duration = Duration::seconds(10);
+
+/// A screenshot taken in game
+#[derive(Debug)]
+pub struct Screenshot {
+    pub timestamp: OffsetDateTime,
+    pub system: String,
+    pub body: Option<String>,
+    /// Bitmap written by the game
+    pub source: PathBuf,
+}
+
+impl Screenshot {
+    /// The screenshot a Screenshot event refers to, with its file in `dir`
+    pub fn from_event(event: &Event, dir: &Path) -> Option<Self> {
+        let Event::Screenshot {
+            timestamp,
+            filename,
+            system,
+            body,
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        Some(Screenshot {
+            timestamp: *timestamp,
+            system: system.to_owned(),
+            body: body.clone(),
+            source: dir.join(filename.rsplit('\\').next()?),
+        })
+    }
+
+    /// Whether the bitmap at `source` is still the one the event is about.
+    /// The game numbers screenshots from Screenshot_0000.bmp again once the
+    /// old ones are gone, so the name alone may belong to a later one.
+    pub fn source_matches(&self) -> bool {
+        let Ok(modified) = fs::metadata(&self.source).and_then(|m| m.modified()) else {
+            return false;
+        };
+
+        (OffsetDateTime::from(modified) - self.timestamp).abs() <= WRITE_DELAY
+    }
+
+    /// File name in the form of "Sol - Earth - 2024-09-15 12-00-00.png".
+    /// Body names that already contain the system name aren't repeated.
+    pub fn png_name(&self) -> String {
+        let place = match &self.body {
+            Some(body) if body.starts_with(&self.system) => body.to_owned(),
+            Some(body) => format!("{} - {}", self.system, body),
+            None => self.system.to_owned(),
+        };
+        let time = self
+            .timestamp
+            .format(format_description!(
+                "[year]-[month]-[day] [hour]-[minute]-[second]"
+            ))
+            .unwrap_or_default();
+
+        format!("{place} - {time}.png")
+            .chars()
+            .map(|c| match c {
+                '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+                c => c,
+            })
+            .collect()
+    }
+
+    /// Where the PNG goes, next to the original without overwriting anything
+    pub fn target(&self) -> PathBuf {
+        let dir = self.source.parent().unwrap_or(Path::new(""));
+        let name = self.png_name();
+        let stem = Path::new(&name).file_stem().unwrap_or_default();
+        let mut target = dir.join(&name);
+
+        let mut n = 2;
+        while target.exists() {
+            target = dir.join(format!("{} ({n}).png", stem.to_string_lossy()));
+            n += 1;
+        }
+
+        target
+    }
+
+    /// Convert to PNG and delete the bitmap unless `keep_original` is set.
+    /// Returns the path of the PNG.
+    pub fn convert(&self, keep_original: bool) -> Result<PathBuf, ImageError> {
+        let target = self.target();
+
+        image::open(&self.source)?.save_with_format(&target, ImageFormat::Png)?;
+        if !keep_original {
+            fs::remove_file(&self.source)?;
+        }
+
+        Ok(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_png_name() {
+        let mut screenshot = Screenshot {
+            timestamp: datetime!(2024-09-15 12:00:00 UTC),
+            system: "Colonia".to_owned(),
+            body: Some("Colonia 2 a".to_owned()),
+            source: PathBuf::from("Screenshot_0000.bmp"),
+        };
+        assert_eq!(
+            screenshot.png_name(),
+            "Colonia 2 a - 2024-09-15 12-00-00.png"
+        );
+
+        screenshot.system = "Sol".to_owned();
+        screenshot.body = Some("Earth".to_owned());
+        assert_eq!(
+            screenshot.png_name(),
+            "Sol - Earth - 2024-09-15 12-00-00.png"
+        );
+    }
+
+    #[test]
+    fn test_target() {
+        let dir = std::env::temp_dir().join(format!("elite-screenshots-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("Screenshot_0000.bmp");
+        fs::File::create(&source)
+            .unwrap()
+            .set_modified(datetime!(2024-09-15 12:00:01 UTC).into())
+            .unwrap();
+
+        let mut screenshot = Screenshot {
+            timestamp: datetime!(2024-09-15 12:00:00 UTC),
+            system: "Sol".to_owned(),
+            body: Some("Earth.png Orbital".to_owned()),
+            source,
+        };
+        assert!(screenshot.source_matches());
+        fs::write(dir.join(screenshot.png_name()), "").unwrap();
+        assert_eq!(
+            screenshot.target(),
+            dir.join("Sol - Earth.png Orbital - 2024-09-15 12-00-00 (2).png")
+        );
+
+        // A later screenshot with the same file name
+        screenshot.timestamp = datetime!(2024-09-14 12:00:00 UTC);
+        assert!(!screenshot.source_matches());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,258 @@
+//! Rhai scripts run as [EventProcessor]s, for what isn't worth a crate of
+//! its own.
+//!
+//! A script defines any of these functions, called like their counterparts
+//! of [EventProcessor]:
+//!
+//! ```rhai
+//! fn on_event(event) {
+//!     if event.event == "FSDJump" {
+//!         this.jumps = (this.jumps ?? 0) + 1;
+//!         notify("Jumped", `${event.StarSystem}, jump ${this.jumps} today`);
+//!     }
+//! }
+//!
+//! fn on_status(status) {}
+//!
+//! fn on_shutdown() {
+//!     append_file("jumps.txt", `${this.jumps ?? 0}\n`);
+//! }
+//! ```
+//!
+//! Events and the status are maps of their fields as the game wrote them.
+//! `this` is a map kept between calls, for counters and the like. Besides
+//! Rhai's own functions scripts can call `notify(summary, body)`,
+//! `write_file(path, text)` and `append_file(path, text)`, with paths
+//! relative to the script's directory that can't lead out of it. `print`
+//! goes to stderr.
+
+use std::{
+    error::Error,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use rhai::{CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    journal::Status,
+    processor::{EventProcessor, Notify, ProcessorResult},
+};
+
+/// Operations a single call may take, so a script stuck in a loop fails
+/// instead of stalling every other processor
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+#[derive(Debug)]
+pub struct Script {
+    name: String,
+    engine: Engine,
+    ast: AST,
+    /// `this` of the script's functions
+    state: Dynamic,
+}
+
+impl Script {
+    /// Compile the script at `path` and run its top level statements
+    pub fn load(path: &Path, notify: Notify) -> Result<Self, Box<EvalAltResult>> {
+        let name = path.file_stem().map_or_else(
+            || path.display().to_string(),
+            |s| s.to_string_lossy().into(),
+        );
+        let dir = path.parent().map(Path::to_owned).unwrap_or_default();
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let prefix = name.clone();
+        engine.on_print(move |text| eprintln!("{prefix}: {text}"));
+        engine.register_fn("notify", move |summary: &str, body: &str| {
+            notify(summary, body)
+        });
+        let root = dir.clone();
+        engine.register_fn(
+            "write_file",
+            move |path: &str, text: &str| -> Result<(), Box<EvalAltResult>> {
+                fs::write(script_file(&root, path)?, text).map_err(|e| file_error(path, e))
+            },
+        );
+        engine.register_fn(
+            "append_file",
+            move |path: &str, text: &str| -> Result<(), Box<EvalAltResult>> {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(script_file(&dir, path)?)
+                    .and_then(|mut file| file.write_all(text.as_bytes()))
+                    .map_err(|e| file_error(path, e))
+            },
+        );
+
+        let ast = engine.compile_file(path.to_owned())?;
+        engine.run_ast(&ast)?;
+
+        Ok(Script {
+            name,
+            engine,
+            ast,
+            state: Map::new().into(),
+        })
+    }
+
+    /// Load every `.rhai` file in `dir` in the order of their names,
+    /// a missing directory has none
+    pub fn load_dir(dir: &Path, notify: Notify) -> Result<Vec<Self>, Box<dyn Error>> {
+        let mut paths = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<Vec<PathBuf>, _>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "rhai"));
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| {
+                Script::load(path, notify.clone())
+                    .map_err(|e| format!("Can't load script {}: {e}", path.display()).into())
+            })
+            .collect()
+    }
+
+    /// Call a function of the script if it defines it
+    fn call(&mut self, function: &str, args: Vec<Dynamic>) -> ProcessorResult {
+        let defined = self
+            .ast
+            .iter_functions()
+            .any(|f| f.name == function && f.params.len() == args.len());
+        if !defined {
+            return Ok(());
+        }
+
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        // Whatever the function returns is of no use
+        let _ = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &self.ast,
+            function,
+            args,
+        )?;
+        Ok(())
+    }
+}
+
+/// `path` within the script's directory `dir`, an error for absolute paths
+/// and ones going up with `..`
+fn script_file(dir: &Path, path: &str) -> Result<PathBuf, Box<EvalAltResult>> {
+    let inside = Path::new(path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    match inside {
+        true => Ok(dir.join(path)),
+        false => Err(format!("Can't write {path}: not within the script's directory").into()),
+    }
+}
+
+fn file_error(path: &str, e: io::Error) -> Box<EvalAltResult> {
+    format!("Can't write {path}: {e}").into()
+}
+
+fn to_dynamic(value: &impl Serialize) -> Result<Dynamic, Box<EvalAltResult>> {
+    rhai::serde::to_dynamic(value)
+}
+
+impl EventProcessor for Script {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_raw_event(&mut self, event: &Value) -> ProcessorResult {
+        self.call("on_event", vec![to_dynamic(event)?])
+    }
+
+    fn on_status(&mut self, status: &Status) -> ProcessorResult {
+        self.call("on_status", vec![to_dynamic(status)?])
+    }
+
+    fn on_shutdown(&mut self) -> ProcessorResult {
+        self.call("on_shutdown", Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn test_script() {
+        let dir = std::env::temp_dir().join(format!("elite-scripts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("jumps.rhai"),
+            r#"
+fn on_event(event) {
+    if event.event == "FSDJump" {
+        this.jumps = (this.jumps ?? 0) + 1;
+        notify("Jumped", `${event.StarSystem}, jump ${this.jumps}`);
+    }
+    if event.event == "CarrierJump" {
+        notify("Carrier jumped", event.Body);
+    }
+}
+
+fn on_shutdown() {
+    write_file("jumps.txt", `${this.jumps}`);
+}
+
+fn escape() {
+    write_file("../escaped.txt", "");
+}
+"#,
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "not a script").unwrap();
+
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let sink = shown.clone();
+        let notify: Notify = Arc::new(move |summary: &str, body: &str| {
+            sink.lock().unwrap().push(format!("{summary}: {body}"));
+        });
+        let mut scripts = Script::load_dir(&dir, notify).unwrap();
+        assert_eq!(scripts.len(), 1);
+
+        for line in [
+            r#"{ "timestamp":"2024-09-15T12:00:00Z", "event":"Music", "MusicTrack":"MainMenu" }"#,
+            r#"{ "timestamp":"2024-09-15T12:05:00Z", "event":"FSDJump", "StarSystem":"Shinrarta Dezhra", "SystemAddress":3932277478106, "StarPos":[55.71875,17.59375,27.15625], "JumpDist":8.5, "FuelUsed":0.9, "FuelLevel":31.1 }"#,
+            r#"{ "timestamp":"2024-09-15T12:09:00Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "JumpDist":10.2, "FuelUsed":1.1, "FuelLevel":30.0 }"#,
+            r#"{ "timestamp":"2024-09-15T12:20:00Z", "event":"CarrierJump", "Docked":false, "StarSystem":"Colonia", "SystemAddress":3238296097059, "StarPos":[-9530.5,-910.28125,19808.125], "Body":"Colonia 5", "BodyID":17, "BodyType":"Planet" }"#,
+        ] {
+            scripts[0]
+                .on_raw_event(&serde_json::from_str(line).unwrap())
+                .unwrap();
+        }
+        scripts[0].on_shutdown().unwrap();
+        let escape = scripts[0].call("escape", Vec::new());
+        let written = fs::read_to_string(dir.join("jumps.txt")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            *shown.lock().unwrap(),
+            [
+                "Jumped: Shinrarta Dezhra, jump 1",
+                "Jumped: Sol, jump 2",
+                "Carrier jumped: Colonia 5"
+            ]
+        );
+        assert_eq!(written, "2");
+        assert!(escape.is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Names and sizes of the ships in the game.
+//!
+//! The roster is read from `ships.csv` next to this file, so supporting a new
+//! ship only takes a new line there. The symbols and names match `symbol`
+//! and `name` of `shipyard.csv` in <https://github.com/EDCD/FDevIDs>, which
+//! the table can be regenerated from with the manufacturer and pad columns
+//! added.
+
+use std::{str::FromStr, sync::OnceLock};
+
+const TABLE: &str = include_str!("ships.csv");
+
+/// Size of landing pad a ship needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PadSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl FromStr for PadSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "S" => Ok(PadSize::Small),
+            "M" => Ok(PadSize::Medium),
+            "L" => Ok(PadSize::Large),
+            other => Err(format!("Unknown pad size {other:?}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShipType {
+    /// Symbol used in the journal in lower case, e.g. "krait_mkii"
+    pub symbol: &'static str,
+    /// Name shown in game, e.g. "Krait Mk II"
+    pub name: &'static str,
+    pub manufacturer: &'static str,
+    pub pad: PadSize,
+}
+
+/// Every ship in the roster
+pub fn ship_types() -> &'static [ShipType] {
+    static SHIPS: OnceLock<Vec<ShipType>> = OnceLock::new();
+    SHIPS.get_or_init(|| {
+        TABLE
+            .lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                let [symbol, name, manufacturer, pad] = fields[..] else {
+                    panic!("Malformed line in ships.csv: {line}");
+                };
+                ShipType {
+                    symbol,
+                    name,
+                    manufacturer,
+                    pad: pad.parse().expect("Malformed pad size in ships.csv"),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Look up a ship by its journal symbol, which is written in any case
+/// e.g. "Krait_MkII" or "krait_mkii"
+pub fn ship_type(symbol: &str) -> Option<&'static ShipType> {
+    ship_types()
+        .iter()
+        .find(|ship| ship.symbol.eq_ignore_ascii_case(symbol))
+}
+
+/// Name shown in game, or the symbol itself for ships missing from the roster
+pub fn ship_name(symbol: &str) -> &str {
+    ship_type(symbol).map_or(symbol, |ship| ship.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ship_types() {
+        // Parses the whole table
+        assert!(ship_types().len() > 40);
+        assert_eq!(ship_name("Python_NX"), "Python Mk II");
+        assert_eq!(ship_type("type9_military").unwrap().pad, PadSize::Large);
+        assert_eq!(ship_name("some_new_ship"), "some_new_ship");
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use time::{Duration, OffsetDateTime};
+
+use crate::journal::Event;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupKind {
+    Wing,
+    Crew,
+}
+
+/// Time spent flying with a single other player
+#[derive(Debug)]
+pub struct Stint {
+    pub name: String,
+    pub kind: GroupKind,
+    pub start: OffsetDateTime,
+    /// None while the stint is still going on
+    pub end: Option<OffsetDateTime>,
+}
+
+impl Stint {
+    pub fn duration(&self) -> Option<Duration> {
+        self.end.map(|end| end - self.start)
+    }
+}
+
+/// Who the commander has flown with in wings and multicrew
+#[derive(Debug, Default)]
+pub struct SocialHistory {
+    pub stints: Vec<Stint>,
+    last_seen: Option<OffsetDateTime>,
+}
+
+impl SocialHistory {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::WingJoin {
+                timestamp, others, ..
+            } => {
+                for name in others {
+                    self.start(name, GroupKind::Wing, *timestamp);
+                }
+            }
+            Event::WingAdd {
+                timestamp, name, ..
+            } => self.start(name, GroupKind::Wing, *timestamp),
+            Event::WingLeave { timestamp } => self.end(|s| s.kind == GroupKind::Wing, *timestamp),
+            Event::JoinACrew { timestamp, captain } => {
+                self.start(captain, GroupKind::Crew, *timestamp)
+            }
+            Event::QuitACrew { timestamp, captain } => self.end(
+                |s| s.kind == GroupKind::Crew && &s.name == captain,
+                *timestamp,
+            ),
+            Event::CrewMemberJoins { timestamp, crew } => {
+                self.start(crew, GroupKind::Crew, *timestamp)
+            }
+            Event::CrewMemberQuits { timestamp, crew }
+            | Event::KickCrewMember {
+                timestamp, crew, ..
+            } => self.end(|s| s.kind == GroupKind::Crew && &s.name == crew, *timestamp),
+            Event::EndCrewSession { timestamp, .. } => {
+                self.end(|s| s.kind == GroupKind::Crew, *timestamp)
+            }
+            Event::Shutdown { timestamp } => self.end(|_| true, *timestamp),
+            // A new session without a clean shutdown, the game probably crashed.
+            // Later parts of a journal carry on the same session.
+            Event::Fileheader { part: 1, .. } | Event::LoadGame { .. } => {
+                if let Some(last_seen) = self.last_seen {
+                    self.end(|_| true, last_seen);
+                }
+            }
+            _ => {}
+        }
+
+        self.last_seen = Some(event.timestamp());
+    }
+
+    /// Number of stints and total time spent with each player
+    pub fn by_person(&self) -> BTreeMap<&str, (u32, Duration)> {
+        let mut people: BTreeMap<&str, (u32, Duration)> = BTreeMap::new();
+
+        for stint in &self.stints {
+            let entry = people.entry(&stint.name).or_default();
+            entry.0 += 1;
+            entry.1 += stint.duration().unwrap_or_default();
+        }
+
+        people
+    }
+
+    fn start(&mut self, name: &str, kind: GroupKind, timestamp: OffsetDateTime) {
+        self.stints.push(Stint {
+            name: name.to_owned(),
+            kind,
+            start: timestamp,
+            end: None,
+        });
+    }
+
+    fn end(&mut self, filter: impl Fn(&Stint) -> bool, timestamp: OffsetDateTime) {
+        self.stints
+            .iter_mut()
+            .filter(|s| s.end.is_none() && filter(s))
+            .for_each(|s| s.end = Some(timestamp));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_social_history() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"WingJoin", "Others":[ "Salomé", "Arissa" ] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"WingAdd", "Name":"Jameson" }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"WingLeave" }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"CrewMemberJoins", "Crew":"Jameson" }
+{ "timestamp":"2024-09-15T13:05:00Z", "event":"CrewMemberJoins", "Crew":"Arissa" }
+{ "timestamp":"2024-09-15T13:20:00Z", "event":"KickCrewMember", "Crew":"Arissa", "OnCrime":false }
+{ "timestamp":"2024-09-15T13:30:00Z", "event":"EndCrewSession", "OnCrime":false }
+{ "timestamp":"2024-09-15T14:00:00Z", "event":"JoinACrew", "Captain":"Salomé" }
+{ "timestamp":"2024-09-15T14:45:00Z", "event":"Music", "MusicTrack":"Supercruise" }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }
+"#,
+        )
+        .unwrap();
+        let mut history = SocialHistory::default();
+        for event in &events {
+            history.update(event);
+        }
+
+        assert_eq!(history.stints.len(), 6);
+        assert!(history.stints.iter().all(|s| s.end.is_some()));
+        let people = history.by_person();
+        assert_eq!(people["Jameson"], (2, Duration::minutes(50)));
+        assert_eq!(people["Arissa"], (2, Duration::minutes(45)));
+        // The game crashed while in Salomé's crew, so the stint ends at
+        // the last event before the next session
+        assert_eq!(people["Salomé"], (2, Duration::minutes(75)));
+        assert_eq!(history.stints[5].kind, GroupKind::Crew);
+    }
+
+    #[test]
+    fn test_journal_continued() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"WingJoin", "Others":[ "Arissa" ] }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"Continued", "Part":2 }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"Fileheader", "part":2, "language":"English/UK", "Odyssey":true, "gameversion":"4.0.0.1904", "build":"r306038/r0 " }
+{ "timestamp":"2024-09-15T13:00:00Z", "event":"WingLeave" }
+"#,
+        )
+        .unwrap();
+        let mut history = SocialHistory::default();
+        for event in &events {
+            history.update(event);
+        }
+
+        assert_eq!(history.stints.len(), 1);
+        assert_eq!(history.stints[0].duration(), Some(Duration::hours(1)));
+    }
+}
@@ -0,0 +1,262 @@
+//! Client for the route plotters and searches of <https://spansh.co.uk>
+
+use std::{error, fmt, thread, time::Duration};
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use ureq::Agent;
+
+const API: &str = "https://spansh.co.uk/api";
+
+#[derive(Debug)]
+pub enum Error {
+    Http(ureq::Error),
+    /// Spansh refused the request, e.g. because a system is unknown
+    Api(String),
+    /// The route took too long to plot
+    Timeout,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(e) => write!(f, "Request to Spansh failed: {e}"),
+            Error::Api(message) => write!(f, "Spansh: {message}"),
+            Error::Timeout => write!(f, "Spansh didn't finish plotting in time"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ureq::Error> for Error {
+    fn from(e: ureq::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+/// System on a neutron highway route
+#[derive(Debug, Deserialize)]
+pub struct NeutronWaypoint {
+    pub system: String,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// Jumps needed to get here from the previous waypoint
+    pub jumps: u32,
+    pub distance_jumped: f64,
+    pub distance_left: f64,
+    pub neutron_star: bool,
+}
+
+/// Single jump of a route from the exact plotter
+#[derive(Debug, Deserialize)]
+pub struct ExactJump {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub distance: f64,
+    pub distance_to_destination: f64,
+    pub fuel_used: f64,
+    pub fuel_in_tank: f64,
+    pub must_refuel: bool,
+    pub has_neutron: bool,
+}
+
+/// Frame shift drive and fuel figures the exact plotter needs,
+/// as shown in the ship's outfitting
+#[derive(Debug, Clone)]
+pub struct ShipParameters {
+    pub fuel_power: f64,
+    pub fuel_multiplier: f64,
+    pub optimal_mass: f64,
+    /// Mass without fuel and cargo
+    pub base_mass: f64,
+    pub tank_size: f64,
+    pub internal_tank_size: f64,
+    pub max_fuel_per_jump: f64,
+    /// Added by a guardian FSD booster
+    pub range_boost: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearestSystem {
+    pub name: String,
+    pub id64: u64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// Distance from the coordinates searched for in light years
+    pub distance: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Station {
+    pub name: String,
+    pub system_name: String,
+    /// Distance from the reference system in light years
+    pub distance: f64,
+    #[serde(default)]
+    pub distance_to_arrival: Option<f64>,
+    #[serde(rename = "type", default)]
+    pub station_type: Option<String>,
+    #[serde(default)]
+    pub has_large_pad: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    agent: Agent,
+    /// How long to wait for a route before giving up
+    pub timeout: Duration,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Client {
+            agent: Agent::config_builder()
+                .user_agent("elite-rs")
+                .timeout_global(Some(Duration::from_secs(30)))
+                // Spansh explains errors in the body of 400 responses
+                .http_status_as_error(false)
+                .build()
+                .into(),
+            timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+impl Client {
+    /// Turn an error response into [Error::Api]
+    fn check(response: Value) -> Result<Value, Error> {
+        match response["error"].as_str() {
+            Some(message) => Err(Error::Api(message.to_owned())),
+            None => Ok(response),
+        }
+    }
+
+    /// Routes are plotted in the background, wait for the job to finish
+    fn job_result<T: DeserializeOwned>(&self, job: Value, key: &str) -> Result<T, Error> {
+        let job = Self::check(job)?;
+        let Some(id) = job["job"].as_str() else {
+            return Err(Error::Api("No job in response".to_owned()));
+        };
+
+        let mut waited = Duration::ZERO;
+        while waited < self.timeout {
+            let response: Value = self
+                .agent
+                .get(format!("{API}/results/{id}"))
+                .call()?
+                .body_mut()
+                .read_json()?;
+            let response = Self::check(response)?;
+
+            if response["status"] != "queued" {
+                return serde_json::from_value(response["result"][key].clone())
+                    .map_err(|e| Error::Api(format!("Unexpected result: {e}")));
+            }
+
+            thread::sleep(Duration::from_secs(1));
+            waited += Duration::from_secs(1);
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Route using neutron stars to supercharge the FSD.
+    /// `efficiency` from 1 to 100 trades jumps for less detours.
+    pub fn neutron_route(
+        &self,
+        from: &str,
+        to: &str,
+        range: f64,
+        efficiency: u32,
+    ) -> Result<Vec<NeutronWaypoint>, Error> {
+        let job = self
+            .agent
+            .post(format!("{API}/route"))
+            .send_form([
+                ("from", from),
+                ("to", to),
+                ("range", &range.to_string()),
+                ("efficiency", &efficiency.to_string()),
+            ])?
+            .body_mut()
+            .read_json()?;
+
+        self.job_result(job, "system_jumps")
+    }
+
+    /// Jump by jump route accounting for fuel use
+    pub fn exact_route(
+        &self,
+        from: &str,
+        to: &str,
+        ship: &ShipParameters,
+        cargo: u32,
+    ) -> Result<Vec<ExactJump>, Error> {
+        let form = [
+            ("source", from.to_owned()),
+            ("destination", to.to_owned()),
+            ("cargo", cargo.to_string()),
+            ("is_supercharged", "0".to_owned()),
+            ("use_supercharge", "1".to_owned()),
+            ("use_injections", "0".to_owned()),
+            ("exclude_secondary", "0".to_owned()),
+            ("refuel_every_scoopable", "1".to_owned()),
+            ("algorithm", "optimistic".to_owned()),
+            ("fuel_power", ship.fuel_power.to_string()),
+            ("fuel_multiplier", ship.fuel_multiplier.to_string()),
+            ("optimal_mass", ship.optimal_mass.to_string()),
+            ("base_mass", ship.base_mass.to_string()),
+            ("tank_size", ship.tank_size.to_string()),
+            ("internal_tank_size", ship.internal_tank_size.to_string()),
+            ("max_fuel_per_jump", ship.max_fuel_per_jump.to_string()),
+            ("range_boost", ship.range_boost.to_string()),
+        ];
+        let job = self
+            .agent
+            .post(format!("{API}/generic/route"))
+            .send_form(form.iter().map(|(k, v)| (*k, v.as_str())))?
+            .body_mut()
+            .read_json()?;
+
+        self.job_result(job, "jumps")
+    }
+
+    /// Known system closest to a position
+    pub fn nearest_system(&self, [x, y, z]: [f64; 3]) -> Result<NearestSystem, Error> {
+        let response: Value = self
+            .agent
+            .get(format!("{API}/nearest"))
+            .query("x", x.to_string())
+            .query("y", y.to_string())
+            .query("z", z.to_string())
+            .call()?
+            .body_mut()
+            .read_json()?;
+
+        serde_json::from_value(Self::check(response)?["system"].take())
+            .map_err(|e| Error::Api(format!("Unexpected result: {e}")))
+    }
+
+    /// Up to `count` stations closest to a system
+    pub fn nearest_stations(&self, system: &str, count: u32) -> Result<Vec<Station>, Error> {
+        let response: Value = self
+            .agent
+            .post(format!("{API}/stations/search"))
+            .send_json(json!({
+                "filters": {},
+                "sort": [{ "distance": { "direction": "asc" } }],
+                "size": count,
+                "reference_system": system,
+            }))?
+            .body_mut()
+            .read_json()?;
+
+        serde_json::from_value(Self::check(response)?["results"].take())
+            .map_err(|e| Error::Api(format!("Unexpected result: {e}")))
+    }
+}
@@ -0,0 +1,129 @@
+use serde::Serialize;
+
+use crate::journal::{Event, GameVersion};
+
+/// Current state of the game as far as it can be told from the journal
+#[derive(Debug, Default, Serialize)]
+pub struct GameState {
+    pub commander: Option<String>,
+    /// Player ID
+    pub fid: Option<String>,
+    /// Game version from the last Fileheader
+    pub game_version: Option<String>,
+    pub odyssey: bool,
+    /// Credits as of the last LoadGame
+    pub credits: Option<u64>,
+    pub ship: Option<String>,
+    pub ship_id: Option<u32>,
+    pub ship_name: Option<String>,
+    pub ship_ident: Option<String>,
+    pub system: Option<String>,
+    pub system_address: Option<u64>,
+    /// Galactic coordinates in light years relative to Sol
+    pub star_pos: Option<[f64; 3]>,
+    pub body: Option<String>,
+    pub docked: bool,
+    pub station: Option<String>,
+    pub market_id: Option<u64>,
+    pub fuel_level: Option<f64>,
+}
+
+impl GameState {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::Fileheader { .. } => {
+                let version = GameVersion::from_fileheader(event);
+                self.odyssey = version.as_ref().is_some_and(|v| v.odyssey);
+                self.game_version = version.map(|v| v.gameversion);
+            }
+            Event::Commander { name, fid, .. } => {
+                self.commander = Some(name.to_owned());
+                self.fid = Some(fid.to_owned()).filter(|f| !f.is_empty());
+            }
+            Event::LoadGame {
+                commander,
+                fid,
+                ship,
+                ship_id,
+                ship_name,
+                ship_ident,
+                fuel_level,
+                credits,
+                ..
+            } => {
+                self.commander = Some(commander.to_owned());
+                self.fid = Some(fid.to_owned()).filter(|f| !f.is_empty());
+                self.credits = Some(*credits);
+                self.ship = ship.clone();
+                self.ship_id = *ship_id;
+                self.ship_name = ship_name.clone();
+                self.ship_ident = ship_ident.clone();
+                self.fuel_level = *fuel_level;
+            }
+            Event::Loadout {
+                ship,
+                ship_id,
+                ship_name,
+                ship_ident,
+                ..
+            } => {
+                self.ship = Some(ship.to_owned());
+                self.ship_id = Some(*ship_id);
+                self.ship_name = Some(ship_name.to_owned());
+                self.ship_ident = Some(ship_ident.to_owned());
+            }
+            Event::Location {
+                star_system,
+                system_address,
+                star_pos,
+                body,
+                docked,
+                station_name,
+                market_id,
+                ..
+            } => {
+                self.system = Some(star_system.to_owned());
+                self.system_address = Some(*system_address);
+                self.star_pos = Some(*star_pos);
+                self.body = body.clone();
+                self.docked = *docked;
+                self.station = station_name.clone();
+                self.market_id = *market_id;
+            }
+            Event::FSDJump {
+                star_system,
+                system_address,
+                star_pos,
+                body,
+                fuel_level,
+                ..
+            } => {
+                self.system = Some(star_system.to_owned());
+                self.system_address = Some(*system_address);
+                self.star_pos = Some(*star_pos);
+                self.body = body.clone();
+                self.docked = false;
+                self.station = None;
+                self.market_id = None;
+                self.fuel_level = Some(*fuel_level);
+            }
+            Event::Docked {
+                station_name,
+                market_id,
+                ..
+            } => {
+                self.docked = true;
+                self.station = Some(station_name.to_owned());
+                self.market_id = Some(*market_id);
+            }
+            Event::ApproachBody { body, .. } => self.body = Some(body.clone()),
+            Event::LeaveBody { .. } => self.body = None,
+            Event::Undocked { .. } => {
+                self.docked = false;
+                self.station = None;
+                self.market_id = None;
+            }
+            _ => {}
+        }
+    }
+}
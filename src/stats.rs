@@ -0,0 +1,33 @@
+use std::collections::BTreeMap;
+
+use time::{Date, OffsetDateTime};
+
+use crate::journal::EventHeader;
+
+/// Number of journal entries grouped in various ways
+#[derive(Debug, Default)]
+pub struct EventCounts {
+    pub by_kind: BTreeMap<String, u32>,
+    pub by_day: BTreeMap<Date, u32>,
+    /// Keyed by the timestamp of the first entry in the session
+    pub by_session: BTreeMap<OffsetDateTime, u32>,
+}
+
+impl EventCounts {
+    /// Count all entries of one session, see [crate::journal::JournalSession]
+    pub fn add_session(&mut self, headers: &[EventHeader]) {
+        let Some(first) = headers.first() else {
+            return;
+        };
+
+        for header in headers {
+            *self.by_kind.entry(header.event.clone()).or_default() += 1;
+            *self.by_day.entry(header.timestamp.date()).or_default() += 1;
+        }
+        *self.by_session.entry(first.timestamp).or_default() += headers.len() as u32;
+    }
+
+    pub fn total(&self) -> u32 {
+        self.by_kind.values().sum()
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::journal::Event;
+
+/// How much of a system's bodies were scanned
+#[derive(Debug, Default)]
+pub struct SystemSurvey {
+    pub system: String,
+    /// Stars and planets, known once the discovery scanner was used
+    pub body_count: Option<u32>,
+    /// Names of stars and planets scanned
+    pub scanned: BTreeSet<String>,
+    /// The game reported every body as found
+    pub all_found: bool,
+}
+
+impl SystemSurvey {
+    /// Bodies left to scan, if the body count is known
+    pub fn remaining(&self) -> Option<u32> {
+        let scanned = self.scanned.len() as u32;
+        self.body_count.map(|count| count.saturating_sub(scanned))
+    }
+
+    pub fn complete(&self) -> bool {
+        self.remaining() == Some(0)
+    }
+}
+
+/// Scan progress of every visited system
+#[derive(Debug, Default)]
+pub struct Surveys {
+    /// Keyed by system address
+    pub systems: BTreeMap<u64, SystemSurvey>,
+    current: Option<u64>,
+}
+
+impl Surveys {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::FSDJump {
+                star_system,
+                system_address,
+                ..
+            }
+            | Event::Location {
+                star_system,
+                system_address,
+                ..
+            } => {
+                self.system(*system_address, star_system);
+                self.current = Some(*system_address);
+            }
+            Event::FSSDiscoveryScan {
+                body_count,
+                system_address,
+                ..
+            } => {
+                if let Some(survey) = system_address
+                    .or(self.current)
+                    .and_then(|address| self.systems.get_mut(&address))
+                {
+                    survey.body_count = Some(*body_count);
+                }
+            }
+            Event::Scan {
+                body_name,
+                system_address,
+                star_type,
+                planet_class,
+                ..
+            } if star_type.is_some() || planet_class.is_some() => {
+                if let Some(survey) = system_address
+                    .or(self.current)
+                    .and_then(|address| self.systems.get_mut(&address))
+                {
+                    survey.scanned.insert(body_name.to_owned());
+                }
+            }
+            Event::FSSAllBodiesFound {
+                system_name,
+                system_address,
+                count,
+                ..
+            } => {
+                let survey = self.system(*system_address, system_name);
+                survey.body_count = Some(*count);
+                survey.all_found = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn system(&mut self, address: u64, name: &str) -> &mut SystemSurvey {
+        self.systems.entry(address).or_insert_with(|| SystemSurvey {
+            system: name.to_owned(),
+            ..Default::default()
+        })
+    }
+
+    /// Survey of the system the commander is in
+    pub fn current(&self) -> Option<&SystemSurvey> {
+        self.systems.get(&self.current?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_surveys() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"FSDJump", "StarSystem":"Pru Euq AA-A g1", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":50.0, "FuelUsed":5.0, "FuelLevel":20.0 }
+{ "timestamp":"2024-09-15T12:00:30Z", "event":"FSSDiscoveryScan", "Progress":0.4, "BodyCount":3, "NonBodyCount":2, "SystemName":"Pru Euq AA-A g1", "SystemAddress":1 }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"Scan", "ScanType":"AutoScan", "BodyName":"Pru Euq AA-A g1 A", "StarSystem":"Pru Euq AA-A g1", "SystemAddress":1, "StarType":"M" }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"Pru Euq AA-A g1 1", "StarSystem":"Pru Euq AA-A g1", "SystemAddress":1, "PlanetClass":"Icy body" }
+{ "timestamp":"2024-09-15T12:02:30Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"Pru Euq AA-A g1 1", "StarSystem":"Pru Euq AA-A g1", "SystemAddress":1, "PlanetClass":"Icy body" }
+"#,
+        )
+        .unwrap();
+        let mut surveys = Surveys::default();
+        for event in &events {
+            surveys.update(event);
+        }
+
+        let survey = surveys.current().unwrap();
+        assert_eq!(survey.remaining(), Some(1));
+        assert!(!survey.complete());
+    }
+}
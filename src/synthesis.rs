@@ -0,0 +1,171 @@
+//! Synthesis recipes and whether the material inventory covers them.
+
+use crate::materials::{material_type, MaterialInventory, MaterialType};
+
+#[derive(Debug)]
+pub struct Recipe {
+    pub name: &'static str,
+    /// "Basic", "Standard" or "Premium"
+    pub grade: &'static str,
+    pub effect: &'static str,
+    /// Material symbol as in Materials and count
+    pub materials: &'static [(&'static str, u32)],
+}
+
+/// Synthesis recipes available in ships and SRVs
+pub const RECIPES: &[Recipe] = &[
+    Recipe {
+        name: "FSD Injection",
+        grade: "Basic",
+        effect: "+25% jump range",
+        materials: &[("carbon", 1), ("vanadium", 1), ("germanium", 1)],
+    },
+    Recipe {
+        name: "FSD Injection",
+        grade: "Standard",
+        effect: "+50% jump range",
+        materials: &[
+            ("carbon", 1),
+            ("vanadium", 1),
+            ("germanium", 1),
+            ("cadmium", 1),
+            ("niobium", 1),
+        ],
+    },
+    Recipe {
+        name: "FSD Injection",
+        grade: "Premium",
+        effect: "+100% jump range",
+        materials: &[
+            ("carbon", 1),
+            ("germanium", 1),
+            ("arsenic", 1),
+            ("niobium", 1),
+            ("yttrium", 1),
+            ("polonium", 1),
+        ],
+    },
+    Recipe {
+        name: "AFMU Refill",
+        grade: "Basic",
+        effect: "Refills 50% of AFMU ammo",
+        materials: &[("nickel", 3), ("zinc", 2), ("chromium", 2), ("vanadium", 1)],
+    },
+    Recipe {
+        name: "AFMU Refill",
+        grade: "Standard",
+        effect: "Refills 100% of AFMU ammo",
+        materials: &[
+            ("tin", 1),
+            ("manganese", 3),
+            ("vanadium", 4),
+            ("molybdenum", 1),
+        ],
+    },
+    Recipe {
+        name: "AFMU Refill",
+        grade: "Premium",
+        effect: "Refills 150% of AFMU ammo",
+        materials: &[
+            ("nickel", 4),
+            ("zinc", 2),
+            ("chromium", 2),
+            ("tellurium", 1),
+            ("ruthenium", 1),
+        ],
+    },
+    Recipe {
+        name: "Limpets",
+        grade: "Basic",
+        effect: "4 limpets",
+        materials: &[("iron", 10), ("nickel", 10)],
+    },
+    Recipe {
+        name: "Heat Sinks",
+        grade: "Basic",
+        effect: "Refills heat sink launchers",
+        materials: &[("vanadium", 2), ("germanium", 2)],
+    },
+    Recipe {
+        name: "Life Support",
+        grade: "Basic",
+        effect: "Refills oxygen",
+        materials: &[("iron", 2), ("nickel", 1)],
+    },
+    Recipe {
+        name: "SRV Refuel",
+        grade: "Basic",
+        effect: "Refills SRV fuel",
+        materials: &[("phosphorus", 1), ("sulphur", 1)],
+    },
+    Recipe {
+        name: "SRV Repair",
+        grade: "Basic",
+        effect: "Repairs 50% of SRV hull",
+        materials: &[("iron", 2), ("nickel", 1)],
+    },
+    Recipe {
+        name: "SRV Ammo",
+        grade: "Basic",
+        effect: "Refills SRV ammo",
+        materials: &[("phosphorus", 1), ("sulphur", 2)],
+    },
+];
+
+impl Recipe {
+    fn material(symbol: &str) -> &'static MaterialType {
+        material_type(symbol).expect("Unknown material in synthesis recipe")
+    }
+
+    /// How many times the recipe can be synthesised
+    pub fn craftable(&self, inventory: &MaterialInventory) -> u32 {
+        self.materials
+            .iter()
+            .map(|(symbol, count)| inventory.count(symbol) / count)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Materials short of synthesising the recipe once and how many of each
+    pub fn missing(&self, inventory: &MaterialInventory) -> Vec<(&'static MaterialType, u32)> {
+        self.materials
+            .iter()
+            .filter(|(symbol, count)| inventory.count(symbol) < *count)
+            .map(|(symbol, count)| (Self::material(symbol), count - inventory.count(symbol)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_recipes() {
+        for recipe in RECIPES {
+            for (symbol, _) in recipe.materials {
+                assert!(material_type(symbol).is_some(), "{symbol}");
+            }
+        }
+
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Materials", "Raw":[ { "Name":"carbon", "Count":5 }, { "Name":"vanadium", "Count":3 }, { "Name":"germanium", "Count":2 }, { "Name":"niobium", "Count":1 } ], "Manufactured":[], "Encoded":[] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"Synthesis", "Name":"FSD Basic", "Materials":[ { "Name":"carbon", "Count":1 }, { "Name":"vanadium", "Count":1 }, { "Name":"germanium", "Count":1 } ] }
+"#,
+        )
+        .unwrap();
+        let mut inventory = MaterialInventory::default();
+        for event in &events {
+            inventory.update(event);
+        }
+
+        assert_eq!(RECIPES[0].craftable(&inventory), 1);
+        assert!(RECIPES[0].missing(&inventory).is_empty());
+        let missing = RECIPES[1].missing(&inventory);
+        assert_eq!(missing.len(), 1);
+        assert_eq!((missing[0].0.symbol, missing[0].1), ("cadmium", 1));
+        assert_eq!(RECIPES[1].craftable(&inventory), 0);
+    }
+}
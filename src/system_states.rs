@@ -0,0 +1,105 @@
+//! Changes in the states of minor factions between visits to a system,
+//! like a war breaking out or a boom ending.
+
+use std::collections::HashMap;
+
+use time::OffsetDateTime;
+
+use crate::journal::Event;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    pub timestamp: OffsetDateTime,
+    pub system: String,
+    pub faction: String,
+    /// `None` if the faction wasn't in the system on the last visit
+    pub from: Option<String>,
+    /// `None` if the faction has left the system
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct SystemStates {
+    /// State of every faction by system as of the last visit
+    pub states: HashMap<String, HashMap<String, String>>,
+    pub visits: HashMap<String, u32>,
+    pub changes: Vec<StateChange>,
+}
+
+impl SystemStates {
+    pub fn update(&mut self, event: &Event) {
+        let (Event::FSDJump {
+            timestamp,
+            star_system,
+            factions,
+            ..
+        }
+        | Event::Location {
+            timestamp,
+            star_system,
+            factions,
+            ..
+        }) = event
+        else {
+            return;
+        };
+        // Unpopulated systems and some Location events have no factions
+        if factions.is_empty() {
+            return;
+        }
+
+        *self.visits.entry(star_system.clone()).or_default() += 1;
+        let current: HashMap<String, String> = factions
+            .iter()
+            .map(|f| (f.name.clone(), f.faction_state.clone()))
+            .collect();
+        let Some(previous) = self.states.insert(star_system.clone(), current.clone()) else {
+            return;
+        };
+
+        let mut names: Vec<&String> = current.keys().chain(previous.keys()).collect();
+        names.sort();
+        names.dedup();
+        for name in names {
+            let (from, to) = (previous.get(name), current.get(name));
+            if from != to {
+                self.changes.push(StateChange {
+                    timestamp: *timestamp,
+                    system: star_system.clone(),
+                    faction: name.clone(),
+                    from: from.cloned(),
+                    to: to.cloned(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_state_changes() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"FSDJump", "StarSystem":"Ross 128", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":10.0, "FuelUsed":1.0, "FuelLevel":10.0, "Factions":[ { "Name":"Ross 128 Blue Vision", "FactionState":"None", "Government":"Democracy", "Influence":0.5, "Allegiance":"Federation" }, { "Name":"Ross 128 Crimson Travel", "FactionState":"Boom", "Government":"Corporate", "Influence":0.5, "Allegiance":"Independent" } ] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":2, "StarPos":[0.0,0.0,0.0], "JumpDist":10.0, "FuelUsed":1.0, "FuelLevel":10.0 }
+{ "timestamp":"2024-09-16T12:00:00Z", "event":"FSDJump", "StarSystem":"Ross 128", "SystemAddress":1, "StarPos":[0.0,0.0,0.0], "JumpDist":10.0, "FuelUsed":1.0, "FuelLevel":10.0, "Factions":[ { "Name":"Ross 128 Blue Vision", "FactionState":"War", "Government":"Democracy", "Influence":0.5, "Allegiance":"Federation" }, { "Name":"Ross 128 Crimson Travel", "FactionState":"Boom", "Government":"Corporate", "Influence":0.4, "Allegiance":"Independent" }, { "Name":"Ross 128 Gold Party", "FactionState":"None", "Government":"Dictatorship", "Influence":0.1, "Allegiance":"Empire" } ] }
+"#,
+        )
+        .unwrap();
+        let mut states = SystemStates::default();
+        for event in &events {
+            states.update(event);
+        }
+
+        assert_eq!(states.visits["Ross 128"], 2);
+        assert_eq!(states.changes.len(), 2);
+        assert_eq!(states.changes[0].faction, "Ross 128 Blue Vision");
+        assert_eq!(states.changes[0].from.as_deref(), Some("None"));
+        assert_eq!(states.changes[0].to.as_deref(), Some("War"));
+        assert_eq!(states.changes[1].from, None);
+    }
+}
@@ -0,0 +1,226 @@
+//! Items unlocked at technology brokers and what is missing for the rest.
+
+use std::collections::HashSet;
+
+use crate::{
+    engineers::{Check, EngineerAdvisor, Requirement},
+    journal::Event,
+};
+
+#[derive(Debug)]
+pub struct BrokerItem {
+    pub name: &'static str,
+    /// Module symbol as in ItemsUnlocked in lower case
+    pub symbol: &'static str,
+    /// "guardian" or "human"
+    pub broker: &'static str,
+    pub cost: &'static [Requirement],
+}
+
+use Requirement::*;
+
+const WEAPON_BLUEPRINT: &str = "Guardian Weapon Blueprint Segment";
+const MODULE_BLUEPRINT: &str = "Guardian Module Blueprint Segment";
+const POWER_CELL: &str = "Guardian Power Cell";
+const POWER_CONDUIT: &str = "Guardian Power Conduit";
+const TECH_COMPONENT: &str = "Guardian Technology Component";
+const WEAPON_PARTS: &str = "Guardian Sentinel Weapon Parts";
+const WRECKAGE: &str = "Guardian Wreckage Components";
+
+/// Modules sold by technology brokers
+pub const BROKER_ITEMS: &[BrokerItem] = &[
+    BrokerItem {
+        name: "Guardian Gauss Cannon",
+        symbol: "hpt_guardian_gausscannon_fixed_medium",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_weaponblueprint", 1, WEAPON_BLUEPRINT),
+            Material("guardian_powercell", 18, POWER_CELL),
+            Material("guardian_sentinel_weaponparts", 15, WEAPON_PARTS),
+            Material("guardian_techcomponent", 12, TECH_COMPONENT),
+        ],
+    },
+    BrokerItem {
+        name: "Guardian Plasma Charger",
+        symbol: "hpt_guardian_plasmalauncher_fixed_medium",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_weaponblueprint", 1, WEAPON_BLUEPRINT),
+            Material("guardian_powerconduit", 18, POWER_CONDUIT),
+            Material("guardian_sentinel_weaponparts", 15, WEAPON_PARTS),
+            Material("guardian_techcomponent", 12, TECH_COMPONENT),
+        ],
+    },
+    BrokerItem {
+        name: "Guardian Shard Cannon",
+        symbol: "hpt_guardian_shardcannon_fixed_medium",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_weaponblueprint", 1, WEAPON_BLUEPRINT),
+            Material("guardian_powercell", 18, POWER_CELL),
+            Material("guardian_sentinel_wreckagecomponents", 15, WRECKAGE),
+            Material("guardian_techcomponent", 12, TECH_COMPONENT),
+        ],
+    },
+    BrokerItem {
+        name: "Guardian FSD Booster",
+        symbol: "int_guardianfsdbooster_size1",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_moduleblueprint", 1, MODULE_BLUEPRINT),
+            Material("guardian_powercell", 21, POWER_CELL),
+            Material("guardian_powerconduit", 21, POWER_CONDUIT),
+            Material("guardian_techcomponent", 9, TECH_COMPONENT),
+            Material("focuscrystals", 24, "Focus Crystals"),
+        ],
+    },
+    BrokerItem {
+        name: "Guardian Hull Reinforcement",
+        symbol: "int_guardianhullreinforcement_size1",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_moduleblueprint", 1, MODULE_BLUEPRINT),
+            Material("guardian_powercell", 21, POWER_CELL),
+            Material("guardian_sentinel_wreckagecomponents", 16, WRECKAGE),
+        ],
+    },
+    BrokerItem {
+        name: "Guardian Module Reinforcement",
+        symbol: "int_guardianmodulereinforcement_size1",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_moduleblueprint", 1, MODULE_BLUEPRINT),
+            Material("guardian_powercell", 18, POWER_CELL),
+            Material("guardian_techcomponent", 15, TECH_COMPONENT),
+        ],
+    },
+    BrokerItem {
+        name: "Guardian Shield Reinforcement",
+        symbol: "int_guardianshieldreinforcement_size1",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_moduleblueprint", 1, MODULE_BLUEPRINT),
+            Material("guardian_powercell", 17, POWER_CELL),
+            Material("guardian_powerconduit", 20, POWER_CONDUIT),
+        ],
+    },
+    BrokerItem {
+        name: "Guardian Power Distributor",
+        symbol: "int_guardianpowerdistributor_size1",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_moduleblueprint", 1, MODULE_BLUEPRINT),
+            Material("guardian_powerconduit", 20, POWER_CONDUIT),
+            Material("guardian_sentinel_wreckagecomponents", 24, WRECKAGE),
+        ],
+    },
+    BrokerItem {
+        name: "Guardian Power Plant",
+        symbol: "int_guardianpowerplant_size2",
+        broker: "guardian",
+        cost: &[
+            Material("guardian_moduleblueprint", 1, MODULE_BLUEPRINT),
+            Material("guardian_powercell", 18, POWER_CELL),
+            Material("guardian_powerconduit", 15, POWER_CONDUIT),
+        ],
+    },
+    BrokerItem {
+        name: "Remote Release Flak Launcher",
+        symbol: "hpt_flakmortar_fixed_medium",
+        broker: "human",
+        cost: &[
+            Material("nickel", 60, "Nickel"),
+            Material("tin", 18, "Tin"),
+            Material("chemicalmanipulators", 16, "Chemical Manipulators"),
+            Commodity("metaalloys", 6, "Meta-Alloys"),
+            Commodity("militarygradealloys", 4, "Military Grade Alloys"),
+        ],
+    },
+    BrokerItem {
+        name: "Shock Cannon",
+        symbol: "hpt_plasmashockcannon_fixed_medium",
+        broker: "human",
+        cost: &[
+            Material("vanadium", 24, "Vanadium"),
+            Material("tungsten", 28, "Tungsten"),
+            Material("rhenium", 28, "Rhenium"),
+            Material("technetium", 26, "Technetium"),
+            Commodity("powergenerators", 12, "Power Generators"),
+        ],
+    },
+];
+
+/// Broker items unlocked so far, with the cargo and materials held
+/// towards the rest
+#[derive(Debug, Default)]
+pub struct TechBroker {
+    /// Symbols in lower case
+    pub unlocked: HashSet<String>,
+    pub holdings: EngineerAdvisor,
+}
+
+impl TechBroker {
+    pub fn update(&mut self, event: &Event) {
+        if let Event::TechnologyBroker { items_unlocked, .. } = event {
+            self.unlocked
+                .extend(items_unlocked.iter().map(|item| item.name.to_lowercase()));
+        }
+        self.holdings.update(event);
+    }
+
+    pub fn is_unlocked(&self, item: &BrokerItem) -> bool {
+        self.unlocked.contains(item.symbol)
+    }
+
+    /// Costs of an item not covered yet with what is missing of each,
+    /// empty once unlocked or affordable
+    pub fn missing(&self, item: &BrokerItem) -> Vec<(Requirement, Check)> {
+        if self.is_unlocked(item) {
+            return Vec::new();
+        }
+        item.cost
+            .iter()
+            .map(|r| (*r, self.holdings.check(r)))
+            .filter(|(_, check)| *check != Check::Met)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+
+    #[test]
+    fn test_tech_broker() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"Materials", "Raw":[], "Manufactured":[ { "Name":"guardian_powercell", "Name_Localised":"Guardian Power Cell", "Count":40 }, { "Name":"guardian_powerconduit", "Name_Localised":"Guardian Power Conduit", "Count":10 } ], "Encoded":[ { "Name":"guardian_moduleblueprint", "Name_Localised":"Guardian Module Blueprint Segment", "Count":2 } ] }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"TechnologyBroker", "BrokerType":"guardian", "MarketID":128666762, "ItemsUnlocked":[ { "Name":"Int_GuardianFSDBooster_Size1", "Name_Localised":"Guardian FSD Booster" } ], "Commodities":[], "Materials":[ { "Name":"guardian_moduleblueprint", "Count":1, "Category":"Encoded" }, { "Name":"guardian_powercell", "Count":21, "Category":"Manufactured" }, { "Name":"guardian_powerconduit", "Count":10, "Category":"Manufactured" } ] }
+"#,
+        )
+        .unwrap();
+        let mut broker = TechBroker::default();
+        for event in &events {
+            broker.update(event);
+        }
+
+        let item = |name| BROKER_ITEMS.iter().find(|i| i.name == name).unwrap();
+        assert!(TechBroker::default()
+            .missing(item("Guardian FSD Booster"))
+            .contains(&(
+                Material("focuscrystals", 24, "Focus Crystals"),
+                Check::Missing(24)
+            )));
+        assert!(broker.is_unlocked(item("Guardian FSD Booster")));
+        assert!(broker.missing(item("Guardian FSD Booster")).is_empty());
+        // Blueprint and power cells are left over, the conduits were used up
+        assert_eq!(
+            broker.missing(item("Guardian Shield Reinforcement")),
+            vec![(
+                Material("guardian_powerconduit", 20, POWER_CONDUIT),
+                Check::Missing(20)
+            )]
+        );
+    }
+}
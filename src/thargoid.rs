@@ -0,0 +1,218 @@
+use std::{collections::BTreeMap, fmt};
+
+use time::OffsetDateTime;
+
+use crate::{cycle::week_start, journal::Event};
+
+const THARGOID_FACTION: &str = "$faction_Thargoid;";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ThargoidVariant {
+    Scout,
+    Marauder,
+    Berserker,
+    Inciter,
+    Regenerator,
+    Scythe,
+    Glaive,
+    Orthrus,
+    Cyclops,
+    Basilisk,
+    Medusa,
+    Hydra,
+    Banshee,
+    Revenant,
+    /// Thargoid kill that couldn't be attributed to a variant
+    Unknown,
+}
+
+impl ThargoidVariant {
+    const NAMED: [ThargoidVariant; 13] = [
+        Self::Marauder,
+        Self::Berserker,
+        Self::Inciter,
+        Self::Regenerator,
+        Self::Scythe,
+        Self::Glaive,
+        Self::Orthrus,
+        Self::Cyclops,
+        Self::Basilisk,
+        Self::Medusa,
+        Self::Hydra,
+        Self::Banshee,
+        Self::Revenant,
+    ];
+
+    /// Variant from a (localised) ship name as written by ShipTargeted or Died
+    pub fn from_ship_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+
+        Self::NAMED
+            .into_iter()
+            .find(|v| name.contains(&v.to_string().to_lowercase()))
+            .or_else(|| name.contains("scout").then_some(Self::Scout))
+    }
+
+    /// Variant from the bond reward.
+    /// Covers the values paid before and after the bond increase of the
+    /// second Thargoid war, scouts all pay the same.
+    pub fn from_reward(reward: u64) -> Self {
+        match reward {
+            10_000 | 25_000 | 80_000 => Self::Scout,
+            2_000_000 | 8_000_000 => Self::Cyclops,
+            6_000_000 | 24_000_000 => Self::Basilisk,
+            10_000_000 | 40_000_000 => Self::Medusa,
+            15_000_000 | 60_000_000 => Self::Hydra,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn is_interceptor(&self) -> bool {
+        matches!(
+            self,
+            Self::Orthrus | Self::Cyclops | Self::Basilisk | Self::Medusa | Self::Hydra
+        )
+    }
+}
+
+impl fmt::Display for ThargoidVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct WarWeek {
+    pub kills: BTreeMap<ThargoidVariant, u32>,
+    pub bonds: u64,
+    pub deaths: u32,
+}
+
+/// Anti-xeno combat grouped by weekly war cycle
+#[derive(Debug, Default)]
+pub struct ThargoidCombat {
+    /// Keyed by the start of the war week
+    pub weeks: BTreeMap<OffsetDateTime, WarWeek>,
+    /// Last Thargoid the commander had locked since the last bond
+    last_target: Option<ThargoidVariant>,
+}
+
+impl ThargoidCombat {
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::ShipTargeted {
+                target_locked: true,
+                ship,
+                ship_localised,
+                ..
+            } => {
+                let name = ship_localised.as_ref().or(ship.as_ref());
+                self.last_target = name.and_then(|n| ThargoidVariant::from_ship_name(n));
+            }
+            Event::FactionKillBond {
+                timestamp,
+                reward,
+                victim_faction,
+                ..
+            } if victim_faction == THARGOID_FACTION => {
+                // The target only tells what the reward doesn't, and only once
+                let target = self.last_target.take();
+                let variant = match ThargoidVariant::from_reward(*reward) {
+                    ThargoidVariant::Unknown => target.unwrap_or(ThargoidVariant::Unknown),
+                    variant => variant,
+                };
+
+                let week = self.weeks.entry(week_start(*timestamp)).or_default();
+                *week.kills.entry(variant).or_default() += 1;
+                week.bonds += reward;
+            }
+            Event::Died {
+                timestamp,
+                killer_name,
+                killer_ship,
+                killers,
+                ..
+            } => {
+                let by_thargoid = killer_ship
+                    .iter()
+                    .chain(killer_name)
+                    .chain(killers.iter().map(|k| &k.ship))
+                    .any(|name| {
+                        name.to_lowercase().contains("thargoid")
+                            || ThargoidVariant::from_ship_name(name).is_some()
+                    });
+
+                if by_thargoid {
+                    self.weeks.entry(week_start(*timestamp)).or_default().deaths += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn total_kills(&self) -> u32 {
+        self.weeks.values().flat_map(|w| w.kills.values()).sum()
+    }
+
+    pub fn total_bonds(&self) -> u64 {
+        self.weeks.values().map(|w| w.bonds).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_events;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_thargoid_combat() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"ShipTargeted", "TargetLocked":true, "Ship":"scout_hq", "Ship_Localised":"Thargoid Scout", "ScanStage":0 }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"FactionKillBond", "Reward":80000, "AwardingFaction":"$faction_PilotsFederation;", "AwardingFaction_Localised":"Pilots' Federation", "VictimFaction":"$faction_Thargoid;", "VictimFaction_Localised":"Thargoids" }
+{ "timestamp":"2024-09-15T12:10:00Z", "event":"ShipTargeted", "TargetLocked":true, "Ship":"glaive", "Ship_Localised":"Glaive", "ScanStage":0 }
+{ "timestamp":"2024-09-15T12:12:00Z", "event":"FactionKillBond", "Reward":4500000, "AwardingFaction":"$faction_PilotsFederation;", "VictimFaction":"$faction_Thargoid;" }
+{ "timestamp":"2024-09-15T12:20:00Z", "event":"FactionKillBond", "Reward":8000000, "AwardingFaction":"$faction_PilotsFederation;", "VictimFaction":"$faction_Thargoid;" }
+{ "timestamp":"2024-09-15T12:25:00Z", "event":"FactionKillBond", "Reward":20000, "AwardingFaction":"Sol Workers' Party", "VictimFaction":"Pirates" }
+{ "timestamp":"2024-09-15T12:30:00Z", "event":"Died", "KillerShip":"thargoid_interceptor_hydra" }
+{ "timestamp":"2024-09-19T08:00:00Z", "event":"Died", "KillerName":"Jameson", "KillerShip":"viper" }
+"#,
+        )
+        .unwrap();
+        let mut combat = ThargoidCombat::default();
+        for event in &events {
+            combat.update(event);
+        }
+
+        assert_eq!(combat.weeks.len(), 1);
+        let week = &combat.weeks[&datetime!(2024-09-12 07:00 UTC)];
+        assert_eq!(week.kills[&ThargoidVariant::Scout], 1);
+        assert_eq!(week.kills[&ThargoidVariant::Glaive], 1);
+        assert_eq!(week.kills[&ThargoidVariant::Cyclops], 1);
+        assert_eq!(week.deaths, 1);
+        assert_eq!(combat.total_kills(), 3);
+        assert_eq!(combat.total_bonds(), 12_580_000);
+    }
+
+    #[test]
+    fn test_scout_after_interceptor() {
+        let events = parse_events(
+            r#"
+{ "timestamp":"2024-09-15T12:00:00Z", "event":"ShipTargeted", "TargetLocked":true, "Ship":"thargoid_interceptor_cyclops", "Ship_Localised":"Cyclops", "ScanStage":0 }
+{ "timestamp":"2024-09-15T12:01:00Z", "event":"FactionKillBond", "Reward":80000, "AwardingFaction":"$faction_PilotsFederation;", "VictimFaction":"$faction_Thargoid;" }
+{ "timestamp":"2024-09-15T12:02:00Z", "event":"FactionKillBond", "Reward":4500000, "AwardingFaction":"$faction_PilotsFederation;", "VictimFaction":"$faction_Thargoid;" }
+"#,
+        )
+        .unwrap();
+        let mut combat = ThargoidCombat::default();
+        for event in &events {
+            combat.update(event);
+        }
+
+        let week = &combat.weeks[&datetime!(2024-09-12 07:00 UTC)];
+        assert_eq!(week.kills[&ThargoidVariant::Scout], 1);
+        assert_eq!(week.kills[&ThargoidVariant::Unknown], 1);
+        assert!(!week.kills.contains_key(&ThargoidVariant::Cyclops));
+    }
+}
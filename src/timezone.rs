@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use time::OffsetDateTime;
+use time_tz::{system, timezones, OffsetDateTimeExt, Tz};
+
+/// Timezone to present journal timestamps in, which are always UTC
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Timezone {
+    #[default]
+    Utc,
+    Zone(&'static Tz),
+}
+
+impl Timezone {
+    /// The timezone configured on this system
+    pub fn local() -> Result<Self, system::Error> {
+        system::get_timezone().map(Timezone::Zone)
+    }
+
+    pub fn convert(&self, timestamp: OffsetDateTime) -> OffsetDateTime {
+        match self {
+            Timezone::Utc => timestamp,
+            Timezone::Zone(tz) => timestamp.to_timezone(*tz),
+        }
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    /// "UTC", "local" or an IANA name like "Europe/Berlin"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "UTC" | "utc" => Ok(Timezone::Utc),
+            "local" => Timezone::local().map_err(|e| e.to_string()),
+            name => timezones::get_by_name(name)
+                .map(Timezone::Zone)
+                .ok_or_else(|| format!("Unknown timezone {name:?}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{datetime, format_description, offset};
+
+    #[test]
+    fn test_convert() {
+        let berlin: Timezone = "Europe/Berlin".parse().unwrap();
+        let format = format_description!("[hour]:[minute] [offset_hour sign:mandatory]");
+        let local = |timestamp| berlin.convert(timestamp).format(format).unwrap();
+
+        // Equal instants compare equal whatever their offset, so compare
+        // the wall clock time
+        assert_eq!(local(datetime!(2024-07-01 12:00 UTC)), "14:00 +02");
+        assert_eq!(local(datetime!(2024-12-01 12:00 UTC)), "13:00 +01");
+        assert_eq!(
+            berlin.convert(datetime!(2024-07-01 12:00 UTC)).offset(),
+            offset!(+2)
+        );
+
+        let utc: Timezone = "UTC".parse().unwrap();
+        assert_eq!(
+            utc.convert(datetime!(2024-07-01 12:00 UTC)).offset(),
+            offset!(UTC)
+        );
+        assert!("Mars/Olympus".parse::<Timezone>().is_err());
+    }
+}
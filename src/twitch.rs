@@ -0,0 +1,562 @@
+//! Announcing events in the chat of a Twitch channel, configured like the
+//! `[twitch]` table of the CLI:
+//!
+//! ```toml
+//! [twitch]
+//! channel = "cmdrjameson"
+//!
+//! [[twitch.announce]]
+//! event = "FSDJump"
+//! when = "StarSystem == \"Colonia\""
+//! message = "CMDR Jameson just arrived in Colonia!"
+//!
+//! [[twitch.announce]]
+//! event = "Bounty"
+//! sum = "TotalReward"
+//! every = 1000000
+//! message = "{Milestone} CR in bounties this session!"
+//! ```
+//!
+//! `when` is a [Filter] on the event and `{Field}` in the message is
+//! replaced by the value of the field. With `sum` the field is added up
+//! over the session as `{Total}`, and with `every` the announcement is
+//! only made each time the total passes a multiple of it, the last one
+//! passed being `{Milestone}`.
+//!
+//! Messages are sent over TLS as the configured account with an OAuth
+//! token, waiting when more would be sent than Twitch allows.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, Read, Write},
+    net::TcpStream,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use rustls::{pki_types::ServerName, ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    journal::{fill_fields, FieldPath, Filter},
+    processor::{EventProcessor, ProcessorResult},
+};
+
+const HOST: &str = "irc.chat.twitch.tv";
+const PORT: u16 = 6697;
+/// How long to wait for the server before checking for new messages
+const TICK: Duration = Duration::from_millis(250);
+const RECONNECT_AFTER: Duration = Duration::from_secs(30);
+/// Messages kept while disconnected, older ones are dropped
+const MAX_QUEUED: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TwitchConfig {
+    /// Channel to announce in, without the `#`
+    pub channel: String,
+    /// Account to send as, the channel's own unless given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Most messages to send in `per_seconds`
+    #[serde(default = "default_messages")]
+    pub messages: usize,
+    #[serde(default = "default_per_seconds")]
+    pub per_seconds: u64,
+    #[serde(default)]
+    pub announce: Vec<Announcement>,
+}
+
+/// Twitch allows 20 messages in 30 seconds to accounts that aren't
+/// moderators of the channel
+fn default_messages() -> usize {
+    20
+}
+
+fn default_per_seconds() -> u64 {
+    30
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Announcement {
+    /// Kind of event to announce
+    pub event: String,
+    /// Condition on the event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    pub message: String,
+    /// Field to add up over the session as `{Total}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sum: Option<String>,
+    /// Only announce when `{Total}` passes a multiple of this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnouncementError {
+    pub event: String,
+    pub message: String,
+}
+
+impl fmt::Display for AnnouncementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Announcement of {}: {}", self.event, self.message)
+    }
+}
+
+impl std::error::Error for AnnouncementError {}
+
+#[derive(Debug)]
+struct CompiledAnnouncement {
+    announcement: Announcement,
+    when: Option<Filter>,
+    sum: Option<FieldPath>,
+    total: f64,
+}
+
+impl CompiledAnnouncement {
+    fn applies(&self, event: &Value) -> bool {
+        event["event"].as_str() == Some(self.announcement.event.as_str())
+            && self.when.as_ref().is_none_or(|when| when.matches(event))
+    }
+
+    /// Add the event to the total, returning the milestone it passed
+    fn add(&mut self, event: &Value) -> Option<f64> {
+        let before = self.total;
+        if let Some(sum) = &self.sum {
+            self.total += sum.get(event).and_then(Value::as_f64).unwrap_or_default();
+        }
+        match self.announcement.every {
+            Some(every) => {
+                let milestone = (self.total / every).floor();
+                (milestone > (before / every).floor()).then_some(milestone * every)
+            }
+            None => Some(self.total),
+        }
+    }
+}
+
+/// JSON number of a total, without a fraction if it has none
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < 2f64.powi(53) {
+        (value as i64).into()
+    } else {
+        value.into()
+    }
+}
+
+/// Decides which events to announce with what message
+#[derive(Debug, Default)]
+pub struct Announcer {
+    announcements: Vec<CompiledAnnouncement>,
+}
+
+impl Announcer {
+    pub fn new(announcements: Vec<Announcement>) -> Result<Self, AnnouncementError> {
+        let announcements = announcements
+            .into_iter()
+            .map(|announcement| {
+                let error = |message: String| AnnouncementError {
+                    event: announcement.event.clone(),
+                    message,
+                };
+                let when = match &announcement.when {
+                    Some(when) => Some(when.parse::<Filter>().map_err(|e| error(e.to_string()))?),
+                    None => None,
+                };
+                let sum = match &announcement.sum {
+                    Some(sum) => Some(sum.parse::<FieldPath>().map_err(|e| error(e.to_string()))?),
+                    None => None,
+                };
+                match (&sum, announcement.every) {
+                    (None, Some(_)) => return Err(error("every needs a field to sum".to_owned())),
+                    (_, Some(every)) if every <= 0.0 => {
+                        return Err(error("every has to be above 0".to_owned()))
+                    }
+                    _ => {}
+                }
+
+                Ok(CompiledAnnouncement {
+                    announcement,
+                    when,
+                    sum,
+                    total: 0.0,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Announcer { announcements })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.announcements.is_empty()
+    }
+
+    /// Add up totals without announcing, e.g. for events from earlier in
+    /// the session
+    pub fn catch_up(&mut self, event: &Value) {
+        for announcement in &mut self.announcements {
+            if announcement.applies(event) {
+                announcement.add(event);
+            }
+        }
+    }
+
+    /// Messages announcing the event
+    pub fn check(&mut self, event: &Value) -> Vec<String> {
+        let mut messages = Vec::new();
+
+        for announcement in &mut self.announcements {
+            if !announcement.applies(event) {
+                continue;
+            }
+            let Some(milestone) = announcement.add(event) else {
+                continue;
+            };
+            let mut payload = event.clone();
+            if announcement.sum.is_some() {
+                payload["Total"] = number(announcement.total);
+                payload["Milestone"] = number(milestone);
+            }
+            // A line break would end the message early
+            let message = fill_fields(&announcement.announcement.message, &payload);
+            messages.push(message.replace(['\r', '\n'], " "));
+        }
+
+        messages
+    }
+}
+
+/// Holds messages back once `messages` were sent within `per`
+#[derive(Debug)]
+struct RateLimit {
+    messages: usize,
+    per: Duration,
+    sent: VecDeque<Instant>,
+}
+
+impl RateLimit {
+    fn new(messages: usize, per: Duration) -> Self {
+        RateLimit {
+            messages,
+            per,
+            sent: VecDeque::new(),
+        }
+    }
+
+    /// Whether a message may be sent now, counting it if so
+    fn take(&mut self, now: Instant) -> bool {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= self.per)
+        {
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= self.messages {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+}
+
+/// What a line from the server asks for
+#[derive(Debug, PartialEq, Eq)]
+enum Reply {
+    Nothing,
+    Pong(String),
+    Joined,
+    Reconnect,
+    Failed(String),
+}
+
+fn reply(line: &str) -> Reply {
+    // Leaves the command and its parameters, without tags and the source
+    let mut rest = line;
+    if rest.starts_with('@') {
+        rest = rest.split_once(' ').map_or("", |(_, rest)| rest);
+    }
+    if rest.starts_with(':') {
+        rest = rest.split_once(' ').map_or("", |(_, rest)| rest);
+    }
+    let (command, params) = rest.split_once(' ').unwrap_or((rest, ""));
+
+    match command {
+        "PING" => Reply::Pong(format!("PONG {params}\r\n")),
+        // End of the names in the channel, sent once it's joined
+        "366" => Reply::Joined,
+        "RECONNECT" => Reply::Reconnect,
+        "NOTICE" => match params.split_once(" :").map_or(params, |(_, text)| text) {
+            text @ ("Login authentication failed" | "Improperly formatted auth") => {
+                Reply::Failed(text.to_owned())
+            }
+            _ => Reply::Nothing,
+        },
+        _ => Reply::Nothing,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Login {
+    channel: String,
+    username: String,
+    token: String,
+    messages: usize,
+    per: Duration,
+}
+
+fn connect() -> io::Result<StreamOwned<ClientConnection, TcpStream>> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.into(),
+    };
+    let config =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(io::Error::other)?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+    let name = ServerName::try_from(HOST).map_err(io::Error::other)?;
+    let connection = ClientConnection::new(Arc::new(config), name).map_err(io::Error::other)?;
+
+    let stream = TcpStream::connect((HOST, PORT))?;
+    stream.set_read_timeout(Some(TICK))?;
+    Ok(StreamOwned::new(connection, stream))
+}
+
+/// Add a message to the queue, dropping the oldest when it's full
+fn enqueue(queue: &mut VecDeque<String>, message: String) {
+    if queue.len() >= MAX_QUEUED {
+        queue.pop_front();
+    }
+    queue.push_back(message);
+}
+
+/// Send messages until the announcer is gone, `Ok` once all were sent
+fn session(
+    mut stream: impl Read + Write,
+    login: &Login,
+    receiver: &Receiver<String>,
+    queue: &mut VecDeque<String>,
+    limit: &mut RateLimit,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "PASS {}\r\nNICK {}\r\nJOIN #{}\r\n",
+        login.token, login.username, login.channel
+    )?;
+    stream.flush()?;
+
+    let mut joined = false;
+    let mut received = Vec::new();
+    let mut buffer = [0; 4096];
+    loop {
+        match stream.read(&mut buffer) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => received.extend_from_slice(&buffer[..n]),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(e) => return Err(e),
+        }
+        while let Some(end) = received.windows(2).position(|w| w == b"\r\n") {
+            let line: Vec<u8> = received.drain(..end + 2).collect();
+            match reply(&String::from_utf8_lossy(&line[..end])) {
+                Reply::Nothing => {}
+                Reply::Pong(pong) => stream.write_all(pong.as_bytes())?,
+                Reply::Joined => joined = true,
+                Reply::Reconnect => return Err(io::Error::other("Server is restarting")),
+                Reply::Failed(text) => return Err(io::Error::other(text)),
+            }
+        }
+
+        let mut gone = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(message) => enqueue(queue, message),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    gone = true;
+                    break;
+                }
+            }
+        }
+        while joined && !queue.is_empty() && limit.take(Instant::now()) {
+            if let Some(message) = queue.pop_front() {
+                write!(stream, "PRIVMSG #{} :{message}\r\n", login.channel)?;
+            }
+        }
+        stream.flush()?;
+        if gone && queue.is_empty() {
+            return Ok(());
+        }
+    }
+}
+
+/// Stay connected and send the messages received, until the announcer is
+/// gone and they were all sent
+fn chat(login: Login, receiver: Receiver<String>) {
+    let mut queue = VecDeque::new();
+    let mut limit = RateLimit::new(login.messages, login.per);
+
+    loop {
+        let error = match connect()
+            .and_then(|stream| session(stream, &login, &receiver, &mut queue, &mut limit))
+        {
+            Ok(()) => return,
+            Err(e) => e,
+        };
+        eprintln!(
+            "Twitch chat failed: {error}, reconnecting in {}s",
+            RECONNECT_AFTER.as_secs()
+        );
+
+        let until = Instant::now() + RECONNECT_AFTER;
+        loop {
+            match receiver.recv_timeout(until.saturating_duration_since(Instant::now())) {
+                Ok(message) => enqueue(&mut queue, message),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+}
+
+/// Announces live events in the chat of a channel
+#[derive(Debug)]
+pub struct TwitchChat {
+    announcer: Announcer,
+    /// Messages for the connection, kept on a thread of its own
+    chat: Option<(Sender<String>, JoinHandle<()>)>,
+}
+
+impl TwitchChat {
+    /// Connect in the background with an OAuth token of the account, the
+    /// `oauth:` in front being optional
+    pub fn start(config: &TwitchConfig, token: &str, announcer: Announcer) -> Self {
+        let channel = config.channel.trim_start_matches('#').to_lowercase();
+        let login = Login {
+            username: config
+                .username
+                .as_deref()
+                .map_or_else(|| channel.clone(), str::to_lowercase),
+            channel,
+            token: match token.starts_with("oauth:") {
+                true => token.to_owned(),
+                false => format!("oauth:{token}"),
+            },
+            messages: config.messages,
+            per: Duration::from_secs(config.per_seconds),
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let chat = thread::spawn(move || chat(login, receiver));
+        TwitchChat {
+            announcer,
+            chat: Some((sender, chat)),
+        }
+    }
+}
+
+impl EventProcessor for TwitchChat {
+    fn name(&self) -> &str {
+        "twitch"
+    }
+
+    fn on_raw_event(&mut self, event: &Value) -> ProcessorResult {
+        let messages = self.announcer.check(event);
+        if let Some((sender, _)) = &self.chat {
+            for message in messages {
+                sender.send(message)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self) -> ProcessorResult {
+        // Lets the connection send the messages still queued
+        if let Some((sender, chat)) = self.chat.take() {
+            drop(sender);
+            chat.join().map_err(|_| "Twitch chat panicked")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_announcer() {
+        let config: TwitchConfig = serde_json::from_value(json!({
+            "channel": "cmdrjameson",
+            "announce": [
+                { "event": "FSDJump", "when": "StarSystem == \"Colonia\"",
+                  "message": "Just arrived in {StarSystem}!" },
+                { "event": "Bounty", "sum": "TotalReward", "every": 1000000,
+                  "message": "{Milestone} CR in bounties, {Total} so far" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(config.messages, 20);
+        let mut announcer = Announcer::new(config.announce).unwrap();
+
+        let jump = |system: &str| json!({ "event": "FSDJump", "StarSystem": system });
+        assert!(announcer.check(&jump("Sol")).is_empty());
+        assert_eq!(
+            announcer.check(&jump("Colonia")),
+            ["Just arrived in Colonia!"]
+        );
+
+        let bounty = |reward: u64| json!({ "event": "Bounty", "TotalReward": reward });
+        announcer.catch_up(&bounty(600000));
+        assert!(announcer.check(&bounty(300000)).is_empty());
+        assert_eq!(
+            announcer.check(&bounty(250000)),
+            ["1000000 CR in bounties, 1150000 so far"]
+        );
+        assert!(announcer.check(&bounty(500000)).is_empty());
+
+        let invalid = Announcement {
+            event: "Bounty".to_owned(),
+            when: None,
+            message: String::new(),
+            sum: None,
+            every: Some(1000.0),
+        };
+        assert!(Announcer::new(vec![invalid]).is_err());
+
+        let mut limit = RateLimit::new(2, Duration::from_secs(30));
+        let start = Instant::now();
+        assert!(limit.take(start));
+        assert!(limit.take(start + Duration::from_secs(10)));
+        assert!(!limit.take(start + Duration::from_secs(20)));
+        assert!(limit.take(start + Duration::from_secs(30)));
+
+        assert_eq!(
+            reply("PING :tmi.twitch.tv"),
+            Reply::Pong("PONG :tmi.twitch.tv\r\n".to_owned())
+        );
+        assert_eq!(
+            reply(":tmi.twitch.tv NOTICE * :Login authentication failed"),
+            Reply::Failed("Login authentication failed".to_owned())
+        );
+        assert_eq!(
+            reply(":cmdrjameson.tmi.twitch.tv 366 cmdrjameson #cmdrjameson :End of /NAMES list"),
+            Reply::Joined
+        );
+    }
+}